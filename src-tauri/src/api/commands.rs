@@ -23,23 +23,63 @@ mod external_audit;
 mod system;
 #[path = "commands/internal_validation.rs"]
 mod internal_validation;
+#[path = "commands/snmp.rs"]
+mod snmp;
+#[path = "commands/upnp.rs"]
+mod upnp;
 
 // --- NETWORK SCANNER ---
 
 #[tauri::command]
 pub async fn scan_network(
     service: tauri::State<'_, crate::application::scanner_service::ScannerService>,
+    traffic: tauri::State<'_, crate::api::state::TrafficState>,
     range: Option<String>,
 ) -> Result<Vec<crate::api::dtos::DeviceDTO>, String> {
-    scanner::scan_network(service, range).await
+    scanner::scan_network(service, traffic, range).await
 }
 
 #[tauri::command]
 pub async fn audit_target(
     service: tauri::State<'_, crate::application::scanner_service::ScannerService>,
+    history: tauri::State<'_, crate::application::port_history_service::PortHistoryService>,
+    app: tauri::AppHandle,
     ip: String,
+    scan_mode: Option<String>,
+    mac: Option<String>,
 ) -> Result<crate::api::dtos::SecurityReportDTO, String> {
-    scanner::audit_target(service, ip).await
+    scanner::audit_target(service, history, app, ip, scan_mode, mac).await
+}
+
+#[tauri::command]
+pub async fn get_port_audit_history(
+    history: tauri::State<'_, crate::application::port_history_service::PortHistoryService>,
+    ip: String,
+    mac: Option<String>,
+) -> Result<Vec<crate::domain::entities::PortAuditRecord>, String> {
+    scanner::get_port_audit_history(history, ip, mac).await
+}
+
+// --- SNMP AUDIT ---
+
+#[tauri::command]
+pub async fn audit_snmp(
+    service: tauri::State<'_, crate::application::snmp_service::SnmpService>,
+    ip: String,
+    device: Option<crate::domain::entities::Device>,
+) -> Result<crate::api::dtos::SnmpAuditResultDTO, String> {
+    snmp::audit_snmp(service, ip, device).await
+}
+
+// --- UPnP IGD AUDIT ---
+
+#[tauri::command]
+pub async fn audit_upnp(
+    service: tauri::State<'_, crate::application::upnp_service::UpnpService>,
+    gateway_ip: String,
+    known_ips: Option<Vec<String>>,
+) -> Result<crate::api::dtos::UpnpAuditResultDTO, String> {
+    upnp::audit_upnp(service, gateway_ip, known_ips).await
 }
 
 // --- ROUTER AUDIT ---
@@ -47,29 +87,65 @@ pub async fn audit_target(
 #[tauri::command]
 pub async fn audit_router(
     service: tauri::State<'_, crate::application::audit_service::AuditService>,
+    app: tauri::AppHandle,
     gateway_ip: String,
 ) -> Result<crate::api::dtos::RouterAuditResultDTO, String> {
-    router_audit::audit_router(service, gateway_ip).await
+    router_audit::audit_router(service, app, gateway_ip).await
+}
+
+#[tauri::command]
+pub async fn start_router_audit(
+    service: tauri::State<'_, crate::application::audit_service::AuditService>,
+    app: tauri::AppHandle,
+    gateway_ip: String,
+) -> Result<String, String> {
+    router_audit::start_router_audit(service, app, gateway_ip).await
+}
+
+#[tauri::command]
+pub async fn cancel_router_audit(
+    service: tauri::State<'_, crate::application::audit_service::AuditService>,
+    audit_id: String,
+) -> Result<(), String> {
+    router_audit::cancel_router_audit(service, audit_id).await
 }
 
 #[tauri::command]
 pub async fn fetch_router_devices(
     service: tauri::State<'_, crate::application::audit_service::AuditService>,
     gateway_ip: String,
-    user: String,
-    pass: String,
+    user: Option<String>,
+    pass: Option<String>,
 ) -> Result<Vec<crate::api::dtos::DeviceDTO>, String> {
     router_audit::fetch_router_devices(service, gateway_ip, user, pass).await
 }
 
+#[tauri::command]
+pub async fn list_router_evidence(
+    service: tauri::State<'_, crate::application::audit_service::AuditService>,
+    audit_id: String,
+) -> Result<Vec<crate::domain::entities::RouterEvidenceItem>, String> {
+    router_audit::list_router_evidence(service, audit_id).await
+}
+
+#[tauri::command]
+pub async fn get_router_evidence(
+    service: tauri::State<'_, crate::application::audit_service::AuditService>,
+    audit_id: String,
+    name: String,
+) -> Result<crate::api::dtos::RouterEvidenceContentDTO, String> {
+    router_audit::get_router_evidence(service, audit_id, name).await
+}
+
 // --- HISTORY ---
 
 #[tauri::command]
 pub async fn save_scan(
     service: tauri::State<'_, crate::application::history_service::HistoryService>,
+    port_history: tauri::State<'_, crate::application::port_history_service::PortHistoryService>,
     devices: Vec<crate::domain::entities::Device>,
 ) -> Result<String, String> {
-    history::save_scan(service, devices).await
+    history::save_scan(service, port_history, devices).await
 }
 
 #[tauri::command]
@@ -163,8 +239,19 @@ pub fn get_identity() -> Result<crate::domain::entities::HostIdentity, String> {
 pub fn start_traffic_sniffing(
     state: tauri::State<'_, crate::api::state::TrafficState>,
     app: tauri::AppHandle,
+    filter: Option<String>,
 ) -> Result<(), String> {
-    system::start_traffic_sniffing(state, app)
+    internal_validation::validate_capture_filter(&filter)?;
+    system::start_traffic_sniffing(state, app, filter)
+}
+
+#[tauri::command]
+pub fn set_traffic_filter(
+    state: tauri::State<'_, crate::api::state::TrafficState>,
+    filter: Option<String>,
+) -> Result<(), String> {
+    internal_validation::validate_capture_filter(&filter)?;
+    system::set_traffic_filter(state, filter)
 }
 
 #[tauri::command]
@@ -172,6 +259,92 @@ pub fn stop_traffic_sniffing(state: tauri::State<'_, crate::api::state::TrafficS
     system::stop_traffic_sniffing(state)
 }
 
+#[tauri::command]
+pub fn get_flows(
+    state: tauri::State<'_, crate::api::state::TrafficState>,
+) -> Result<Vec<crate::domain::entities::TrafficFlow>, String> {
+    system::get_flows(state)
+}
+
+#[tauri::command]
+pub fn get_dns_log(
+    state: tauri::State<'_, crate::api::state::TrafficState>,
+    device_ip: Option<String>,
+) -> Result<Vec<crate::domain::entities::DeviceDomainLog>, String> {
+    internal_validation::validate_device_log_input(&device_ip)?;
+    system::get_dns_log(state, device_ip)
+}
+
+#[tauri::command]
+pub fn get_tls_log(
+    state: tauri::State<'_, crate::api::state::TrafficState>,
+    device_ip: Option<String>,
+) -> Result<Vec<crate::domain::entities::DeviceTlsLog>, String> {
+    internal_validation::validate_device_log_input(&device_ip)?;
+    system::get_tls_log(state, device_ip)
+}
+
+#[tauri::command]
+pub fn get_user_agents(
+    state: tauri::State<'_, crate::api::state::TrafficState>,
+    device_ip: Option<String>,
+) -> Result<Vec<crate::domain::entities::DeviceUserAgents>, String> {
+    internal_validation::validate_device_log_input(&device_ip)?;
+    system::get_user_agents(state, device_ip)
+}
+
+#[tauri::command]
+pub fn get_bandwidth_stats(
+    state: tauri::State<'_, crate::api::state::TrafficState>,
+    window: Option<String>,
+    top_n: Option<usize>,
+) -> Result<crate::domain::entities::BandwidthStats, String> {
+    let (window, top_n) = internal_validation::parse_bandwidth_query(&window, top_n)?;
+    system::get_bandwidth_stats(state, window, top_n)
+}
+
+#[tauri::command]
+pub fn get_arp_watch(
+    state: tauri::State<'_, crate::api::state::TrafficState>,
+) -> Result<crate::domain::entities::ArpWatchReport, String> {
+    system::get_arp_watch(state)
+}
+
+#[tauri::command]
+pub fn start_capture_file(
+    state: tauri::State<'_, crate::api::state::TrafficState>,
+    max_bytes: Option<u64>,
+    max_seconds: Option<u64>,
+) -> Result<crate::domain::entities::CaptureFileStatus, String> {
+    internal_validation::validate_capture_limits(max_bytes, max_seconds)?;
+    system::start_capture_file(state, max_bytes, max_seconds)
+}
+
+#[tauri::command]
+pub fn stop_capture_file(
+    state: tauri::State<'_, crate::api::state::TrafficState>,
+) -> Result<crate::domain::entities::CaptureFileStatus, String> {
+    system::stop_capture_file(state)
+}
+
+#[tauri::command]
+pub fn rotate_capture_file(
+    state: tauri::State<'_, crate::api::state::TrafficState>,
+) -> Result<crate::domain::entities::CaptureFileStatus, String> {
+    system::rotate_capture_file(state)
+}
+
+#[tauri::command]
+pub async fn analyze_capture_file(
+    path: String,
+    focus_ip: Option<String>,
+    filter: Option<String>,
+) -> Result<crate::domain::entities::CaptureAnalysis, String> {
+    internal_validation::validate_capture_file_input(&path, &focus_ip)?;
+    internal_validation::validate_capture_filter(&filter)?;
+    system::analyze_capture_file(path, focus_ip, filter).await
+}
+
 #[tauri::command]
 pub fn start_jamming(
     state: tauri::State<'_, crate::api::state::JammerState>,
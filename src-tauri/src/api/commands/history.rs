@@ -3,12 +3,19 @@
 use tauri::State;
 
 use crate::application::history_service::HistoryService;
+use crate::application::port_history_service::PortHistoryService;
 use crate::domain::entities::{Device, ScanSession};
 
 // --- HISTORY ---
-pub async fn save_scan(service: State<'_, HistoryService>, devices: Vec<Device>) -> Result<String, String> {
+pub async fn save_scan(
+    service: State<'_, HistoryService>,
+    port_history: State<'_, PortHistoryService>,
+    devices: Vec<Device>,
+) -> Result<String, String> {
     // Nota: el frontend envia JSON que coincide con la estructura de `Device`.
     // Tauri hace el parseo automatico gracias al `Deserialize` de `src-tauri/src/domain/entities.rs`.
+    // Los puertos auditados viven en su propio historial: los adjuntamos para que la sesion los conserve.
+    let devices = port_history.attach_latest_ports(devices).await;
     service.save_session(devices).await
 }
 
@@ -1,7 +1,8 @@
 // src-tauri/src/api/commands/internal_validation.rs
 
-use crate::api::validators::{validate_ipv4_or_cidr, validate_non_empty, validate_usable_host_ipv4};
+use crate::api::validators::{validate_ip, validate_ipv4_or_cidr, validate_non_empty, validate_usable_host_ipv4};
 use crate::api::validators::validate_mac_address;
+use crate::domain::entities::{BandwidthWindow, PortScanMode, RouterCredentials};
 
 pub fn validate_scan_range(range: &Option<String>) -> Result<(), String> {
     if let Some(raw) = range {
@@ -10,6 +11,16 @@ pub fn validate_scan_range(range: &Option<String>) -> Result<(), String> {
     Ok(())
 }
 
+// `scan_mode` opcional: ausente => connect (comportamiento historico).
+pub fn parse_scan_mode(scan_mode: &Option<String>) -> Result<PortScanMode, String> {
+    match scan_mode.as_deref().map(|m| m.trim().to_ascii_lowercase()) {
+        None => Ok(PortScanMode::Connect),
+        Some(m) if m.is_empty() || m == "connect" => Ok(PortScanMode::Connect),
+        Some(m) if m == "syn" => Ok(PortScanMode::Syn),
+        Some(m) => Err(format!("scan_mode must be 'connect' or 'syn' (got '{m}')")),
+    }
+}
+
 pub fn validate_router_credentials_input(gateway_ip: &str, user: &str, pass: &str) -> Result<(), String> {
     validate_usable_host_ipv4(gateway_ip, "gateway_ip")?;
     validate_non_empty(user, "user", 64)?;
@@ -17,6 +28,25 @@ pub fn validate_router_credentials_input(gateway_ip: &str, user: &str, pass: &st
     Ok(())
 }
 
+// `user`/`pass` opcionales (juntos): ausentes => el servicio usa las credenciales guardadas.
+pub fn parse_optional_router_credentials(
+    gateway_ip: &str,
+    user: Option<String>,
+    pass: Option<String>,
+) -> Result<Option<RouterCredentials>, String> {
+    match (user, pass) {
+        (Some(user), Some(pass)) => {
+            validate_router_credentials_input(gateway_ip, &user, &pass)?;
+            Ok(Some(RouterCredentials { user, pass }))
+        }
+        (None, None) => {
+            validate_usable_host_ipv4(gateway_ip, "gateway_ip")?;
+            Ok(None)
+        }
+        _ => Err("user and pass must be provided together".to_string()),
+    }
+}
+
 pub fn validate_start_jamming_input(ip: &str, mac: &str, gateway_ip: &str) -> Result<(), String> {
     validate_usable_host_ipv4(ip, "ip")?;
     validate_mac_address(mac, "mac")?;
@@ -31,10 +61,107 @@ pub fn validate_stop_jamming_input(ip: &str) -> Result<(), String> {
     validate_usable_host_ipv4(ip, "ip")
 }
 
+// Rotacion PCAPNG: limites por debajo de esto generan miles de ficheros diminutos.
+pub fn validate_capture_limits(max_bytes: Option<u64>, max_seconds: Option<u64>) -> Result<(), String> {
+    if let Some(bytes) = max_bytes {
+        if !(1024 * 1024..=4 * 1024 * 1024 * 1024).contains(&bytes) {
+            return Err("max_bytes must be between 1 MiB and 4 GiB".to_string());
+        }
+    }
+    if let Some(seconds) = max_seconds {
+        if !(10..=24 * 3600).contains(&seconds) {
+            return Err("max_seconds must be between 10 and 86400".to_string());
+        }
+    }
+    Ok(())
+}
+
+// Solo ficheros existentes con extension de captura; el lector valida el formato real.
+pub fn validate_capture_file_input(path: &str, focus_ip: &Option<String>) -> Result<(), String> {
+    validate_non_empty(path, "path", 1024)?;
+    let path = std::path::Path::new(path.trim());
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    if !matches!(extension.as_str(), "pcap" | "pcapng" | "cap") {
+        return Err("path must be a .pcap, .pcapng or .cap file".to_string());
+    }
+    if !path.is_file() {
+        return Err("path does not exist or is not a file".to_string());
+    }
+    if let Some(ip) = focus_ip {
+        validate_ip(ip, "focus_ip")?;
+    }
+    Ok(())
+}
+
+// Logs por equipo del monitor (DNS, TLS, HTTP): IPv4 o IPv6 opcional.
+pub fn validate_device_log_input(device_ip: &Option<String>) -> Result<(), String> {
+    if let Some(ip) = device_ip {
+        validate_ip(ip, "device_ip")?;
+    }
+    Ok(())
+}
+
+// Solo la forma: la sintaxis la valida el compilador del filtro al aplicarlo.
+pub fn validate_capture_filter(filter: &Option<String>) -> Result<(), String> {
+    if let Some(filter) = filter {
+        if filter.len() > 512 {
+            return Err("filter is too long".to_string());
+        }
+        if filter.chars().any(char::is_control) {
+            return Err("filter contains invalid characters".to_string());
+        }
+    }
+    Ok(())
+}
+
+// `window` opcional: ausente => ultimo minuto. `top_n` acotado para no volcar miles de extremos.
+pub fn parse_bandwidth_query(window: &Option<String>, top_n: Option<usize>) -> Result<(BandwidthWindow, usize), String> {
+    let window = match window.as_deref().map(|w| w.trim().to_ascii_lowercase()) {
+        None => BandwidthWindow::Minute,
+        Some(w) if w.is_empty() || w == "minute" => BandwidthWindow::Minute,
+        Some(w) if w == "second" => BandwidthWindow::Second,
+        Some(w) if w == "hour" => BandwidthWindow::Hour,
+        Some(w) => return Err(format!("window must be 'second', 'minute' or 'hour' (got '{w}')")),
+    };
+    let top_n = top_n.unwrap_or(10);
+    if !(1..=100).contains(&top_n) {
+        return Err("top_n must be between 1 and 100".to_string());
+    }
+    Ok((window, top_n))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn validate_capture_file_input_requires_existing_capture_and_valid_focus() {
+        let path = std::env::temp_dir().join(format!("netsentinel_validation_{}.pcapng", std::process::id()));
+        std::fs::write(&path, b"x").unwrap();
+        let path_str = path.to_string_lossy().to_string();
+
+        assert!(validate_capture_file_input(&path_str, &None).is_ok());
+        assert!(validate_capture_file_input(&path_str, &Some("192.168.1.20".to_string())).is_ok());
+        assert!(validate_capture_file_input(&path_str, &Some("2001:db8::20".to_string())).is_ok());
+        assert!(validate_capture_file_input(&path_str, &Some("nope".to_string())).is_err());
+        assert!(validate_capture_file_input("/etc/passwd", &None).is_err());
+        assert!(validate_capture_file_input("/missing/capture.pcap", &None).is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn validate_capture_limits_rejects_tiny_or_huge_rotation() {
+        assert!(validate_capture_limits(None, None).is_ok());
+        assert!(validate_capture_limits(Some(50 * 1024 * 1024), Some(600)).is_ok());
+        assert!(validate_capture_limits(Some(1000), None).is_err());
+        assert!(validate_capture_limits(None, Some(1)).is_err());
+    }
+
     #[test]
     fn validate_scan_range_accepts_none_and_valid_value() {
         assert!(validate_scan_range(&None).is_ok());
@@ -46,6 +173,22 @@ mod tests {
         assert!(validate_scan_range(&Some("192.168.1.0/99".to_string())).is_err());
     }
 
+    #[test]
+    fn parse_scan_mode_defaults_to_connect_and_rejects_unknown() {
+        assert_eq!(parse_scan_mode(&None).unwrap(), PortScanMode::Connect);
+        assert_eq!(parse_scan_mode(&Some("SYN".to_string())).unwrap(), PortScanMode::Syn);
+        assert!(parse_scan_mode(&Some("xmas".to_string())).is_err());
+    }
+
+    #[test]
+    fn parse_bandwidth_query_defaults_and_bounds() {
+        assert_eq!(parse_bandwidth_query(&None, None).unwrap(), (BandwidthWindow::Minute, 10));
+        assert_eq!(parse_bandwidth_query(&Some(" Hour ".to_string()), Some(5)).unwrap(), (BandwidthWindow::Hour, 5));
+        assert!(parse_bandwidth_query(&Some("day".to_string()), None).is_err());
+        assert!(parse_bandwidth_query(&None, Some(0)).is_err());
+        assert!(parse_bandwidth_query(&None, Some(1000)).is_err());
+    }
+
     #[test]
     fn validate_router_credentials_rejects_invalid_input() {
         assert!(validate_router_credentials_input("x.y.z.w", "admin", "1234").is_err());
@@ -54,6 +197,15 @@ mod tests {
         assert!(validate_router_credentials_input("127.0.0.1", "admin", "1234").is_err());
     }
 
+    #[test]
+    fn parse_optional_router_credentials_requires_both_or_none() {
+        let creds = parse_optional_router_credentials("192.168.1.1", Some("admin".into()), Some("a:b".into())).unwrap();
+        assert_eq!(creds.map(|c| c.pass), Some("a:b".to_string()));
+        assert!(parse_optional_router_credentials("192.168.1.1", None, None).unwrap().is_none());
+        assert!(parse_optional_router_credentials("192.168.1.1", Some("admin".into()), None).is_err());
+        assert!(parse_optional_router_credentials("x.y.z.w", None, None).is_err());
+    }
+
     #[test]
     fn validate_start_jamming_input_accepts_valid_values() {
         assert!(validate_start_jamming_input("192.168.1.20", "AA:BB:CC:DD:EE:FF", "192.168.1.1").is_ok());
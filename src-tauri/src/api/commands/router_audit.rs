@@ -1,30 +1,77 @@
 // src-tauri/src/api/commands/router_audit.rs
 
+use std::sync::Arc;
+
 use tauri::State;
 
-use crate::api::dtos::{DeviceDTO, RouterAuditResultDTO};
-use crate::api::validators::validate_usable_host_ipv4;
-use crate::application::audit_service::AuditService;
+use crate::api::dtos::{DeviceDTO, RouterAuditResultDTO, RouterEvidenceContentDTO};
+use crate::api::validators::{validate_non_empty, validate_usable_host_ipv4};
+use crate::application::audit_service::{AuditService, TauriRouterAuditSink};
+use crate::domain::entities::RouterEvidenceItem;
 
-use super::internal_validation::validate_router_credentials_input;
+use super::internal_validation::parse_optional_router_credentials;
 
 // --- ROUTER AUDIT ---
-pub async fn audit_router(service: State<'_, AuditService>, gateway_ip: String) -> Result<RouterAuditResultDTO, String> {
+pub async fn audit_router(
+    service: State<'_, AuditService>,
+    app: tauri::AppHandle,
+    gateway_ip: String,
+) -> Result<RouterAuditResultDTO, String> {
     validate_usable_host_ipv4(&gateway_ip, "gateway_ip")?;
 
-    // Nota: el logging se emite via evento global (wiring en `src-tauri/src/lib.rs`).
-    let result = service.brute_force_gateway(gateway_ip).await;
+    // Nota: el logging libre se emite via evento global (wiring en `src-tauri/src/lib.rs`);
+    // el progreso tipado (con `auditId` cancelable) via `router-audit-progress`.
+    let result = service
+        .brute_force_gateway(gateway_ip, Arc::new(TauriRouterAuditSink::new(app)))
+        .await;
     Ok(RouterAuditResultDTO::from(result))
 }
 
+pub async fn start_router_audit(
+    service: State<'_, AuditService>,
+    app: tauri::AppHandle,
+    gateway_ip: String,
+) -> Result<String, String> {
+    validate_usable_host_ipv4(&gateway_ip, "gateway_ip")?;
+
+    Ok(service
+        .start_gateway_audit(gateway_ip, Arc::new(TauriRouterAuditSink::new(app)))
+        .await)
+}
+
+pub async fn cancel_router_audit(service: State<'_, AuditService>, audit_id: String) -> Result<(), String> {
+    service.cancel_gateway_audit(&audit_id).await
+}
+
 pub async fn fetch_router_devices(
     service: State<'_, AuditService>,
     gateway_ip: String,
-    user: String,
-    pass: String,
+    user: Option<String>,
+    pass: Option<String>,
 ) -> Result<Vec<DeviceDTO>, String> {
-    validate_router_credentials_input(&gateway_ip, &user, &pass)?;
+    let creds = parse_optional_router_credentials(&gateway_ip, user, pass)?;
 
-    let devices = service.extract_router_data(gateway_ip, user, pass).await;
+    let devices = service.extract_router_data(gateway_ip, creds).await?;
     Ok(devices.into_iter().map(DeviceDTO::from).collect())
 }
+
+// --- EVIDENCIAS (por audit_id) ---
+// El repositorio solo acepta nombres de su lista cerrada ("landing.png"...), nunca rutas.
+pub async fn list_router_evidence(
+    service: State<'_, AuditService>,
+    audit_id: String,
+) -> Result<Vec<RouterEvidenceItem>, String> {
+    validate_non_empty(&audit_id, "audit_id", 64)?;
+    service.list_evidence(audit_id.trim())
+}
+
+pub async fn get_router_evidence(
+    service: State<'_, AuditService>,
+    audit_id: String,
+    name: String,
+) -> Result<RouterEvidenceContentDTO, String> {
+    validate_non_empty(&audit_id, "audit_id", 64)?;
+    validate_non_empty(&name, "name", 64)?;
+    let (item, content) = service.get_evidence(audit_id.trim(), name.trim())?;
+    Ok(RouterEvidenceContentDTO::new(item, &content))
+}
@@ -3,14 +3,18 @@
 use tauri::State;
 
 use crate::api::dtos::{DeviceDTO, SecurityReportDTO};
-use crate::api::validators::validate_usable_host_ipv4;
+use crate::api::state::TrafficState;
+use crate::api::validators::{validate_mac_address, validate_usable_host_ipv4};
+use crate::application::port_history_service::{PortHistoryService, TauriPortChangeSink};
 use crate::application::scanner_service::ScannerService;
+use crate::domain::entities::PortAuditRecord;
 
-use super::internal_validation::validate_scan_range;
+use super::internal_validation::{parse_scan_mode, validate_scan_range};
 
 // --- NETWORK SCANNER ---
 pub async fn scan_network(
     service: State<'_, ScannerService>,
+    traffic: State<'_, TrafficState>,
     range: Option<String>,
 ) -> Result<Vec<DeviceDTO>, String> {
     validate_scan_range(&range)?;
@@ -18,18 +22,50 @@ pub async fn scan_network(
     // 1) Llamamos al caso de uso.
     let devices = service.run_network_scan(range).await;
 
-    // 2) Convertimos a DTO.
+    // 2) Los vinculos IP -> MAC del escaneo son la referencia del vigilante ARP (best-effort).
+    if let Ok(traffic) = traffic.0.lock() {
+        if let Err(e) = traffic.seed_arp_bindings(&devices) {
+            println!("⚠️ [API] No se pudo sembrar la tabla ARP: {}", e);
+        }
+    }
+
+    // 3) Convertimos a DTO.
     Ok(devices.into_iter().map(DeviceDTO::from).collect())
 }
 
-pub async fn audit_target(service: State<'_, ScannerService>, ip: String) -> Result<SecurityReportDTO, String> {
+pub async fn audit_target(
+    service: State<'_, ScannerService>,
+    history: State<'_, PortHistoryService>,
+    app: tauri::AppHandle,
+    ip: String,
+    scan_mode: Option<String>,
+    mac: Option<String>,
+) -> Result<SecurityReportDTO, String> {
     validate_usable_host_ipv4(&ip, "ip")?;
+    if let Some(mac) = &mac {
+        validate_mac_address(mac, "mac")?;
+    }
+    let mode = parse_scan_mode(&scan_mode)?;
+
+    let report = service.audit_ip(ip, mode).await;
 
-    let (ports, risk) = service.audit_ip(ip.clone()).await;
+    // La persistencia no debe tumbar la auditoria: si falla, devolvemos el informe igualmente.
+    let sink = TauriPortChangeSink::new(app);
+    if let Err(e) = history.record_audit(&report, mac, &sink).await {
+        println!("⚠️ [API] No se pudo guardar la auditoria de puertos: {}", e);
+    }
 
-    Ok(SecurityReportDTO {
-        target_ip: ip,
-        open_ports: ports,
-        risk_level: risk,
-    })
+    Ok(SecurityReportDTO::from(report))
+}
+
+pub async fn get_port_audit_history(
+    history: State<'_, PortHistoryService>,
+    ip: String,
+    mac: Option<String>,
+) -> Result<Vec<PortAuditRecord>, String> {
+    validate_usable_host_ipv4(&ip, "ip")?;
+    if let Some(mac) = &mac {
+        validate_mac_address(mac, "mac")?;
+    }
+    Ok(history.get_history(&ip, mac.as_deref()).await)
 }
@@ -0,0 +1,24 @@
+// src-tauri/src/api/commands/snmp.rs
+
+use tauri::State;
+
+use crate::api::dtos::{DeviceDTO, SnmpAuditResultDTO};
+use crate::api::validators::validate_usable_host_ipv4;
+use crate::application::snmp_service::SnmpService;
+use crate::domain::entities::Device;
+
+// --- SNMP AUDIT ---
+pub async fn audit_snmp(
+    service: State<'_, SnmpService>,
+    ip: String,
+    device: Option<Device>,
+) -> Result<SnmpAuditResultDTO, String> {
+    validate_usable_host_ipv4(&ip, "ip")?;
+
+    let result = service.audit_snmp(ip).await;
+    let enriched = device.map(|d| SnmpService::enrich_device(d, &result));
+
+    let mut dto = SnmpAuditResultDTO::from(result);
+    dto.device = enriched.map(DeviceDTO::from);
+    Ok(dto)
+}
@@ -3,16 +3,26 @@
 use tauri::{AppHandle, State};
 
 use crate::api::state::{JammerState, TrafficState};
-use crate::domain::entities::HostIdentity;
+use crate::application::traffic_service::TrafficService;
+use crate::domain::entities::{
+    ArpWatchReport, BandwidthStats, BandwidthWindow, CaptureAnalysis, CaptureFileStatus, DeviceDomainLog, DeviceTlsLog,
+    DeviceUserAgents, HostIdentity, TrafficFlow,
+};
+use crate::infrastructure::network::traffic_sniffer::capture_file::CaptureLimits;
 use crate::infrastructure::repositories::local_intelligence;
 
 pub fn get_identity() -> Result<HostIdentity, String> {
     local_intelligence::get_host_identity().map_err(|e| e.to_string())
 }
 
-pub fn start_traffic_sniffing(state: State<'_, TrafficState>, app: AppHandle) -> Result<(), String> {
+pub fn start_traffic_sniffing(state: State<'_, TrafficState>, app: AppHandle, filter: Option<String>) -> Result<(), String> {
     let service = state.0.lock().map_err(|_| "Failed to lock traffic state".to_string())?;
-    service.start_monitoring(app)
+    service.start_monitoring(app, filter)
+}
+
+pub fn set_traffic_filter(state: State<'_, TrafficState>, filter: Option<String>) -> Result<(), String> {
+    let service = state.0.lock().map_err(|_| "Failed to lock traffic state".to_string())?;
+    service.set_filter(filter)
 }
 
 pub fn stop_traffic_sniffing(state: State<'_, TrafficState>) -> Result<(), String> {
@@ -21,6 +31,77 @@ pub fn stop_traffic_sniffing(state: State<'_, TrafficState>) -> Result<(), Strin
     Ok(())
 }
 
+pub fn get_flows(state: State<'_, TrafficState>) -> Result<Vec<TrafficFlow>, String> {
+    let service = state.0.lock().map_err(|_| "Failed to lock traffic state".to_string())?;
+    service.get_flows()
+}
+
+pub fn get_dns_log(state: State<'_, TrafficState>, device_ip: Option<String>) -> Result<Vec<DeviceDomainLog>, String> {
+    let service = state.0.lock().map_err(|_| "Failed to lock traffic state".to_string())?;
+    service.get_dns_log(canonical_ip(device_ip).as_deref())
+}
+
+pub fn get_tls_log(state: State<'_, TrafficState>, device_ip: Option<String>) -> Result<Vec<DeviceTlsLog>, String> {
+    let service = state.0.lock().map_err(|_| "Failed to lock traffic state".to_string())?;
+    service.get_tls_log(canonical_ip(device_ip).as_deref())
+}
+
+pub fn get_user_agents(state: State<'_, TrafficState>, device_ip: Option<String>) -> Result<Vec<DeviceUserAgents>, String> {
+    let service = state.0.lock().map_err(|_| "Failed to lock traffic state".to_string())?;
+    service.get_user_agents(canonical_ip(device_ip).as_deref())
+}
+
+pub fn get_bandwidth_stats(
+    state: State<'_, TrafficState>,
+    window: BandwidthWindow,
+    top_n: usize,
+) -> Result<BandwidthStats, String> {
+    let service = state.0.lock().map_err(|_| "Failed to lock traffic state".to_string())?;
+    service.get_bandwidth_stats(window, top_n)
+}
+
+pub fn get_arp_watch(state: State<'_, TrafficState>) -> Result<ArpWatchReport, String> {
+    let service = state.0.lock().map_err(|_| "Failed to lock traffic state".to_string())?;
+    service.get_arp_watch()
+}
+
+pub fn start_capture_file(
+    state: State<'_, TrafficState>,
+    max_bytes: Option<u64>,
+    max_seconds: Option<u64>,
+) -> Result<CaptureFileStatus, String> {
+    let service = state.0.lock().map_err(|_| "Failed to lock traffic state".to_string())?;
+    service.start_capture_file(CaptureLimits { max_bytes, max_seconds })
+}
+
+pub fn stop_capture_file(state: State<'_, TrafficState>) -> Result<CaptureFileStatus, String> {
+    let service = state.0.lock().map_err(|_| "Failed to lock traffic state".to_string())?;
+    service.stop_capture_file()
+}
+
+pub fn rotate_capture_file(state: State<'_, TrafficState>) -> Result<CaptureFileStatus, String> {
+    let service = state.0.lock().map_err(|_| "Failed to lock traffic state".to_string())?;
+    service.rotate_capture_file()
+}
+
+pub async fn analyze_capture_file(
+    path: String,
+    focus_ip: Option<String>,
+    filter: Option<String>,
+) -> Result<CaptureAnalysis, String> {
+    // Lectura en streaming de ficheros potencialmente grandes: fuera del runtime async.
+    let focus_ip = canonical_ip(focus_ip);
+    tauri::async_runtime::spawn_blocking(move || {
+        TrafficService::analyze_capture_file(
+            std::path::Path::new(path.trim()),
+            focus_ip.as_deref(),
+            filter.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 pub fn start_jamming(state: State<'_, JammerState>, ip: String, mac: String, gateway_ip: String) -> Result<(), String> {
     let service = state.0.lock().map_err(|_| "Failed to lock jammer state".to_string())?;
     service.start_jamming(ip, mac, gateway_ip);
@@ -32,3 +113,11 @@ pub fn stop_jamming(state: State<'_, JammerState>, ip: String) -> Result<(), Str
     service.stop_jamming(ip);
     Ok(())
 }
+
+// Los logs guardan la forma canonica (`fe80::1`, no `FE80:0::0001`): la consulta se normaliza igual.
+fn canonical_ip(ip: Option<String>) -> Option<String> {
+    ip.map(|ip| {
+        let ip = ip.trim();
+        ip.parse::<std::net::IpAddr>().map(|parsed| parsed.to_string()).unwrap_or_else(|_| ip.to_string())
+    })
+}
@@ -0,0 +1,26 @@
+// src-tauri/src/api/commands/upnp.rs
+
+use tauri::State;
+
+use crate::api::dtos::UpnpAuditResultDTO;
+use crate::api::validators::validate_usable_host_ipv4;
+use crate::application::upnp_service::UpnpService;
+
+// Inventario enviado por el frontend (IPs de la ultima foto); acotado para no aceptar payloads enormes.
+const MAX_KNOWN_IPS: usize = 4096;
+
+// --- UPnP IGD AUDIT ---
+pub async fn audit_upnp(
+    service: State<'_, UpnpService>,
+    gateway_ip: String,
+    known_ips: Option<Vec<String>>,
+) -> Result<UpnpAuditResultDTO, String> {
+    validate_usable_host_ipv4(&gateway_ip, "gateway_ip")?;
+    let known_ips = known_ips.unwrap_or_default();
+    if known_ips.len() > MAX_KNOWN_IPS {
+        return Err(format!("known_ips admite como maximo {} entradas", MAX_KNOWN_IPS));
+    }
+
+    let result = service.audit_upnp(gateway_ip, &known_ips).await;
+    Ok(UpnpAuditResultDTO::from(result))
+}
@@ -1,5 +1,9 @@
 use serde::{Serialize, Deserialize};
-use crate::domain::entities::{Device, RouterAuditResult, WifiEntity};
+use crate::domain::entities::{
+    Device, RouterAuditResult, RouterCredentials, RouterEvidenceItem, RouterEvidenceKind, RouterFingerprint,
+    SnmpAuditResult, SnmpCommunityAccess, SnmpInterface, SnmpSystemInfo, UpnpAuditResult, UpnpPortMapping,
+    Vulnerability, WifiEntity,
+};
 
 // 1) DISPOSITIVO DTO (queremos `camelCase` para React).
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -15,6 +19,10 @@ pub struct DeviceDTO {
     pub signal_strength: Option<String>, 
     pub signal_rate: Option<String>,     
     pub wifi_band: Option<String>,       
+    pub model: Option<String>,
+    pub firmware: Option<String>,
+    pub interface_type: Option<String>,
+    pub active: Option<bool>,
 }
 
 impl From<Device> for DeviceDTO {
@@ -30,6 +38,10 @@ impl From<Device> for DeviceDTO {
             signal_strength: d.signal_strength,
             signal_rate: d.signal_rate,
             wifi_band: d.wifi_band,
+            model: d.model,
+            firmware: d.firmware,
+            interface_type: d.interface_type,
+            active: d.active,
         }
     }
 }
@@ -41,23 +53,134 @@ pub struct SecurityReportDTO {
     pub target_ip: String,
     pub open_ports: Vec<crate::domain::entities::OpenPort>, 
     pub risk_level: String,
+    // Hallazgos confirmados por sondas activas (con evidencia).
+    pub vulnerabilities: Vec<crate::domain::entities::Vulnerability>,
+    // Modo de escaneo realmente usado ("connect" | "syn") y puertos sin respuesta (solo SYN).
+    pub scan_mode: crate::domain::entities::PortScanMode,
+    pub filtered_ports: Vec<u16>,
+}
+
+impl From<crate::domain::entities::PortAuditReport> for SecurityReportDTO {
+    fn from(r: crate::domain::entities::PortAuditReport) -> Self {
+        Self {
+            target_ip: r.target_ip,
+            open_ports: r.open_ports,
+            risk_level: r.risk_level,
+            vulnerabilities: r.vulnerabilities,
+            scan_mode: r.scan_mode,
+            filtered_ports: r.filtered_ports,
+        }
+    }
 }
 
 // 3. RESULTAT AUDITORIA DTO
 // ⚠️ FIX: Eliminem 'rename_all' perquè el frontend espera 'credentials_found' (snake_case)
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RouterAuditResultDTO {
+    pub audit_id: Option<String>, // clau per a list_router_evidence / get_router_evidence
     pub vulnerable: bool,
-    pub credentials_found: Option<String>, // React ho llegirà com result.credentials_found
+    pub credentials_found: Option<RouterCredentials>, // { user, password }: la clau pot contenir ':'
     pub message: String,
+    pub fingerprint: Option<RouterFingerprint>,
+    pub findings: Vec<Vulnerability>,
 }
 
 impl From<RouterAuditResult> for RouterAuditResultDTO {
     fn from(r: RouterAuditResult) -> Self {
         Self {
+            audit_id: r.audit_id,
             vulnerable: r.vulnerable,
             credentials_found: r.credentials_found,
             message: r.message,
+            fingerprint: r.fingerprint,
+            findings: r.findings,
+        }
+    }
+}
+
+// 3a. EVIDENCIA DE AUDITORIA DE ROUTER DTO (contenido en base64 para el informe)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RouterEvidenceContentDTO {
+    #[serde(flatten)]
+    pub item: RouterEvidenceItem,
+    pub mime_type: String,
+    pub data_base64: String,
+}
+
+impl RouterEvidenceContentDTO {
+    pub fn new(item: RouterEvidenceItem, content: &[u8]) -> Self {
+        use base64::Engine;
+
+        let mime_type = match item.kind {
+            RouterEvidenceKind::Screenshot => "image/png",
+            RouterEvidenceKind::Html => "text/html; charset=utf-8",
+        };
+        Self {
+            item,
+            mime_type: mime_type.to_string(),
+            data_base64: base64::engine::general_purpose::STANDARD.encode(content),
+        }
+    }
+}
+
+// 3b. AUDITORIA SNMP DTO
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SnmpAuditResultDTO {
+    pub target_ip: String,
+    pub communities: Vec<SnmpCommunityAccess>,
+    pub system: Option<SnmpSystemInfo>,
+    pub interfaces: Vec<SnmpInterface>,
+    pub model: Option<String>,
+    pub firmware: Option<String>,
+    pub vulnerabilities: Vec<Vulnerability>,
+    pub risk_level: String,
+    // Dispositivo enriquecido (solo si el frontend lo envia en la peticion).
+    pub device: Option<DeviceDTO>,
+}
+
+impl From<SnmpAuditResult> for SnmpAuditResultDTO {
+    fn from(r: SnmpAuditResult) -> Self {
+        Self {
+            target_ip: r.target_ip,
+            communities: r.communities,
+            system: r.system,
+            interfaces: r.interfaces,
+            model: r.model,
+            firmware: r.firmware,
+            vulnerabilities: r.vulnerabilities,
+            risk_level: r.risk_level,
+            device: None,
+        }
+    }
+}
+
+// 3c. AUDITORIA UPnP IGD DTO
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpnpAuditResultDTO {
+    pub gateway_ip: String,
+    pub igd_found: bool,
+    pub service_type: Option<String>,
+    pub control_url: Option<String>,
+    pub external_ip: Option<String>,
+    pub port_mappings: Vec<UpnpPortMapping>,
+    pub vulnerabilities: Vec<Vulnerability>,
+    pub risk_level: String,
+}
+
+impl From<UpnpAuditResult> for UpnpAuditResultDTO {
+    fn from(r: UpnpAuditResult) -> Self {
+        Self {
+            gateway_ip: r.gateway_ip,
+            igd_found: r.igd_found,
+            service_type: r.service_type,
+            control_url: r.control_url,
+            external_ip: r.external_ip,
+            port_mappings: r.port_mappings,
+            vulnerabilities: r.vulnerabilities,
+            risk_level: r.risk_level,
         }
     }
 }
@@ -1,4 +1,4 @@
-use std::net::Ipv4Addr;
+use std::net::{IpAddr, Ipv4Addr};
 
 pub fn validate_ipv4(value: &str, field: &str) -> Result<(), String> {
     let trimmed = value.trim();
@@ -11,6 +11,17 @@ pub fn validate_ipv4(value: &str, field: &str) -> Result<(), String> {
     Ok(())
 }
 
+pub fn validate_ip(value: &str, field: &str) -> Result<(), String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(format!("{field} cannot be empty"));
+    }
+    if trimmed.parse::<IpAddr>().is_err() {
+        return Err(format!("{field} must be a valid IPv4 or IPv6 address"));
+    }
+    Ok(())
+}
+
 pub fn validate_usable_host_ipv4(value: &str, field: &str) -> Result<(), String> {
     validate_ipv4(value, field)?;
     let parsed = value
@@ -111,6 +122,15 @@ mod tests {
         assert!(validate_ipv4_or_cidr("abc", "range").is_err());
     }
 
+    #[test]
+    fn validate_ip_accepts_both_families() {
+        assert!(validate_ip("192.168.1.10", "ip").is_ok());
+        assert!(validate_ip(" fe80::1c2a:3bff:fe4d:5e6f ", "ip").is_ok());
+        assert!(validate_ip("", "ip").is_err());
+        assert!(validate_ip("fe80::1::2", "ip").is_err());
+        assert!(validate_ip("192.168.1.0/24", "ip").is_err());
+    }
+
     #[test]
     fn validate_mac_address_rejects_invalid_mac() {
         assert!(validate_mac_address("AA:BB:CC:DD:EE:FF", "mac").is_ok());
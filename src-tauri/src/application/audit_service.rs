@@ -1,52 +1,281 @@
-use crate::domain::{ports::RouterAuditorPort, entities::{RouterAuditResult, Device}};
-use std::sync::Arc;
+use crate::domain::{
+    entities::{
+        Device, RouterAuditProgress, RouterAuditResult, RouterCredentials, RouterEvidence, RouterEvidenceItem,
+        RouterFingerprint, Vulnerability,
+    },
+    ports::{CredentialStorePort, RouterAuditObserver, RouterAuditorPort, RouterEvidenceRepositoryPort},
+};
+use std::collections::HashMap;
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{oneshot, Mutex};
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouterAuditProgressEvent {
+    pub audit_id: String,
+    #[serde(flatten)]
+    pub progress: RouterAuditProgress,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouterAuditFinishedEvent {
+    pub audit_id: String,
+    pub cancelled: bool,
+    pub vulnerable: bool,
+    pub credentials_found: Option<RouterCredentials>,
+    pub message: String,
+    pub fingerprint: Option<RouterFingerprint>,
+    pub findings: Vec<Vulnerability>,
+}
+
+// Destino de los eventos de auditoria de router (Tauri en produccion, memoria en tests).
+pub trait RouterAuditEventSink: Send + Sync + 'static {
+    fn on_progress(&self, evt: RouterAuditProgressEvent);
+    fn on_finished(&self, evt: RouterAuditFinishedEvent);
+}
+
+pub struct TauriRouterAuditSink {
+    app: tauri::AppHandle,
+}
+
+impl TauriRouterAuditSink {
+    pub fn new(app: tauri::AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl RouterAuditEventSink for TauriRouterAuditSink {
+    fn on_progress(&self, evt: RouterAuditProgressEvent) {
+        use tauri::Emitter;
+
+        // Best-effort: el UI puede estar cerrado o no escuchar eventos aun.
+        let _ = self.app.emit("router-audit-progress", evt);
+    }
+
+    fn on_finished(&self, evt: RouterAuditFinishedEvent) {
+        use tauri::Emitter;
+
+        let _ = self.app.emit("router-audit-finished", evt);
+    }
+}
+
+pub struct RunningRouterAudit {
+    pub cancel: oneshot::Sender<()>,
+}
+
+// Puente job -> adaptador: reenvia el progreso al sink y traduce el oneshot de cancelacion
+// a una consulta sincrona (el adaptador corre en `spawn_blocking`).
+struct RouterAuditJob {
+    audit_id: String,
+    sink: Arc<dyn RouterAuditEventSink>,
+    evidence: Arc<dyn RouterEvidenceRepositoryPort>,
+    cancel_rx: std::sync::Mutex<oneshot::Receiver<()>>,
+    cancelled: AtomicBool,
+}
+
+impl RouterAuditObserver for RouterAuditJob {
+    fn on_progress(&self, progress: RouterAuditProgress) {
+        self.sink.on_progress(RouterAuditProgressEvent {
+            audit_id: self.audit_id.clone(),
+            progress,
+        });
+    }
+
+    fn is_cancelled(&self) -> bool {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return true;
+        }
+        let fired = matches!(self.cancel_rx.lock().map(|mut rx| rx.try_recv()), Ok(Ok(())));
+        if fired {
+            self.cancelled.store(true, Ordering::Relaxed);
+        }
+        fired
+    }
+
+    fn on_evidence(&self, evidence: RouterEvidence) {
+        if let Err(e) = self.evidence.save_evidence(&self.audit_id, &evidence) {
+            println!("⚠️ [APP] Evidencia {} descartada ({}): {}", evidence.stage.as_str(), self.audit_id, e);
+        }
+    }
+}
 
 pub struct AuditService {
     auditor_port: Arc<dyn RouterAuditorPort>,
+    // Mismo almacen que `CredentialService`: credenciales guardadas tras una auditoria con exito.
+    credential_store: Arc<dyn CredentialStorePort>,
+    // Capturas de cada auditoria (pantallazos + HTML saneado), consultables por audit_id.
+    evidence_repo: Arc<dyn RouterEvidenceRepositoryPort>,
+    running: Arc<Mutex<HashMap<String, RunningRouterAudit>>>,
 }
 
 impl AuditService {
-    pub fn new(auditor_port: Arc<dyn RouterAuditorPort>) -> Self {
-        Self { auditor_port }
+    pub fn new(
+        auditor_port: Arc<dyn RouterAuditorPort>,
+        credential_store: Arc<dyn CredentialStorePort>,
+        evidence_repo: Arc<dyn RouterEvidenceRepositoryPort>,
+    ) -> Self {
+        Self {
+            auditor_port,
+            credential_store,
+            evidence_repo,
+            running: Arc::new(Mutex::new(HashMap::new())),
+        }
     }
 
-    pub async fn brute_force_gateway(&self, ip: String) -> RouterAuditResult {
+    // Auditoria como job en segundo plano: devuelve el id y el resultado llega en `on_finished`.
+    pub async fn start_gateway_audit(&self, ip: String, sink: Arc<dyn RouterAuditEventSink>) -> String {
+        let job = self.register_job(sink).await;
+        let audit_id = job.audit_id.clone();
+        let port = Arc::clone(&self.auditor_port);
+        let running = Arc::clone(&self.running);
+        tokio::spawn(async move {
+            run_gateway_audit(port, running, job, ip).await;
+        });
+        audit_id
+    }
+
+    // Variante que espera el resultado; el job sigue siendo cancelable con el id de sus eventos.
+    pub async fn brute_force_gateway(&self, ip: String, sink: Arc<dyn RouterAuditEventSink>) -> RouterAuditResult {
         println!("🧠 [APP] Iniciando protocolo de auditoria de gateway en {}", ip);
-        self.auditor_port.audit_gateway(&ip).await
+        let job = self.register_job(sink).await;
+        run_gateway_audit(Arc::clone(&self.auditor_port), Arc::clone(&self.running), job, ip).await
     }
 
-    pub async fn extract_router_data(&self, ip: String, user: String, pass: String) -> Vec<Device> {
-        let creds = format!("{}:{}", user, pass); // Formatem segons necessita el port
-        self.auditor_port.fetch_connected_devices(&ip, &creds).await
+    pub async fn cancel_gateway_audit(&self, audit_id: &str) -> Result<(), String> {
+        let cancel = {
+            let mut guard = self.running.lock().await;
+            guard.remove(audit_id).map(|r| r.cancel)
+        };
+
+        let Some(tx) = cancel else {
+            return Err("audit_id no encontrado o ya finalizado".to_string());
+        };
+
+        // El adaptador lo vera antes del siguiente intento de credenciales.
+        let _ = tx.send(());
+        Ok(())
     }
+
+    // Sin credenciales explicitas se usan las guardadas localmente para ese gateway.
+    pub async fn extract_router_data(&self, ip: String, creds: Option<RouterCredentials>) -> Result<Vec<Device>, String> {
+        let creds = match creds {
+            Some(creds) => creds,
+            None => self
+                .credential_store
+                .get_gateway_credentials(&ip)
+                .await?
+                .map(RouterCredentials::from)
+                .ok_or_else(|| format!("No hay credenciales guardadas para {}", ip))?,
+        };
+        Ok(self.auditor_port.fetch_connected_devices(&ip, &creds).await)
+    }
+
+    pub fn list_evidence(&self, audit_id: &str) -> Result<Vec<RouterEvidenceItem>, String> {
+        self.evidence_repo.list_evidence(audit_id)
+    }
+
+    pub fn get_evidence(&self, audit_id: &str, name: &str) -> Result<(RouterEvidenceItem, Vec<u8>), String> {
+        self.evidence_repo.read_evidence(audit_id, name)
+    }
+
+    async fn register_job(&self, sink: Arc<dyn RouterAuditEventSink>) -> Arc<RouterAuditJob> {
+        let audit_id = new_router_audit_id();
+        let (cancel_tx, cancel_rx) = oneshot::channel::<()>();
+        self.running
+            .lock()
+            .await
+            .insert(audit_id.clone(), RunningRouterAudit { cancel: cancel_tx });
+
+        Arc::new(RouterAuditJob {
+            audit_id,
+            sink,
+            evidence: Arc::clone(&self.evidence_repo),
+            cancel_rx: std::sync::Mutex::new(cancel_rx),
+            cancelled: AtomicBool::new(false),
+        })
+    }
+}
+
+async fn run_gateway_audit(
+    port: Arc<dyn RouterAuditorPort>,
+    running: Arc<Mutex<HashMap<String, RunningRouterAudit>>>,
+    job: Arc<RouterAuditJob>,
+    ip: String,
+) -> RouterAuditResult {
+    let observer: Arc<dyn RouterAuditObserver> = job.clone();
+    let mut result = port.audit_gateway(&ip, observer).await;
+    // Mismo id que los eventos: con el se consultan las evidencias del informe.
+    result.audit_id = Some(job.audit_id.clone());
+
+    running.lock().await.remove(&job.audit_id);
+    job.sink.on_finished(RouterAuditFinishedEvent {
+        audit_id: job.audit_id.clone(),
+        cancelled: job.is_cancelled(),
+        vulnerable: result.vulnerable,
+        credentials_found: result.credentials_found.clone(),
+        message: result.message.clone(),
+        fingerprint: result.fingerprint.clone(),
+        findings: result.findings.clone(),
+    });
+    result
+}
+
+static ROUTER_AUDIT_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn new_router_audit_id() -> String {
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::from_secs(0))
+        .as_millis();
+    let n = ROUTER_AUDIT_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("router_audit_{ts}_{n}")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::entities::{
+        GatewayCredentials, RouterAuditPhase, RouterEvidenceKind, RouterEvidenceStage, RouterLoginOutcome,
+    };
     use async_trait::async_trait;
-    use std::sync::{Arc, Mutex};
+    use std::sync::Mutex;
 
     struct MockRouterAuditor {
         last_ip: Arc<Mutex<Option<String>>>,
-        last_creds: Arc<Mutex<Option<String>>>,
+        last_creds: Arc<Mutex<Option<RouterCredentials>>>,
     }
 
     #[async_trait]
     impl RouterAuditorPort for MockRouterAuditor {
-        async fn audit_gateway(&self, ip: &str) -> RouterAuditResult {
+        async fn audit_gateway(&self, ip: &str, observer: Arc<dyn RouterAuditObserver>) -> RouterAuditResult {
             *self.last_ip.lock().unwrap() = Some(ip.to_string());
+            observer.on_evidence(RouterEvidence {
+                stage: RouterEvidenceStage::Landing,
+                kind: RouterEvidenceKind::Html,
+                content: b"<html>panel</html>".to_vec(),
+            });
             RouterAuditResult {
+                audit_id: None,
                 target_ip: ip.to_string(),
                 vulnerable: true,
-                credentials_found: Some("admin:1234".to_string()),
+                credentials_found: Some(RouterCredentials {
+                    user: "admin".to_string(),
+                    pass: "12:34".to_string(),
+                }),
                 message: "ok".to_string(),
+                fingerprint: None,
+                findings: Vec::new(),
             }
         }
 
-        async fn fetch_connected_devices(&self, ip: &str, creds: &str) -> Vec<Device> {
+        async fn fetch_connected_devices(&self, ip: &str, creds: &RouterCredentials) -> Vec<Device> {
             *self.last_ip.lock().unwrap() = Some(ip.to_string());
-            *self.last_creds.lock().unwrap() = Some(creds.to_string());
+            *self.last_creds.lock().unwrap() = Some(creds.clone());
             vec![Device {
                 ip: "192.168.1.50".to_string(),
                 mac: "AA:BB:CC".to_string(),
@@ -59,46 +288,263 @@ mod tests {
                 signal_rate: None,
                 wifi_band: None,
                 open_ports: None,
+                model: None,
+                firmware: None,
+                interface_type: None,
+                active: None,
             }]
         }
     }
 
+    // Prueba "credenciales" hasta que el job se cancela (como Chrome entre intentos).
+    struct SlowRouterAuditor;
+
+    #[async_trait]
+    impl RouterAuditorPort for SlowRouterAuditor {
+        async fn audit_gateway(&self, ip: &str, observer: Arc<dyn RouterAuditObserver>) -> RouterAuditResult {
+            let mut attempts = 0;
+            while !observer.is_cancelled() && attempts < 200 {
+                attempts += 1;
+                observer.on_progress(RouterAuditProgress {
+                    phase: RouterAuditPhase::Attempted,
+                    backend: "mock".to_string(),
+                    profile_id: None,
+                    attempt_index: Some(attempts),
+                    attempt_total: Some(200),
+                    user: Some("admin".to_string()),
+                    outcome: Some(RouterLoginOutcome::Rejected),
+                });
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            RouterAuditResult {
+                audit_id: None,
+                target_ip: ip.to_string(),
+                vulnerable: false,
+                credentials_found: None,
+                message: format!("Cancelled after {} attempts", attempts),
+                fingerprint: None,
+                findings: Vec::new(),
+            }
+        }
+
+        async fn fetch_connected_devices(&self, _ip: &str, _creds: &RouterCredentials) -> Vec<Device> {
+            Vec::new()
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryCredentialStore {
+        creds: Mutex<Vec<GatewayCredentials>>,
+    }
+
+    #[async_trait]
+    impl CredentialStorePort for MemoryCredentialStore {
+        async fn save_gateway_credentials(&self, creds: GatewayCredentials) -> Result<(), String> {
+            self.creds.lock().unwrap().push(creds);
+            Ok(())
+        }
+
+        async fn get_gateway_credentials(&self, gateway_ip: &str) -> Result<Option<GatewayCredentials>, String> {
+            Ok(self.creds.lock().unwrap().iter().find(|c| c.gateway_ip == gateway_ip).cloned())
+        }
+
+        async fn delete_gateway_credentials(&self, gateway_ip: &str) -> Result<(), String> {
+            self.creds.lock().unwrap().retain(|c| c.gateway_ip != gateway_ip);
+            Ok(())
+        }
+    }
+
+    // audit_id -> (item, contenido), sin tocar disco.
+    #[derive(Default)]
+    struct MemoryEvidenceRepository {
+        saved: Mutex<Vec<(RouterEvidenceItem, Vec<u8>)>>,
+    }
+
+    impl RouterEvidenceRepositoryPort for MemoryEvidenceRepository {
+        fn save_evidence(&self, audit_id: &str, evidence: &RouterEvidence) -> Result<RouterEvidenceItem, String> {
+            let item = RouterEvidenceItem {
+                audit_id: audit_id.to_string(),
+                name: format!("{}.{}", evidence.stage.as_str(), evidence.kind.extension()),
+                stage: evidence.stage,
+                kind: evidence.kind,
+                size_bytes: evidence.content.len() as u64,
+                captured_at: 0,
+            };
+            self.saved.lock().unwrap().push((item.clone(), evidence.content.clone()));
+            Ok(item)
+        }
+
+        fn list_evidence(&self, audit_id: &str) -> Result<Vec<RouterEvidenceItem>, String> {
+            let saved = self.saved.lock().unwrap();
+            Ok(saved.iter().filter(|(i, _)| i.audit_id == audit_id).map(|(i, _)| i.clone()).collect())
+        }
+
+        fn read_evidence(&self, audit_id: &str, name: &str) -> Result<(RouterEvidenceItem, Vec<u8>), String> {
+            let saved = self.saved.lock().unwrap();
+            saved
+                .iter()
+                .find(|(i, _)| i.audit_id == audit_id && i.name == name)
+                .cloned()
+                .ok_or_else(|| "no encontrada".to_string())
+        }
+    }
+
+    fn service_with(port: Arc<dyn RouterAuditorPort>) -> AuditService {
+        AuditService::new(
+            port,
+            Arc::new(MemoryCredentialStore::default()),
+            Arc::new(MemoryEvidenceRepository::default()),
+        )
+    }
+
+    #[derive(Default)]
+    struct MemorySink {
+        progress: Mutex<Vec<RouterAuditProgressEvent>>,
+        finished: Mutex<Vec<RouterAuditFinishedEvent>>,
+    }
+
+    impl RouterAuditEventSink for MemorySink {
+        fn on_progress(&self, evt: RouterAuditProgressEvent) {
+            self.progress.lock().unwrap().push(evt);
+        }
+
+        fn on_finished(&self, evt: RouterAuditFinishedEvent) {
+            self.finished.lock().unwrap().push(evt);
+        }
+    }
+
+    #[tokio::test]
+    async fn router_audit_job_can_be_cancelled_between_attempts() {
+        let service = service_with(Arc::new(SlowRouterAuditor));
+        let sink = Arc::new(MemorySink::default());
+
+        let audit_id = service.start_gateway_audit("192.168.1.1".to_string(), sink.clone()).await;
+        assert!(audit_id.starts_with("router_audit_"));
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        service.cancel_gateway_audit(&audit_id).await.unwrap();
+
+        for _ in 0..100 {
+            if !sink.finished.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        // Ya no esta en curso: un segundo cancel falla.
+        assert!(service.cancel_gateway_audit(&audit_id).await.is_err());
+
+        let finished = sink.finished.lock().unwrap();
+        assert_eq!(finished.len(), 1);
+        assert!(finished[0].cancelled);
+        assert_eq!(finished[0].audit_id, audit_id);
+        let progress = sink.progress.lock().unwrap();
+        assert!(!progress.is_empty() && progress.len() < 200);
+        assert!(progress.iter().all(|e| e.audit_id == audit_id));
+    }
+
     #[tokio::test]
     async fn brute_force_gateway_debe_delegar_al_puerto() {
         let last_ip = Arc::new(Mutex::new(None));
         let last_creds = Arc::new(Mutex::new(None));
-        let service = AuditService::new(Arc::new(MockRouterAuditor {
+        let service = service_with(Arc::new(MockRouterAuditor {
             last_ip: last_ip.clone(),
             last_creds,
         }));
 
-        let result = service.brute_force_gateway("192.168.1.1".to_string()).await;
+        let sink = Arc::new(MemorySink::default());
+        let result = service.brute_force_gateway("192.168.1.1".to_string(), sink.clone()).await;
 
         assert!(result.vulnerable);
-        assert_eq!(result.credentials_found.as_deref(), Some("admin:1234"));
+        let finished = sink.finished.lock().unwrap();
+        assert_eq!(finished.len(), 1);
+        assert!(!finished[0].cancelled);
+        // La clave vuelve tal cual, con sus ':' (nada que partir en el frontend).
+        let found = result.credentials_found.clone().unwrap();
+        assert_eq!((found.user.as_str(), found.pass.as_str()), ("admin", "12:34"));
+        assert_eq!(finished[0].credentials_found, Some(found));
         assert_eq!(last_ip.lock().unwrap().as_deref(), Some("192.168.1.1"));
+        // El resultado sincrono tambien lleva el id con el que se guardaron sus evidencias.
+        let audit_id = result.audit_id.as_deref().unwrap();
+        assert_eq!(audit_id, finished[0].audit_id);
+        assert_eq!(service.list_evidence(audit_id).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn evidence_from_the_adapter_is_stored_under_the_audit_id() {
+        let service = service_with(Arc::new(MockRouterAuditor {
+            last_ip: Arc::new(Mutex::new(None)),
+            last_creds: Arc::new(Mutex::new(None)),
+        }));
+        let sink = Arc::new(MemorySink::default());
+
+        let audit_id = service.start_gateway_audit("192.168.1.1".to_string(), sink.clone()).await;
+        for _ in 0..100 {
+            if !sink.finished.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let items = service.list_evidence(&audit_id).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "landing.html");
+        let (_, content) = service.get_evidence(&audit_id, "landing.html").unwrap();
+        assert_eq!(content, b"<html>panel</html>");
+        assert!(service.list_evidence("router_audit_otro").unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn extract_router_data_debe_formatear_credenciales() {
+    async fn extract_router_data_pasa_credenciales_tipadas_sin_partir_la_clave() {
         let last_ip = Arc::new(Mutex::new(None));
         let last_creds = Arc::new(Mutex::new(None));
-        let service = AuditService::new(Arc::new(MockRouterAuditor {
+        let service = service_with(Arc::new(MockRouterAuditor {
             last_ip: last_ip.clone(),
             last_creds: last_creds.clone(),
         }));
 
+        let creds = RouterCredentials {
+            user: "admin".to_string(),
+            pass: "p4ss:w0rd".to_string(),
+        };
         let devices = service
-            .extract_router_data(
-                "192.168.1.1".to_string(),
-                "admin".to_string(),
-                "1234".to_string(),
-            )
-            .await;
+            .extract_router_data("192.168.1.1".to_string(), Some(creds.clone()))
+            .await
+            .unwrap();
 
         assert_eq!(devices.len(), 1);
         assert_eq!(last_ip.lock().unwrap().as_deref(), Some("192.168.1.1"));
-        assert_eq!(last_creds.lock().unwrap().as_deref(), Some("admin:1234"));
+        assert_eq!(last_creds.lock().unwrap().as_ref(), Some(&creds));
+    }
+
+    #[tokio::test]
+    async fn extract_router_data_sin_credenciales_usa_las_guardadas() {
+        let last_creds = Arc::new(Mutex::new(None));
+        let store = Arc::new(MemoryCredentialStore::default());
+        let service = AuditService::new(
+            Arc::new(MockRouterAuditor {
+                last_ip: Arc::new(Mutex::new(None)),
+                last_creds: last_creds.clone(),
+            }),
+            store.clone(),
+            Arc::new(MemoryEvidenceRepository::default()),
+        );
+
+        let missing = service.extract_router_data("192.168.1.1".to_string(), None).await;
+        assert!(missing.is_err());
+        assert!(last_creds.lock().unwrap().is_none());
+
+        store
+            .save_gateway_credentials(GatewayCredentials {
+                gateway_ip: "192.168.1.1".to_string(),
+                user: "admin".to_string(),
+                pass: "1234".to_string(),
+                saved_at: 0,
+            })
+            .await
+            .unwrap();
+        let devices = service.extract_router_data("192.168.1.1".to_string(), None).await.unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(last_creds.lock().unwrap().as_ref().map(|c| c.pass.as_str()), Some("1234"));
     }
 }
 // src-tauri/src/application/audit_service.rs
@@ -78,6 +78,10 @@ mod tests {
             signal_rate: None,
             wifi_band: None,
             open_ports: None,
+            model: None,
+            firmware: None,
+            interface_type: None,
+            active: None,
         }
     }
 
@@ -0,0 +1,287 @@
+// src-tauri/src/application/port_history_service.rs
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tokio::sync::Mutex;
+
+use crate::domain::entities::{Device, OpenPort, PortAuditRecord, PortAuditReport, PortChangeEvent};
+use crate::domain::ports::PortAuditRepositoryPort;
+
+// Destino de los eventos de cambio (Tauri en produccion, memoria en tests).
+pub trait PortChangeEventSink: Send + Sync {
+    fn on_change(&self, evt: PortChangeEvent);
+}
+
+pub struct TauriPortChangeSink {
+    app: tauri::AppHandle,
+}
+
+impl TauriPortChangeSink {
+    pub fn new(app: tauri::AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl PortChangeEventSink for TauriPortChangeSink {
+    fn on_change(&self, evt: PortChangeEvent) {
+        use tauri::Emitter;
+
+        // Best-effort: el UI puede estar cerrado o no escuchar eventos aun.
+        let _ = self.app.emit("port-audit-changed", evt);
+    }
+}
+
+// Persistencia de auditorias de puertos por dispositivo y deteccion de cambios entre auditorias.
+pub struct PortHistoryService {
+    repo: Arc<dyn PortAuditRepositoryPort>,
+    // Un candado por dispositivo: leer la auditoria anterior y guardar la nueva es una sola operacion.
+    device_locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+impl PortHistoryService {
+    pub fn new(repo: Arc<dyn PortAuditRepositoryPort>) -> Self {
+        Self {
+            repo,
+            device_locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // La MAC sobrevive a cambios de IP por DHCP; sin MAC usamos la IP como clave de respaldo.
+    pub fn device_key(ip: &str, mac: Option<&str>) -> String {
+        match mac.map(normalize_mac) {
+            Some(mac) if !mac.is_empty() && mac != "00:00:00:00:00:00" => mac,
+            _ => format!("ip:{}", ip.trim()),
+        }
+    }
+
+    // Guarda la auditoria y, si habia una anterior, emite los puertos abiertos/cerrados desde entonces.
+    pub async fn record_audit(
+        &self,
+        report: &PortAuditReport,
+        mac: Option<String>,
+        sink: &dyn PortChangeEventSink,
+    ) -> Result<Option<PortChangeEvent>, String> {
+        let device_key = Self::device_key(&report.target_ip, mac.as_deref());
+        // Dos auditorias simultaneas del mismo equipo comparaban contra la misma anterior (y una se perdia).
+        let device_lock = Arc::clone(self.device_locks.lock().await.entry(device_key.clone()).or_default());
+        let _guard = device_lock.lock().await;
+        let previous = self.repo.list_records(&device_key).await?.into_iter().next();
+
+        let record = PortAuditRecord {
+            device_key: device_key.clone(),
+            ip: report.target_ip.clone(),
+            mac: mac.map(|m| normalize_mac(&m)),
+            timestamp: now_millis(),
+            scan_mode: report.scan_mode,
+            risk_level: report.risk_level.clone(),
+            open_ports: report.open_ports.clone(),
+        };
+        self.repo.save_record(record.clone()).await?;
+
+        // Primera auditoria del equipo: es la linea base, no hay cambios que notificar.
+        let Some(previous) = previous else {
+            return Ok(None);
+        };
+        let (opened, closed) = diff_ports(&previous.open_ports, &record.open_ports);
+        if opened.is_empty() && closed.is_empty() {
+            return Ok(None);
+        }
+
+        for p in &opened {
+            println!("🚨 [APP] {} ({}) expone un puerto nuevo: {} {}", record.ip, device_key, p.port, p.service);
+        }
+
+        let evt = PortChangeEvent {
+            device_key,
+            ip: record.ip,
+            mac: record.mac,
+            timestamp: record.timestamp,
+            previous_timestamp: previous.timestamp,
+            opened,
+            closed,
+        };
+        sink.on_change(evt.clone());
+        Ok(Some(evt))
+    }
+
+    pub async fn get_history(&self, ip: &str, mac: Option<&str>) -> Vec<PortAuditRecord> {
+        self.repo
+            .list_records(&Self::device_key(ip, mac))
+            .await
+            .unwrap_or_default()
+    }
+
+    // Rellena `open_ports` con la ultima auditoria conocida para que el historial de sesiones la conserve.
+    pub async fn attach_latest_ports(&self, devices: Vec<Device>) -> Vec<Device> {
+        let mut out = Vec::with_capacity(devices.len());
+        for mut device in devices {
+            if device.open_ports.is_none() {
+                let mac = Some(device.mac.as_str());
+                if let Some(latest) = self.get_history(&device.ip, mac).await.into_iter().next() {
+                    device.open_ports = Some(latest.open_ports);
+                }
+            }
+            out.push(device);
+        }
+        out
+    }
+}
+
+fn diff_ports(previous: &[OpenPort], current: &[OpenPort]) -> (Vec<OpenPort>, Vec<OpenPort>) {
+    let before: HashSet<u16> = previous.iter().map(|p| p.port).collect();
+    let after: HashSet<u16> = current.iter().map(|p| p.port).collect();
+
+    let mut opened: Vec<OpenPort> = current.iter().filter(|p| !before.contains(&p.port)).cloned().collect();
+    let mut closed: Vec<OpenPort> = previous.iter().filter(|p| !after.contains(&p.port)).cloned().collect();
+    opened.sort_by_key(|p| p.port);
+    closed.sort_by_key(|p| p.port);
+    (opened, closed)
+}
+
+fn normalize_mac(mac: &str) -> String {
+    mac.trim().replace('-', ":").to_uppercase()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::PortScanMode;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryRepo {
+        records: Mutex<Vec<PortAuditRecord>>,
+        // Simula un disco lento: abre la ventana entre leer la anterior y guardar la nueva.
+        save_delay: std::time::Duration,
+    }
+
+    #[async_trait]
+    impl PortAuditRepositoryPort for MemoryRepo {
+        async fn save_record(&self, record: PortAuditRecord) -> Result<(), String> {
+            tokio::time::sleep(self.save_delay).await;
+            self.records.lock().unwrap().insert(0, record);
+            Ok(())
+        }
+
+        async fn list_records(&self, device_key: &str) -> Result<Vec<PortAuditRecord>, String> {
+            Ok(self
+                .records
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|r| r.device_key == device_key)
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[derive(Default)]
+    struct MemorySink {
+        events: Mutex<Vec<PortChangeEvent>>,
+    }
+
+    impl PortChangeEventSink for MemorySink {
+        fn on_change(&self, evt: PortChangeEvent) {
+            self.events.lock().unwrap().push(evt);
+        }
+    }
+
+    fn port(port: u16, service: &str) -> OpenPort {
+        OpenPort {
+            port,
+            status: "Open".to_string(),
+            service: service.to_string(),
+            risk_level: "LOW".to_string(),
+            description: None,
+            vulnerability: None,
+            version: None,
+        }
+    }
+
+    fn report(ip: &str, ports: Vec<OpenPort>) -> PortAuditReport {
+        PortAuditReport {
+            target_ip: ip.to_string(),
+            open_ports: ports,
+            filtered_ports: vec![],
+            scan_mode: PortScanMode::Connect,
+            risk_level: "LOW".to_string(),
+            vulnerabilities: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn second_audit_emits_opened_and_closed_ports() {
+        let service = PortHistoryService::new(Arc::new(MemoryRepo::default()));
+        let sink = MemorySink::default();
+        let mac = Some("aa-bb-cc-dd-ee-ff".to_string());
+
+        let first = service
+            .record_audit(&report("192.168.1.30", vec![port(80, "HTTP"), port(554, "RTSP")]), mac.clone(), &sink)
+            .await
+            .unwrap();
+        assert!(first.is_none(), "la primera auditoria es linea base");
+
+        // La camara cambia de IP (DHCP) y empieza a exponer Telnet.
+        let evt = service
+            .record_audit(&report("192.168.1.31", vec![port(23, "TELNET"), port(80, "HTTP")]), mac, &sink)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(evt.device_key, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(evt.opened.iter().map(|p| p.port).collect::<Vec<_>>(), vec![23]);
+        assert_eq!(evt.closed.iter().map(|p| p.port).collect::<Vec<_>>(), vec![554]);
+        assert_eq!(sink.events.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn unchanged_audit_does_not_emit_and_ip_key_is_fallback() {
+        let service = PortHistoryService::new(Arc::new(MemoryRepo::default()));
+        let sink = MemorySink::default();
+
+        for _ in 0..2 {
+            let evt = service
+                .record_audit(&report("192.168.1.40", vec![port(22, "SSH")]), None, &sink)
+                .await
+                .unwrap();
+            assert!(evt.is_none());
+        }
+
+        assert!(sink.events.lock().unwrap().is_empty());
+        assert_eq!(service.get_history("192.168.1.40", None).await.len(), 2);
+        assert_eq!(PortHistoryService::device_key("192.168.1.40", None), "ip:192.168.1.40");
+    }
+
+    #[tokio::test]
+    async fn concurrent_audits_of_a_device_compare_against_each_other() {
+        let repo = MemoryRepo {
+            save_delay: std::time::Duration::from_millis(20),
+            ..MemoryRepo::default()
+        };
+        let service = PortHistoryService::new(Arc::new(repo));
+        let sink = MemorySink::default();
+        let mac = Some("AA:BB:CC:DD:EE:01".to_string());
+        service.record_audit(&report("192.168.1.50", vec![port(80, "HTTP")]), mac.clone(), &sink).await.unwrap();
+
+        // Las dos ven Telnet nuevo; solo la primera lo es respecto a la auditoria anterior.
+        let telnet = report("192.168.1.50", vec![port(23, "TELNET"), port(80, "HTTP")]);
+        let (a, b) = tokio::join!(
+            service.record_audit(&telnet, mac.clone(), &sink),
+            service.record_audit(&telnet, mac.clone(), &sink)
+        );
+
+        assert_eq!([a.unwrap().is_some(), b.unwrap().is_some()], [true, false]);
+        assert_eq!(sink.events.lock().unwrap().len(), 1);
+        assert_eq!(service.get_history("192.168.1.50", mac.as_deref()).await.len(), 3);
+    }
+}
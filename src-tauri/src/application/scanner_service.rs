@@ -1,19 +1,23 @@
 // src-tauri/src/application/scanner_service.rs
 
 use crate::domain::{
-    entities::{Device, OpenPort},
-    ports::NetworkScannerPort,
+    entities::{Device, OpenPort, PortAuditReport, PortScanMode, Vulnerability},
+    ports::{NetworkScannerPort, ServiceProbePort},
 };
 use crate::infrastructure::network::service_dictionary::ServiceDictionary;
 use std::sync::Arc;
 
 pub struct ScannerService {
     scanner_port: Arc<dyn NetworkScannerPort>,
+    service_probe: Arc<dyn ServiceProbePort>,
 }
 
 impl ScannerService {
-    pub fn new(scanner_port: Arc<dyn NetworkScannerPort>) -> Self {
-        Self { scanner_port }
+    pub fn new(scanner_port: Arc<dyn NetworkScannerPort>, service_probe: Arc<dyn ServiceProbePort>) -> Self {
+        Self {
+            scanner_port,
+            service_probe,
+        }
     }
 
     pub async fn run_network_scan(&self, subnet: Option<String>) -> Vec<Device> {
@@ -37,13 +41,14 @@ impl ScannerService {
         self.scanner_port.scan_network(&final_base).await
     }
 
-    pub async fn audit_ip(&self, ip: String) -> (Vec<OpenPort>, String) {
-        println!("🧠 [APP] Auditando puertos de {}", ip);
+    pub async fn audit_ip(&self, ip: String, mode: PortScanMode) -> PortAuditReport {
+        println!("🧠 [APP] Auditando puertos de {} (modo {:?})", ip, mode);
 
-        let raw_ports = self.scanner_port.scan_ports(&ip).await;
+        let outcome = self.scanner_port.scan_ports(&ip, mode).await;
 
         // Enriquecemos los datos usando `ServiceDictionary`.
-        let enriched_ports: Vec<OpenPort> = raw_ports
+        let mut enriched_ports: Vec<OpenPort> = outcome
+            .open_ports
             .into_iter()
             .map(|mut p| {
                 let info = ServiceDictionary::lookup(p.port);
@@ -51,31 +56,64 @@ impl ScannerService {
                 p.service = info.name.to_string();
                 p.risk_level = info.risk.to_string();
                 p.description = Some(info.description.to_string());
-
-                // Si el risc és crític, afegim una "vulnerabilitat" automàtica
-                // (Aquí podries connectar amb una BD de CVEs en el futur)
-                
                 p
             })
             .collect();
 
+        // Sondas activas: confirmamos la exposicion hablando el protocolo (FTP anonimo, relay SMTP...).
+        let mut vulnerabilities: Vec<Vulnerability> = Vec::new();
+        for p in enriched_ports.iter_mut() {
+            let Some(probe) = self.service_probe.probe_service(&ip, p.port).await else {
+                continue;
+            };
+            p.version = probe.version;
+            if let Some(worst) = probe
+                .vulnerabilities
+                .iter()
+                .max_by_key(|v| severity_rank(&v.severity))
+            {
+                if severity_rank(&worst.severity) > severity_rank(&p.risk_level) {
+                    p.risk_level = worst.severity.clone();
+                }
+                p.vulnerability = Some(worst.clone());
+            }
+            vulnerabilities.extend(probe.vulnerabilities);
+        }
+
         // Calculamos el riesgo global del dispositivo.
         let mut global_risk = "SAFE";
         if !enriched_ports.is_empty() {
             global_risk = "LOW";
         }
-
-        for p in &enriched_ports {
-            if p.risk_level == "CRITICAL" {
-                global_risk = "CRITICAL";
-                break;
-            }
-            if p.risk_level == "HIGH" && global_risk != "CRITICAL" {
-                global_risk = "HIGH";
+        for risk in enriched_ports
+            .iter()
+            .map(|p| p.risk_level.as_str())
+            .chain(vulnerabilities.iter().map(|v| v.severity.as_str()))
+        {
+            if severity_rank(risk) > severity_rank(global_risk) {
+                global_risk = risk;
             }
         }
 
-        (enriched_ports, global_risk.to_string())
+        PortAuditReport {
+            target_ip: ip.clone(),
+            risk_level: global_risk.to_string(),
+            open_ports: enriched_ports,
+            filtered_ports: outcome.filtered_ports,
+            scan_mode: outcome.mode_used,
+            vulnerabilities,
+        }
+    }
+}
+
+// Orden de severidad compartido por diccionario y sondas ("Unknown" u otros valores cuentan como 0).
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "LOW" => 1,
+        "MEDIUM" => 2,
+        "HIGH" => 3,
+        "CRITICAL" => 4,
+        _ => 0,
     }
 }
 
@@ -83,7 +121,7 @@ impl ScannerService {
 #[cfg(test)]
 mod tests {
     use super::*; 
-    use crate::domain::entities::{Device, OpenPort};
+    use crate::domain::entities::{Device, OpenPort, PortScanOutcome, ServiceProbeResult};
     use async_trait::async_trait;
     use std::sync::Arc;
 
@@ -106,6 +144,10 @@ mod tests {
                     signal_rate: None,
                     wifi_band: None,
                     open_ports: None,
+                    model: None,
+                    firmware: None,
+                    interface_type: None,
+                    active: None,
                 },
                 Device {
                     ip: "192.168.1.50".to_string(),
@@ -119,12 +161,17 @@ mod tests {
                     signal_rate: None,
                     wifi_band: None,
                     open_ports: None,
+                    model: None,
+                    firmware: None,
+                    interface_type: None,
+                    active: None,
                 },
             ]
         }
 
-        async fn scan_ports(&self, ip: &str) -> Vec<OpenPort> {
-            if ip == "192.168.1.1" {
+        // Simula un host sin privilegios: SYN cae siempre a connect.
+        async fn scan_ports(&self, ip: &str, _mode: PortScanMode) -> PortScanOutcome {
+            let open_ports = if ip == "192.168.1.1" {
                 vec![OpenPort {
                     port: 23,
                     status: "Open".to_string(),
@@ -132,19 +179,56 @@ mod tests {
                     risk_level: "Unknown".to_string(),
                     description: None,
                     vulnerability: None,
+                    version: None,
                 }]
             } else {
                 vec![]
+            };
+            PortScanOutcome {
+                open_ports,
+                filtered_ports: vec![],
+                mode_used: PortScanMode::Connect,
             }
         }
     }
 
+    struct NoProbe;
+
+    #[async_trait]
+    impl ServiceProbePort for NoProbe {
+        async fn probe_service(&self, _ip: &str, _port: u16) -> Option<ServiceProbeResult> {
+            None
+        }
+    }
+
+    // Simula un Telnet con prompt de login expuesto.
+    struct TelnetProbe;
+
+    #[async_trait]
+    impl ServiceProbePort for TelnetProbe {
+        async fn probe_service(&self, _ip: &str, port: u16) -> Option<ServiceProbeResult> {
+            Some(ServiceProbeResult {
+                port,
+                service: "TELNET".to_string(),
+                version: Some("MikroTik".to_string()),
+                banner: Some("MikroTik v6.49\nLogin:".to_string()),
+                vulnerabilities: vec![Vulnerability {
+                    id: "TELNET-LOGIN-EXPOSED".to_string(),
+                    description: "Telnet expone login".to_string(),
+                    severity: "CRITICAL".to_string(),
+                    recommendation: "Desactivar Telnet".to_string(),
+                    evidence: Some("Login:".to_string()),
+                }],
+            })
+        }
+    }
+
     // --- TESTS REALS ---
 
     #[tokio::test]
     async fn test_scan_network_flow() {
         let mock_infra = Arc::new(MockScanner);
-        let service = ScannerService::new(mock_infra);
+        let service = ScannerService::new(mock_infra, Arc::new(NoProbe));
 
         let devices = service
             .run_network_scan(Some("192.168.1.0/24".to_string()))
@@ -157,12 +241,27 @@ mod tests {
     #[tokio::test]
     async fn test_risk_calculation_logic() {
         let mock_infra = Arc::new(MockScanner);
-        let service = ScannerService::new(mock_infra);
+        let service = ScannerService::new(mock_infra, Arc::new(NoProbe));
+
+        let report = service.audit_ip("192.168.1.1".to_string(), PortScanMode::Connect).await;
+
+        assert_eq!(report.open_ports.len(), 1);
+        assert_eq!(report.open_ports[0].service, "TELNET"); // El diccionario convierte el 23 en TELNET.
+        assert_eq!(report.risk_level, "CRITICAL");
+        assert!(report.vulnerabilities.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_audit_attaches_probe_findings() {
+        let service = ScannerService::new(Arc::new(MockScanner), Arc::new(TelnetProbe));
 
-        let (ports, risk_global) = service.audit_ip("192.168.1.1".to_string()).await;
+        let report = service.audit_ip("192.168.1.1".to_string(), PortScanMode::Syn).await;
 
-        assert_eq!(ports.len(), 1);
-        assert_eq!(ports[0].service, "TELNET"); // El diccionario convierte el 23 en TELNET.
-        assert_eq!(risk_global, "CRITICAL");
+        assert_eq!(report.scan_mode, PortScanMode::Connect); // el adaptador informa del fallback
+        let port = &report.open_ports[0];
+        assert_eq!(port.version.as_deref(), Some("MikroTik"));
+        assert_eq!(port.vulnerability.as_ref().unwrap().id, "TELNET-LOGIN-EXPOSED");
+        assert_eq!(report.vulnerabilities.len(), 1);
+        assert_eq!(report.vulnerabilities[0].evidence.as_deref(), Some("Login:"));
     }
 }
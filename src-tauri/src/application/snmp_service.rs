@@ -0,0 +1,181 @@
+// src-tauri/src/application/snmp_service.rs
+
+use std::sync::Arc;
+
+use crate::domain::entities::{Device, SnmpAuditResult, Vulnerability};
+use crate::domain::ports::SnmpAuditorPort;
+
+pub struct SnmpService {
+    auditor: Arc<dyn SnmpAuditorPort>,
+}
+
+impl SnmpService {
+    pub fn new(auditor: Arc<dyn SnmpAuditorPort>) -> Self {
+        Self { auditor }
+    }
+
+    pub async fn audit_snmp(&self, ip: String) -> SnmpAuditResult {
+        println!("🧠 [APP] Auditando exposicion SNMP de {}", ip);
+        let mut result = self.auditor.probe_snmp(&ip).await;
+        classify_exposure(&mut result);
+        result
+    }
+
+    // Aplica al dispositivo lo aprendido por SNMP sin pisar datos que ya tenia.
+    pub fn enrich_device(mut device: Device, result: &SnmpAuditResult) -> Device {
+        if device.model.is_none() {
+            device.model = result.model.clone();
+        }
+        if device.firmware.is_none() {
+            device.firmware = result.firmware.clone();
+        }
+        let sys_name = result.system.as_ref().and_then(|s| s.name.clone());
+        if device.hostname.is_none() {
+            device.hostname = sys_name.clone();
+        }
+        if device.name.is_none() {
+            device.name = sys_name;
+        }
+        device
+    }
+}
+
+// Convierte communities aceptadas en vulnerabilidades y calcula el riesgo global.
+fn classify_exposure(result: &mut SnmpAuditResult) {
+    result.vulnerabilities.clear();
+
+    for access in &result.communities {
+        let vulnerability = if access.writable == Some(true) {
+            Vulnerability {
+                id: "SNMP-WRITABLE-COMMUNITY".to_string(),
+                description: format!(
+                    "SNMP {} acepta la community '{}' con permisos de ESCRITURA: cualquiera en la LAN puede reconfigurar el equipo.",
+                    access.version, access.community
+                ),
+                severity: "CRITICAL".to_string(),
+                recommendation: "Eliminar communities de escritura o migrar a SNMPv3 con autenticacion y cifrado.".to_string(),
+                evidence: Some(format!("SET sysContact con community '{}' ({}) aceptado", access.community, access.version)),
+            }
+        } else {
+            Vulnerability {
+                id: "SNMP-READABLE-COMMUNITY".to_string(),
+                description: format!(
+                    "SNMP {} responde con la community '{}': expone modelo, firmware e interfaces en texto plano.",
+                    access.version, access.community
+                ),
+                severity: "HIGH".to_string(),
+                recommendation: "Cambiar la community por defecto, restringir por ACL o desactivar SNMP v1/v2c.".to_string(),
+                evidence: Some(format!(
+                    "GET sysDescr con community '{}' ({}) respondido{}",
+                    access.community,
+                    access.version,
+                    if access.writable.is_none() { "; escritura no comprobada (sysContact ilegible)" } else { "" }
+                )),
+            }
+        };
+        result.vulnerabilities.push(vulnerability);
+    }
+
+    result.risk_level = if result.communities.iter().any(|c| c.writable == Some(true)) {
+        "CRITICAL"
+    } else if !result.communities.is_empty() {
+        "HIGH"
+    } else {
+        "SAFE"
+    }
+    .to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{SnmpCommunityAccess, SnmpSystemInfo};
+    use async_trait::async_trait;
+
+    struct MockSnmpAuditor {
+        writable: Option<bool>,
+    }
+
+    #[async_trait]
+    impl SnmpAuditorPort for MockSnmpAuditor {
+        async fn probe_snmp(&self, ip: &str) -> SnmpAuditResult {
+            SnmpAuditResult {
+                target_ip: ip.to_string(),
+                communities: vec![SnmpCommunityAccess {
+                    community: "public".to_string(),
+                    version: "v2c".to_string(),
+                    writable: self.writable,
+                }],
+                system: Some(SnmpSystemInfo {
+                    name: Some("PRINTER-01".to_string()),
+                    ..Default::default()
+                }),
+                interfaces: Vec::new(),
+                model: Some("Brother NC-8300h".to_string()),
+                firmware: Some("1.13".to_string()),
+                vulnerabilities: Vec::new(),
+                risk_level: "UNKNOWN".to_string(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn audit_snmp_marca_community_de_escritura_como_critica() {
+        let service = SnmpService::new(Arc::new(MockSnmpAuditor { writable: Some(true) }));
+        let result = service.audit_snmp("192.168.1.40".to_string()).await;
+
+        assert_eq!(result.risk_level, "CRITICAL");
+        assert_eq!(result.vulnerabilities.len(), 1);
+        assert_eq!(result.vulnerabilities[0].severity, "CRITICAL");
+    }
+
+    #[tokio::test]
+    async fn audit_snmp_community_de_lectura_es_high() {
+        let service = SnmpService::new(Arc::new(MockSnmpAuditor { writable: Some(false) }));
+        let result = service.audit_snmp("192.168.1.40".to_string()).await;
+
+        assert_eq!(result.risk_level, "HIGH");
+        assert_eq!(result.vulnerabilities[0].id, "SNMP-READABLE-COMMUNITY");
+    }
+
+    #[tokio::test]
+    async fn enrich_device_aplica_modelo_firmware_y_nombre() {
+        let service = SnmpService::new(Arc::new(MockSnmpAuditor { writable: Some(false) }));
+        let result = service.audit_snmp("192.168.1.40".to_string()).await;
+
+        let device = Device {
+            ip: "192.168.1.40".to_string(),
+            mac: "00:1B:A9:01:02:03".to_string(),
+            vendor: "Brother".to_string(),
+            hostname: None,
+            name: Some("Impresora planta 1".to_string()),
+            is_gateway: false,
+            ping: None,
+            signal_strength: None,
+            signal_rate: None,
+            wifi_band: None,
+            open_ports: None,
+            model: None,
+            firmware: None,
+            interface_type: None,
+            active: None,
+        };
+
+        let enriched = SnmpService::enrich_device(device.clone(), &result);
+        assert_eq!(enriched.model.as_deref(), Some("Brother NC-8300h"));
+        assert_eq!(enriched.firmware.as_deref(), Some("1.13"));
+        assert_eq!(enriched.hostname.as_deref(), Some("PRINTER-01"));
+        // El alias puesto por el usuario se respeta.
+        assert_eq!(enriched.name.as_deref(), Some("Impresora planta 1"));
+
+        // Modelo/firmware ya conocidos (p. ej. del fingerprint del router) tampoco se pisan.
+        let known = Device {
+            model: Some("HL-L8360CDW".to_string()),
+            firmware: Some("2.0".to_string()),
+            ..device
+        };
+        let enriched = SnmpService::enrich_device(known, &result);
+        assert_eq!(enriched.model.as_deref(), Some("HL-L8360CDW"));
+        assert_eq!(enriched.firmware.as_deref(), Some("2.0"));
+    }
+}
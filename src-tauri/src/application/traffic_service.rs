@@ -1,56 +1,199 @@
 // src-tauri/src/application/traffic_service.rs
 
-use crate::infrastructure::network::traffic_sniffer::TrafficSniffer;
+use crate::domain::entities::{
+    ArpWatchReport, BandwidthStats, BandwidthWindow, CaptureAnalysis, CaptureFileStatus, Device, DeviceDomainLog,
+    DeviceTlsLog, DeviceUserAgents, TrafficFlow,
+};
+use crate::infrastructure::network::traffic_sniffer::capture_file::{CaptureLimits, PcapRecorder};
+use crate::infrastructure::network::traffic_sniffer::filter::CaptureFilter;
+use crate::infrastructure::network::traffic_sniffer::flows::FlowTable;
+use crate::infrastructure::network::traffic_sniffer::{CaptureState, SnifferEvent, TrafficSniffer};
 
 use crate::infrastructure::repositories::local_intelligence;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter};
 
 pub struct TrafficService {
     is_running: Arc<AtomicBool>,
+    // Interfaz del monitor en curso: la grabacion PCAPNG la describe en su cabecera.
+    interface: Mutex<Option<String>>,
+    // Grabacion, filtro (releido en cada paquete), conversaciones y log DNS del hilo de captura.
+    capture: CaptureState,
 }
 
 impl TrafficService {
     pub fn new() -> Self {
-        Self { is_running: Arc::new(AtomicBool::new(false)) }
+        Self {
+            is_running: Arc::new(AtomicBool::new(false)),
+            interface: Mutex::new(None),
+            capture: CaptureState::new(),
+        }
     }
 
-    pub fn start_monitoring(&self, app_handle: AppHandle) -> Result<(), String> {
+    pub fn start_monitoring(&self, app_handle: AppHandle, filter: Option<String>) -> Result<(), String> {
         if self.is_running.load(Ordering::Relaxed) {
             return Err("El monitor ya esta en marcha".to_string());
         }
+        self.set_filter(filter)?;
 
         // 1) Obtener la IP real (para no conectarnos a Hyper-V).
         let identity = local_intelligence::get_host_identity()?;
         let target_ip = identity.ip;
+        // Sin escaneo previo, el vigilante ARP sabe al menos cual es el gateway.
+        self.capture.set_arp_gateway(&identity.gateway_ip)?;
 
         // Preflight: valida que podemos abrir el canal antes de marcar como "running".
-        TrafficSniffer::preflight("auto", &target_ip)?;
+        let interface = TrafficSniffer::preflight("auto", &target_ip)?;
+        if let Ok(mut guard) = self.interface.lock() {
+            *guard = Some(interface);
+        }
 
         println!("🚀 [APP] Iniciando monitor de trafico sobre IP: {}...", target_ip);
         
         self.is_running.store(true, Ordering::Relaxed);
         let running_clone = self.is_running.clone();
 
-        let callback = move |packet| {
-            let _ = app_handle.emit("traffic-event", packet);
+        // Cada sesion de monitor empieza con la tabla de conversaciones vacia.
+        if let Ok(mut flows) = self.capture.flows.lock() {
+            *flows = FlowTable::new();
+        }
+
+        let callback = move |event| match event {
+            SnifferEvent::Packets(packets) => {
+                let _ = app_handle.emit("traffic-packets", packets);
+            }
+            SnifferEvent::Flows(flows) => {
+                let _ = app_handle.emit("traffic-flows", flows);
+            }
+            SnifferEvent::Bandwidth(summary) => {
+                let _ = app_handle.emit("traffic-bandwidth", summary);
+            }
+            SnifferEvent::ArpAlert(alert) => {
+                let _ = app_handle.emit("traffic-arp-alert", alert);
+            }
         };
 
         // Pasamos la IP al sniffer.
-        TrafficSniffer::start_capture("auto".to_string(), target_ip, running_clone, callback);
+        TrafficSniffer::start_capture(
+            "auto".to_string(),
+            target_ip,
+            running_clone,
+            self.capture.clone(),
+            callback,
+        );
         Ok(())
     }
 
     pub fn stop_monitoring(&self) {
         println!("🛑 [APP] Deteniendo monitor...");
         self.is_running.store(false, Ordering::Relaxed);
+        // Sin monitor no llegan tramas: cerramos el fichero para que quede completo en disco.
+        if let Err(e) = self.stop_capture_file() {
+            eprintln!("❌ [APP] Error al cerrar la grabacion: {e}");
+        }
+    }
+
+    // Cambia el filtro en caliente; `None` o vacio vuelve al filtro por defecto (mi trafico + interceptado).
+    pub fn set_filter(&self, filter: Option<String>) -> Result<(), String> {
+        let compiled = match filter.as_deref().map(str::trim) {
+            Some(expr) if !expr.is_empty() => Some(CaptureFilter::compile(expr)?),
+            _ => None,
+        };
+        if let Some(filter) = &compiled {
+            println!("🔎 [APP] Filtro de captura: {}", filter.source());
+        }
+        let mut guard = self.capture.filter.write().map_err(|_| "Estado de filtro corrupto".to_string())?;
+        *guard = compiled;
+        Ok(())
+    }
+
+    // Conversaciones vivas (las mas recientes primero) y despues las ya cerradas.
+    pub fn get_flows(&self) -> Result<Vec<TrafficFlow>, String> {
+        let flows = self.capture.flows.lock().map_err(|_| "Estado de flujos corrupto".to_string())?;
+        Ok(flows.snapshot())
+    }
+
+    // Dominios consultados por equipo (todos los monitores de esta ejecucion); `device_ip` filtra uno.
+    pub fn get_dns_log(&self, device_ip: Option<&str>) -> Result<Vec<DeviceDomainLog>, String> {
+        self.capture.dns_log(device_ip)
+    }
+
+    // SNI, ALPN y huella JA3 de los clientes TLS/QUIC de cada equipo.
+    pub fn get_tls_log(&self, device_ip: Option<&str>) -> Result<Vec<DeviceTlsLog>, String> {
+        self.capture.tls_log(device_ip)
+    }
+
+    // User-Agents HTTP en claro por equipo, con la pista de SO/tipo para el clasificador.
+    pub fn get_user_agents(&self, device_ip: Option<&str>) -> Result<Vec<DeviceUserAgents>, String> {
+        self.capture.user_agents(device_ip)
+    }
+
+    // Quien consume el ancho de banda: top-N equipos locales y extremos remotos en la ventana.
+    pub fn get_bandwidth_stats(&self, window: BandwidthWindow, top_n: usize) -> Result<BandwidthStats, String> {
+        self.capture.bandwidth_stats(window, top_n)
+    }
+
+    // Vinculos IP -> MAC vigilados y alertas de ARP spoofing / conflictos MAC-IP.
+    pub fn get_arp_watch(&self) -> Result<ArpWatchReport, String> {
+        self.capture.arp_watch()
+    }
+
+    // El escaneo fija la referencia: un cambio posterior de MAC para esas IPs es sospechoso.
+    pub fn seed_arp_bindings(&self, devices: &[Device]) -> Result<(), String> {
+        self.capture.seed_arp_bindings(devices)
+    }
+
+    // Grabacion PCAPNG en paralelo a los eventos en vivo (requiere el monitor en marcha).
+    pub fn start_capture_file(&self, limits: CaptureLimits) -> Result<CaptureFileStatus, String> {
+        if !self.is_running.load(Ordering::Relaxed) {
+            return Err("Inicia el monitor de trafico antes de grabar".to_string());
+        }
+        let interface = self.interface.lock().map_err(|_| "Estado de interfaz corrupto".to_string())?.clone();
+        let mut guard = self.lock_recorder()?;
+        if guard.is_some() {
+            return Err("Ya hay una grabacion en curso".to_string());
+        }
+        let recorder = PcapRecorder::start(PcapRecorder::default_dir(), interface.as_deref().unwrap_or(""), limits)?;
+        let status = recorder.status();
+        *guard = Some(recorder);
+        Ok(status)
+    }
+
+    pub fn stop_capture_file(&self) -> Result<CaptureFileStatus, String> {
+        match self.lock_recorder()?.take() {
+            Some(recorder) => recorder.finish(),
+            None => Ok(CaptureFileStatus::default()),
+        }
+    }
+
+    pub fn rotate_capture_file(&self) -> Result<CaptureFileStatus, String> {
+        let mut guard = self.lock_recorder()?;
+        let recorder = guard.as_mut().ok_or_else(|| "No hay ninguna grabacion en curso".to_string())?;
+        recorder.rotate()?;
+        Ok(recorder.status())
+    }
+
+    // No depende del monitor: analiza capturas de clientes o de grabaciones anteriores (bloqueante).
+    pub fn analyze_capture_file(
+        path: &Path,
+        focus_ip: Option<&str>,
+        filter: Option<&str>,
+    ) -> Result<CaptureAnalysis, String> {
+        let filter = filter.filter(|f| !f.trim().is_empty()).map(CaptureFilter::compile).transpose()?;
+        TrafficSniffer::analyze_file(path, focus_ip, filter.as_ref())
+    }
+
+    fn lock_recorder(&self) -> Result<std::sync::MutexGuard<'_, Option<PcapRecorder>>, String> {
+        self.capture.recorder.lock().map_err(|_| "Estado de grabacion corrupto".to_string())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::infrastructure::network::traffic_sniffer::test_support;
 
     #[test]
     fn stop_monitoring_sets_running_false() {
@@ -62,9 +205,117 @@ mod tests {
         assert!(!service.is_running.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn capture_file_requires_running_monitor_and_stop_is_idempotent() {
+        let service = TrafficService::new();
+        assert!(service.start_capture_file(CaptureLimits::default()).is_err());
+        assert!(service.rotate_capture_file().is_err());
+        assert!(!service.stop_capture_file().unwrap().active);
+    }
+
+    #[test]
+    fn set_filter_compiles_replaces_and_clears() {
+        let service = TrafficService::new();
+        service.set_filter(Some("host 192.168.1.20 and udp port 53".to_string())).unwrap();
+        assert!(service.capture.filter.read().unwrap().is_some());
+
+        // Una expresion invalida no pisa el filtro vigente.
+        assert!(service.set_filter(Some("port".to_string())).is_err());
+        assert_eq!(
+            service.capture.filter.read().unwrap().as_ref().map(|f| f.source().to_string()).as_deref(),
+            Some("host 192.168.1.20 and udp port 53")
+        );
+
+        service.set_filter(Some("  ".to_string())).unwrap();
+        assert!(service.capture.filter.read().unwrap().is_none());
+    }
+
     #[test]
     fn service_starts_in_stopped_state() {
         let service = TrafficService::new();
         assert!(!service.is_running.load(Ordering::Relaxed));
     }
+
+    fn now() -> u64 {
+        std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis() as u64
+    }
+
+    #[test]
+    fn get_flows_lists_observed_conversations() {
+        let service = TrafficService::new();
+        let query = test_support::dns_query("time.vendor.com");
+        test_support::feed(&service.capture, &test_support::udp_packet("fd00::20", "fd00::1", 53, &query), now());
+
+        let flows = service.get_flows().unwrap();
+        assert_eq!((flows.len(), flows[0].resp_port), (1, Some(53)));
+    }
+
+    #[test]
+    fn get_dns_log_reports_queried_domains() {
+        let service = TrafficService::new();
+        let query = test_support::dns_query("time.vendor.com");
+        test_support::feed(&service.capture, &test_support::udp_packet("fd00::20", "fd00::1", 53, &query), now());
+
+        let log = service.get_dns_log(Some("fd00::20")).unwrap();
+        assert_eq!(log[0].domains[0].domain, "time.vendor.com");
+    }
+
+    #[test]
+    fn get_tls_log_reports_client_hellos() {
+        let service = TrafficService::new();
+        let hello = test_support::tls_client_hello("api.vendor.com");
+        test_support::feed(&service.capture, &test_support::tcp_packet("fd00::20", "2001:db8::5", 443, &hello), now());
+
+        let log = service.get_tls_log(Some("fd00::20")).unwrap();
+        assert_eq!(log[0].clients[0].server_name.as_deref(), Some("api.vendor.com"));
+    }
+
+    #[test]
+    fn get_user_agents_reports_http_clients() {
+        let service = TrafficService::new();
+        let request = test_support::http_get("example.com", "curl/8.4.0");
+        test_support::feed(&service.capture, &test_support::tcp_packet("fd00::20", "2001:db8::5", 80, &request), now());
+
+        let agents = service.get_user_agents(Some("fd00::20")).unwrap();
+        assert_eq!(agents[0].agents[0].user_agent, "curl/8.4.0");
+    }
+
+    #[test]
+    fn get_bandwidth_stats_ranks_local_devices() {
+        let service = TrafficService::new();
+        let request = test_support::http_get("example.com", "curl/8.4.0");
+        // La ventana solo cuenta segundos ya cerrados.
+        let packet = test_support::tcp_packet("fd00::20", "2001:db8::5", 80, &request);
+        test_support::feed(&service.capture, &packet, now() - 1_000);
+
+        let stats = service.get_bandwidth_stats(BandwidthWindow::Minute, 10).unwrap();
+        assert_eq!(stats.devices[0].ip, "fd00::20");
+        assert!(stats.total_bytes > 0);
+    }
+
+    #[test]
+    fn get_arp_watch_reports_seeded_bindings() {
+        let service = TrafficService::new();
+        let router = Device {
+            ip: "192.168.1.1".to_string(),
+            mac: "AA:BB:CC:00:00:01".to_string(),
+            vendor: "Router".to_string(),
+            hostname: None,
+            name: None,
+            is_gateway: true,
+            ping: None,
+            signal_strength: None,
+            signal_rate: None,
+            wifi_band: None,
+            open_ports: None,
+            model: None,
+            firmware: None,
+            interface_type: None,
+            active: None,
+        };
+        service.seed_arp_bindings(&[router]).unwrap();
+
+        let report = service.get_arp_watch().unwrap();
+        assert_eq!(report.bindings[0].ip, "192.168.1.1");
+    }
 }
@@ -0,0 +1,163 @@
+// src-tauri/src/application/upnp_service.rs
+
+use std::sync::Arc;
+
+use crate::domain::entities::{UpnpAuditResult, UpnpPortMapping, Vulnerability};
+use crate::domain::ports::UpnpAuditorPort;
+
+// Servicios que nunca deberian quedar expuestos a Internet via UPnP.
+const DANGEROUS_FORWARDS: &[(u16, &str)] = &[(3389, "RDP"), (445, "SMB"), (139, "SMB/NetBIOS"), (23, "Telnet"), (2323, "Telnet")];
+
+pub struct UpnpService {
+    auditor: Arc<dyn UpnpAuditorPort>,
+}
+
+impl UpnpService {
+    pub fn new(auditor: Arc<dyn UpnpAuditorPort>) -> Self {
+        Self { auditor }
+    }
+
+    // `known_ips`: inventario actual; un mapeo hacia una IP fuera de el se marca como sospechoso.
+    pub async fn audit_upnp(&self, gateway_ip: String, known_ips: &[String]) -> UpnpAuditResult {
+        println!("🧠 [APP] Auditando UPnP IGD del gateway {}", gateway_ip);
+        let mut result = self.auditor.probe_igd(&gateway_ip).await;
+        classify_exposure(&mut result, known_ips);
+        result
+    }
+}
+
+// Convierte IGD activo y mapeos peligrosos en hallazgos del gateway y calcula el riesgo global.
+fn classify_exposure(result: &mut UpnpAuditResult, known_ips: &[String]) {
+    result.vulnerabilities.clear();
+    if !result.igd_found {
+        result.risk_level = "SAFE".to_string();
+        return;
+    }
+
+    result.vulnerabilities.push(Vulnerability {
+        id: "UPNP-IGD-ENABLED".to_string(),
+        description: "El router acepta UPnP IGD: cualquier dispositivo de la LAN (o malware) puede abrir puertos entrantes sin autenticacion.".to_string(),
+        severity: "MEDIUM".to_string(),
+        recommendation: "Desactivar UPnP en el router y abrir manualmente solo los puertos necesarios.".to_string(),
+        evidence: result.control_url.as_ref().map(|url| format!("WANIPConnection accesible en {}", url)),
+    });
+
+    for mapping in result.port_mappings.iter().filter(|m| m.enabled) {
+        if let Some(service) = dangerous_service(mapping) {
+            result.vulnerabilities.push(Vulnerability {
+                id: "UPNP-DANGEROUS-FORWARD".to_string(),
+                description: format!(
+                    "Mapeo UPnP publica {} de {} a Internet (puerto externo {}/{}).",
+                    service, mapping.internal_client, mapping.external_port, mapping.protocol
+                ),
+                severity: "CRITICAL".to_string(),
+                recommendation: format!("Eliminar el mapeo y acceder a {} solo via VPN.", service),
+                evidence: Some(mapping_evidence(mapping)),
+            });
+        }
+        if !known_ips.is_empty() && !known_ips.iter().any(|ip| ip.trim() == mapping.internal_client) {
+            result.vulnerabilities.push(Vulnerability {
+                id: "UPNP-UNKNOWN-CLIENT".to_string(),
+                description: format!(
+                    "Mapeo UPnP hacia {}, que no aparece en el inventario de la red.",
+                    mapping.internal_client
+                ),
+                severity: "HIGH".to_string(),
+                recommendation: "Identificar el equipo que abrio el puerto y revisar si esta comprometido.".to_string(),
+                evidence: Some(mapping_evidence(mapping)),
+            });
+        }
+    }
+
+    result.risk_level = ["CRITICAL", "HIGH", "MEDIUM"]
+        .into_iter()
+        .find(|level| result.vulnerabilities.iter().any(|v| v.severity == *level))
+        .unwrap_or("SAFE")
+        .to_string();
+}
+
+fn dangerous_service(mapping: &UpnpPortMapping) -> Option<&'static str> {
+    DANGEROUS_FORWARDS
+        .iter()
+        .find(|(port, _)| *port == mapping.internal_port || *port == mapping.external_port)
+        .map(|(_, name)| *name)
+}
+
+fn mapping_evidence(mapping: &UpnpPortMapping) -> String {
+    format!(
+        "{}:{}/{} -> {}:{} \"{}\"",
+        if mapping.remote_host.is_empty() { "*" } else { &mapping.remote_host },
+        mapping.external_port,
+        mapping.protocol,
+        mapping.internal_client,
+        mapping.internal_port,
+        mapping.description
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    struct MockUpnpAuditor {
+        mappings: Vec<UpnpPortMapping>,
+    }
+
+    #[async_trait]
+    impl UpnpAuditorPort for MockUpnpAuditor {
+        async fn probe_igd(&self, gateway_ip: &str) -> UpnpAuditResult {
+            UpnpAuditResult {
+                gateway_ip: gateway_ip.to_string(),
+                igd_found: true,
+                service_type: Some("urn:schemas-upnp-org:service:WANIPConnection:1".to_string()),
+                control_url: Some("http://192.168.1.1:5000/ctl/IPConn".to_string()),
+                external_ip: Some("203.0.113.7".to_string()),
+                port_mappings: self.mappings.clone(),
+                vulnerabilities: Vec::new(),
+                risk_level: "UNKNOWN".to_string(),
+            }
+        }
+    }
+
+    fn mapping(port: u16, client: &str) -> UpnpPortMapping {
+        UpnpPortMapping {
+            remote_host: String::new(),
+            external_port: port,
+            protocol: "TCP".to_string(),
+            internal_port: port,
+            internal_client: client.to_string(),
+            enabled: true,
+            description: "test".to_string(),
+            lease_duration: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn audit_upnp_marca_rdp_como_critico_y_clientes_desconocidos() {
+        let service = UpnpService::new(Arc::new(MockUpnpAuditor {
+            mappings: vec![mapping(3389, "192.168.1.20"), mapping(8080, "192.168.1.99")],
+        }));
+        let known = vec!["192.168.1.20".to_string()];
+
+        let result = service.audit_upnp("192.168.1.1".to_string(), &known).await;
+
+        let ids: Vec<&str> = result.vulnerabilities.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, vec!["UPNP-IGD-ENABLED", "UPNP-DANGEROUS-FORWARD", "UPNP-UNKNOWN-CLIENT"]);
+        assert!(result.vulnerabilities[1].description.contains("RDP"));
+        assert!(result.vulnerabilities[2].description.contains("192.168.1.99"));
+        assert_eq!(result.risk_level, "CRITICAL");
+    }
+
+    #[tokio::test]
+    async fn audit_upnp_sin_mapeos_peligrosos_es_medio() {
+        let service = UpnpService::new(Arc::new(MockUpnpAuditor {
+            mappings: vec![mapping(51413, "192.168.1.30")],
+        }));
+
+        // Sin inventario no se marca ningun cliente como desconocido.
+        let result = service.audit_upnp("192.168.1.1".to_string(), &[]).await;
+        assert_eq!(result.vulnerabilities.len(), 1);
+        assert_eq!(result.risk_level, "MEDIUM");
+    }
+}
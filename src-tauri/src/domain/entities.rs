@@ -21,6 +21,12 @@ pub struct Device {
     #[serde(alias = "wifi_band")]
     pub wifi_band: Option<String>,
     pub open_ports: Option<Vec<OpenPort>>,
+    // Identificacion aprendida por sondas activas (ej: SNMP sysDescr).
+    pub model: Option<String>,
+    pub firmware: Option<String>,
+    // Tabla de hosts del router (TR-064): interfaz ("Ethernet", "802.11"...) y si sigue conectado.
+    pub interface_type: Option<String>,
+    pub active: Option<bool>,
 }
 
 // 2. VULNERABILITAT
@@ -31,6 +37,8 @@ pub struct Vulnerability {
     pub description: String,
     pub severity: String,
     pub recommendation: String,
+    // Prueba capturada por la sonda activa (banner, respuesta del servidor...). Ya saneada.
+    pub evidence: Option<String>,
 }
 
 // 3. PORT OBERT
@@ -43,16 +51,243 @@ pub struct OpenPort {
     pub risk_level: String,
     pub description: Option<String>,
     pub vulnerability: Option<Vulnerability>,
+    // Version/producto detectado en el banner del servicio.
+    pub version: Option<String>,
+}
+
+// 3a. MODO DE ESCANEO DE PUERTOS
+// Connect: handshake completo (sin privilegios). Syn: half-open con raw sockets (cae a Connect si no hay permisos).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PortScanMode {
+    #[default]
+    Connect,
+    Syn,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortScanOutcome {
+    pub open_ports: Vec<OpenPort>,
+    // Solo el modo SYN distingue filtrado (sin respuesta) de cerrado (RST).
+    pub filtered_ports: Vec<u16>,
+    // Modo realmente usado (puede diferir del pedido tras el fallback).
+    pub mode_used: PortScanMode,
+}
+
+// 3b. SONDA ACTIVA DE SERVICIO (FTP/TELNET/SMTP...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceProbeResult {
+    pub port: u16,
+    pub service: String,
+    pub version: Option<String>,
+    pub banner: Option<String>,
+    pub vulnerabilities: Vec<Vulnerability>,
+}
+
+// 3c. INFORME DE AUDITORIA DE PUERTOS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortAuditReport {
+    pub target_ip: String,
+    pub open_ports: Vec<OpenPort>,
+    pub filtered_ports: Vec<u16>,
+    pub scan_mode: PortScanMode,
+    pub risk_level: String,
+    pub vulnerabilities: Vec<Vulnerability>,
 }
 
 // 4. RESULTAT D'AUDITORIA
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RouterAuditResult {
+    // Job que produjo el resultado (clave de sus evidencias). Lo asigna `AuditService`, no el adaptador.
+    #[serde(default)]
+    pub audit_id: Option<String>,
     pub target_ip: String,
     pub vulnerable: bool,
-    pub credentials_found: Option<String>,
+    pub credentials_found: Option<RouterCredentials>,
+    // Sin la clave: el mensaje acaba en logs y en la UI.
     pub message: String,
+    // Identificacion del router (cabeceras, favicon, paginas de estado) y hallazgos de la
+    // lista local de firmwares vulnerables / modelos sin soporte.
+    #[serde(default)]
+    pub fingerprint: Option<RouterFingerprint>,
+    #[serde(default)]
+    pub findings: Vec<Vulnerability>,
+}
+
+// 4a. PROGRESO DE AUDITORIA DE ROUTER (eventos tipados por intento)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouterLoginOutcome {
+    Success,
+    Rejected,
+    LockedOut,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouterAuditPhase {
+    Detecting,  // buscando formulario/perfil de login
+    Attempting, // credencial N enviada
+    Attempted,  // resultado de la credencial N
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouterAuditProgress {
+    pub phase: RouterAuditPhase,
+    pub backend: String,
+    pub profile_id: Option<String>,
+    // 1..=attempt_total (sin la clave: nunca sale del backend en eventos)
+    pub attempt_index: Option<usize>,
+    pub attempt_total: Option<usize>,
+    pub user: Option<String>,
+    pub outcome: Option<RouterLoginOutcome>,
+}
+
+// 4b. AUDITORIA SNMP (UDP 161)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnmpCommunityAccess {
+    pub community: String,
+    pub version: String, // "v1" | "v2c"
+    pub writable: Option<bool>, // None: sysContact ilegible, no se intento el SET
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnmpSystemInfo {
+    pub descr: Option<String>,
+    pub name: Option<String>,
+    pub contact: Option<String>,
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnmpInterface {
+    pub index: u32,
+    pub descr: String,
+    pub mac: Option<String>,
+    pub oper_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnmpAuditResult {
+    pub target_ip: String,
+    pub communities: Vec<SnmpCommunityAccess>,
+    pub system: Option<SnmpSystemInfo>,
+    pub interfaces: Vec<SnmpInterface>,
+    pub model: Option<String>,
+    pub firmware: Option<String>,
+    pub vulnerabilities: Vec<Vulnerability>,
+    pub risk_level: String,
+}
+
+// 4c. AUDITORIA UPnP IGD (gateway)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpnpPortMapping {
+    pub remote_host: String, // "" = cualquier origen
+    pub external_port: u16,
+    pub protocol: String, // "TCP" | "UDP"
+    pub internal_port: u16,
+    pub internal_client: String,
+    pub enabled: bool,
+    pub description: String,
+    pub lease_duration: u32, // 0 = permanente
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpnpAuditResult {
+    pub gateway_ip: String,
+    pub igd_found: bool,
+    pub service_type: Option<String>,
+    pub control_url: Option<String>,
+    pub external_ip: Option<String>,
+    pub port_mappings: Vec<UpnpPortMapping>,
+    pub vulnerabilities: Vec<Vulnerability>,
+    pub risk_level: String,
+}
+
+// 4d. HUELLA DEL ROUTER (modelo / hardware / firmware)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouterFingerprint {
+    pub vendor: Option<String>,
+    pub model: Option<String>,
+    pub hardware_version: Option<String>,
+    pub firmware_version: Option<String>,
+    // Cabecera `Server` tal cual (ej: "RomPager/4.07 UPnP/1.0").
+    pub server: Option<String>,
+    pub favicon_md5: Option<String>,
+    // Origen de cada dato: "header:server", "realm", "favicon", "page:/RST_status.htm"...
+    pub sources: Vec<String>,
+}
+
+// 4e. EVIDENCIAS DE AUDITORIA DE ROUTER (capturas + HTML saneado, por audit_id)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouterEvidenceStage {
+    LoginPage,
+    Landing,
+    DeviceList,
+}
+
+impl RouterEvidenceStage {
+    pub const ALL: [RouterEvidenceStage; 3] = [Self::LoginPage, Self::Landing, Self::DeviceList];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LoginPage => "login_page",
+            Self::Landing => "landing",
+            Self::DeviceList => "device_list",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RouterEvidenceKind {
+    Screenshot,
+    Html,
+}
+
+impl RouterEvidenceKind {
+    pub const ALL: [RouterEvidenceKind; 2] = [Self::Screenshot, Self::Html];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Screenshot => "png",
+            Self::Html => "html",
+        }
+    }
+}
+
+// Contenido capturado por el adaptador (PNG o HTML ya saneado); no viaja al frontend tal cual.
+#[derive(Debug, Clone)]
+pub struct RouterEvidence {
+    pub stage: RouterEvidenceStage,
+    pub kind: RouterEvidenceKind,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouterEvidenceItem {
+    pub audit_id: String,
+    // Nombre de fichero: "<stage>.<ext>" (ej: "landing.png").
+    pub name: String,
+    pub stage: RouterEvidenceStage,
+    pub kind: RouterEvidenceKind,
+    pub size_bytes: u64,
+    pub captured_at: u64,
 }
 
 // 5. SESSIÓ D'ESCANEIG
@@ -73,6 +308,33 @@ pub struct LatestSnapshot {
     pub devices: Vec<Device>,
 }
 
+// 5b2. HISTORIAL DE AUDITORIAS DE PUERTOS (por dispositivo)
+// `device_key` = MAC normalizada cuando se conoce; si no, "ip:<ip>".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortAuditRecord {
+    pub device_key: String,
+    pub ip: String,
+    pub mac: Option<String>,
+    pub timestamp: u64,
+    pub scan_mode: PortScanMode,
+    pub risk_level: String,
+    pub open_ports: Vec<OpenPort>,
+}
+
+// Diferencia entre dos auditorias consecutivas del mismo dispositivo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortChangeEvent {
+    pub device_key: String,
+    pub ip: String,
+    pub mac: Option<String>,
+    pub timestamp: u64,
+    pub previous_timestamp: u64,
+    pub opened: Vec<OpenPort>,
+    pub closed: Vec<OpenPort>,
+}
+
 // 5c. CREDENCIALES (gateway) - Solo almacenamiento local en el equipo del auditor
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -83,6 +345,31 @@ pub struct GatewayCredentials {
     pub saved_at: u64,
 }
 
+// Par usuario/clave para el login del router: viaja tipado hasta el adaptador y de vuelta en el
+// resultado (nunca "user:pass"; la clave puede contener ':').
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouterCredentials {
+    pub user: String,
+    #[serde(rename = "password")]
+    pub pass: String,
+}
+
+// Debug sin la clave: estos valores acaban en logs de error y panics de tests.
+impl std::fmt::Debug for RouterCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouterCredentials")
+            .field("user", &self.user)
+            .field("pass", &"***")
+            .finish()
+    }
+}
+
+impl From<GatewayCredentials> for RouterCredentials {
+    fn from(c: GatewayCredentials) -> Self {
+        Self { user: c.user, pass: c.pass }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct HostIdentity {
@@ -99,14 +386,249 @@ pub struct HostIdentity {
 pub struct TrafficPacket {
     pub id: usize, // ID secuencial para listas React (key).
     pub timestamp: u64,
-    pub source_ip: String,
+    pub source_ip: String, // IPv4 o IPv6 en forma canonica.
     pub destination_ip: String,
-    pub protocol: String, // TCP, UDP, ICMP
+    pub protocol: String, // TCP, UDP, NDP, ICMPv6, MCAST...
     pub length: usize,
     pub info: String,     // Ex: "HTTPS Traffic" o "DNS Query"
     pub is_intercepted: bool,
 }
 
+// Conversacion agregada por 5-tupla (ambos sentidos). Campos alineados con el conn.log de Zeek:
+// `orig` es quien inicio la conexion y `conn_state` usa sus codigos (S0, S1, SF, REJ, RSTO...).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficFlow {
+    pub uid: String,
+    pub protocol: String, // "tcp" | "udp" | "icmp" | numero IP
+    pub service: String,  // etiqueta del clasificador del primer paquete
+    pub orig_ip: String,
+    pub orig_port: Option<u16>,
+    pub resp_ip: String,
+    pub resp_port: Option<u16>,
+    pub orig_packets: u64,
+    pub orig_bytes: u64,
+    pub resp_packets: u64,
+    pub resp_bytes: u64,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    // Solo TCP: syn_sent, syn_received, established, closing, closed, reset o midstream.
+    pub tcp_state: Option<String>,
+    pub conn_state: String,
+    // false: cerrado (FIN/RST) o caducado por inactividad.
+    pub active: bool,
+}
+
+// Dominios consultados por un equipo de la red (DNS decodificado por el monitor).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceDomainLog {
+    pub ip: String,
+    pub total_queries: u64,
+    pub domains: Vec<DnsDomainStat>, // mas consultados primero
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DnsDomainStat {
+    pub domain: String,
+    pub queries: u64,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub query_types: Vec<String>, // A, AAAA, HTTPS...
+    // Ultimas respuestas distintas (IPs o nombres CNAME), acotadas.
+    pub answers: Vec<String>,
+    pub last_rcode: Option<String>, // NOERROR, NXDOMAIN...
+}
+
+// Huellas TLS de un equipo (ClientHello de TLS sobre TCP y de QUIC).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceTlsLog {
+    pub ip: String,
+    // Hashes JA3 distintos: un equipo IoT suele tener uno o dos; muchos indican varios clientes.
+    pub fingerprints: Vec<String>,
+    pub clients: Vec<TlsClientStat>, // mas recientes primero
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsClientStat {
+    pub server_name: Option<String>, // SNI
+    pub alpn: Vec<String>,
+    pub tls_version: String,
+    pub ja3: String,
+    pub ja3_hash: String,
+    pub transport: String, // "TLS" | "QUIC"
+    pub connections: u64,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub last_server_ip: String,
+}
+
+// User-Agents HTTP en claro enviados por un equipo, con la pista de SO/tipo que se deduce de ellos.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceUserAgents {
+    pub ip: String,
+    // Pista del User-Agent con mas peticiones que la aporta.
+    pub os_hint: Option<String>,
+    pub device_hint: Option<String>, // PHONE, PC, TV, IOT (vocabulario del clasificador)
+    pub agents: Vec<UserAgentStat>, // mas peticiones primero
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAgentStat {
+    pub user_agent: String,
+    pub os: Option<String>,
+    pub client: Option<String>,
+    pub device_type: Option<String>,
+    pub requests: u64,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    // Ultimos Host distintos pedidos con este User-Agent, acotados.
+    pub hosts: Vec<String>,
+}
+
+// Ventana de `get_bandwidth_stats`: ultimo segundo completo, ultimos 60 s o ultimos 60 min.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BandwidthWindow {
+    Second,
+    #[default]
+    Minute,
+    Hour,
+}
+
+// Consumo de ancho de banda por equipo local y por extremo remoto (top-N de la ventana).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BandwidthStats {
+    pub timestamp: u64,
+    pub window: BandwidthWindow,
+    // Todo el trafico observado en la ventana (cada paquete una vez).
+    pub total_bytes: u64,
+    pub bytes_per_second: u64,
+    pub devices: Vec<HostBandwidth>,   // mas bytes primero
+    pub endpoints: Vec<HostBandwidth>, // mas bytes primero
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostBandwidth {
+    pub ip: String,
+    // En la ventana pedida, desde el punto de vista de esta IP (recibido / enviado).
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub total_bytes_in: u64,
+    pub total_bytes_out: u64,
+    pub packets: u64,
+    pub last_seen: u64,
+    // Series por segundo (60), minuto (60) y hora (24); vacias en el evento periodico.
+    pub per_second: Vec<BandwidthPoint>,
+    pub per_minute: Vec<BandwidthPoint>,
+    pub per_hour: Vec<BandwidthPoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BandwidthPoint {
+    pub timestamp: u64, // inicio de la cubeta
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+}
+
+// Vigilancia ARP del monitor: vinculos IP -> MAC (sembrados con el escaneo) y alertas de suplantacion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArpBinding {
+    pub ip: String,
+    pub mac: String, // aa:bb:cc:dd:ee:ff
+    pub source: String, // "scan" (ultimo escaneo) o "arp" (aprendido del trafico)
+    pub is_gateway: bool,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub mac_changes: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArpAlert {
+    pub id: u64,
+    pub timestamp: u64,
+    // mac_change, gratuitous_arp_change, gateway_impersonation, mac_multiple_ips, unsolicited_reply_flood
+    pub kind: String,
+    pub severity: String, // LOW, MEDIUM, HIGH, CRITICAL (como en Vulnerability)
+    pub ip: Option<String>,
+    pub mac: String,
+    pub message: String,
+    // Hechos observados que justifican la alerta (vinculo previo, trama, contadores...).
+    pub evidence: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArpWatchReport {
+    pub gateway_ip: Option<String>,
+    pub bindings: Vec<ArpBinding>,
+    pub alerts: Vec<ArpAlert>, // las mas recientes primero
+}
+
+// Analisis offline de un pcap/pcapng (mismo pipeline que el monitor en vivo, marcas de tiempo originales).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureAnalysis {
+    pub file_name: String,
+    pub format: String, // "pcap" | "pcapng"
+    pub total_frames: u64,
+    pub decoded_packets: u64,
+    // Tramas sin IP decodificable (ARP, enlaces distintos de Ethernet/Linux SLL) o fuera del foco pedido.
+    pub skipped_frames: u64,
+    pub first_timestamp: Option<u64>,
+    pub last_timestamp: Option<u64>,
+    pub protocols: Vec<CaptureProtocolStat>,
+    pub top_talkers: Vec<CaptureTalkerStat>,
+    // Primeros paquetes decodificados (acotado); `packets_truncated` indica que hay mas.
+    pub packets: Vec<TrafficPacket>,
+    pub packets_truncated: bool,
+    // Conversaciones del fichero (las mas recientes si hay demasiadas).
+    pub flows: Vec<TrafficFlow>,
+    // Fichero cortado o corrupto: el analisis cubre lo leido hasta ese punto.
+    pub read_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureProtocolStat {
+    pub protocol: String,
+    pub info: String,
+    pub packets: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureTalkerStat {
+    pub ip: String,
+    pub packets: u64,
+    pub bytes: u64,
+}
+
+// Grabacion PCAPNG en paralelo al monitor (rotacion por tamano/tiempo).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureFileStatus {
+    pub active: bool,
+    pub current_file: Option<String>,
+    // Ficheros cerrados o en curso de esta grabacion (el mas reciente al final).
+    pub files: Vec<String>,
+    pub packets_written: u64,
+    pub bytes_written: u64,
+    pub max_bytes: Option<u64>,
+    pub max_seconds: Option<u64>,
+}
+
 // 6. WIFI (Radar View)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -1,6 +1,7 @@
 // src-tauri/src/domain/ports.rs
 use async_trait::async_trait;
-use crate::domain::entities::{Device, RouterAuditResult, ScanSession, OpenPort, LatestSnapshot, GatewayCredentials}; 
+use std::sync::Arc;
+use crate::domain::entities::{Device, RouterAuditResult, RouterAuditProgress, RouterCredentials, ScanSession, LatestSnapshot, PortScanMode, PortScanOutcome, GatewayCredentials, SnmpAuditResult, ServiceProbeResult, PortAuditRecord, UpnpAuditResult, RouterEvidence, RouterEvidenceItem};
 
 // PORT 1: ESCANER DE RED
 #[async_trait]
@@ -9,14 +10,32 @@ pub trait NetworkScannerPort: Send + Sync {
     async fn scan_network(&self, subnet: &str) -> Vec<Device>;
 
     // Metodo integrado en el mismo puerto para evitar duplicar adaptadores.
-    async fn scan_ports(&self, ip: &str) -> Vec<OpenPort>;
+    // `mode` es una preferencia: el adaptador informa del modo realmente usado.
+    async fn scan_ports(&self, ip: &str, mode: PortScanMode) -> PortScanOutcome;
 }
 
 // PORT 2: AUDITOR DE ROUTER
 #[async_trait]
 pub trait RouterAuditorPort: Send + Sync {
-    async fn audit_gateway(&self, ip: &str) -> RouterAuditResult;
-    async fn fetch_connected_devices(&self, ip: &str, creds: &str) -> Vec<Device>;
+    async fn audit_gateway(&self, ip: &str, observer: Arc<dyn RouterAuditObserver>) -> RouterAuditResult;
+    async fn fetch_connected_devices(&self, ip: &str, creds: &RouterCredentials) -> Vec<Device>;
+}
+
+// Observador de una auditoria de router en curso: progreso tipado + cancelacion cooperativa.
+// El adaptador consulta `is_cancelled` entre intentos (nunca a mitad de un login).
+pub trait RouterAuditObserver: Send + Sync {
+    fn on_progress(&self, progress: RouterAuditProgress);
+    fn is_cancelled(&self) -> bool;
+    // Capturas para el informe (pantallazos / HTML saneado). Por defecto se descartan.
+    fn on_evidence(&self, _evidence: RouterEvidence) {}
+}
+
+// PORT 2b: EVIDENCIAS DE AUDITORIA DE ROUTER (por audit_id)
+// Sincrono: se guarda desde el hilo bloqueante del auditor (Chrome corre en `spawn_blocking`).
+pub trait RouterEvidenceRepositoryPort: Send + Sync {
+    fn save_evidence(&self, audit_id: &str, evidence: &RouterEvidence) -> Result<RouterEvidenceItem, String>;
+    fn list_evidence(&self, audit_id: &str) -> Result<Vec<RouterEvidenceItem>, String>;
+    fn read_evidence(&self, audit_id: &str, name: &str) -> Result<(RouterEvidenceItem, Vec<u8>), String>;
 }
 
 // PORT 3: REPOSITORIO DE HISTORIAL
@@ -33,6 +52,14 @@ pub trait LatestSnapshotRepositoryPort: Send + Sync {
     async fn load_latest(&self) -> Result<Option<LatestSnapshot>, String>;
 }
 
+// PORT 3b2: HISTORIAL DE AUDITORIAS DE PUERTOS (por dispositivo)
+#[async_trait]
+pub trait PortAuditRepositoryPort: Send + Sync {
+    async fn save_record(&self, record: PortAuditRecord) -> Result<(), String>;
+    // Mas reciente primero.
+    async fn list_records(&self, device_key: &str) -> Result<Vec<PortAuditRecord>, String>;
+}
+
 // PORT 3c: ALMACENAMIENTO DE CREDENCIALES (gateway) en el equipo local
 #[async_trait]
 pub trait CredentialStorePort: Send + Sync {
@@ -52,3 +79,25 @@ pub trait WifiScannerPort: Send + Sync {
 pub trait VendorLookupPort: Send + Sync {
     fn resolve_vendor(&self, mac_or_bssid: &str) -> String;
 }
+
+// PORT 6: AUDITOR SNMP (UDP 161)
+// Prueba communities v1/v2c y devuelve lo aprendido. La clasificacion de riesgo vive en application.
+#[async_trait]
+pub trait SnmpAuditorPort: Send + Sync {
+    async fn probe_snmp(&self, ip: &str) -> SnmpAuditResult;
+}
+
+// PORT 7: SONDAS ACTIVAS DE SERVICIO (TCP)
+// Habla el protocolo del puerto abierto (sin credenciales reales) y devuelve version + hallazgos.
+// `None` si el puerto no tiene sonda o el servicio no responde como se espera.
+#[async_trait]
+pub trait ServiceProbePort: Send + Sync {
+    async fn probe_service(&self, ip: &str, port: u16) -> Option<ServiceProbeResult>;
+}
+
+// PORT 8: AUDITOR UPnP IGD (SSDP + SOAP en el gateway)
+// Devuelve IP WAN y mapeos tal cual; la clasificacion de riesgo vive en application.
+#[async_trait]
+pub trait UpnpAuditorPort: Send + Sync {
+    async fn probe_igd(&self, gateway_ip: &str) -> UpnpAuditResult;
+}
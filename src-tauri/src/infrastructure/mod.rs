@@ -1,9 +1,14 @@
 // src-tauri/src/infrastructure/mod.rs
 
 pub mod system_scanner;
+pub mod snmp_auditor;
+pub mod upnp_auditor;
+pub mod service_probes;
 pub mod router_audit;
 pub mod fs_repository;  
 pub mod latest_snapshot_repository;
+pub mod port_audit_repository;
+pub mod router_evidence_repository;
 pub mod credential_store;
 pub mod repositories;
 pub mod network;
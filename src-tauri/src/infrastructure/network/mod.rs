@@ -1,10 +1,13 @@
 pub mod arp_client;
 pub mod ping_executor;
 pub mod port_scanner;
+pub mod syn_scanner;
 pub mod vendor_lookup;
 pub mod vendor_resolver;
 pub mod service_dictionary;
 pub mod packet_injector;
 pub mod traffic_sniffer;
 pub mod hostname_resolver;
+pub mod snmp_client;
+pub mod upnp_client;
 
@@ -21,12 +21,12 @@ impl ServiceDictionary {
                 description: "Secure Shell. Acceso remoto cifrado.",
                 risk: "LOW", // Es seguro si la contrasena es buena.
             },
-            23 => ServiceInfo {
+            23 | 2323 => ServiceInfo {
                 name: "TELNET",
                 description: "Acceso remoto antiguo y sin cifrar. Critico.",
                 risk: "CRITICAL",
             },
-            25 => ServiceInfo {
+            25 | 587 => ServiceInfo {
                 name: "SMTP",
                 description: "Correo saliente. A menudo abierto en impresoras.",
                 risk: "MEDIUM",
@@ -56,16 +56,46 @@ impl ServiceDictionary {
                 description: "Web segura cifrada.",
                 risk: "SAFE",
             },
+            1883 => ServiceInfo {
+                name: "MQTT",
+                description: "Broker IoT sin cifrar. Si admite anonimos, controla dispositivos.",
+                risk: "HIGH",
+            },
+            2375 => ServiceInfo {
+                name: "DOCKER-API",
+                description: "API de Docker sin TLS. Equivale a root en el host.",
+                risk: "CRITICAL",
+            },
             3389 => ServiceInfo {
                 name: "RDP",
                 description: "Escritorio remoto de Windows.",
                 risk: "HIGH", // Muy atacado por fuerza bruta.
             },
+            5984 => ServiceInfo {
+                name: "COUCHDB",
+                description: "API REST de CouchDB. Revisar 'admin party'.",
+                risk: "HIGH",
+            },
+            6379 => ServiceInfo {
+                name: "REDIS",
+                description: "Redis. Sin requirepass permite leer datos y escribir ficheros.",
+                risk: "HIGH",
+            },
             8080 => ServiceInfo {
                 name: "HTTP-ALT",
                 description: "Servidor web alternativo (a menudo paneles de admin).",
                 risk: "MEDIUM",
             },
+            9200 => ServiceInfo {
+                name: "ELASTICSEARCH",
+                description: "API REST de Elasticsearch. Sin seguridad expone todos los indices.",
+                risk: "HIGH",
+            },
+            27017 => ServiceInfo {
+                name: "MONGODB",
+                description: "MongoDB. Sin --auth cualquiera lee y borra las bases de datos.",
+                risk: "HIGH",
+            },
             _ => ServiceInfo {
                 name: "UNKNOWN",
                 description: "Servicio no estandar o desconocido.",
@@ -0,0 +1,186 @@
+// src-tauri/src/infrastructure/network/snmp_client.rs
+
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Duration, Instant};
+
+// Submodulos para separar responsabilidades (SOLID) sin cambiar la API publica del modulo.
+#[path = "snmp_client/ber.rs"]
+pub mod ber;
+
+use ber::{BerValue, SnmpMessage};
+
+pub const SNMP_PORT: u16 = 161;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SnmpVersion {
+    V1,
+    V2c,
+}
+
+impl SnmpVersion {
+    fn wire_value(self) -> i64 {
+        match self {
+            SnmpVersion::V1 => 0,
+            SnmpVersion::V2c => 1,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SnmpVersion::V1 => "v1",
+            SnmpVersion::V2c => "v2c",
+        }
+    }
+}
+
+static REQUEST_COUNTER: AtomicI64 = AtomicI64::new(1);
+
+// Cliente SNMP v1/v2c sobre UDP (bloqueante, pensado para `spawn_blocking`).
+// Una community incorrecta no genera respuesta: se detecta por timeout.
+pub struct SnmpClient {
+    target: SocketAddr,
+    community: String,
+    version: SnmpVersion,
+    timeout: Duration,
+}
+
+impl SnmpClient {
+    pub fn new(target: SocketAddr, community: &str, version: SnmpVersion, timeout: Duration) -> Self {
+        Self {
+            target,
+            community: community.to_string(),
+            version,
+            timeout,
+        }
+    }
+
+    pub fn get(&self, oids: &[Vec<u32>]) -> Result<SnmpMessage, String> {
+        let varbinds = oids.iter().map(|o| (o.clone(), BerValue::Null)).collect();
+        self.request(ber::PDU_GET_REQUEST, varbinds)
+    }
+
+    pub fn get_next(&self, oid: &[u32]) -> Result<SnmpMessage, String> {
+        self.request(ber::PDU_GET_NEXT_REQUEST, vec![(oid.to_vec(), BerValue::Null)])
+    }
+
+    pub fn set(&self, oid: &[u32], value: BerValue) -> Result<SnmpMessage, String> {
+        self.request(ber::PDU_SET_REQUEST, vec![(oid.to_vec(), value)])
+    }
+
+    // Recorre un subarbol con GETNEXT. `max_rows` acota el trafico en agentes con tablas enormes.
+    pub fn walk(&self, root: &[u32], max_rows: usize) -> Vec<(Vec<u32>, BerValue)> {
+        let mut rows = Vec::new();
+        let mut cursor = root.to_vec();
+        while rows.len() < max_rows {
+            let Ok(resp) = self.get_next(&cursor) else {
+                break;
+            };
+            if resp.error_status != 0 {
+                break;
+            }
+            let Some((oid, value)) = resp.varbinds.into_iter().next() else {
+                break;
+            };
+            if !oid.starts_with(root) || value.is_exception() || oid <= cursor {
+                break;
+            }
+            cursor = oid.clone();
+            rows.push((oid, value));
+        }
+        rows
+    }
+
+    fn request(&self, pdu_type: u8, varbinds: Vec<(Vec<u32>, BerValue)>) -> Result<SnmpMessage, String> {
+        let request_id = REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed) & 0x7FFF_FFFF;
+        let packet = ber::encode_message(&SnmpMessage {
+            version: self.version.wire_value(),
+            community: self.community.clone(),
+            pdu_type,
+            request_id,
+            error_status: 0,
+            error_index: 0,
+            varbinds,
+        })?;
+
+        let bind_addr = if self.target.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+        let socket = UdpSocket::bind(bind_addr).map_err(|e| format!("Error bind UDP: {e}"))?;
+        socket
+            .send_to(&packet, self.target)
+            .map_err(|e| format!("Error enviando SNMP: {e}"))?;
+
+        // Ignoramos datagramas ajenos (otro origen o request-id distinto) hasta agotar el timeout.
+        let deadline = Instant::now() + self.timeout;
+        let mut buf = [0u8; 65_535];
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err("timeout SNMP".to_string());
+            }
+            socket
+                .set_read_timeout(Some(remaining))
+                .map_err(|e| e.to_string())?;
+            let (len, from) = match socket.recv_from(&mut buf) {
+                Ok(v) => v,
+                Err(_) => return Err("timeout SNMP".to_string()),
+            };
+            if from.ip() != self.target.ip() {
+                continue;
+            }
+            let Ok(resp) = ber::decode_message(&buf[..len]) else {
+                continue;
+            };
+            if resp.pdu_type == ber::PDU_GET_RESPONSE && resp.request_id == request_id {
+                return Ok(resp);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    // Agente SNMP minimo en loopback: responde sysDescr solo a la community "public".
+    fn spawn_stand_in_agent() -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = socket.local_addr().unwrap();
+        thread::spawn(move || {
+            let mut buf = [0u8; 2048];
+            while let Ok((len, from)) = socket.recv_from(&mut buf) {
+                let Ok(req) = ber::decode_message(&buf[..len]) else {
+                    continue;
+                };
+                if req.community != "public" {
+                    continue;
+                }
+                let resp = SnmpMessage {
+                    pdu_type: ber::PDU_GET_RESPONSE,
+                    varbinds: req
+                        .varbinds
+                        .iter()
+                        .map(|(oid, _)| (oid.clone(), BerValue::OctetString(b"Stand-in Agent 1.0".to_vec())))
+                        .collect(),
+                    ..req
+                };
+                let _ = socket.send_to(&ber::encode_message(&resp).unwrap(), from);
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn get_returns_value_for_valid_community_and_times_out_otherwise() {
+        let addr = spawn_stand_in_agent();
+        let oid = vec![1, 3, 6, 1, 2, 1, 1, 1, 0];
+
+        let ok = SnmpClient::new(addr, "public", SnmpVersion::V2c, Duration::from_millis(500))
+            .get(std::slice::from_ref(&oid))
+            .unwrap();
+        assert_eq!(ok.varbinds[0].1.as_display().as_deref(), Some("Stand-in Agent 1.0"));
+
+        let denied = SnmpClient::new(addr, "wrong", SnmpVersion::V2c, Duration::from_millis(150)).get(&[oid]);
+        assert!(denied.is_err());
+    }
+}
@@ -0,0 +1,516 @@
+// src-tauri/src/infrastructure/network/snmp_client/ber.rs
+
+// Codificacion BER minima para SNMP v1/v2c.
+// Solo cubre lo que necesita el auditor (GET/GETNEXT/SET + respuesta). Sin dependencias externas.
+
+pub const TAG_INTEGER: u8 = 0x02;
+pub const TAG_OCTET_STRING: u8 = 0x04;
+pub const TAG_NULL: u8 = 0x05;
+pub const TAG_OID: u8 = 0x06;
+pub const TAG_SEQUENCE: u8 = 0x30;
+pub const TAG_IP_ADDRESS: u8 = 0x40;
+pub const TAG_COUNTER32: u8 = 0x41;
+pub const TAG_GAUGE32: u8 = 0x42;
+pub const TAG_TIMETICKS: u8 = 0x43;
+pub const TAG_COUNTER64: u8 = 0x46;
+pub const TAG_NO_SUCH_OBJECT: u8 = 0x80;
+pub const TAG_NO_SUCH_INSTANCE: u8 = 0x81;
+pub const TAG_END_OF_MIB_VIEW: u8 = 0x82;
+
+pub const PDU_GET_REQUEST: u8 = 0xA0;
+pub const PDU_GET_NEXT_REQUEST: u8 = 0xA1;
+pub const PDU_GET_RESPONSE: u8 = 0xA2;
+pub const PDU_SET_REQUEST: u8 = 0xA3;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BerValue {
+    Integer(i64),
+    OctetString(Vec<u8>),
+    Null,
+    Oid(Vec<u32>),
+    IpAddress([u8; 4]),
+    Counter32(u32),
+    Gauge32(u32),
+    TimeTicks(u32),
+    Counter64(u64),
+    NoSuchObject,
+    NoSuchInstance,
+    EndOfMibView,
+    Other(u8, Vec<u8>),
+}
+
+impl BerValue {
+    // Valor "util" para UI/logs. Los octet strings no imprimibles (ej: MAC) se muestran en hex.
+    pub fn as_display(&self) -> Option<String> {
+        match self {
+            BerValue::OctetString(bytes) => {
+                if let Ok(text) = std::str::from_utf8(bytes) {
+                    if text.chars().all(|c| !c.is_control() || c == '\n' || c == '\r' || c == '\t') {
+                        return Some(text.trim_end_matches('\0').trim().to_string());
+                    }
+                }
+                Some(format_hex(bytes))
+            }
+            BerValue::Integer(v) => Some(v.to_string()),
+            BerValue::Oid(arcs) => Some(format_oid(arcs)),
+            BerValue::IpAddress(o) => Some(format!("{}.{}.{}.{}", o[0], o[1], o[2], o[3])),
+            BerValue::Counter32(v) | BerValue::Gauge32(v) | BerValue::TimeTicks(v) => Some(v.to_string()),
+            BerValue::Counter64(v) => Some(v.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn is_exception(&self) -> bool {
+        matches!(
+            self,
+            BerValue::NoSuchObject | BerValue::NoSuchInstance | BerValue::EndOfMibView
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnmpMessage {
+    pub version: i64, // 0 = v1, 1 = v2c
+    pub community: String,
+    pub pdu_type: u8,
+    pub request_id: i64,
+    pub error_status: i64,
+    pub error_index: i64,
+    pub varbinds: Vec<(Vec<u32>, BerValue)>,
+}
+
+// --- OID helpers ---
+
+pub fn format_oid(arcs: &[u32]) -> String {
+    arcs.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(".")
+}
+
+pub fn format_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+// --- ENCODING ---
+
+pub fn encode_length(len: usize, out: &mut Vec<u8>) {
+    if len < 0x80 {
+        out.push(len as u8);
+        return;
+    }
+    let bytes = len.to_be_bytes();
+    let first = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len() - 1);
+    let significant = &bytes[first..];
+    out.push(0x80 | significant.len() as u8);
+    out.extend_from_slice(significant);
+}
+
+fn encode_tlv(tag: u8, content: &[u8], out: &mut Vec<u8>) {
+    out.push(tag);
+    encode_length(content.len(), out);
+    out.extend_from_slice(content);
+}
+
+pub fn encode_integer(value: i64) -> Vec<u8> {
+    // Complemento a dos minimo: quitamos bytes redundantes de signo.
+    let bytes = value.to_be_bytes();
+    let mut start = 0;
+    while start < bytes.len() - 1 {
+        let (b, next) = (bytes[start], bytes[start + 1]);
+        if (b == 0x00 && next & 0x80 == 0) || (b == 0xFF && next & 0x80 != 0) {
+            start += 1;
+        } else {
+            break;
+        }
+    }
+    bytes[start..].to_vec()
+}
+
+fn encode_unsigned(value: u64) -> Vec<u8> {
+    let mut content = value.to_be_bytes().to_vec();
+    while content.len() > 1 && content[0] == 0 {
+        content.remove(0);
+    }
+    if content[0] & 0x80 != 0 {
+        content.insert(0, 0);
+    }
+    content
+}
+
+pub fn encode_oid(arcs: &[u32]) -> Result<Vec<u8>, String> {
+    if arcs.len() < 2 || arcs[0] > 2 || (arcs[0] < 2 && arcs[1] >= 40) {
+        return Err(format!("OID no codificable: {}", format_oid(arcs)));
+    }
+    let mut out = Vec::new();
+    encode_base128(arcs[0] * 40 + arcs[1], &mut out);
+    for arc in &arcs[2..] {
+        encode_base128(*arc, &mut out);
+    }
+    Ok(out)
+}
+
+fn encode_base128(mut value: u32, out: &mut Vec<u8>) {
+    let mut stack = [0u8; 5];
+    let mut n = 0;
+    loop {
+        stack[n] = (value & 0x7F) as u8;
+        n += 1;
+        value >>= 7;
+        if value == 0 {
+            break;
+        }
+    }
+    for i in (0..n).rev() {
+        let continuation = if i == 0 { 0 } else { 0x80 };
+        out.push(stack[i] | continuation);
+    }
+}
+
+pub fn encode_value(value: &BerValue, out: &mut Vec<u8>) -> Result<(), String> {
+    match value {
+        BerValue::Integer(v) => encode_tlv(TAG_INTEGER, &encode_integer(*v), out),
+        BerValue::OctetString(bytes) => encode_tlv(TAG_OCTET_STRING, bytes, out),
+        BerValue::Null => encode_tlv(TAG_NULL, &[], out),
+        BerValue::Oid(arcs) => encode_tlv(TAG_OID, &encode_oid(arcs)?, out),
+        BerValue::IpAddress(o) => encode_tlv(TAG_IP_ADDRESS, o, out),
+        BerValue::Counter32(v) => encode_tlv(TAG_COUNTER32, &encode_unsigned(*v as u64), out),
+        BerValue::Gauge32(v) => encode_tlv(TAG_GAUGE32, &encode_unsigned(*v as u64), out),
+        BerValue::TimeTicks(v) => encode_tlv(TAG_TIMETICKS, &encode_unsigned(*v as u64), out),
+        BerValue::Counter64(v) => encode_tlv(TAG_COUNTER64, &encode_unsigned(*v), out),
+        BerValue::NoSuchObject => encode_tlv(TAG_NO_SUCH_OBJECT, &[], out),
+        BerValue::NoSuchInstance => encode_tlv(TAG_NO_SUCH_INSTANCE, &[], out),
+        BerValue::EndOfMibView => encode_tlv(TAG_END_OF_MIB_VIEW, &[], out),
+        BerValue::Other(tag, bytes) => encode_tlv(*tag, bytes, out),
+    }
+    Ok(())
+}
+
+pub fn encode_message(msg: &SnmpMessage) -> Result<Vec<u8>, String> {
+    let mut varbind_list = Vec::new();
+    for (oid, value) in &msg.varbinds {
+        let mut varbind = Vec::new();
+        encode_tlv(TAG_OID, &encode_oid(oid)?, &mut varbind);
+        encode_value(value, &mut varbind)?;
+        encode_tlv(TAG_SEQUENCE, &varbind, &mut varbind_list);
+    }
+
+    let mut pdu = Vec::new();
+    encode_tlv(TAG_INTEGER, &encode_integer(msg.request_id), &mut pdu);
+    encode_tlv(TAG_INTEGER, &encode_integer(msg.error_status), &mut pdu);
+    encode_tlv(TAG_INTEGER, &encode_integer(msg.error_index), &mut pdu);
+    encode_tlv(TAG_SEQUENCE, &varbind_list, &mut pdu);
+
+    let mut body = Vec::new();
+    encode_tlv(TAG_INTEGER, &encode_integer(msg.version), &mut body);
+    encode_tlv(TAG_OCTET_STRING, msg.community.as_bytes(), &mut body);
+    encode_tlv(msg.pdu_type, &pdu, &mut body);
+
+    let mut out = Vec::with_capacity(body.len() + 4);
+    encode_tlv(TAG_SEQUENCE, &body, &mut out);
+    Ok(out)
+}
+
+// --- DECODING ---
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn read_byte(&mut self) -> Result<u8, String> {
+        let b = *self.data.get(self.pos).ok_or("BER truncado")?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn read_length(&mut self) -> Result<usize, String> {
+        let first = self.read_byte()?;
+        if first & 0x80 == 0 {
+            return Ok(first as usize);
+        }
+        let count = (first & 0x7F) as usize;
+        if count == 0 || count > 4 {
+            return Err("longitud BER no soportada".to_string());
+        }
+        let mut len = 0usize;
+        for _ in 0..count {
+            len = (len << 8) | self.read_byte()? as usize;
+        }
+        Ok(len)
+    }
+
+    fn read_tlv(&mut self) -> Result<(u8, &'a [u8]), String> {
+        let tag = self.read_byte()?;
+        let len = self.read_length()?;
+        let end = self.pos.checked_add(len).ok_or("longitud BER invalida")?;
+        if end > self.data.len() {
+            return Err("BER truncado".to_string());
+        }
+        let content = &self.data[self.pos..end];
+        self.pos = end;
+        Ok((tag, content))
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<&'a [u8], String> {
+        let (tag, content) = self.read_tlv()?;
+        if tag != expected {
+            return Err(format!("tag BER inesperado: 0x{tag:02X} (esperado 0x{expected:02X})"));
+        }
+        Ok(content)
+    }
+}
+
+pub fn decode_integer(content: &[u8]) -> Result<i64, String> {
+    if content.is_empty() || content.len() > 8 {
+        return Err("INTEGER BER invalido".to_string());
+    }
+    let mut value: i64 = if content[0] & 0x80 != 0 { -1 } else { 0 };
+    for b in content {
+        value = (value << 8) | *b as i64;
+    }
+    Ok(value)
+}
+
+fn decode_unsigned(content: &[u8]) -> Result<u64, String> {
+    if content.len() > 9 {
+        return Err("entero sin signo BER invalido".to_string());
+    }
+    Ok(content.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64))
+}
+
+pub fn decode_oid(content: &[u8]) -> Result<Vec<u32>, String> {
+    if content.is_empty() {
+        return Err("OID BER vacio".to_string());
+    }
+    let mut sub_ids = Vec::new();
+    let mut current: u32 = 0;
+    for (i, b) in content.iter().enumerate() {
+        current = current
+            .checked_mul(128)
+            .and_then(|v| v.checked_add((b & 0x7F) as u32))
+            .ok_or("OID BER desbordado")?;
+        if b & 0x80 == 0 {
+            sub_ids.push(current);
+            current = 0;
+        } else if i == content.len() - 1 {
+            return Err("OID BER truncado".to_string());
+        }
+    }
+
+    let first = sub_ids[0];
+    let (a, b) = if first < 40 {
+        (0, first)
+    } else if first < 80 {
+        (1, first - 40)
+    } else {
+        (2, first - 80)
+    };
+    let mut arcs = vec![a, b];
+    arcs.extend_from_slice(&sub_ids[1..]);
+    Ok(arcs)
+}
+
+pub fn decode_value(tag: u8, content: &[u8]) -> Result<BerValue, String> {
+    Ok(match tag {
+        TAG_INTEGER => BerValue::Integer(decode_integer(content)?),
+        TAG_OCTET_STRING => BerValue::OctetString(content.to_vec()),
+        TAG_NULL => BerValue::Null,
+        TAG_OID => BerValue::Oid(decode_oid(content)?),
+        TAG_IP_ADDRESS if content.len() == 4 => {
+            BerValue::IpAddress([content[0], content[1], content[2], content[3]])
+        }
+        TAG_COUNTER32 => BerValue::Counter32(decode_unsigned(content)? as u32),
+        TAG_GAUGE32 => BerValue::Gauge32(decode_unsigned(content)? as u32),
+        TAG_TIMETICKS => BerValue::TimeTicks(decode_unsigned(content)? as u32),
+        TAG_COUNTER64 => BerValue::Counter64(decode_unsigned(content)?),
+        TAG_NO_SUCH_OBJECT => BerValue::NoSuchObject,
+        TAG_NO_SUCH_INSTANCE => BerValue::NoSuchInstance,
+        TAG_END_OF_MIB_VIEW => BerValue::EndOfMibView,
+        other => BerValue::Other(other, content.to_vec()),
+    })
+}
+
+pub fn decode_message(data: &[u8]) -> Result<SnmpMessage, String> {
+    let mut outer = Reader::new(data);
+    let body = outer.expect(TAG_SEQUENCE)?;
+
+    let mut r = Reader::new(body);
+    let version = decode_integer(r.expect(TAG_INTEGER)?)?;
+    let community = String::from_utf8_lossy(r.expect(TAG_OCTET_STRING)?).to_string();
+    let (pdu_type, pdu) = r.read_tlv()?;
+
+    let mut p = Reader::new(pdu);
+    let request_id = decode_integer(p.expect(TAG_INTEGER)?)?;
+    let error_status = decode_integer(p.expect(TAG_INTEGER)?)?;
+    let error_index = decode_integer(p.expect(TAG_INTEGER)?)?;
+    let list = p.expect(TAG_SEQUENCE)?;
+
+    let mut varbinds = Vec::new();
+    let mut l = Reader::new(list);
+    while !l.is_empty() {
+        let vb = l.expect(TAG_SEQUENCE)?;
+        let mut v = Reader::new(vb);
+        let oid = decode_oid(v.expect(TAG_OID)?)?;
+        let (tag, content) = v.read_tlv()?;
+        varbinds.push((oid, decode_value(tag, content)?));
+    }
+
+    Ok(SnmpMessage {
+        version,
+        community,
+        pdu_type,
+        request_id,
+        error_status,
+        error_index,
+        varbinds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_oid(text: &str) -> Result<Vec<u32>, String> {
+        text.split('.')
+            .map(|p| p.parse::<u32>().map_err(|_| format!("OID invalido: {text}")))
+            .collect()
+    }
+
+    #[test]
+    fn encode_length_short_and_long_form() {
+        let mut out = Vec::new();
+        encode_length(5, &mut out);
+        assert_eq!(out, vec![0x05]);
+
+        let mut out = Vec::new();
+        encode_length(200, &mut out);
+        assert_eq!(out, vec![0x81, 0xC8]);
+
+        let mut out = Vec::new();
+        encode_length(0x1234, &mut out);
+        assert_eq!(out, vec![0x82, 0x12, 0x34]);
+    }
+
+    #[test]
+    fn encode_integer_uses_minimal_twos_complement() {
+        assert_eq!(encode_integer(0), vec![0x00]);
+        assert_eq!(encode_integer(127), vec![0x7F]);
+        assert_eq!(encode_integer(128), vec![0x00, 0x80]);
+        assert_eq!(encode_integer(256), vec![0x01, 0x00]);
+        assert_eq!(encode_integer(-1), vec![0xFF]);
+        assert_eq!(encode_integer(-129), vec![0xFF, 0x7F]);
+
+        for v in [0i64, 1, -1, 127, 128, -128, -129, 65535, i32::MAX as i64, i32::MIN as i64] {
+            assert_eq!(decode_integer(&encode_integer(v)).unwrap(), v);
+        }
+    }
+
+    #[test]
+    fn encode_oid_matches_known_bytes() {
+        // sysDescr.0 = 1.3.6.1.2.1.1.1.0
+        let oid = parse_oid("1.3.6.1.2.1.1.1.0").unwrap();
+        assert_eq!(
+            encode_oid(&oid).unwrap(),
+            vec![0x2B, 0x06, 0x01, 0x02, 0x01, 0x01, 0x01, 0x00]
+        );
+
+        // Arcos > 127 usan base-128 con bit de continuacion.
+        let big = vec![1, 3, 6, 1, 4, 1, 2636];
+        let encoded = encode_oid(&big).unwrap();
+        assert_eq!(&encoded[5..], &[0x94, 0x4C]);
+        assert_eq!(decode_oid(&encoded).unwrap(), big);
+    }
+
+    #[test]
+    fn encode_get_request_matches_reference_packet() {
+        // Paquete de referencia (snmpget -v2c -c public host sysDescr.0, request-id 1).
+        let msg = SnmpMessage {
+            version: 1,
+            community: "public".to_string(),
+            pdu_type: PDU_GET_REQUEST,
+            request_id: 1,
+            error_status: 0,
+            error_index: 0,
+            varbinds: vec![(parse_oid("1.3.6.1.2.1.1.1.0").unwrap(), BerValue::Null)],
+        };
+        let expected: Vec<u8> = vec![
+            0x30, 0x26, 0x02, 0x01, 0x01, 0x04, 0x06, b'p', b'u', b'b', b'l', b'i', b'c', 0xA0, 0x19, 0x02,
+            0x01, 0x01, 0x02, 0x01, 0x00, 0x02, 0x01, 0x00, 0x30, 0x0E, 0x30, 0x0C, 0x06, 0x08, 0x2B, 0x06,
+            0x01, 0x02, 0x01, 0x01, 0x01, 0x00, 0x05, 0x00,
+        ];
+        assert_eq!(encode_message(&msg).unwrap(), expected);
+    }
+
+    #[test]
+    fn decode_message_roundtrip_with_typed_values() {
+        let msg = SnmpMessage {
+            version: 0,
+            community: "private".to_string(),
+            pdu_type: PDU_GET_RESPONSE,
+            request_id: 0x1234_5678,
+            error_status: 0,
+            error_index: 0,
+            varbinds: vec![
+                (vec![1, 3, 6, 1, 2, 1, 1, 1, 0], BerValue::OctetString(b"HP LaserJet".to_vec())),
+                (vec![1, 3, 6, 1, 2, 1, 1, 3, 0], BerValue::TimeTicks(4_000_000_000)),
+                (vec![1, 3, 6, 1, 2, 1, 4, 20, 1, 1], BerValue::IpAddress([192, 168, 1, 10])),
+                (vec![1, 3, 6, 1, 2, 1, 31, 1, 1, 1, 6, 1], BerValue::Counter64(u64::MAX)),
+                (vec![1, 3, 6, 1, 2, 1, 1, 9, 0], BerValue::NoSuchInstance),
+            ],
+        };
+        let bytes = encode_message(&msg).unwrap();
+        assert_eq!(decode_message(&bytes).unwrap(), msg);
+    }
+
+    #[test]
+    fn decode_message_handles_long_form_lengths() {
+        let long_descr = "x".repeat(300);
+        let msg = SnmpMessage {
+            version: 1,
+            community: "public".to_string(),
+            pdu_type: PDU_GET_RESPONSE,
+            request_id: 7,
+            error_status: 0,
+            error_index: 0,
+            varbinds: vec![(vec![1, 3, 6, 1, 2, 1, 1, 1, 0], BerValue::OctetString(long_descr.clone().into_bytes()))],
+        };
+        let bytes = encode_message(&msg).unwrap();
+        assert_eq!(bytes[1], 0x82);
+        let decoded = decode_message(&bytes).unwrap();
+        assert_eq!(decoded.varbinds[0].1.as_display().as_deref(), Some(long_descr.as_str()));
+    }
+
+    #[test]
+    fn decode_message_rejects_truncated_input() {
+        let msg = SnmpMessage {
+            version: 1,
+            community: "public".to_string(),
+            pdu_type: PDU_GET_REQUEST,
+            request_id: 1,
+            error_status: 0,
+            error_index: 0,
+            varbinds: vec![(vec![1, 3, 6, 1, 2, 1, 1, 5, 0], BerValue::Null)],
+        };
+        let bytes = encode_message(&msg).unwrap();
+        assert!(decode_message(&bytes[..bytes.len() - 3]).is_err());
+        assert!(decode_message(&[]).is_err());
+        assert!(decode_message(&[0x30, 0x84, 0xFF, 0xFF, 0xFF, 0xFF]).is_err());
+    }
+
+    #[test]
+    fn as_display_renders_binary_octets_as_hex() {
+        let mac = BerValue::OctetString(vec![0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]);
+        assert_eq!(mac.as_display().as_deref(), Some("00:1A:2B:3C:4D:5E"));
+    }
+}
@@ -0,0 +1,207 @@
+// src-tauri/src/infrastructure/network/syn_scanner.rs
+
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::tcp::{self, MutableTcpPacket, TcpFlags, TcpOption, TcpPacket};
+use pnet::transport::{self, TransportChannelType, TransportProtocol};
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Cabecera TCP (20) + opcion MSS (4): un SYN "normal" pasa mejor por firewalls que uno sin opciones.
+const SYN_PACKET_LEN: usize = 24;
+const SEND_ROUNDS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SynPortState {
+    Open,
+    Closed,
+    Filtered,
+}
+
+// Escaneo TCP half-open sobre raw sockets (pnet, capa 4):
+// - envia SYN y nunca completa el handshake (el kernel responde RST al SYN-ACK)
+// - SYN-ACK => abierto, RST => cerrado, sin respuesta tras los reintentos => filtrado
+// Requiere privilegios (root/CAP_NET_RAW). Sin ellos devuelve Err para que el llamador use connect().
+pub struct SynScanner;
+
+impl SynScanner {
+    pub fn scan(target: Ipv4Addr, ports: &[u16], timeout: Duration) -> Result<Vec<(u16, SynPortState)>, String> {
+        let protocol = TransportChannelType::Layer4(TransportProtocol::Ipv4(IpNextHeaderProtocols::Tcp));
+        let (mut tx, mut rx) =
+            transport::transport_channel(4096, protocol).map_err(|e| format!("raw socket no disponible: {e}"))?;
+
+        let source_ip = source_ipv4_for(target)?;
+        let source_port = ephemeral_port();
+        let wanted: HashSet<u16> = ports.iter().copied().collect();
+        let answers: Arc<Mutex<HashMap<u16, SynPortState>>> = Arc::new(Mutex::new(HashMap::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        // Receptor en paralelo: empareja respuestas mientras seguimos enviando SYN.
+        let receiver = {
+            let answers = answers.clone();
+            let stop = stop.clone();
+            let wanted = wanted.clone();
+            thread::spawn(move || {
+                let mut iter = transport::tcp_packet_iter(&mut rx);
+                while !stop.load(Ordering::Relaxed) {
+                    let Ok(next) = iter.next_with_timeout(Duration::from_millis(50)) else {
+                        break;
+                    };
+                    let Some((packet, from)) = next else {
+                        continue;
+                    };
+                    if from != IpAddr::V4(target)
+                        || packet.get_destination() != source_port
+                        || !wanted.contains(&packet.get_source())
+                    {
+                        continue;
+                    }
+                    if let Some(state) = classify_flags(packet.get_flags()) {
+                        if let Ok(mut map) = answers.lock() {
+                            map.entry(packet.get_source()).or_insert(state);
+                        }
+                    }
+                }
+            })
+        };
+
+        let mut buffer = [0u8; SYN_PACKET_LEN];
+        for round in 0..SEND_ROUNDS {
+            let pending: Vec<u16> = {
+                let map = answers.lock().map_err(|_| "estado SYN envenenado".to_string())?;
+                ports.iter().copied().filter(|p| !map.contains_key(p)).collect()
+            };
+            if pending.is_empty() {
+                break;
+            }
+            for port in pending {
+                let seq = initial_sequence(port, round);
+                let packet = build_syn_packet(&mut buffer, source_ip, target, source_port, port, seq)
+                    .and_then(TcpPacket::new)
+                    .ok_or("no se pudo construir el SYN")?;
+                if let Err(e) = tx.send_to(packet, IpAddr::V4(target)) {
+                    stop.store(true, Ordering::Relaxed);
+                    let _ = receiver.join();
+                    return Err(format!("error enviando SYN: {e}"));
+                }
+            }
+            wait_for_answers(&answers, ports.len(), timeout);
+        }
+
+        stop.store(true, Ordering::Relaxed);
+        let _ = receiver.join();
+
+        let map = answers.lock().map_err(|_| "estado SYN envenenado".to_string())?;
+        Ok(ports
+            .iter()
+            .map(|p| (*p, map.get(p).copied().unwrap_or(SynPortState::Filtered)))
+            .collect())
+    }
+}
+
+fn wait_for_answers(answers: &Mutex<HashMap<u16, SynPortState>>, expected: usize, timeout: Duration) {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if answers.lock().map(|m| m.len() >= expected).unwrap_or(true) {
+            return;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+pub fn classify_flags(flags: u8) -> Option<SynPortState> {
+    if flags & TcpFlags::RST != 0 {
+        return Some(SynPortState::Closed);
+    }
+    if flags & (TcpFlags::SYN | TcpFlags::ACK) == TcpFlags::SYN | TcpFlags::ACK {
+        return Some(SynPortState::Open);
+    }
+    None
+}
+
+pub fn build_syn_packet(
+    buffer: &mut [u8; SYN_PACKET_LEN],
+    source: Ipv4Addr,
+    destination: Ipv4Addr,
+    source_port: u16,
+    destination_port: u16,
+    sequence: u32,
+) -> Option<&[u8]> {
+    buffer.fill(0);
+    let mut packet = MutableTcpPacket::new(buffer)?;
+    packet.set_source(source_port);
+    packet.set_destination(destination_port);
+    packet.set_sequence(sequence);
+    packet.set_acknowledgement(0);
+    packet.set_data_offset((SYN_PACKET_LEN / 4) as u8);
+    packet.set_flags(TcpFlags::SYN);
+    packet.set_window(64240);
+    packet.set_options(&[TcpOption::mss(1460)]);
+    let checksum = tcp::ipv4_checksum(&packet.to_immutable(), &source, &destination);
+    packet.set_checksum(checksum);
+    Some(&buffer[..])
+}
+
+// IP origen que usaria el kernel hacia `target` (UDP connect no envia trafico).
+// Necesaria para el checksum TCP: el pseudo-header incluye la IP origen real.
+fn source_ipv4_for(target: Ipv4Addr) -> Result<Ipv4Addr, String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| e.to_string())?;
+    socket.connect((target, 9)).map_err(|e| format!("sin ruta hacia {target}: {e}"))?;
+    match socket.local_addr().map_err(|e| e.to_string())?.ip() {
+        IpAddr::V4(ip) => Ok(ip),
+        IpAddr::V6(_) => Err("origen IPv6 inesperado".to_string()),
+    }
+}
+
+fn ephemeral_port() -> u16 {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos()).unwrap_or(0);
+    40_000 + (nanos % 20_000) as u16
+}
+
+fn initial_sequence(port: u16, round: usize) -> u32 {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos()).unwrap_or(0);
+    nanos ^ ((port as u32) << 16) ^ round as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn build_syn_packet_sets_flags_options_and_valid_checksum() {
+        let src = Ipv4Addr::new(192, 168, 1, 10);
+        let dst = Ipv4Addr::new(192, 168, 1, 1);
+        let mut buffer = [0u8; SYN_PACKET_LEN];
+        let raw = build_syn_packet(&mut buffer, src, dst, 45000, 443, 0xDEADBEEF).unwrap().to_vec();
+
+        let packet = TcpPacket::new(&raw).unwrap();
+        assert_eq!(packet.get_flags(), TcpFlags::SYN);
+        assert_eq!(packet.get_destination(), 443);
+        assert_eq!(packet.get_sequence(), 0xDEADBEEF);
+        assert_eq!(packet.get_data_offset(), 6);
+        assert_eq!(packet.get_checksum(), tcp::ipv4_checksum(&packet, &src, &dst));
+    }
+
+    #[test]
+    fn classify_flags_maps_syn_ack_and_rst() {
+        assert_eq!(classify_flags(TcpFlags::SYN | TcpFlags::ACK), Some(SynPortState::Open));
+        assert_eq!(classify_flags(TcpFlags::RST | TcpFlags::ACK), Some(SynPortState::Closed));
+        assert_eq!(classify_flags(TcpFlags::ACK), None);
+    }
+
+    #[test]
+    fn scan_loopback_when_privileged() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let open = listener.local_addr().unwrap().port();
+
+        // Sin CAP_NET_RAW el escaneo debe fallar limpio (el adaptador cae a connect).
+        let Ok(results) = SynScanner::scan(Ipv4Addr::LOCALHOST, &[open], Duration::from_millis(300)) else {
+            return;
+        };
+        assert_eq!(results, vec![(open, SynPortState::Open)]);
+    }
+}
@@ -1,35 +1,197 @@
 // src-tauri/src/infrastructure/network/traffic_sniffer.rs
 
-use crate::domain::entities::TrafficPacket;
-use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
-use pnet::packet::ipv4::Ipv4Packet;
-use pnet::packet::Packet;
+use crate::domain::entities::{
+    ArpAlert, ArpWatchReport, BandwidthStats, BandwidthWindow, CaptureAnalysis, Device, DeviceDomainLog, DeviceTlsLog,
+    DeviceUserAgents, TrafficFlow, TrafficPacket,
+};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
 use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 // Submodulos para separar responsabilidades (SOLID) sin cambiar la API publica del modulo.
 #[path = "traffic_sniffer/net.rs"]
 mod net;
+#[path = "traffic_sniffer/ip.rs"]
+mod ip;
+#[path = "traffic_sniffer/icmpv6.rs"]
+mod icmpv6;
 #[path = "traffic_sniffer/protocol.rs"]
 mod protocol;
+#[path = "traffic_sniffer/pcapng.rs"]
+mod pcapng;
+#[path = "traffic_sniffer/capture_file.rs"]
+pub mod capture_file;
+#[path = "traffic_sniffer/filter.rs"]
+pub mod filter;
+#[path = "traffic_sniffer/flows.rs"]
+pub mod flows;
+#[path = "traffic_sniffer/dns.rs"]
+mod dns;
+#[path = "traffic_sniffer/domain_log.rs"]
+mod domain_log;
+#[path = "traffic_sniffer/tls.rs"]
+mod tls;
+#[path = "traffic_sniffer/quic.rs"]
+mod quic;
+#[path = "traffic_sniffer/tls_log.rs"]
+mod tls_log;
+#[path = "traffic_sniffer/http.rs"]
+mod http;
+#[path = "traffic_sniffer/http_log.rs"]
+mod http_log;
+#[path = "traffic_sniffer/bandwidth.rs"]
+mod bandwidth;
+#[path = "traffic_sniffer/arp.rs"]
+mod arp;
+#[path = "traffic_sniffer/arp_guard.rs"]
+mod arp_guard;
+#[path = "traffic_sniffer/pcap_reader.rs"]
+mod pcap_reader;
+#[path = "traffic_sniffer/offline.rs"]
+mod offline;
+
+use arp_guard::ArpGuard;
+use bandwidth::BandwidthAccountant;
+use capture_file::PcapRecorder;
+use filter::{CaptureFilter, PacketMeta};
+use ip::IpFrame;
+use domain_log::DomainLog;
+use flows::FlowTable;
+use http_log::UserAgentLog;
+use tls_log::{HelloEvent, TlsLog};
+
+// Grabacion opcional a disco, compartida con el servicio (start/stop/rotate mientras captura).
+pub type SharedRecorder = Arc<Mutex<Option<PcapRecorder>>>;
+// Filtro de captura activo; se puede cambiar con el monitor en marcha.
+pub type SharedFilter = Arc<RwLock<Option<CaptureFilter>>>;
+// Conversaciones del monitor en curso (`get_flows` la consulta mientras el hilo la alimenta).
+pub type SharedFlows = Arc<Mutex<FlowTable>>;
+
+// Analizadores pasivos del hilo de captura: un solo candado por trama para todos. Se conservan
+// entre sesiones del monitor.
+struct Analyzers {
+    // Dominios consultados por equipo.
+    domains: DomainLog,
+    // Huellas TLS/QUIC por equipo.
+    tls: TlsLog,
+    // User-Agents HTTP en claro por equipo.
+    user_agents: UserAgentLog,
+    // Bytes por equipo/extremo en cubetas de 1 s, 1 min y 1 h; las ventanas caducan solas.
+    bandwidth: BandwidthAccountant,
+    // Vinculos IP -> MAC y alertas de ARP spoofing (tambien recibe el escaneo).
+    arp: ArpGuard,
+}
+
+// Estado compartido entre el hilo de captura y el servicio (consultas y cambios en caliente).
+#[derive(Clone)]
+pub struct CaptureState {
+    pub recorder: SharedRecorder,
+    pub filter: SharedFilter,
+    pub flows: SharedFlows,
+    analyzers: Arc<Mutex<Analyzers>>,
+}
+
+impl CaptureState {
+    pub fn new() -> Self {
+        Self {
+            recorder: Arc::new(Mutex::new(None)),
+            filter: Arc::new(RwLock::new(None)),
+            flows: Arc::new(Mutex::new(FlowTable::new())),
+            analyzers: Arc::new(Mutex::new(Analyzers {
+                domains: DomainLog::new(),
+                tls: TlsLog::new(),
+                user_agents: UserAgentLog::new(),
+                bandwidth: BandwidthAccountant::new(),
+                arp: ArpGuard::new(),
+            })),
+        }
+    }
+
+    fn lock_analyzers(&self, what: &str) -> Result<std::sync::MutexGuard<'_, Analyzers>, String> {
+        self.analyzers.lock().map_err(|_| format!("Estado {} corrupto", what))
+    }
+
+    pub fn dns_log(&self, device_ip: Option<&str>) -> Result<Vec<DeviceDomainLog>, String> {
+        Ok(self.lock_analyzers("DNS")?.domains.snapshot(device_ip))
+    }
+
+    pub fn tls_log(&self, device_ip: Option<&str>) -> Result<Vec<DeviceTlsLog>, String> {
+        Ok(self.lock_analyzers("TLS")?.tls.snapshot(device_ip))
+    }
+
+    pub fn user_agents(&self, device_ip: Option<&str>) -> Result<Vec<DeviceUserAgents>, String> {
+        Ok(self.lock_analyzers("HTTP")?.user_agents.snapshot(device_ip))
+    }
+
+    // Top-N con las series de cada IP (el evento periodico va sin ellas).
+    pub fn bandwidth_stats(&self, window: BandwidthWindow, top_n: usize) -> Result<BandwidthStats, String> {
+        let analyzers = self.lock_analyzers("de ancho de banda")?;
+        Ok(analyzers.bandwidth.stats(protocol::unix_ms(), window, top_n, true))
+    }
+
+    pub fn arp_watch(&self) -> Result<ArpWatchReport, String> {
+        Ok(self.lock_analyzers("ARP")?.arp.report())
+    }
+
+    // Siembra los vinculos IP -> MAC con el resultado de un escaneo (referencia para las alertas).
+    pub fn seed_arp_bindings(&self, devices: &[Device]) -> Result<(), String> {
+        let mut analyzers = self.lock_analyzers("ARP")?;
+        let now = protocol::unix_ms();
+        for device in devices {
+            analyzers.arp.seed(&device.ip, &device.mac, device.is_gateway, now);
+        }
+        Ok(())
+    }
+
+    pub fn set_arp_gateway(&self, gateway_ip: &str) -> Result<(), String> {
+        self.lock_analyzers("ARP")?.arp.set_gateway(gateway_ip);
+        Ok(())
+    }
+}
+
+// Cada cuanto se caducan flujos inactivos y se emiten los cambios a la UI.
+const FLOW_UPDATE_INTERVAL: Duration = Duration::from_secs(2);
+// Resumen de ancho de banda para la UI (ultimo segundo completo, top-N sin series).
+const BANDWIDTH_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+const BANDWIDTH_SUMMARY_TOP: usize = 10;
+// Los paquetes que pasan el filtro se agrupan: un evento por tick en vez de uno por paquete.
+const PACKET_BATCH_INTERVAL: Duration = Duration::from_millis(250);
+// Tope por lote (~800 paquetes/s a la UI); el exceso sigue contando en flujos, logs y ancho de banda.
+const MAX_PACKETS_PER_BATCH: usize = 200;
+
+pub enum SnifferEvent {
+    // Paquetes que pasaron el filtro desde el lote anterior, en orden de llegada.
+    Packets(Vec<TrafficPacket>),
+    // Flujos nuevos, actualizados o cerrados desde la emision anterior.
+    Flows(Vec<TrafficFlow>),
+    // Resumen periodico de consumo (sustituye a sumar `length` paquete a paquete en la UI).
+    Bandwidth(BandwidthStats),
+    // Posible ARP spoofing o conflicto MAC/IP (se emite al detectarlo).
+    ArpAlert(ArpAlert),
+}
 
 pub struct TrafficSniffer;
 
 impl TrafficSniffer {
-    pub fn preflight(interface_hint: &str, target_ip: &str) -> Result<(), String> {
+    // Devuelve el nombre de la interfaz elegida (descripcion de interfaz del PCAPNG).
+    pub fn preflight(interface_hint: &str, target_ip: &str) -> Result<String, String> {
         let (interface, _my_ip) = net::select_interface(interface_hint, target_ip)?;
         let _rx = net::open_ethernet_rx(&interface)?;
-        Ok(())
+        Ok(interface.name)
     }
 
     pub fn start_capture<F>(
         interface_hint: String,
         target_ip: String,
         running: Arc<AtomicBool>,
+        state: CaptureState,
         callback: F,
     ) where
-        F: Fn(TrafficPacket) + Send + Sync + 'static,
+        F: Fn(SnifferEvent) + Send + Sync + 'static,
     {
         thread::spawn(move || {
             println!(
@@ -45,7 +207,11 @@ impl TrafficSniffer {
                 }
             };
 
-            println!("✅ [SNIFFER] Interfaz='{}' mi_ip={} objetivo={}", interface.name, my_ip, target_ip);
+            let my_ipv6 = net::ipv6_addresses(&interface);
+            println!(
+                "✅ [SNIFFER] Interfaz='{}' mi_ip={} ipv6={:?} objetivo={}",
+                interface.name, my_ip, my_ipv6, target_ip
+            );
 
             let mut rx = match net::open_ethernet_rx(&interface) {
                 Ok(rx) => rx,
@@ -57,63 +223,362 @@ impl TrafficSniffer {
 
             // Bucle de captura (thread dedicado). Si el driver no soporta timeout, el stop puede tardar en reflejarse.
             let mut packet_id: usize = 0;
+            let mut last_flow_update = Instant::now();
+            let mut last_bandwidth_update = Instant::now();
+            let mut last_packet_batch = Instant::now();
+            let mut batch: Vec<TrafficPacket> = Vec::new();
+            let mut dropped: usize = 0;
 
             while running.load(Ordering::Relaxed) {
+                if last_packet_batch.elapsed() >= PACKET_BATCH_INTERVAL {
+                    last_packet_batch = Instant::now();
+                    emit_packet_batch(&mut batch, &mut dropped, &callback);
+                }
+                // El timeout de lectura garantiza que esto corre aunque no haya trafico.
+                if last_flow_update.elapsed() >= FLOW_UPDATE_INTERVAL {
+                    last_flow_update = Instant::now();
+                    emit_flow_updates(&state.flows, &callback, false);
+                }
+                if last_bandwidth_update.elapsed() >= BANDWIDTH_UPDATE_INTERVAL {
+                    last_bandwidth_update = Instant::now();
+                    emit_bandwidth_summary(&state.analyzers, &callback);
+                }
+
                 let packet = match rx.next() {
                     Ok(p) => p,
                     Err(_) => continue,
                 };
 
-                if let Some(dto) = map_packet(packet, &my_ip, &target_ip, packet_id) {
-                    callback(dto);
-                    packet_id = packet_id.saturating_add(1);
+                record_frame(&state.recorder, packet);
+                let now = protocol::unix_ms();
+                // ARP no es IP (no pasa por `map_packet`): va directo al vigilante.
+                if let Some(message) = arp::parse_frame(packet) {
+                    let alerts = state.analyzers.lock().map(|mut analyzers| analyzers.arp.observe(&message, now));
+                    for alert in alerts.unwrap_or_default() {
+                        callback(SnifferEvent::ArpAlert(alert));
+                    }
+                    continue;
+                }
+                // Ethernet/IP/transporte se leen una sola vez; todos los modulos reciben la misma vista.
+                let Some(frame) = ip::parse_frame(packet) else {
+                    continue;
+                };
+                let (classified, hello) = observe_frame(&state, &frame, now);
+                let scope = Some(Scope { my_ip: &my_ip, target_ip: &target_ip, my_ipv6: &my_ipv6 });
+                let active_filter = state.filter.read().ok();
+                let active_filter = active_filter.as_ref().and_then(|guard| guard.as_ref());
+                let Some(mut dto) = map_packet(&frame, classified, scope, active_filter, packet_id, now) else {
+                    continue;
+                };
+                // SNI/ALPN en el paquete que completa el ClientHello (puede llegar en varios segmentos).
+                if let Some(hello) = hello {
+                    dto.info = hello.describe();
                 }
+                if batch.len() < MAX_PACKETS_PER_BATCH {
+                    batch.push(dto);
+                } else {
+                    dropped += 1;
+                }
+                packet_id = packet_id.saturating_add(1);
             }
 
+            // Al parar, todo lo vivo pasa a cerrado (la tabla sigue consultable con `get_flows`).
+            emit_packet_batch(&mut batch, &mut dropped, &callback);
+            emit_flow_updates(&state.flows, &callback, true);
+
             println!("🛑 [SNIFFER] Detenido.");
         });
     }
+
+    // Analisis offline de un pcap/pcapng: mismo `map_packet` que en vivo, con las marcas originales.
+    // `focus_ip` limita el resultado al trafico de ese equipo; `filter` aplica el mismo lenguaje que en vivo.
+    pub fn analyze_file(
+        path: &Path,
+        focus_ip: Option<&str>,
+        filter: Option<&CaptureFilter>,
+    ) -> Result<CaptureAnalysis, String> {
+        let file = File::open(path).map_err(|e| format!("No se pudo abrir {}: {}", path.display(), e))?;
+        let reader = pcap_reader::CaptureReader::open(BufReader::new(file))?;
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        println!("📂 [SNIFFER] Analizando captura {} ({})", file_name, reader.format().as_str());
+        Ok(offline::analyze(reader, focus_ip, filter, file_name))
+    }
 }
 
-fn map_packet(packet: &[u8], my_ip: &str, target_ip: &str, packet_id: usize) -> Option<TrafficPacket> {
-    let now = protocol::unix_ms();
+fn emit_packet_batch<F: Fn(SnifferEvent)>(batch: &mut Vec<TrafficPacket>, dropped: &mut usize, callback: &F) {
+    if *dropped > 0 {
+        println!("⚠️ [SNIFFER] {} paquetes fuera del lote para la UI (tope {})", dropped, MAX_PACKETS_PER_BATCH);
+        *dropped = 0;
+    }
+    if !batch.is_empty() {
+        callback(SnifferEvent::Packets(std::mem::take(batch)));
+    }
+}
 
-    let eth = EthernetPacket::new(packet)?;
-    if eth.get_ethertype() != EtherTypes::Ipv4 {
-        return None;
+fn emit_flow_updates<F: Fn(SnifferEvent)>(flows: &SharedFlows, callback: &F, close_all: bool) {
+    let updates = {
+        let Ok(mut table) = flows.lock() else {
+            return;
+        };
+        if close_all {
+            table.close_all();
+        } else {
+            table.expire(protocol::unix_ms());
+        }
+        table.take_updates()
+    };
+    if !updates.is_empty() {
+        callback(SnifferEvent::Flows(updates));
     }
+}
+
+fn emit_bandwidth_summary<F: Fn(SnifferEvent)>(analyzers: &Mutex<Analyzers>, callback: &F) {
+    let summary = {
+        let Ok(analyzers) = analyzers.lock() else {
+            return;
+        };
+        analyzers.bandwidth.stats(protocol::unix_ms(), BandwidthWindow::Second, BANDWIDTH_SUMMARY_TOP, false)
+    };
+    callback(SnifferEvent::Bandwidth(summary));
+}
+
+// Best-effort: un error de disco cierra la grabacion pero no para el monitor en vivo.
+fn record_frame(recorder: &SharedRecorder, frame: &[u8]) {
+    let Ok(mut guard) = recorder.lock() else {
+        return;
+    };
+    let Some(active) = guard.as_mut() else {
+        return;
+    };
+    let timestamp_us = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros() as u64;
+    if let Err(e) = active.write_frame(timestamp_us, frame) {
+        eprintln!("❌ [SNIFFER] Grabacion PCAPNG detenida: {e}");
+        if let Some(recorder) = guard.take() {
+            let _ = recorder.finish();
+        }
+    }
+}
 
-    let ipv4 = Ipv4Packet::new(eth.payload())?;
+// Quien soy en la red durante una captura en vivo.
+#[derive(Clone, Copy)]
+struct Scope<'a> {
+    my_ip: &'a str,
+    // IP que pasa la UI (normalmente, mi IP local).
+    target_ip: &'a str,
+    // Direcciones IPv6 de la interfaz (link-local, globales, temporales), en forma canonica.
+    my_ipv6: &'a [String],
+}
 
-    let src = ipv4.get_source().to_string();
-    let dst = ipv4.get_destination().to_string();
+impl Scope<'_> {
+    fn is_my_ipv6(&self, ip: &str) -> bool {
+        self.my_ipv6.iter().any(|mine| mine == ip)
+    }
 
-    let is_intercepted = protocol::is_intercepted(my_ip, &src, &dst);
+    // Me implica a mi (target). En IPv6 cuenta cualquiera de mis direcciones si el objetivo soy yo.
+    fn involves_target(&self, src: &str, dst: &str) -> bool {
+        src == self.target_ip
+            || dst == self.target_ip
+            || (self.target_ip == self.my_ip && (self.is_my_ipv6(src) || self.is_my_ipv6(dst)))
+    }
 
-    // Filtro maestro: mostramos si me implica a mi (target) o si es un paquete interceptado.
-    // Nota: `target_ip` es la IP que pasa la UI (normalmente, mi IP local).
-    if src != target_ip && dst != target_ip && !is_intercepted {
-        return None;
+    fn is_intercepted(&self, src: &str, dst: &str) -> bool {
+        !self.is_my_ipv6(src) && !self.is_my_ipv6(dst) && protocol::is_intercepted(self.my_ip, src, dst)
     }
+}
+
+// Analizadores y flujos ven todo lo que ve la interfaz: el filtro (o el alcance por defecto) solo
+// decide que paquetes se pintan en la lista. Devuelve la clasificacion y el ClientHello completado.
+fn observe_frame(state: &CaptureState, frame: &IpFrame, now: u64) -> ((String, String), Option<HelloEvent>) {
+    let classified = protocol::analyze(frame);
+    let hello = state.analyzers.lock().ok().and_then(|mut analyzers| {
+        analyzers.bandwidth.observe_ip(frame, now);
+        analyzers.domains.observe_ip(frame, now);
+        analyzers.user_agents.observe_ip(frame, now);
+        analyzers.tls.observe_ip(frame, now)
+    });
+    if let Ok(mut table) = state.flows.lock() {
+        table.observe_ip(frame, now, &classified.1);
+    }
+    (classified, hello)
+}
 
-    let (proto, info) = protocol::analyze_ipv4(&ipv4);
+// `classified`: (protocolo, info) de `protocol::analyze`, ya calculado para los flujos.
+// `scope` en vivo; `None` en analisis offline (sin "interceptado").
+// Con `filter` manda la expresion del usuario; sin el, el filtro maestro del monitor.
+fn map_packet(
+    frame: &IpFrame,
+    classified: (String, String),
+    scope: Option<Scope>,
+    filter: Option<&CaptureFilter>,
+    packet_id: usize,
+    timestamp: u64,
+) -> Option<TrafficPacket> {
+    let src = frame.src.to_string();
+    let dst = frame.dst.to_string();
 
+    let is_intercepted = scope.is_some_and(|scope| scope.is_intercepted(&src, &dst));
+
+    match (filter, scope) {
+        (Some(filter), _) => {
+            let (src_port, dst_port) = frame.ports();
+            let meta = PacketMeta {
+                src: frame.src,
+                dst: frame.dst,
+                protocol: frame.protocol,
+                src_port,
+                dst_port,
+                length: frame.length,
+            };
+            if !filter.matches(&meta) {
+                return None;
+            }
+        }
+        // Filtro maestro: mostramos si me implica a mi (target) o si es un paquete interceptado.
+        (None, Some(scope)) => {
+            if !scope.involves_target(&src, &dst) && !is_intercepted {
+                return None;
+            }
+        }
+        (None, None) => {}
+    }
+
+    let (proto, info) = classified;
     Some(TrafficPacket {
         id: packet_id,
-        timestamp: now,
+        timestamp,
         source_ip: src,
         destination_ip: dst,
         protocol: proto,
-        length: ipv4.get_total_length() as usize,
+        length: frame.length,
         info,
         is_intercepted,
     })
 }
 
+#[cfg(test)]
+pub mod test_support {
+    use super::*;
+
+    // Trama IPv6 UDP desde el puerto 40000 hacia `dst_port`.
+    pub fn udp_packet(src: &str, dst: &str, dst_port: u16, payload: &[u8]) -> Vec<u8> {
+        let mut udp = 40000u16.to_be_bytes().to_vec();
+        udp.extend_from_slice(&dst_port.to_be_bytes());
+        udp.extend_from_slice(&((8 + payload.len()) as u16).to_be_bytes());
+        udp.extend_from_slice(&[0, 0]);
+        udp.extend_from_slice(payload);
+        ip::tests::ipv6_frame(src, dst, ip::PROTO_UDP, &udp)
+    }
+
+    // Trama IPv6 TCP (PSH|ACK) desde el puerto 40000 hacia `dst_port`.
+    pub fn tcp_packet(src: &str, dst: &str, dst_port: u16, payload: &[u8]) -> Vec<u8> {
+        let mut tcp = 40000u16.to_be_bytes().to_vec();
+        tcp.extend_from_slice(&dst_port.to_be_bytes());
+        tcp.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 0, 0x50, 0x18, 0xFF, 0xFF, 0, 0, 0, 0]);
+        tcp.extend_from_slice(payload);
+        ip::tests::ipv6_frame(src, dst, ip::PROTO_TCP, &tcp)
+    }
+
+    pub fn dns_query(name: &str) -> Vec<u8> {
+        dns::tests::query(7, name, 1)
+    }
+
+    pub fn tls_client_hello(server_name: &str) -> Vec<u8> {
+        tls::tests::record(&tls::tests::client_hello(server_name))
+    }
+
+    pub fn http_get(host: &str, user_agent: &str) -> Vec<u8> {
+        format!("GET / HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\n\r\n", host, user_agent).into_bytes()
+    }
+
+    // Lo mismo que hace el bucle de captura con cada trama, sin filtro ni lista de paquetes.
+    pub fn feed(state: &CaptureState, packet: &[u8], now: u64) {
+        observe_frame(state, &ip::parse_frame(packet).unwrap(), now);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn udp_frame(src: [u8; 4], dst: [u8; 4], dst_port: u16) -> Vec<u8> {
+        let mut frame = vec![0x02, 0, 0, 0, 0, 0x01, 0x02, 0, 0, 0, 0, 0x02, 0x08, 0x00];
+        frame.extend_from_slice(&[0x45, 0, 0, 28, 0, 0, 0, 0, 64, 17, 0, 0]);
+        frame.extend_from_slice(&src);
+        frame.extend_from_slice(&dst);
+        frame.extend_from_slice(&40000u16.to_be_bytes());
+        frame.extend_from_slice(&dst_port.to_be_bytes());
+        frame.extend_from_slice(&[0, 8, 0, 0]);
+        frame
+    }
+
+    fn mapped(packet: &[u8], scope: Option<Scope>, filter: Option<&CaptureFilter>) -> Option<TrafficPacket> {
+        let frame = ip::parse_frame(packet)?;
+        map_packet(&frame, protocol::analyze(&frame), scope, filter, 0, 0)
+    }
+
+    fn scope<'a>(my_ipv6: &'a [String]) -> Option<Scope<'a>> {
+        Some(Scope { my_ip: "192.168.1.10", target_ip: "192.168.1.10", my_ipv6 })
+    }
+
+    #[test]
+    fn user_filter_replaces_the_default_scope() {
+        let scope = scope(&[]);
+        let other_dns = udp_frame([192, 168, 1, 20], [192, 168, 1, 1], 53);
+        let other_ntp = udp_frame([192, 168, 1, 20], [192, 168, 1, 1], 123);
+        let broadcast = udp_frame([192, 168, 1, 20], [255, 255, 255, 255], 53);
+
+        // Sin filtro: lo "interceptado" pasa y el broadcast ajeno no.
+        assert!(mapped(&other_ntp, scope, None).is_some_and(|p| p.is_intercepted));
+        assert!(mapped(&broadcast, scope, None).is_none());
+
+        let dns_of_device = CaptureFilter::compile("host 192.168.1.20 and udp port 53").unwrap();
+        assert!(mapped(&other_dns, scope, Some(&dns_of_device)).is_some());
+        assert!(mapped(&other_ntp, scope, Some(&dns_of_device)).is_none());
+        assert!(mapped(&broadcast, scope, Some(&dns_of_device)).is_some());
+    }
+
+    #[test]
+    fn ipv6_frames_are_scoped_and_classified() {
+        let mine = vec!["fe80::10".to_string(), "2001:db8::10".to_string()];
+        let scope = scope(&mine);
+        let udp = |src: &str, dst: &str, port: u16| {
+            let mut body = 40000u16.to_be_bytes().to_vec();
+            body.extend_from_slice(&port.to_be_bytes());
+            body.extend_from_slice(&[0, 8, 0, 0]);
+            ip::tests::ipv6_frame(src, dst, ip::PROTO_UDP, &body)
+        };
+
+        // Trafico mio por la direccion global: se muestra y no es interceptado.
+        let packet = mapped(&udp("2001:db8::10", "2001:4860:4860::8888", 53), scope, None).unwrap();
+        assert_eq!((packet.source_ip.as_str(), packet.protocol.as_str()), ("2001:db8::10", "DNS"));
+        assert_eq!(packet.length, 48);
+        assert!(!packet.is_intercepted);
+        // Entre dos terceros: interceptado. Multicast ajeno: ni se muestra.
+        let third_party = udp("2001:db8::20", "2001:db8::30", 123);
+        assert!(mapped(&third_party, scope, None).is_some_and(|p| p.is_intercepted));
+        assert!(mapped(&udp("fe80::20", "ff02::fb", 5353), scope, None).is_none());
+
+        let ns = icmpv6::tests::neighbor_advertisement("fe80::1", [0x02, 0, 0, 0, 0, 1], true);
+        let frame = ip::tests::ipv6_frame("fe80::1", "fe80::10", ip::PROTO_ICMPV6, &ns);
+        let packet = mapped(&frame, scope, None).unwrap();
+        assert_eq!(packet.protocol, "NDP");
+        assert!(packet.info.starts_with("NDP Neighbor Advertisement: fe80::1 esta en 02:00:00:00:00:01"));
+
+        let ndp_only = CaptureFilter::compile("ip6 and icmp6 and src net fe80::/10").unwrap();
+        assert!(mapped(&frame, scope, Some(&ndp_only)).is_some());
+        assert!(mapped(&udp("fe80::20", "ff02::fb", 5353), None, Some(&ndp_only)).is_none());
+
+        let (p, _) = protocol::classify("ff02::c", protocol::ProtoKind::Udp, 1234, 1900);
+        assert_eq!(p, "SSDP");
+        assert!(!protocol::is_intercepted("192.168.1.10", "2001:db8::20", "ff02::1"));
+    }
+
     #[test]
     fn intercepted_detection_is_reasonable() {
         assert!(!protocol::is_intercepted("192.168.1.10", "192.168.1.10", "192.168.1.20"));
@@ -131,4 +596,22 @@ mod tests {
         let (p, _) = protocol::classify("239.255.255.250", protocol::ProtoKind::Udp, 1234, 1900);
         assert_eq!(p, "SSDP");
     }
+
+    #[test]
+    fn analyzers_and_flows_see_packets_hidden_by_the_filter() {
+        let state = CaptureState::new();
+        let query = test_support::dns_query("cloud.vendor.cn");
+        let packet = test_support::udp_packet("2001:db8::20", "2001:db8::1", 53, &query);
+        let frame = ip::parse_frame(&packet).unwrap();
+
+        // Un filtro estrecho oculta la consulta de la lista, pero no de los registros.
+        let (classified, _) = observe_frame(&state, &frame, 1_000);
+        let https_only = CaptureFilter::compile("tcp port 443").unwrap();
+        assert!(map_packet(&frame, classified, None, Some(&https_only), 0, 1_000).is_none());
+
+        let domains = state.dns_log(Some("2001:db8::20")).unwrap();
+        assert_eq!(domains[0].domains[0].domain, "cloud.vendor.cn");
+        let flows = state.flows.lock().unwrap().snapshot();
+        assert_eq!((flows.len(), flows[0].resp_port), (1, Some(53)));
+    }
 }
@@ -0,0 +1,124 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/arp.rs
+
+use std::net::Ipv4Addr;
+
+use pnet::packet::arp::{ArpHardwareTypes, ArpOperations, ArpPacket};
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::util::MacAddr;
+
+// ARP sobre Ethernet/IPv4 (RFC 826): lo unico que se puede suplantar en una LAN IPv4 sin routers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArpMessage {
+    pub is_reply: bool,
+    // MAC origen de la trama Ethernet; si no coincide con `sender_mac`, alguien reenvia o falsifica.
+    pub eth_source: MacAddr,
+    pub sender_mac: MacAddr,
+    pub sender_ip: Ipv4Addr,
+    pub target_mac: MacAddr,
+    pub target_ip: Ipv4Addr,
+}
+
+impl ArpMessage {
+    // Gratuito: anuncia su propia IP (sender == target) o responde a nadie (destino broadcast).
+    pub fn is_gratuitous(&self) -> bool {
+        self.sender_ip == self.target_ip || (self.is_reply && self.target_mac == MacAddr::broadcast())
+    }
+
+    // ARP probe (RFC 5227): pregunta por una IP sin reclamar ninguna.
+    pub fn is_probe(&self) -> bool {
+        self.sender_ip.is_unspecified()
+    }
+
+    pub fn describe(&self) -> String {
+        if self.is_probe() {
+            return format!("ARP probe: {} en uso?", self.target_ip);
+        }
+        let text = if self.is_reply {
+            format!("ARP reply: {} esta en {}", self.sender_ip, self.sender_mac)
+        } else {
+            format!("ARP request: quien tiene {}? (pregunta {})", self.target_ip, self.sender_ip)
+        };
+        if self.is_gratuitous() {
+            format!("{} (gratuito)", text)
+        } else {
+            text
+        }
+    }
+}
+
+pub fn parse_frame(frame: &[u8]) -> Option<ArpMessage> {
+    let eth = EthernetPacket::new(frame)?;
+    if eth.get_ethertype() != EtherTypes::Arp {
+        return None;
+    }
+    let arp = ArpPacket::new(frame.get(EthernetPacket::minimum_packet_size()..)?)?;
+    if arp.get_hardware_type() != ArpHardwareTypes::Ethernet
+        || arp.get_protocol_type() != EtherTypes::Ipv4
+        || arp.get_hw_addr_len() != 6
+        || arp.get_proto_addr_len() != 4
+    {
+        return None;
+    }
+    let is_reply = match arp.get_operation() {
+        ArpOperations::Reply => true,
+        ArpOperations::Request => false,
+        _ => return None,
+    };
+    Some(ArpMessage {
+        is_reply,
+        eth_source: eth.get_source(),
+        sender_mac: arp.get_sender_hw_addr(),
+        sender_ip: arp.get_sender_proto_addr(),
+        target_mac: arp.get_target_hw_addr(),
+        target_ip: arp.get_target_proto_addr(),
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    // Trama Ethernet + ARP con la misma MAC en la cabecera Ethernet y en `sender`.
+    pub fn arp_frame(is_reply: bool, sender: ([u8; 6], [u8; 4]), target: ([u8; 6], [u8; 4])) -> Vec<u8> {
+        let eth_dst = if is_reply && target.0 != [0; 6] { target.0 } else { [0xFF; 6] };
+        let mut frame = eth_dst.to_vec();
+        frame.extend_from_slice(&sender.0);
+        frame.extend_from_slice(&[0x08, 0x06, 0, 1, 0x08, 0x00, 6, 4, 0, if is_reply { 2 } else { 1 }]);
+        frame.extend_from_slice(&sender.0);
+        frame.extend_from_slice(&sender.1);
+        frame.extend_from_slice(&target.0);
+        frame.extend_from_slice(&target.1);
+        frame
+    }
+
+    #[test]
+    fn parses_requests_replies_and_gratuitous_announcements() {
+        let router = ([0x02, 0, 0, 0, 0, 0x01], [192, 168, 1, 1]);
+        let laptop = ([0x02, 0, 0, 0, 0, 0x20], [192, 168, 1, 20]);
+
+        let request = parse_frame(&arp_frame(false, laptop, ([0; 6], router.1))).unwrap();
+        assert!(!request.is_reply && !request.is_gratuitous());
+        assert_eq!(request.describe(), "ARP request: quien tiene 192.168.1.1? (pregunta 192.168.1.20)");
+
+        let reply = parse_frame(&arp_frame(true, router, laptop)).unwrap();
+        assert_eq!(
+            (reply.sender_ip, reply.sender_mac),
+            (Ipv4Addr::new(192, 168, 1, 1), MacAddr::new(2, 0, 0, 0, 0, 1))
+        );
+        assert_eq!(reply.eth_source, reply.sender_mac);
+        assert!(!reply.is_gratuitous());
+
+        let announce = parse_frame(&arp_frame(false, router, ([0; 6], router.1))).unwrap();
+        assert!(announce.is_gratuitous());
+        assert_eq!(announce.describe(), "ARP request: quien tiene 192.168.1.1? (pregunta 192.168.1.1) (gratuito)");
+        let probe = parse_frame(&arp_frame(false, (laptop.0, [0; 4]), ([0; 6], laptop.1))).unwrap();
+        assert!(probe.is_probe());
+
+        // Truncada o de otro tipo de hardware: se ignora.
+        let frame = arp_frame(true, router, laptop);
+        assert!(parse_frame(&frame[..30]).is_none());
+        let mut token_ring = frame.clone();
+        token_ring[15] = 6;
+        assert!(parse_frame(&token_ring).is_none());
+    }
+}
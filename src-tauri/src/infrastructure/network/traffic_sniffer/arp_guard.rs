@@ -0,0 +1,446 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/arp_guard.rs
+
+use std::collections::{HashMap, VecDeque};
+use std::net::Ipv4Addr;
+
+use pnet::util::MacAddr;
+
+use crate::domain::entities::{ArpAlert, ArpBinding, ArpWatchReport};
+
+use super::arp::ArpMessage;
+
+const MAX_BINDINGS: usize = 1_024;
+const MAX_ALERTS: usize = 500;
+const MAX_PENDING_REQUESTS: usize = 1_024;
+// Una respuesta es "solicitada" si alguien pregunto por esa IP hace poco.
+const REQUEST_TTL_MS: u64 = 3_000;
+// Un envenenador (como JammerService) repite la respuesta varias veces por segundo; un equipo
+// legitimo manda alguna gratuita al conectarse y poco mas.
+const FLOOD_WINDOW_MS: u64 = 10_000;
+const FLOOD_THRESHOLD: usize = 15;
+// IPs reclamadas por una MAC que cuentan como "a la vez".
+const CLAIM_TTL_MS: u64 = 300_000;
+// La misma alerta (tipo, IP, MAC) no se repite antes de esto.
+const ALERT_COOLDOWN_MS: u64 = 60_000;
+
+// Tipo, IP y MAC de la alerta.
+type AlertKey = (String, Option<String>, String);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Scan,
+    Arp,
+}
+
+impl Source {
+    fn as_str(self) -> &'static str {
+        match self {
+            Source::Scan => "scan",
+            Source::Arp => "arp",
+        }
+    }
+}
+
+struct Binding {
+    mac: MacAddr,
+    source: Source,
+    first_seen: u64,
+    last_seen: u64,
+    mac_changes: u32,
+}
+
+// IDS pasivo de ARP: lo contrario de lo que hace JammerService. Solo ve lo que llega a esta
+// interfaz (preguntas en broadcast, respuestas para mi y anuncios gratuitos), que es justo lo
+// que usa un envenenador para llegar a sus victimas.
+#[derive(Default)]
+pub struct ArpGuard {
+    bindings: HashMap<Ipv4Addr, Binding>,
+    gateway: Option<Ipv4Addr>,
+    // MAC del gateway segun el escaneo: el router puede tener varias IPs sin ser un ataque.
+    gateway_mac: Option<MacAddr>,
+    // IPs reclamadas por cada MAC (como emisora de ARP) y la ultima vez.
+    claims: HashMap<MacAddr, HashMap<Ipv4Addr, u64>>,
+    // IP por la que alguien pregunto -> cuando.
+    pending: HashMap<Ipv4Addr, u64>,
+    // Respuestas sin pregunta previa por MAC emisora, dentro de la ventana.
+    unsolicited: HashMap<MacAddr, VecDeque<u64>>,
+    alerts: VecDeque<ArpAlert>,
+    last_raised: HashMap<AlertKey, u64>,
+    next_id: u64,
+}
+
+impl ArpGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_gateway(&mut self, ip: &str) {
+        if let Ok(ip) = ip.trim().parse::<Ipv4Addr>() {
+            self.gateway = Some(ip);
+        }
+    }
+
+    // Vinculo conocido por el escaneo. Manda sobre lo aprendido: es la referencia para las alertas.
+    pub fn seed(&mut self, ip: &str, mac: &str, is_gateway: bool, now: u64) {
+        let (Ok(ip), Some(mac)) = (ip.trim().parse::<Ipv4Addr>(), parse_mac(mac)) else {
+            return;
+        };
+        if is_gateway {
+            self.gateway = Some(ip);
+            self.gateway_mac = Some(mac);
+        }
+        match self.bindings.get_mut(&ip) {
+            Some(binding) => {
+                if binding.mac != mac {
+                    binding.mac = mac;
+                    binding.first_seen = now;
+                }
+                binding.source = Source::Scan;
+                binding.last_seen = binding.last_seen.max(now);
+            }
+            None => self.insert_binding(ip, mac, Source::Scan, now),
+        }
+        self.claims.entry(mac).or_default().insert(ip, now);
+    }
+
+    // Devuelve las alertas nuevas que provoca este mensaje.
+    pub fn observe(&mut self, message: &ArpMessage, now: u64) -> Vec<ArpAlert> {
+        let mut raised = Vec::new();
+        self.expire(now);
+        if !message.is_reply
+            && (self.pending.len() < MAX_PENDING_REQUESTS || self.pending.contains_key(&message.target_ip))
+        {
+            self.pending.insert(message.target_ip, now);
+        }
+        if message.is_probe() || message.sender_mac == MacAddr::zero() || message.sender_mac.is_broadcast() {
+            return raised;
+        }
+        if message.is_reply && !self.pending.contains_key(&message.sender_ip) {
+            self.check_flood(message, now, &mut raised);
+        }
+        self.check_binding(message, now, &mut raised);
+        self.check_claims(message, now, &mut raised);
+        raised
+    }
+
+    pub fn report(&self) -> ArpWatchReport {
+        let mut bindings: Vec<ArpBinding> = self
+            .bindings
+            .iter()
+            .map(|(ip, b)| ArpBinding {
+                ip: ip.to_string(),
+                mac: b.mac.to_string(),
+                source: b.source.as_str().to_string(),
+                is_gateway: self.gateway == Some(*ip),
+                first_seen: b.first_seen,
+                last_seen: b.last_seen,
+                mac_changes: b.mac_changes,
+            })
+            .collect();
+        bindings.sort_by_key(|b| b.ip.parse::<Ipv4Addr>().ok());
+        ArpWatchReport {
+            gateway_ip: self.gateway.map(|ip| ip.to_string()),
+            bindings,
+            alerts: self.alerts.iter().rev().cloned().collect(),
+        }
+    }
+
+    fn check_flood(&mut self, message: &ArpMessage, now: u64, raised: &mut Vec<ArpAlert>) {
+        if !self.unsolicited.contains_key(&message.sender_mac) && self.unsolicited.len() >= MAX_BINDINGS {
+            return;
+        }
+        let times = self.unsolicited.entry(message.sender_mac).or_default();
+        times.push_back(now);
+        while times.front().is_some_and(|t| now.saturating_sub(*t) > FLOOD_WINDOW_MS) {
+            times.pop_front();
+        }
+        let count = times.len();
+        if count < FLOOD_THRESHOLD {
+            return;
+        }
+        let is_gateway = self.gateway == Some(message.sender_ip);
+        let alert = new_alert(
+            "unsolicited_reply_flood",
+            if is_gateway { "CRITICAL" } else { "HIGH" },
+            Some(message.sender_ip),
+            message.sender_mac,
+            format!(
+                "{} envia respuestas ARP que nadie ha pedido: envenenamiento de caches en curso",
+                message.sender_mac
+            ),
+            vec![
+                format!("{} respuestas ARP sin pregunta previa en {} s", count, FLOOD_WINDOW_MS / 1000),
+                format!("Ultima: {} para {}", message.describe(), message.target_ip),
+                format!("IPs reclamadas por {}: {}", message.sender_mac, self.claimed_by(message.sender_mac)),
+            ],
+            now,
+        );
+        self.raise(alert, raised);
+    }
+
+    fn check_binding(&mut self, message: &ArpMessage, now: u64, raised: &mut Vec<ArpAlert>) {
+        let ip = message.sender_ip;
+        let Some(binding) = self.bindings.get_mut(&ip) else {
+            self.insert_binding(ip, message.sender_mac, Source::Arp, now);
+            return;
+        };
+        if binding.mac == message.sender_mac {
+            binding.last_seen = binding.last_seen.max(now);
+            return;
+        }
+        let (previous, source, last_seen) = (binding.mac, binding.source, binding.last_seen);
+        binding.mac = message.sender_mac;
+        binding.source = Source::Arp;
+        binding.last_seen = now;
+        binding.mac_changes += 1;
+        let changes = binding.mac_changes;
+
+        let is_gateway = self.gateway == Some(ip);
+        let gratuitous = message.is_gratuitous();
+        let severity = if is_gateway {
+            "CRITICAL"
+        } else if source == Source::Scan || gratuitous {
+            "HIGH"
+        } else {
+            "MEDIUM"
+        };
+        let message_text = if is_gateway {
+            format!(
+                "La MAC del gateway {} ha cambiado ({} -> {}): posible ARP spoofing (MITM)",
+                ip, previous, message.sender_mac
+            )
+        } else {
+            format!("{} ha cambiado de MAC ({} -> {})", ip, previous, message.sender_mac)
+        };
+        let mut evidence = vec![
+            format!(
+                "Vinculo previo: {} -> {} (origen {}, visto hace {} s)",
+                ip,
+                previous,
+                source.as_str(),
+                now.saturating_sub(last_seen) / 1000
+            ),
+            format!("Nuevo vinculo por {}", message.describe()),
+            format!("Cambios de MAC de {}: {}", ip, changes),
+        ];
+        if message.eth_source != message.sender_mac {
+            evidence.push(format!(
+                "La cabecera Ethernet viene de {}, no de la MAC anunciada {}",
+                message.eth_source, message.sender_mac
+            ));
+        }
+        let kind = if gratuitous { "gratuitous_arp_change" } else { "mac_change" };
+        let alert = new_alert(kind, severity, Some(ip), message.sender_mac, message_text, evidence, now);
+        self.raise(alert, raised);
+    }
+
+    fn check_claims(&mut self, message: &ArpMessage, now: u64, raised: &mut Vec<ArpAlert>) {
+        let mac = message.sender_mac;
+        if !self.claims.contains_key(&mac) && self.claims.len() >= MAX_BINDINGS {
+            return;
+        }
+        let ips = self.claims.entry(mac).or_default();
+        ips.insert(message.sender_ip, now);
+        if ips.len() < 2 || self.gateway_mac == Some(mac) {
+            return;
+        }
+        let claimed = self.claimed_by(mac);
+        let evidence = vec![
+            format!("IPs reclamadas por {} en los ultimos {} min: {}", mac, CLAIM_TTL_MS / 60_000, claimed),
+            format!("Ultima: {}", message.describe()),
+        ];
+        let alert = match self.gateway.filter(|gw| self.claims[&mac].contains_key(gw)) {
+            Some(gateway) => {
+                let mut evidence = evidence;
+                evidence.push(match self.gateway_mac {
+                    Some(real) => format!("MAC del gateway segun el escaneo: {}", real),
+                    None => "Sin escaneo previo: la MAC real del gateway es desconocida".to_string(),
+                });
+                new_alert(
+                    "gateway_impersonation",
+                    "CRITICAL",
+                    Some(gateway),
+                    mac,
+                    format!("{} se hace pasar por el gateway {} y por otros equipos ({})", mac, gateway, claimed),
+                    evidence,
+                    now,
+                )
+            }
+            None => new_alert(
+                "mac_multiple_ips",
+                "MEDIUM",
+                None,
+                mac,
+                format!("{} reclama varias IPs a la vez ({})", mac, claimed),
+                evidence,
+                now,
+            ),
+        };
+        self.raise(alert, raised);
+    }
+
+    fn claimed_by(&self, mac: MacAddr) -> String {
+        let mut ips: Vec<Ipv4Addr> = self.claims.get(&mac).map(|ips| ips.keys().copied().collect()).unwrap_or_default();
+        ips.sort();
+        ips.iter().map(Ipv4Addr::to_string).collect::<Vec<_>>().join(", ")
+    }
+
+    fn insert_binding(&mut self, ip: Ipv4Addr, mac: MacAddr, source: Source, now: u64) {
+        if self.bindings.len() >= MAX_BINDINGS {
+            // Se descarta el vinculo visto hace mas tiempo.
+            if let Some(oldest) = self.bindings.iter().min_by_key(|(_, b)| b.last_seen).map(|(ip, _)| *ip) {
+                self.bindings.remove(&oldest);
+            }
+        }
+        self.bindings.insert(ip, Binding { mac, source, first_seen: now, last_seen: now, mac_changes: 0 });
+    }
+
+    fn raise(&mut self, mut alert: ArpAlert, raised: &mut Vec<ArpAlert>) {
+        let key = (alert.kind.clone(), alert.ip.clone(), alert.mac.clone());
+        if self.last_raised.get(&key).is_some_and(|t| alert.timestamp.saturating_sub(*t) < ALERT_COOLDOWN_MS) {
+            return;
+        }
+        self.last_raised.insert(key, alert.timestamp);
+        self.next_id += 1;
+        alert.id = self.next_id;
+        println!("🚨 [ARP] {} {}", alert.severity, alert.message);
+        if self.alerts.len() >= MAX_ALERTS {
+            self.alerts.pop_front();
+        }
+        self.alerts.push_back(alert.clone());
+        raised.push(alert);
+    }
+
+    fn expire(&mut self, now: u64) {
+        self.pending.retain(|_, t| now.saturating_sub(*t) <= REQUEST_TTL_MS);
+        self.unsolicited.retain(|_, times| times.back().is_some_and(|t| now.saturating_sub(*t) <= FLOOD_WINDOW_MS));
+        for ips in self.claims.values_mut() {
+            ips.retain(|_, t| now.saturating_sub(*t) <= CLAIM_TTL_MS);
+        }
+        self.claims.retain(|_, ips| !ips.is_empty());
+        self.last_raised.retain(|_, t| now.saturating_sub(*t) < ALERT_COOLDOWN_MS);
+    }
+}
+
+fn new_alert(
+    kind: &str,
+    severity: &str,
+    ip: Option<Ipv4Addr>,
+    mac: MacAddr,
+    message: String,
+    evidence: Vec<String>,
+    now: u64,
+) -> ArpAlert {
+    ArpAlert {
+        id: 0,
+        timestamp: now,
+        kind: kind.to_string(),
+        severity: severity.to_string(),
+        ip: ip.map(|ip| ip.to_string()),
+        mac: mac.to_string(),
+        message,
+        evidence,
+    }
+}
+
+// El escaneo entrega "AA-BB-CC-DD-EE-FF" o "aa:bb:..."; sin MAC real no hay vinculo.
+fn parse_mac(mac: &str) -> Option<MacAddr> {
+    mac.trim().replace('-', ":").parse::<MacAddr>().ok().filter(|m| *m != MacAddr::zero() && !m.is_broadcast())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::network::traffic_sniffer::arp::{self, tests::arp_frame};
+
+    const ROUTER: ([u8; 6], [u8; 4]) = ([0x02, 0, 0, 0, 0, 0x01], [192, 168, 1, 1]);
+    const LAPTOP: ([u8; 6], [u8; 4]) = ([0x02, 0, 0, 0, 0, 0x20], [192, 168, 1, 20]);
+    const ATTACKER: ([u8; 6], [u8; 4]) = ([0x02, 0, 0, 0, 0, 0x66], [192, 168, 1, 66]);
+    const ASK: [u8; 6] = [0; 6];
+
+    fn feed(guard: &mut ArpGuard, frame: &[u8], now: u64) -> Vec<ArpAlert> {
+        guard.observe(&arp::parse_frame(frame).unwrap(), now)
+    }
+
+    fn seeded() -> ArpGuard {
+        let mut guard = ArpGuard::new();
+        guard.seed("192.168.1.1", "02-00-00-00-00-01", true, 0);
+        guard.seed("192.168.1.20", "02:00:00:00:00:20", false, 0);
+        guard.seed("192.168.1.66", "02:00:00:00:00:66", false, 0);
+        guard
+    }
+
+    #[test]
+    fn normal_resolution_raises_nothing() {
+        let mut guard = seeded();
+        let mut alerts = feed(&mut guard, &arp_frame(false, LAPTOP, (ASK, ROUTER.1)), 1_000);
+        alerts.extend(feed(&mut guard, &arp_frame(true, ROUTER, LAPTOP), 1_010));
+        // Anuncio gratuito con la misma MAC: refresca el vinculo.
+        alerts.extend(feed(&mut guard, &arp_frame(false, LAPTOP, (ASK, LAPTOP.1)), 2_000));
+        assert!(alerts.is_empty());
+
+        let report = guard.report();
+        assert_eq!(report.gateway_ip.as_deref(), Some("192.168.1.1"));
+        let ips: Vec<&str> = report.bindings.iter().map(|b| b.ip.as_str()).collect();
+        assert_eq!(ips, vec!["192.168.1.1", "192.168.1.20", "192.168.1.66"]);
+        assert!(report.bindings[0].is_gateway && report.bindings[0].source == "scan");
+        assert_eq!(report.bindings[1].last_seen, 2_000);
+    }
+
+    #[test]
+    fn detects_gateway_poisoning_like_the_jammer() {
+        let mut guard = seeded();
+        // Lo que envia JammerService: "el gateway esta en mi MAC", repetido cada 250 ms y sin pregunta.
+        let poison = arp_frame(true, (ATTACKER.0, ROUTER.1), LAPTOP);
+        let mut alerts = Vec::new();
+        for i in 0..20 {
+            alerts.extend(feed(&mut guard, &poison, 10_000 + i * 250));
+        }
+        let kinds: Vec<(&str, &str)> = alerts.iter().map(|a| (a.kind.as_str(), a.severity.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("mac_change", "CRITICAL"),
+                ("gateway_impersonation", "CRITICAL"),
+                ("unsolicited_reply_flood", "CRITICAL"),
+            ]
+        );
+        assert_eq!(alerts[0].ip.as_deref(), Some("192.168.1.1"));
+        assert_eq!(alerts[0].mac, "02:00:00:00:00:66");
+        assert!(alerts[0].evidence[0].starts_with("Vinculo previo: 192.168.1.1 -> 02:00:00:00:00:01 (origen scan"));
+        assert!(alerts[1].evidence[0].ends_with("192.168.1.1, 192.168.1.66"));
+        assert!(alerts[1].evidence[2].contains("02:00:00:00:00:01"));
+        assert!(alerts[2].evidence[0].starts_with("15 respuestas ARP sin pregunta previa"));
+
+        // Enfriamiento: pasado un minuto la suplantacion vuelve a avisar; el vinculo ya es el del atacante.
+        assert!(feed(&mut guard, &poison, 60_000).is_empty());
+        let again = feed(&mut guard, &poison, 75_000);
+        assert_eq!((again.len(), again[0].kind.as_str(), again[0].id), (1, "gateway_impersonation", 4));
+        assert_eq!(guard.report().alerts[0].id, 4);
+        assert_eq!(guard.report().bindings[0].mac, "02:00:00:00:00:66");
+    }
+
+    #[test]
+    fn gratuitous_changes_and_multiple_ips_per_mac() {
+        let mut guard = seeded();
+        // Una respuesta solicitada no cuenta para la avalancha, pero el cambio de MAC si se avisa.
+        feed(&mut guard, &arp_frame(false, ROUTER, (ASK, LAPTOP.1)), 1_000);
+        let alerts = feed(&mut guard, &arp_frame(true, (ATTACKER.0, LAPTOP.1), ROUTER), 1_100);
+        assert_eq!((alerts[0].kind.as_str(), alerts[0].severity.as_str()), ("mac_change", "HIGH"));
+        assert_eq!((alerts[1].kind.as_str(), alerts[1].severity.as_str()), ("mac_multiple_ips", "MEDIUM"));
+        assert!(alerts[1].ip.is_none());
+
+        // Anuncio gratuito que cambia un vinculo aprendido por ARP.
+        let phone = ([0x02, 0, 0, 0, 0, 0x30], [192, 168, 1, 30]);
+        feed(&mut guard, &arp_frame(false, phone, (ASK, ROUTER.1)), 2_000);
+        let mut spoofed = arp_frame(false, ([0x02, 0, 0, 0, 0, 0x31], phone.1), (ASK, phone.1));
+        spoofed[6..12].copy_from_slice(&ATTACKER.0);
+        let alerts = feed(&mut guard, &spoofed, 3_000);
+        assert_eq!((alerts[0].kind.as_str(), alerts[0].severity.as_str()), ("gratuitous_arp_change", "HIGH"));
+        assert!(alerts[0].evidence.iter().any(|e| e.contains("cabecera Ethernet viene de 02:00:00:00:00:66")));
+
+        // El router con varias IPs (MAC del escaneo) no es una suplantacion.
+        let alias = arp_frame(false, (ROUTER.0, [192, 168, 1, 254]), (ASK, LAPTOP.1));
+        assert!(feed(&mut guard, &alias, 4_000).is_empty());
+    }
+}
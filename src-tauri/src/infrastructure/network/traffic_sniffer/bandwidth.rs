@@ -0,0 +1,382 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/bandwidth.rs
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::domain::entities::{BandwidthPoint, BandwidthStats, BandwidthWindow, HostBandwidth};
+
+use super::icmpv6::{self, Icmpv6Message};
+use super::ip::{self, IpFrame};
+
+// Tres resoluciones: ultimo minuto por segundo, ultima hora por minuto, ultimo dia por hora.
+const SECOND_MS: u64 = 1_000;
+const MINUTE_MS: u64 = 60_000;
+const HOUR_MS: u64 = 3_600_000;
+const SECONDS_KEPT: u64 = 60;
+const MINUTES_KEPT: u64 = 60;
+const HOURS_KEPT: u64 = 24;
+// Un escaneo o trafico a miles de destinos no debe llenar la memoria.
+const MAX_DEVICES: usize = 512;
+const MAX_ENDPOINTS: usize = 4_096;
+// Una LAN domestica tiene uno o dos /64; el tope evita que NDP falsificado lo llene todo.
+const MAX_LOCAL_PREFIXES: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Bucket {
+    slot: u64,
+    bytes_in: u64,
+    bytes_out: u64,
+}
+
+// Cubetas consecutivas de `width_ms`; solo se guardan las que tienen trafico.
+struct Ring {
+    width_ms: u64,
+    kept: u64,
+    buckets: VecDeque<Bucket>,
+}
+
+impl Ring {
+    fn new(width_ms: u64, kept: u64) -> Self {
+        Self { width_ms, kept, buckets: VecDeque::new() }
+    }
+
+    fn add(&mut self, timestamp: u64, bytes_in: u64, bytes_out: u64) {
+        let slot = timestamp / self.width_ms;
+        let newest = self.buckets.back().map_or(slot, |b| b.slot.max(slot));
+        // Paquetes algo desordenados (hilos, reloj): van a su cubeta si aun esta en la ventana.
+        if slot + self.kept <= newest {
+            return;
+        }
+        let bucket = Bucket { slot, bytes_in, bytes_out };
+        match self.buckets.iter().rposition(|b| b.slot <= slot) {
+            Some(i) if self.buckets[i].slot == slot => {
+                self.buckets[i].bytes_in += bytes_in;
+                self.buckets[i].bytes_out += bytes_out;
+            }
+            Some(i) => self.buckets.insert(i + 1, bucket),
+            None => self.buckets.push_front(bucket),
+        }
+        while self.buckets.front().is_some_and(|b| b.slot + self.kept <= newest) {
+            self.buckets.pop_front();
+        }
+    }
+
+    // Suma de las cubetas en [from_slot, to_slot].
+    fn sum(&self, from_slot: u64, to_slot: u64) -> (u64, u64) {
+        self.buckets
+            .iter()
+            .filter(|b| b.slot >= from_slot && b.slot <= to_slot)
+            .fold((0, 0), |(i, o), b| (i + b.bytes_in, o + b.bytes_out))
+    }
+
+    // Cubetas aun dentro de la ventana que acaba en `now` (la actual, incompleta, incluida).
+    fn points(&self, now: u64) -> Vec<BandwidthPoint> {
+        let now_slot = now / self.width_ms;
+        self.buckets
+            .iter()
+            .filter(|b| b.slot + self.kept > now_slot)
+            .map(|b| BandwidthPoint { timestamp: b.slot * self.width_ms, bytes_in: b.bytes_in, bytes_out: b.bytes_out })
+            .collect()
+    }
+}
+
+struct HostTraffic {
+    total_in: u64,
+    total_out: u64,
+    packets: u64,
+    last_seen: u64,
+    seconds: Ring,
+    minutes: Ring,
+    hours: Ring,
+}
+
+impl HostTraffic {
+    fn new() -> Self {
+        Self {
+            total_in: 0,
+            total_out: 0,
+            packets: 0,
+            last_seen: 0,
+            seconds: Ring::new(SECOND_MS, SECONDS_KEPT),
+            minutes: Ring::new(MINUTE_MS, MINUTES_KEPT),
+            hours: Ring::new(HOUR_MS, HOURS_KEPT),
+        }
+    }
+
+    fn add(&mut self, timestamp: u64, bytes_in: u64, bytes_out: u64) {
+        self.total_in += bytes_in;
+        self.total_out += bytes_out;
+        self.packets += 1;
+        self.last_seen = self.last_seen.max(timestamp);
+        self.seconds.add(timestamp, bytes_in, bytes_out);
+        self.minutes.add(timestamp, bytes_in, bytes_out);
+        self.hours.add(timestamp, bytes_in, bytes_out);
+    }
+
+    // Bytes de la ventana: ultimo segundo completo, ultimos 60 s o ultimos 60 min (completos).
+    fn window(&self, window: BandwidthWindow, now: u64) -> (u64, u64) {
+        match window {
+            BandwidthWindow::Second => {
+                let slot = (now / SECOND_MS).saturating_sub(1);
+                self.seconds.sum(slot, slot)
+            }
+            BandwidthWindow::Minute => {
+                let last = (now / SECOND_MS).saturating_sub(1);
+                self.seconds.sum(last.saturating_sub(SECONDS_KEPT - 1), last)
+            }
+            BandwidthWindow::Hour => {
+                let last = (now / MINUTE_MS).saturating_sub(1);
+                self.minutes.sum(last.saturating_sub(MINUTES_KEPT - 1), last)
+            }
+        }
+    }
+}
+
+// Bytes por equipo local (RFC 1918 / link-local / ULA / prefijo IPv6 de la LAN) y por extremo
+// remoto. Entrada/salida desde el punto de vista de cada IP: lo que recibe y lo que envia.
+#[derive(Default)]
+pub struct BandwidthAccountant {
+    devices: HashMap<IpAddr, HostTraffic>,
+    endpoints: HashMap<IpAddr, HostTraffic>,
+    // Todo el trafico observado (cada paquete una vez), en `bytes_out`.
+    network: Option<HostTraffic>,
+    // /64 globales de la LAN (64 bits altos), aprendidos de NDP: solo circula dentro del enlace.
+    // Lo anterior a aprenderlos queda contado como extremo remoto.
+    local_prefixes: Vec<u64>,
+}
+
+impl BandwidthAccountant {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe_ip(&mut self, frame: &IpFrame, timestamp: u64) {
+        if frame.protocol == ip::PROTO_ICMPV6 {
+            self.learn_prefixes(frame);
+        }
+        self.observe(frame.src, frame.dst, frame.length as u64, timestamp);
+    }
+
+    fn learn_prefixes(&mut self, frame: &IpFrame) {
+        let Some(message) = icmpv6::parse(frame.payload).filter(Icmpv6Message::is_ndp) else {
+            return;
+        };
+        let mut on_link = vec![frame.src];
+        if let Icmpv6Message::NeighborAdvertisement { target, .. } = message {
+            on_link.push(IpAddr::V6(target));
+        }
+        for ip in on_link {
+            let IpAddr::V6(v6) = ip else {
+                continue;
+            };
+            if !is_global_unicast(v6) || self.local_prefixes.len() >= MAX_LOCAL_PREFIXES {
+                continue;
+            }
+            let prefix = prefix64(v6);
+            if !self.local_prefixes.contains(&prefix) {
+                self.local_prefixes.push(prefix);
+            }
+        }
+    }
+
+    pub fn observe(&mut self, src: IpAddr, dst: IpAddr, bytes: u64, timestamp: u64) {
+        self.network.get_or_insert_with(HostTraffic::new).add(timestamp, 0, bytes);
+        // Broadcast/multicast solo cuentan como salida del emisor.
+        for (ip, bytes_in, bytes_out) in [(src, 0, bytes), (dst, bytes, 0)] {
+            if is_group(ip) || ip.is_unspecified() {
+                continue;
+            }
+            let (table, max) = if self.is_local(ip) {
+                (&mut self.devices, MAX_DEVICES)
+            } else {
+                (&mut self.endpoints, MAX_ENDPOINTS)
+            };
+            if !table.contains_key(&ip) && table.len() >= max {
+                // Se descarta la IP inactiva desde hace mas tiempo.
+                if let Some(oldest) = table.iter().min_by_key(|(_, t)| t.last_seen).map(|(ip, _)| *ip) {
+                    table.remove(&oldest);
+                }
+            }
+            table.entry(ip).or_insert_with(HostTraffic::new).add(timestamp, bytes_in, bytes_out);
+        }
+    }
+
+    fn is_local(&self, ip: IpAddr) -> bool {
+        match ip {
+            IpAddr::V4(v4) => v4.is_private() || v4.is_link_local(),
+            // fc00::/7 (ULA), fe80::/10 (link-local) o un /64 visto en NDP.
+            IpAddr::V6(v6) => {
+                (v6.segments()[0] & 0xfe00) == 0xfc00
+                    || (v6.segments()[0] & 0xffc0) == 0xfe80
+                    || self.local_prefixes.contains(&prefix64(v6))
+            }
+        }
+    }
+
+    // Top-N por bytes (entrada + salida) en la ventana pedida. Sin `with_series` no se envian las
+    // cubetas (resumen periodico para la UI); con ellas, las tres resoluciones de cada IP.
+    pub fn stats(&self, now: u64, window: BandwidthWindow, top_n: usize, with_series: bool) -> BandwidthStats {
+        let (_, total_bytes) = self.network.as_ref().map(|n| n.window(window, now)).unwrap_or_default();
+        let seconds = match window {
+            BandwidthWindow::Second => 1,
+            BandwidthWindow::Minute => SECONDS_KEPT,
+            BandwidthWindow::Hour => MINUTES_KEPT * 60,
+        };
+        BandwidthStats {
+            timestamp: now,
+            window,
+            total_bytes,
+            bytes_per_second: total_bytes / seconds,
+            devices: top(&self.devices, now, window, top_n, with_series),
+            endpoints: top(&self.endpoints, now, window, top_n, with_series),
+        }
+    }
+}
+
+fn top(
+    hosts: &HashMap<IpAddr, HostTraffic>,
+    now: u64,
+    window: BandwidthWindow,
+    top_n: usize,
+    with_series: bool,
+) -> Vec<HostBandwidth> {
+    let mut ranked: Vec<(&IpAddr, &HostTraffic, (u64, u64))> = hosts
+        .iter()
+        .map(|(ip, t)| (ip, t, t.window(window, now)))
+        .filter(|(_, _, (i, o))| i + o > 0)
+        .collect();
+    ranked.sort_by_key(|(ip, _, (i, o))| (Reverse(i + o), **ip));
+    ranked
+        .into_iter()
+        .take(top_n)
+        .map(|(ip, t, (bytes_in, bytes_out))| HostBandwidth {
+            ip: ip.to_string(),
+            bytes_in,
+            bytes_out,
+            total_bytes_in: t.total_in,
+            total_bytes_out: t.total_out,
+            packets: t.packets,
+            last_seen: t.last_seen,
+            per_second: if with_series { t.seconds.points(now) } else { Vec::new() },
+            per_minute: if with_series { t.minutes.points(now) } else { Vec::new() },
+            per_hour: if with_series { t.hours.points(now) } else { Vec::new() },
+        })
+        .collect()
+}
+
+// 2000::/3: lo unico que puede confundirse con un extremo remoto.
+fn is_global_unicast(ip: Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xe000) == 0x2000
+}
+
+fn prefix64(ip: Ipv6Addr) -> u64 {
+    (u128::from(ip) >> 64) as u64
+}
+
+fn is_group(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_multicast() || v4.is_broadcast() || is_subnet_broadcast(v4),
+        IpAddr::V6(v6) => v6.is_multicast(),
+    }
+}
+
+// Sin mascara a mano: .255 en una LAN domestica es casi siempre el broadcast dirigido.
+fn is_subnet_broadcast(ip: Ipv4Addr) -> bool {
+    ip.is_private() && ip.octets()[3] == 255
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(acc: &mut BandwidthAccountant, frame: &[u8], timestamp: u64) {
+        acc.observe_ip(&ip::parse_frame(frame).unwrap(), timestamp);
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn accounts_devices_and_endpoints_in_rolling_windows() {
+        let mut acc = BandwidthAccountant::new();
+        let laptop = ip("192.168.1.20");
+        let tv = ip("192.168.1.30");
+        let cdn = ip("151.101.1.1");
+        let t0 = 10 * HOUR_MS;
+
+        // Minuto anterior: la TV descarga mucho.
+        for s in 0..30 {
+            acc.observe(cdn, tv, 1_500, t0 + s * SECOND_MS);
+        }
+        // Dos minutos despues: el portatil sube algo y descarga poco; un broadcast no cuenta como destino.
+        let now = t0 + 2 * MINUTE_MS + 5 * SECOND_MS;
+        acc.observe(laptop, cdn, 400, now - 2 * SECOND_MS);
+        acc.observe(cdn, laptop, 100, now - 800);
+        acc.observe(laptop, ip("192.168.1.255"), 60, now - 500);
+
+        let second = acc.stats(now, BandwidthWindow::Second, 10, false);
+        assert_eq!(second.total_bytes, 160);
+        assert_eq!(second.devices.len(), 1);
+        assert_eq!((second.devices[0].bytes_in, second.devices[0].bytes_out), (100, 60));
+        assert!(second.devices[0].per_second.is_empty());
+
+        let minute = acc.stats(now, BandwidthWindow::Minute, 10, false);
+        assert_eq!(minute.devices[0].ip, "192.168.1.20");
+        assert_eq!((minute.devices[0].bytes_in, minute.devices[0].bytes_out), (100, 460));
+        // Las descargas de la TV salieron de la ventana de 60 s pero siguen en la de una hora.
+        assert!(minute.devices.iter().all(|d| d.ip != "192.168.1.30"));
+
+        let hour = acc.stats(now, BandwidthWindow::Hour, 1, true);
+        assert_eq!(hour.devices.len(), 1);
+        let top = &hour.devices[0];
+        assert_eq!((top.ip.as_str(), top.bytes_in, top.total_bytes_in), ("192.168.1.30", 45_000, 45_000));
+        assert_eq!(top.per_second, Vec::new());
+        assert_eq!(top.per_minute, vec![BandwidthPoint { timestamp: t0, bytes_in: 45_000, bytes_out: 0 }]);
+        assert_eq!(top.per_hour.len(), 1);
+        assert_eq!(hour.endpoints[0].ip, "151.101.1.1");
+        assert_eq!((hour.endpoints[0].bytes_out, hour.endpoints[0].bytes_in), (45_000, 0));
+        assert_eq!(hour.bytes_per_second, 45_000 / 3_600);
+    }
+
+    #[test]
+    fn ipv6_locals_are_link_local_ula_or_prefixes_seen_in_ndp() {
+        let mut acc = BandwidthAccountant::new();
+        let now = 10 * HOUR_MS;
+        let udp = [0xD4, 0x31, 0x01, 0xBB, 0, 8, 0, 0];
+        let upload = ip::tests::ipv6_frame("2001:db8:1::20", "2a00:1450::1", ip::PROTO_UDP, &udp);
+
+        feed(&mut acc, &upload, now);
+        let before = acc.stats(now + SECOND_MS, BandwidthWindow::Minute, 10, false);
+        assert_eq!(before.endpoints.len(), 2);
+
+        // Un NA del propio equipo revela que 2001:db8:1::/64 esta en el enlace.
+        let na = icmpv6::tests::neighbor_advertisement("2001:db8:1::20", [0x02, 0, 0, 0, 0, 0x20], false);
+        feed(&mut acc, &ip::tests::ipv6_frame("2001:db8:1::20", "fe80::1", ip::PROTO_ICMPV6, &na), now);
+        feed(&mut acc, &upload, now);
+        acc.observe(ip("fd00::5"), ip("ff02::1"), 100, now);
+
+        let after = acc.stats(now + SECOND_MS, BandwidthWindow::Minute, 10, false);
+        let devices: Vec<&str> = after.devices.iter().map(|d| d.ip.as_str()).collect();
+        assert_eq!(devices, vec!["2001:db8:1::20", "fd00::5", "fe80::1"]);
+        assert_eq!(after.devices[0].bytes_out, 48 + 72);
+        assert!(after.endpoints.iter().any(|e| e.ip == "2a00:1450::1"));
+    }
+
+    #[test]
+    fn ring_keeps_only_its_window_and_tolerates_reordering() {
+        let mut ring = Ring::new(SECOND_MS, 3);
+        ring.add(1_000, 1, 0);
+        ring.add(2_000, 1, 0);
+        ring.add(1_500, 1, 0);
+        ring.add(4_000, 1, 0);
+        ring.add(3_100, 1, 0);
+        let slots = |ring: &Ring| ring.buckets.iter().map(|b| (b.slot, b.bytes_in)).collect::<Vec<_>>();
+        assert_eq!(slots(&ring), vec![(2, 1), (3, 1), (4, 1)]);
+        // Mas viejo que la ventana: se ignora.
+        ring.add(500, 1, 0);
+        assert_eq!(ring.sum(0, 10), (3, 0));
+        assert_eq!(ring.points(6_000).len(), 1);
+    }
+}
@@ -0,0 +1,175 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/capture_file.rs
+
+use directories::ProjectDirs;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::domain::entities::CaptureFileStatus;
+
+use super::pcapng::{PcapngWriter, DEFAULT_SNAPLEN};
+use super::protocol;
+
+// Tope de ficheros por grabacion: con limites pequenos y nadie que la pare, no llena el disco.
+const MAX_FILES_PER_RECORDING: usize = 100;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CaptureLimits {
+    // Al superarse cualquiera de los dos se rota a un fichero nuevo.
+    pub max_bytes: Option<u64>,
+    pub max_seconds: Option<u64>,
+}
+
+// Grabacion PCAPNG de las tramas crudas que ve el sniffer (todas, no solo las que llegan a la UI).
+pub struct PcapRecorder {
+    dir: PathBuf,
+    interface: String,
+    limits: CaptureLimits,
+    writer: Option<PcapngWriter<BufWriter<File>>>,
+    opened_at: Instant,
+    files: Vec<PathBuf>,
+    packets_written: u64,
+    // Bytes de los ficheros ya cerrados; el actual se suma aparte.
+    bytes_closed: u64,
+}
+
+impl PcapRecorder {
+    pub fn start(dir: PathBuf, interface: &str, limits: CaptureLimits) -> Result<Self, String> {
+        fs::create_dir_all(&dir).map_err(|e| format!("No se pudo crear {}: {}", dir.display(), e))?;
+        let mut recorder = Self {
+            dir,
+            interface: interface.to_string(),
+            limits,
+            writer: None,
+            opened_at: Instant::now(),
+            files: Vec::new(),
+            packets_written: 0,
+            bytes_closed: 0,
+        };
+        recorder.open_next()?;
+        Ok(recorder)
+    }
+
+    // AppData `captures/`.
+    pub fn default_dir() -> PathBuf {
+        if let Some(proj_dirs) = ProjectDirs::from("com", "netsentinel", "app") {
+            return proj_dirs.data_dir().join("captures");
+        }
+        PathBuf::from("netsentinel_captures")
+    }
+
+    pub fn write_frame(&mut self, timestamp_us: u64, frame: &[u8]) -> Result<(), String> {
+        if self.limit_reached() {
+            self.rotate()?;
+        }
+        let writer = self.writer.as_mut().ok_or_else(|| "Grabacion cerrada".to_string())?;
+        writer.write_packet(timestamp_us, frame).map_err(|e| e.to_string())?;
+        self.packets_written += 1;
+        Ok(())
+    }
+
+    pub fn rotate(&mut self) -> Result<(), String> {
+        if self.files.len() >= MAX_FILES_PER_RECORDING {
+            return Err(format!("Limite de {} ficheros por grabacion alcanzado", MAX_FILES_PER_RECORDING));
+        }
+        self.close_current()?;
+        self.open_next()
+    }
+
+    pub fn finish(mut self) -> Result<CaptureFileStatus, String> {
+        self.close_current()?;
+        Ok(self.status())
+    }
+
+    pub fn status(&self) -> CaptureFileStatus {
+        let current_bytes = self.writer.as_ref().map(|w| w.bytes_written()).unwrap_or(0);
+        CaptureFileStatus {
+            active: self.writer.is_some(),
+            current_file: self
+                .writer
+                .as_ref()
+                .and(self.files.last())
+                .map(|p| p.display().to_string()),
+            files: self.files.iter().map(|p| p.display().to_string()).collect(),
+            packets_written: self.packets_written,
+            bytes_written: self.bytes_closed + current_bytes,
+            max_bytes: self.limits.max_bytes,
+            max_seconds: self.limits.max_seconds,
+        }
+    }
+
+    fn limit_reached(&self) -> bool {
+        let Some(writer) = &self.writer else {
+            return false;
+        };
+        let by_size = self.limits.max_bytes.is_some_and(|max| writer.bytes_written() >= max);
+        let by_time = self
+            .limits
+            .max_seconds
+            .is_some_and(|max| self.opened_at.elapsed() >= Duration::from_secs(max));
+        by_size || by_time
+    }
+
+    fn close_current(&mut self) -> Result<(), String> {
+        if let Some(mut writer) = self.writer.take() {
+            self.bytes_closed += writer.bytes_written();
+            writer.flush().map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn open_next(&mut self) -> Result<(), String> {
+        let path = self
+            .dir
+            .join(format!("capture_{}_{:03}.pcapng", protocol::unix_ms(), self.files.len() + 1));
+        let file = File::create(&path).map_err(|e| format!("No se pudo crear {}: {}", path.display(), e))?;
+        let writer = PcapngWriter::new(BufWriter::new(file), &self.interface, DEFAULT_SNAPLEN).map_err(|e| e.to_string())?;
+        println!("💾 [SNIFFER] Grabando PCAPNG en {}", path.display());
+        self.writer = Some(writer);
+        self.opened_at = Instant::now();
+        self.files.push(path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_by_size_and_reports_every_file() {
+        let dir = std::env::temp_dir().join(format!("netsentinel_capture_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let limits = CaptureLimits {
+            max_bytes: Some(300),
+            max_seconds: None,
+        };
+        let mut recorder = PcapRecorder::start(dir.clone(), "eth0", limits).unwrap();
+
+        // Cada trama de 100 bytes ocupa 132 en el fichero (+ 88 de cabeceras): 2 por fichero.
+        for i in 0..5 {
+            recorder.write_frame(1_700_000_000_000_000 + i, &[0u8; 100]).unwrap();
+        }
+        let status = recorder.status();
+        assert!(status.active);
+        assert_eq!(status.files.len(), 3);
+        assert_eq!(status.current_file.as_deref(), status.files.last().map(String::as_str));
+
+        recorder.rotate().unwrap();
+        let finished = recorder.finish().unwrap();
+        assert!(!finished.active);
+        assert_eq!(finished.current_file, None);
+        assert_eq!(finished.files.len(), 4);
+        assert_eq!(finished.packets_written, 5);
+
+        let on_disk: u64 = finished.files.iter().map(|f| fs::metadata(f).unwrap().len()).sum();
+        assert_eq!(on_disk, finished.bytes_written);
+        // Cada fichero empieza con su propio SHB (abrible por separado en Wireshark).
+        for file in &finished.files {
+            assert_eq!(&fs::read(file).unwrap()[..4], &[0x0A, 0x0D, 0x0D, 0x0A]);
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,294 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/dns.rs
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+// Decodificador DNS (RFC 1035) de solo lectura: cabecera, preguntas y respuestas.
+// Acotado a proposito: los paquetes vienen de la red y pueden ser hostiles.
+const MAX_QUESTIONS: u16 = 16;
+const MAX_ANSWERS: u16 = 32;
+const MAX_NAME_LEN: usize = 255;
+// Saltos de compresion por nombre: corta bucles de punteros.
+const MAX_POINTER_JUMPS: usize = 32;
+const MAX_TXT_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnsQuestion {
+    pub name: String,
+    pub qtype: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnsAnswer {
+    pub name: String,
+    pub rtype: u16,
+    pub ttl: u32,
+    // Legible: IP para A/AAAA, nombre para CNAME/PTR/NS/MX, texto para TXT.
+    pub data: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnsMessage {
+    pub id: u16,
+    pub is_response: bool,
+    pub rcode: u8,
+    pub questions: Vec<DnsQuestion>,
+    pub answers: Vec<DnsAnswer>,
+}
+
+impl DnsMessage {
+    pub fn rcode_name(&self) -> String {
+        match self.rcode {
+            0 => "NOERROR".to_string(),
+            1 => "FORMERR".to_string(),
+            2 => "SERVFAIL".to_string(),
+            3 => "NXDOMAIN".to_string(),
+            4 => "NOTIMP".to_string(),
+            5 => "REFUSED".to_string(),
+            other => format!("RCODE{}", other),
+        }
+    }
+
+    // Texto para `TrafficPacket.info`.
+    pub fn describe(&self) -> String {
+        let Some(question) = self.questions.first() else {
+            return "DNS".to_string();
+        };
+        let qtype = type_name(question.qtype);
+        if !self.is_response {
+            return format!("DNS {} {}", qtype, question.name);
+        }
+        if self.rcode != 0 {
+            return format!("DNS {} {} {}", self.rcode_name(), qtype, question.name);
+        }
+        let answers: Vec<&str> = self.answers.iter().take(3).map(|a| a.data.as_str()).collect();
+        if answers.is_empty() {
+            format!("DNS respuesta {} {} (sin datos)", qtype, question.name)
+        } else {
+            format!("DNS respuesta {} {} -> {}", qtype, question.name, answers.join(", "))
+        }
+    }
+}
+
+pub fn type_name(rtype: u16) -> String {
+    match rtype {
+        1 => "A".to_string(),
+        2 => "NS".to_string(),
+        5 => "CNAME".to_string(),
+        6 => "SOA".to_string(),
+        12 => "PTR".to_string(),
+        15 => "MX".to_string(),
+        16 => "TXT".to_string(),
+        28 => "AAAA".to_string(),
+        33 => "SRV".to_string(),
+        64 => "SVCB".to_string(),
+        65 => "HTTPS".to_string(),
+        255 => "ANY".to_string(),
+        other => format!("TYPE{}", other),
+    }
+}
+
+// Carga util de UDP/53. Sobre TCP el mensaje va precedido de 2 bytes de longitud.
+pub fn parse_tcp(payload: &[u8]) -> Option<DnsMessage> {
+    let len = u16::from_be_bytes([*payload.first()?, *payload.get(1)?]) as usize;
+    parse(payload.get(2..2 + len)?)
+}
+
+pub fn parse(msg: &[u8]) -> Option<DnsMessage> {
+    if msg.len() < 12 {
+        return None;
+    }
+    let id = read_u16(msg, 0)?;
+    let flags = read_u16(msg, 2)?;
+    // Solo consultas estandar (opcode 0): el resto no describe dominios consultados.
+    if (flags >> 11) & 0x0F != 0 {
+        return None;
+    }
+    let qdcount = read_u16(msg, 4)?;
+    let ancount = read_u16(msg, 6)?;
+    if qdcount == 0 || qdcount > MAX_QUESTIONS {
+        return None;
+    }
+
+    let mut pos = 12;
+    let mut questions = Vec::with_capacity(qdcount as usize);
+    for _ in 0..qdcount {
+        let (name, next) = read_name(msg, pos)?;
+        let qtype = read_u16(msg, next)?;
+        read_u16(msg, next + 2)?;
+        questions.push(DnsQuestion { name, qtype });
+        pos = next + 4;
+    }
+
+    let mut answers = Vec::new();
+    for _ in 0..ancount.min(MAX_ANSWERS) {
+        // Una respuesta cortada no invalida lo ya leido.
+        let Some((answer, next)) = read_answer(msg, pos) else {
+            break;
+        };
+        answers.extend(answer);
+        pos = next;
+    }
+
+    Some(DnsMessage {
+        id,
+        is_response: flags & 0x8000 != 0,
+        rcode: (flags & 0x000F) as u8,
+        questions,
+        answers,
+    })
+}
+
+// `None` dentro del `Some`: registro valido de una clase que no es IN (se salta).
+fn read_answer(msg: &[u8], pos: usize) -> Option<(Option<DnsAnswer>, usize)> {
+    let (name, next) = read_name(msg, pos)?;
+    let rtype = read_u16(msg, next)?;
+    let class = read_u16(msg, next + 2)?;
+    let ttl = u32::from_be_bytes(msg.get(next + 4..next + 8)?.try_into().ok()?);
+    let rdlength = read_u16(msg, next + 8)? as usize;
+    let rdata_start = next + 10;
+    let rdata = msg.get(rdata_start..rdata_start + rdlength)?;
+    let end = rdata_start + rdlength;
+    if class != 1 {
+        return Some((None, end));
+    }
+
+    let data = match rtype {
+        1 if rdata.len() == 4 => Ipv4Addr::new(rdata[0], rdata[1], rdata[2], rdata[3]).to_string(),
+        28 if rdata.len() == 16 => {
+            let octets: [u8; 16] = rdata.try_into().ok()?;
+            Ipv6Addr::from(octets).to_string()
+        }
+        2 | 5 | 12 => read_name(msg, rdata_start)?.0,
+        15 if rdata.len() > 2 => read_name(msg, rdata_start + 2)?.0,
+        16 => {
+            let len = *rdata.first()? as usize;
+            let text = String::from_utf8_lossy(rdata.get(1..1 + len)?);
+            text.chars().take(MAX_TXT_LEN).collect()
+        }
+        _ => format!("{} bytes", rdlength),
+    };
+    Some((Some(DnsAnswer { name, rtype, ttl, data }), end))
+}
+
+// Devuelve el nombre (minusculas, sin punto final) y la posicion tras el nombre en el mensaje.
+fn read_name(msg: &[u8], start: usize) -> Option<(String, usize)> {
+    let mut name = String::new();
+    let mut pos = start;
+    let mut end_after_name = None;
+    let mut jumps = 0;
+
+    loop {
+        let len = *msg.get(pos)? as usize;
+        match len & 0xC0 {
+            0x00 => {
+                if len == 0 {
+                    let end = end_after_name.unwrap_or(pos + 1);
+                    return Some((if name.is_empty() { ".".to_string() } else { name }, end));
+                }
+                let label = msg.get(pos + 1..pos + 1 + len)?;
+                if !name.is_empty() {
+                    name.push('.');
+                }
+                for &b in label {
+                    // Caracteres no imprimibles escapados como en dig (\DDD).
+                    if b.is_ascii_graphic() && b != b'.' && b != b'\\' {
+                        name.push(b.to_ascii_lowercase() as char);
+                    } else {
+                        name.push_str(&format!("\\{:03}", b));
+                    }
+                }
+                if name.len() > MAX_NAME_LEN {
+                    return None;
+                }
+                pos += 1 + len;
+            }
+            0xC0 => {
+                jumps += 1;
+                if jumps > MAX_POINTER_JUMPS {
+                    return None;
+                }
+                let target = (read_u16(msg, pos)? & 0x3FFF) as usize;
+                end_after_name.get_or_insert(pos + 2);
+                pos = target;
+            }
+            // 0x40 / 0x80: tipos de etiqueta obsoletos.
+            _ => return None,
+        }
+    }
+}
+
+fn read_u16(msg: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([*msg.get(offset)?, *msg.get(offset + 1)?]))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn query(id: u16, name: &str, qtype: u16) -> Vec<u8> {
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&id.to_be_bytes());
+        msg.extend_from_slice(&[0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0]);
+        for label in name.split('.') {
+            msg.push(label.len() as u8);
+            msg.extend_from_slice(label.as_bytes());
+        }
+        msg.push(0);
+        msg.extend_from_slice(&qtype.to_be_bytes());
+        msg.extend_from_slice(&[0, 1]);
+        msg
+    }
+
+    // Respuesta a `query` con un CNAME y un A, ambos con nombres comprimidos.
+    pub fn response(id: u16, name: &str, rcode: u8, ip: [u8; 4]) -> Vec<u8> {
+        let mut msg = query(id, name, 1);
+        msg[2] = 0x81;
+        msg[3] = 0x80 | rcode;
+        if rcode != 0 {
+            return msg;
+        }
+        msg[7] = 2;
+        // CNAME: nombre -> "cdn." + nombre original (puntero a la pregunta, offset 12).
+        msg.extend_from_slice(&[0xC0, 12, 0, 5, 0, 1, 0, 0, 0x0E, 0x10, 0, 6, 3, b'c', b'd', b'n', 0xC0, 12]);
+        let cname_offset = (msg.len() - 6) as u8;
+        msg.extend_from_slice(&[0xC0, cname_offset, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4]);
+        msg.extend_from_slice(&ip);
+        msg
+    }
+
+    #[test]
+    fn decodes_queries_and_compressed_responses() {
+        let q = parse(&query(0x1234, "Example.COM", 28)).unwrap();
+        assert!(!q.is_response);
+        assert_eq!(q.questions, vec![DnsQuestion { name: "example.com".to_string(), qtype: 28 }]);
+        assert_eq!(q.describe(), "DNS AAAA example.com");
+
+        let r = parse(&response(0x1234, "example.com", 0, [93, 184, 216, 34])).unwrap();
+        assert!(r.is_response);
+        assert_eq!(r.rcode_name(), "NOERROR");
+        assert_eq!(r.answers.len(), 2);
+        assert_eq!((r.answers[0].rtype, r.answers[0].data.as_str()), (5, "cdn.example.com"));
+        assert_eq!((r.answers[1].name.as_str(), r.answers[1].ttl), ("cdn.example.com", 60));
+        assert_eq!(r.describe(), "DNS respuesta A example.com -> cdn.example.com, 93.184.216.34");
+
+        let nx = parse(&response(7, "nope.invalid", 3, [0; 4])).unwrap();
+        assert_eq!(nx.describe(), "DNS NXDOMAIN A nope.invalid");
+
+        let mut tcp = vec![0, 0];
+        tcp.extend(query(1, "a.b", 1));
+        tcp[1] = (tcp.len() - 2) as u8;
+        assert_eq!(parse_tcp(&tcp).unwrap().questions[0].name, "a.b");
+    }
+
+    #[test]
+    fn rejects_truncated_and_looping_messages() {
+        let q = query(1, "example.com", 1);
+        assert!(parse(&q[..q.len() - 3]).is_none());
+        assert!(parse(&[0xDE, 0xAD, 0xBE, 0xEF]).is_none());
+
+        // Puntero que apunta a si mismo.
+        let mut looping = q[..12].to_vec();
+        looping.extend_from_slice(&[0xC0, 12, 0, 1, 0, 1]);
+        assert!(parse(&looping).is_none());
+    }
+}
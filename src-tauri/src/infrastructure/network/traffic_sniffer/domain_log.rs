@@ -0,0 +1,192 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/domain_log.rs
+
+use std::collections::HashMap;
+
+use pnet::packet::Packet;
+
+use crate::domain::entities::{DeviceDomainLog, DnsDomainStat};
+
+use super::dns::{self, DnsMessage};
+use super::ip::{IpFrame, Transport};
+
+// Cotas: un equipo comprometido que consulta dominios aleatorios no debe llenar la memoria.
+const MAX_DEVICES: usize = 512;
+const MAX_DOMAINS_PER_DEVICE: usize = 2_000;
+const MAX_ANSWERS_PER_DOMAIN: usize = 8;
+const DNS_PORT: u16 = 53;
+
+#[derive(Default)]
+struct DomainEntry {
+    queries: u64,
+    first_seen: u64,
+    last_seen: u64,
+    query_types: Vec<String>,
+    answers: Vec<String>,
+    last_rcode: Option<String>,
+}
+
+// Dominios consultados por cada equipo (la IP que pregunta; en respuestas, el destino).
+#[derive(Default)]
+pub struct DomainLog {
+    devices: HashMap<String, HashMap<String, DomainEntry>>,
+}
+
+impl DomainLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe_ip(&mut self, ip: &IpFrame, timestamp: u64) {
+        if let Some((client, message)) = dns_from_ip(ip) {
+            self.observe(&client, &message, timestamp);
+        }
+    }
+
+    pub fn observe(&mut self, client: &str, message: &DnsMessage, timestamp: u64) {
+        let Some(question) = message.questions.first() else {
+            return;
+        };
+        if !self.devices.contains_key(client) && self.devices.len() >= MAX_DEVICES {
+            return;
+        }
+        let domains = self.devices.entry(client.to_string()).or_default();
+        if !domains.contains_key(&question.name) && domains.len() >= MAX_DOMAINS_PER_DEVICE {
+            // Se descarta el dominio visto hace mas tiempo.
+            if let Some(oldest) = domains.iter().min_by_key(|(_, e)| e.last_seen).map(|(d, _)| d.clone()) {
+                domains.remove(&oldest);
+            }
+        }
+        let entry = domains.entry(question.name.clone()).or_insert_with(|| DomainEntry {
+            first_seen: timestamp,
+            ..Default::default()
+        });
+        entry.last_seen = entry.last_seen.max(timestamp);
+
+        if !message.is_response {
+            entry.queries += 1;
+            let qtype = dns::type_name(question.qtype);
+            if !entry.query_types.contains(&qtype) {
+                entry.query_types.push(qtype);
+            }
+            return;
+        }
+        entry.last_rcode = Some(message.rcode_name());
+        for answer in &message.answers {
+            if !entry.answers.contains(&answer.data) {
+                if entry.answers.len() >= MAX_ANSWERS_PER_DOMAIN {
+                    entry.answers.remove(0);
+                }
+                entry.answers.push(answer.data.clone());
+            }
+        }
+    }
+
+    // `device_ip` limita a un equipo. Dominios mas consultados primero.
+    pub fn snapshot(&self, device_ip: Option<&str>) -> Vec<DeviceDomainLog> {
+        let mut devices: Vec<DeviceDomainLog> = self
+            .devices
+            .iter()
+            .filter(|(ip, _)| device_ip.is_none_or(|wanted| wanted == ip.as_str()))
+            .map(|(ip, domains)| {
+                let mut domains: Vec<DnsDomainStat> = domains
+                    .iter()
+                    .map(|(domain, e)| DnsDomainStat {
+                        domain: domain.clone(),
+                        queries: e.queries,
+                        first_seen: e.first_seen,
+                        last_seen: e.last_seen,
+                        query_types: e.query_types.clone(),
+                        answers: e.answers.clone(),
+                        last_rcode: e.last_rcode.clone(),
+                    })
+                    .collect();
+                domains.sort_by(|a, b| b.queries.cmp(&a.queries).then_with(|| a.domain.cmp(&b.domain)));
+                DeviceDomainLog {
+                    ip: ip.clone(),
+                    total_queries: domains.iter().map(|d| d.queries).sum(),
+                    domains,
+                }
+            })
+            .collect();
+        devices.sort_by(|a, b| b.total_queries.cmp(&a.total_queries).then_with(|| a.ip.cmp(&b.ip)));
+        devices
+    }
+}
+
+// Mensaje DNS (UDP o TCP, puerto 53) y la IP del cliente que pregunta.
+fn dns_from_ip(ip: &IpFrame) -> Option<(String, DnsMessage)> {
+    let message = match &ip.transport {
+        Transport::Udp(udp) => {
+            if udp.get_source() != DNS_PORT && udp.get_destination() != DNS_PORT {
+                return None;
+            }
+            dns::parse(udp.payload())?
+        }
+        Transport::Tcp(tcp) => {
+            if tcp.get_source() != DNS_PORT && tcp.get_destination() != DNS_PORT {
+                return None;
+            }
+            dns::parse_tcp(tcp.payload())?
+        }
+        Transport::Other => return None,
+    };
+    let client = if message.is_response { ip.dst } else { ip.src };
+    Some((client.to_string(), message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::dns::tests::{query, response};
+    use super::super::ip::parse_frame;
+    use super::*;
+
+    fn feed(log: &mut DomainLog, frame: &[u8], timestamp: u64) {
+        log.observe_ip(&parse_frame(frame).unwrap(), timestamp);
+    }
+
+    fn udp_frame(src: [u8; 4], dst: [u8; 4], src_port: u16, dst_port: u16, payload: &[u8]) -> Vec<u8> {
+        let total_len = (20 + 8 + payload.len()) as u16;
+        let mut frame = vec![0x02, 0, 0, 0, 0, 0x01, 0x02, 0, 0, 0, 0, 0x02, 0x08, 0x00];
+        frame.extend_from_slice(&[0x45, 0, (total_len >> 8) as u8, total_len as u8, 0, 0, 0, 0, 64, 17, 0, 0]);
+        frame.extend_from_slice(&src);
+        frame.extend_from_slice(&dst);
+        frame.extend_from_slice(&src_port.to_be_bytes());
+        frame.extend_from_slice(&dst_port.to_be_bytes());
+        frame.extend_from_slice(&(8 + payload.len() as u16).to_be_bytes());
+        frame.extend_from_slice(&[0, 0]);
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    #[test]
+    fn logs_domains_per_querying_device_with_answers() {
+        let camera = [192, 168, 1, 50];
+        let laptop = [192, 168, 1, 20];
+        let resolver = [192, 168, 1, 1];
+        let mut log = DomainLog::new();
+
+        feed(&mut log, &udp_frame(camera, resolver, 40000, 53, &query(1, "cloud.vendor.cn", 1)), 1_000);
+        feed(&mut log, &udp_frame(camera, resolver, 40001, 53, &query(2, "cloud.vendor.cn", 28)), 2_000);
+        let answer = response(1, "cloud.vendor.cn", 0, [1, 2, 3, 4]);
+        feed(&mut log, &udp_frame(resolver, camera, 53, 40000, &answer), 2_010);
+        feed(&mut log, &udp_frame(laptop, resolver, 40002, 53, &query(3, "example.com", 1)), 3_000);
+        feed(&mut log, &udp_frame(resolver, laptop, 53, 40002, &response(3, "example.com", 3, [0; 4])), 3_010);
+        // Puerto 53 sin DNS valido: se ignora.
+        feed(&mut log, &udp_frame(laptop, resolver, 40003, 53, &[0xDE, 0xAD]), 3_020);
+
+        let all = log.snapshot(None);
+        assert_eq!(all.len(), 2);
+        assert_eq!((all[0].ip.as_str(), all[0].total_queries), ("192.168.1.50", 2));
+        let cloud = &all[0].domains[0];
+        assert_eq!(cloud.domain, "cloud.vendor.cn");
+        assert_eq!((cloud.first_seen, cloud.last_seen), (1_000, 2_010));
+        assert_eq!(cloud.query_types, vec!["A", "AAAA"]);
+        assert_eq!(cloud.answers, vec!["cdn.cloud.vendor.cn", "1.2.3.4"]);
+        assert_eq!(cloud.last_rcode.as_deref(), Some("NOERROR"));
+
+        let laptop_log = log.snapshot(Some("192.168.1.20"));
+        assert_eq!(laptop_log.len(), 1);
+        assert_eq!(laptop_log[0].domains[0].last_rcode.as_deref(), Some("NXDOMAIN"));
+        assert!(laptop_log[0].domains[0].answers.is_empty());
+    }
+}
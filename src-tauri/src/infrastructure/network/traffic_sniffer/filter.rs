@@ -0,0 +1,429 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/filter.rs
+
+use std::net::IpAddr;
+
+// Lenguaje de filtro estilo BPF (subconjunto), compilado una vez y evaluado por paquete:
+//   [src|dst] host IP | [src|dst] net A.B.C.D/N o X:Y::/N | [src|dst] port N
+//   proto tcp|udp|icmp|icmp6|N (o `tcp`, `udp`, `icmp`, `icmp6` a secas) | ip | ip6
+//   len <|<=|>|>=|=|!= N, combinados con and/&&, or/||, not/! y parentesis.
+// Ej: "host 192.168.1.20 and udp port 53" (un `and` implicito entre primitivas, como en tcpdump).
+const MAX_FILTER_LEN: usize = 512;
+const MAX_DEPTH: usize = 32;
+
+// Campos que el filtro puede mirar; los rellena `map_packet` a partir de la cabecera IP/TCP/UDP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacketMeta {
+    pub src: IpAddr,
+    pub dst: IpAddr,
+    // Numero de protocolo IP (6 TCP, 17 UDP, 1 ICMP, 58 ICMPv6...); en IPv6, tras las extensiones.
+    pub protocol: u8,
+    pub src_port: Option<u16>,
+    pub dst_port: Option<u16>,
+    // Longitud IP total (la que muestra la UI).
+    pub length: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct CaptureFilter {
+    source: String,
+    expr: Expr,
+}
+
+impl CaptureFilter {
+    pub fn compile(source: &str) -> Result<Self, String> {
+        let source = source.trim();
+        if source.is_empty() {
+            return Err("Filtro vacio".to_string());
+        }
+        if source.len() > MAX_FILTER_LEN {
+            return Err(format!("Filtro demasiado largo (max. {} caracteres)", MAX_FILTER_LEN));
+        }
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0, depth: 0 };
+        let expr = parser.parse_or()?;
+        if let Some(extra) = parser.peek() {
+            return Err(format!("Filtro invalido: sobra '{}'", extra));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            expr,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn matches(&self, packet: &PacketMeta) -> bool {
+        self.expr.eval(packet)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dir {
+    Src,
+    Dst,
+    Either,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cmp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Host(Dir, IpAddr),
+    Net(Dir, IpAddr, u8),
+    Port(Dir, u16),
+    Proto(u8),
+    // `ip` / `ip6`: familia de direcciones (true = IPv6).
+    Family(bool),
+    Len(Cmp, usize),
+}
+
+impl Expr {
+    fn eval(&self, p: &PacketMeta) -> bool {
+        match self {
+            Expr::And(a, b) => a.eval(p) && b.eval(p),
+            Expr::Or(a, b) => a.eval(p) || b.eval(p),
+            Expr::Not(a) => !a.eval(p),
+            Expr::Host(dir, ip) => by_dir(*dir, p.src == *ip, p.dst == *ip),
+            Expr::Net(dir, network, prefix) => {
+                by_dir(*dir, in_net(p.src, *network, *prefix), in_net(p.dst, *network, *prefix))
+            }
+            Expr::Port(dir, port) => by_dir(*dir, p.src_port == Some(*port), p.dst_port == Some(*port)),
+            Expr::Proto(proto) => p.protocol == *proto,
+            Expr::Family(ipv6) => p.src.is_ipv6() == *ipv6,
+            Expr::Len(cmp, value) => match cmp {
+                Cmp::Lt => p.length < *value,
+                Cmp::Le => p.length <= *value,
+                Cmp::Gt => p.length > *value,
+                Cmp::Ge => p.length >= *value,
+                Cmp::Eq => p.length == *value,
+                Cmp::Ne => p.length != *value,
+            },
+        }
+    }
+}
+
+fn by_dir(dir: Dir, src: bool, dst: bool) -> bool {
+    match dir {
+        Dir::Src => src,
+        Dir::Dst => dst,
+        Dir::Either => src || dst,
+    }
+}
+
+// Una red IPv4 nunca contiene direcciones IPv6 ni al reves.
+fn in_net(ip: IpAddr, network: IpAddr, prefix: u8) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) => u32::from(ip) & v4_mask(prefix) == u32::from(network),
+        (IpAddr::V6(ip), IpAddr::V6(network)) => u128::from(ip) & v6_mask(prefix) == u128::from(network),
+        _ => false,
+    }
+}
+
+fn v4_mask(prefix: u8) -> u32 {
+    u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0)
+}
+
+fn v6_mask(prefix: u8) -> u128 {
+    u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0)
+}
+
+fn tokenize(source: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' || c == ')' {
+            tokens.push(c.to_string());
+            chars.next();
+        } else if "!<>=&|".contains(c) {
+            // Operadores de 1-2 caracteres: !, !=, <, <=, >, >=, =, ==, &&, ||.
+            let mut op = String::from(c);
+            chars.next();
+            if let Some(&next) = chars.peek() {
+                if next == '=' || (next == c && (c == '&' || c == '|' || c == '=')) {
+                    op.push(next);
+                    chars.next();
+                }
+            }
+            if op == "&" || op == "|" {
+                return Err(format!("Filtro invalido: operador '{}' (usa '{}{}' o and/or)", op, op, op));
+            }
+            tokens.push(op);
+        } else if c.is_ascii_alphanumeric() || "./-_:".contains(c) {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_ascii_alphanumeric() || "./-_:".contains(c) {
+                    word.push(c.to_ascii_lowercase());
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(word);
+        } else {
+            return Err(format!("Filtro invalido: caracter '{}'", c));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect_value(&mut self, what: &str) -> Result<String, String> {
+        self.next().ok_or_else(|| format!("Filtro invalido: falta {}", what))
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_and()?;
+        while matches!(self.peek(), Some("or" | "||")) {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_unary()?;
+        loop {
+            match self.peek() {
+                Some("and" | "&&") => self.pos += 1,
+                // Primitivas seguidas ("udp port 53"): `and` implicito.
+                Some(token) if token != "or" && token != "||" && token != ")" => {}
+                _ => break,
+            }
+            let right = self.parse_unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err("Filtro demasiado anidado".to_string());
+        }
+        let expr = match self.peek() {
+            Some("not" | "!") => {
+                self.pos += 1;
+                Expr::Not(Box::new(self.parse_unary()?))
+            }
+            Some("(") => {
+                self.pos += 1;
+                let inner = self.parse_or()?;
+                if self.next().as_deref() != Some(")") {
+                    return Err("Filtro invalido: falta ')'".to_string());
+                }
+                inner
+            }
+            _ => self.parse_primitive()?,
+        };
+        self.depth -= 1;
+        Ok(expr)
+    }
+
+    fn parse_primitive(&mut self) -> Result<Expr, String> {
+        let token = self.expect_value("una expresion")?;
+        let (dir, keyword) = match token.as_str() {
+            "src" => (Dir::Src, self.expect_value("host/net/port tras 'src'")?),
+            "dst" => (Dir::Dst, self.expect_value("host/net/port tras 'dst'")?),
+            _ => (Dir::Either, token),
+        };
+        match keyword.as_str() {
+            "host" => {
+                let value = self.expect_value("la IP de 'host'")?;
+                let ip = value
+                    .parse::<IpAddr>()
+                    .map_err(|_| format!("Filtro invalido: '{}' no es una IP", value))?;
+                Ok(Expr::Host(dir, ip))
+            }
+            "net" => {
+                let value = self.expect_value("la red de 'net'")?;
+                let (network, prefix) = parse_cidr(&value)?;
+                Ok(Expr::Net(dir, network, prefix))
+            }
+            "port" => {
+                let value = self.expect_value("el numero de 'port'")?;
+                let port = value
+                    .parse::<u16>()
+                    .map_err(|_| format!("Filtro invalido: puerto '{}'", value))?;
+                Ok(Expr::Port(dir, port))
+            }
+            other if dir != Dir::Either => Err(format!("Filtro invalido: '{}' no admite src/dst", other)),
+            "proto" => {
+                let value = self.expect_value("el protocolo de 'proto'")?;
+                Ok(Expr::Proto(parse_proto(&value)?))
+            }
+            "tcp" | "udp" | "icmp" | "icmp6" => Ok(Expr::Proto(parse_proto(&keyword)?)),
+            "ip" => Ok(Expr::Family(false)),
+            "ip6" => Ok(Expr::Family(true)),
+            "len" => {
+                let op = self.expect_value("el comparador de 'len'")?;
+                let cmp = match op.as_str() {
+                    "<" => Cmp::Lt,
+                    "<=" => Cmp::Le,
+                    ">" => Cmp::Gt,
+                    ">=" => Cmp::Ge,
+                    "=" | "==" => Cmp::Eq,
+                    "!=" => Cmp::Ne,
+                    _ => return Err(format!("Filtro invalido: comparador '{}'", op)),
+                };
+                let value = self.expect_value("la longitud de 'len'")?;
+                let len = value
+                    .parse::<usize>()
+                    .map_err(|_| format!("Filtro invalido: longitud '{}'", value))?;
+                Ok(Expr::Len(cmp, len))
+            }
+            other => Err(format!("Filtro invalido: '{}' no es una primitiva conocida", other)),
+        }
+    }
+}
+
+// Devuelve la red ya enmascarada y la longitud del prefijo.
+fn parse_cidr(value: &str) -> Result<(IpAddr, u8), String> {
+    let invalid = || format!("Filtro invalido: red '{}' (formato A.B.C.D/N o X:Y::/N)", value);
+    let (ip, prefix) = value.split_once('/').ok_or_else(invalid)?;
+    let ip: IpAddr = ip.parse().map_err(|_| invalid())?;
+    let max_prefix = if ip.is_ipv6() { 128 } else { 32 };
+    let prefix: u8 = prefix.parse().ok().filter(|p| *p <= max_prefix).ok_or_else(invalid)?;
+    let network = match ip {
+        IpAddr::V4(ip) => IpAddr::V4((u32::from(ip) & v4_mask(prefix)).into()),
+        IpAddr::V6(ip) => IpAddr::V6((u128::from(ip) & v6_mask(prefix)).into()),
+    };
+    Ok((network, prefix))
+}
+
+fn parse_proto(value: &str) -> Result<u8, String> {
+    match value {
+        "icmp" => Ok(1),
+        "igmp" => Ok(2),
+        "tcp" => Ok(6),
+        "udp" => Ok(17),
+        "gre" => Ok(47),
+        "esp" => Ok(50),
+        "icmp6" | "icmpv6" => Ok(58),
+        other => other
+            .parse::<u8>()
+            .map_err(|_| format!("Filtro invalido: protocolo '{}'", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn udp(src: &str, dst: &str, src_port: u16, dst_port: u16, length: usize) -> PacketMeta {
+        PacketMeta {
+            src: src.parse().unwrap(),
+            dst: dst.parse().unwrap(),
+            protocol: 17,
+            src_port: Some(src_port),
+            dst_port: Some(dst_port),
+            length,
+        }
+    }
+
+    fn matches(filter: &str, packet: &PacketMeta) -> bool {
+        CaptureFilter::compile(filter).unwrap().matches(packet)
+    }
+
+    #[test]
+    fn primitives_directions_and_boolean_operators() {
+        let dns = udp("192.168.1.20", "192.168.1.1", 50000, 53, 70);
+
+        assert!(matches("host 192.168.1.20 and udp port 53", &dns));
+        assert!(matches("src host 192.168.1.20 && dst port 53", &dns));
+        assert!(!matches("dst host 192.168.1.20", &dns));
+        assert!(matches("net 192.168.1.0/24", &dns));
+        assert!(!matches("src net 10.0.0.0/8", &dns));
+        assert!(matches("proto 17 and not tcp", &dns));
+        assert!(matches("tcp or (udp and len <= 100)", &dns));
+        assert!(!matches("len > 100 || port 443", &dns));
+        assert!(matches("!(port 80 or port 443)", &dns));
+        assert!(matches("HOST 192.168.1.1 AND NOT SRC PORT 53", &dns));
+
+        let icmp = PacketMeta {
+            protocol: 1,
+            src_port: None,
+            dst_port: None,
+            ..dns
+        };
+        assert!(matches("icmp", &icmp));
+        assert!(!matches("port 53", &icmp));
+    }
+
+    #[test]
+    fn ipv6_hosts_networks_and_family() {
+        let mdns = udp("fe80::1c2a:3bff:fe4d:5e6f", "ff02::fb", 5353, 5353, 120);
+
+        assert!(matches("ip6 and udp port 5353", &mdns));
+        assert!(!matches("ip", &mdns));
+        assert!(matches("src host FE80::1C2A:3BFF:FE4D:5E6F", &mdns));
+        assert!(matches("dst net ff00::/8 and src net fe80::/10", &mdns));
+        assert!(!matches("net 2001:db8::/32", &mdns));
+        // Familias cruzadas: una red IPv4 /0 no contiene direcciones IPv6.
+        assert!(!matches("net 0.0.0.0/0", &mdns));
+        assert!(matches("net ::/0", &mdns));
+
+        let ndp = PacketMeta { protocol: 58, src_port: None, dst_port: None, ..mdns };
+        assert!(matches("icmp6 and not icmp", &ndp));
+        assert!(matches("proto icmpv6", &ndp));
+        assert!(matches("ip", &udp("192.168.1.20", "192.168.1.1", 50000, 53, 70)));
+
+        assert!(CaptureFilter::compile("net 2001:db8::/129").is_err());
+        assert!(CaptureFilter::compile("host fe80::1::2").is_err());
+        assert!(CaptureFilter::compile("src ip6").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for bad in [
+            "",
+            "host",
+            "host 999.1.1.1",
+            "net 10.0.0.0/33",
+            "port 70000",
+            "len ~ 5",
+            "src tcp",
+            "(udp",
+            "udp)",
+            "udp or",
+            "port 53 & udp",
+            "foo 1",
+            "host 1.1.1.1; rm -rf",
+        ] {
+            assert!(CaptureFilter::compile(bad).is_err(), "'{}' deberia fallar", bad);
+        }
+        assert!(CaptureFilter::compile(&"(".repeat(40)).is_err());
+        assert_eq!(CaptureFilter::compile("  udp port 53 ").unwrap().source(), "udp port 53");
+    }
+}
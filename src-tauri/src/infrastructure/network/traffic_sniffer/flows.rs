@@ -0,0 +1,461 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/flows.rs
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::net::IpAddr;
+
+use pnet::packet::tcp::TcpFlags;
+
+use crate::domain::entities::TrafficFlow;
+
+use super::ip::{self, IpFrame, Transport};
+
+// Inactividad tras la que un flujo se da por terminado (valores cercanos a los de Zeek/conntrack).
+const TCP_IDLE_MS: u64 = 300_000;
+const TCP_CLOSED_IDLE_MS: u64 = 5_000;
+const UDP_IDLE_MS: u64 = 60_000;
+const OTHER_IDLE_MS: u64 = 30_000;
+// Tope de flujos vivos: al superarlo se cierra el mas antiguo (un escaneo no llena la memoria).
+const MAX_ACTIVE_FLOWS: usize = 10_000;
+// Historial de flujos cerrados que devuelve `get_flows`.
+const MAX_CLOSED_FLOWS: usize = 1_000;
+
+type Endpoint = (IpAddr, u16);
+
+// Clave normalizada (extremo menor primero): ambos sentidos caen en el mismo flujo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct FlowKey {
+    protocol: u8,
+    low: Endpoint,
+    high: Endpoint,
+}
+
+impl FlowKey {
+    fn new(protocol: u8, a: Endpoint, b: Endpoint) -> Self {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        Self { protocol, low, high }
+    }
+}
+
+// Lo que se extrae de una trama para contabilizarla.
+struct FlowPacket {
+    protocol: u8,
+    src: Endpoint,
+    dst: Endpoint,
+    has_ports: bool,
+    tcp_flags: Option<u8>,
+    bytes: u64,
+}
+
+#[derive(Default)]
+struct TcpTrack {
+    syn: bool,
+    syn_ack: bool,
+    established: bool,
+    fin_orig: bool,
+    fin_resp: bool,
+    rst_orig: bool,
+    rst_resp: bool,
+}
+
+impl TcpTrack {
+    fn observe(&mut self, flags: u8, from_orig: bool) {
+        let syn = flags & TcpFlags::SYN != 0;
+        let ack = flags & TcpFlags::ACK != 0;
+        if syn && !ack && from_orig {
+            self.syn = true;
+        } else if syn && ack && !from_orig {
+            self.syn_ack = true;
+        } else if ack && self.syn_ack && from_orig {
+            self.established = true;
+        }
+        if flags & TcpFlags::FIN != 0 {
+            if from_orig {
+                self.fin_orig = true;
+            } else {
+                self.fin_resp = true;
+            }
+        }
+        if flags & TcpFlags::RST != 0 {
+            if from_orig {
+                self.rst_orig = true;
+            } else {
+                self.rst_resp = true;
+            }
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.rst_orig || self.rst_resp || (self.fin_orig && self.fin_resp)
+    }
+
+    fn state(&self) -> &'static str {
+        if self.rst_orig || self.rst_resp {
+            "reset"
+        } else if self.fin_orig && self.fin_resp {
+            "closed"
+        } else if self.fin_orig || self.fin_resp {
+            "closing"
+        } else if self.established {
+            "established"
+        } else if self.syn_ack {
+            "syn_received"
+        } else if self.syn {
+            "syn_sent"
+        } else {
+            "midstream"
+        }
+    }
+
+    // `conn_state` de Zeek (conn.log).
+    fn conn_state(&self) -> &'static str {
+        let answered = self.syn_ack || self.established;
+        match (self.syn, answered) {
+            (false, _) => "OTH",
+            (true, false) if self.rst_resp => "REJ",
+            (true, false) if self.fin_orig => "SH",
+            (true, false) => "S0",
+            (true, true) if self.rst_orig => "RSTO",
+            (true, true) if self.rst_resp => "RSTR",
+            (true, true) => match (self.fin_orig, self.fin_resp) {
+                (true, true) => "SF",
+                (true, false) => "S2",
+                (false, true) => "S3",
+                (false, false) => "S1",
+            },
+        }
+    }
+}
+
+struct FlowEntry {
+    uid: String,
+    orig: Endpoint,
+    resp: Endpoint,
+    has_ports: bool,
+    service: String,
+    orig_packets: u64,
+    orig_bytes: u64,
+    resp_packets: u64,
+    resp_bytes: u64,
+    first_seen: u64,
+    last_seen: u64,
+    tcp: Option<TcpTrack>,
+    // Cambiado desde la ultima actualizacion emitida.
+    dirty: bool,
+}
+
+impl FlowEntry {
+    fn idle_timeout(&self) -> u64 {
+        match &self.tcp {
+            Some(tcp) if tcp.is_closed() => TCP_CLOSED_IDLE_MS,
+            Some(_) => TCP_IDLE_MS,
+            None if self.has_ports => UDP_IDLE_MS,
+            None => OTHER_IDLE_MS,
+        }
+    }
+
+    fn to_flow(&self, protocol: u8, active: bool) -> TrafficFlow {
+        let port = |p: u16| self.has_ports.then_some(p);
+        let conn_state = match &self.tcp {
+            Some(tcp) => tcp.conn_state(),
+            // Sin estado de transporte: como Zeek, S0 si nadie respondio y SF si hubo ida y vuelta.
+            None if self.resp_packets > 0 => "SF",
+            None => "S0",
+        };
+        TrafficFlow {
+            uid: self.uid.clone(),
+            protocol: protocol_name(protocol),
+            service: self.service.clone(),
+            orig_ip: self.orig.0.to_string(),
+            orig_port: port(self.orig.1),
+            resp_ip: self.resp.0.to_string(),
+            resp_port: port(self.resp.1),
+            orig_packets: self.orig_packets,
+            orig_bytes: self.orig_bytes,
+            resp_packets: self.resp_packets,
+            resp_bytes: self.resp_bytes,
+            first_seen: self.first_seen,
+            last_seen: self.last_seen,
+            tcp_state: self.tcp.as_ref().map(|t| t.state().to_string()),
+            conn_state: conn_state.to_string(),
+            active,
+        }
+    }
+}
+
+// Tabla de conversaciones por 5-tupla. Sin reloj propio: las marcas vienen de cada trama
+// (en vivo, la hora actual; offline, la del fichero).
+pub struct FlowTable {
+    flows: HashMap<FlowKey, FlowEntry>,
+    // Los mismos flujos ordenados por `last_seen`: desalojar el mas antiguo no recorre la tabla.
+    by_last_seen: BTreeSet<(u64, FlowKey)>,
+    closed: VecDeque<TrafficFlow>,
+    max_closed: usize,
+    // Cerrados desde la ultima actualizacion emitida.
+    closed_pending: Vec<TrafficFlow>,
+    next_uid: u64,
+}
+
+impl FlowTable {
+    pub fn new() -> Self {
+        Self::with_history(MAX_CLOSED_FLOWS)
+    }
+
+    // `max_closed`: cuantos flujos cerrados se conservan (los mas antiguos se descartan).
+    pub fn with_history(max_closed: usize) -> Self {
+        Self {
+            flows: HashMap::new(),
+            by_last_seen: BTreeSet::new(),
+            closed: VecDeque::new(),
+            max_closed,
+            closed_pending: Vec::new(),
+            next_uid: 0,
+        }
+    }
+
+    // Contabiliza una trama Ethernet/IPv4; `service` es la etiqueta del clasificador (`TrafficPacket.info`).
+    pub fn observe_ip(&mut self, ip: &IpFrame, timestamp: u64, service: &str) {
+        let packet = flow_packet(ip);
+        let key = FlowKey::new(packet.protocol, packet.src, packet.dst);
+
+        if !self.flows.contains_key(&key) {
+            if self.flows.len() >= MAX_ACTIVE_FLOWS {
+                self.evict_oldest();
+            }
+            self.next_uid += 1;
+            // Si lo primero que vemos es un SYN-ACK, el originador es el destino.
+            let reversed = packet
+                .tcp_flags
+                .is_some_and(|f| f & TcpFlags::SYN != 0 && f & TcpFlags::ACK != 0);
+            let (orig, resp) = if reversed { (packet.dst, packet.src) } else { (packet.src, packet.dst) };
+            self.flows.insert(
+                key,
+                FlowEntry {
+                    uid: format!("C{:x}{:06x}", timestamp, self.next_uid),
+                    orig,
+                    resp,
+                    has_ports: packet.has_ports,
+                    service: service.to_string(),
+                    orig_packets: 0,
+                    orig_bytes: 0,
+                    resp_packets: 0,
+                    resp_bytes: 0,
+                    first_seen: timestamp,
+                    last_seen: timestamp,
+                    tcp: packet.tcp_flags.map(|_| TcpTrack::default()),
+                    dirty: true,
+                },
+            );
+            self.by_last_seen.insert((timestamp, key));
+        }
+
+        let Some(entry) = self.flows.get_mut(&key) else {
+            return;
+        };
+        let from_orig = packet.src == entry.orig;
+        if from_orig {
+            entry.orig_packets += 1;
+            entry.orig_bytes += packet.bytes;
+        } else {
+            entry.resp_packets += 1;
+            entry.resp_bytes += packet.bytes;
+        }
+        if let (Some(tcp), Some(flags)) = (entry.tcp.as_mut(), packet.tcp_flags) {
+            tcp.observe(flags, from_orig);
+        }
+        if timestamp > entry.last_seen {
+            self.by_last_seen.remove(&(entry.last_seen, key));
+            self.by_last_seen.insert((timestamp, key));
+            entry.last_seen = timestamp;
+        }
+        entry.dirty = true;
+    }
+
+    // Cierra los flujos inactivos respecto a `now`.
+    pub fn expire(&mut self, now: u64) {
+        let expired: Vec<FlowKey> = self
+            .flows
+            .iter()
+            .filter(|(_, e)| now.saturating_sub(e.last_seen) >= e.idle_timeout())
+            .map(|(k, _)| *k)
+            .collect();
+        for key in expired {
+            self.close(key);
+        }
+    }
+
+    // Fin de captura (offline o parada del monitor): todo lo vivo pasa a cerrado.
+    pub fn close_all(&mut self) {
+        let keys: Vec<FlowKey> = self.flows.keys().copied().collect();
+        for key in keys {
+            self.close(key);
+        }
+    }
+
+    // Flujos cambiados o cerrados desde la llamada anterior (eventos periodicos a la UI).
+    pub fn take_updates(&mut self) -> Vec<TrafficFlow> {
+        let mut updates = std::mem::take(&mut self.closed_pending);
+        for (key, entry) in self.flows.iter_mut().filter(|(_, e)| e.dirty) {
+            entry.dirty = false;
+            updates.push(entry.to_flow(key.protocol, true));
+        }
+        updates
+    }
+
+    // Vivos (mas recientes primero) seguidos del historial de cerrados.
+    pub fn snapshot(&self) -> Vec<TrafficFlow> {
+        let mut flows: Vec<TrafficFlow> = self.flows.iter().map(|(k, e)| e.to_flow(k.protocol, true)).collect();
+        flows.sort_by_key(|f| std::cmp::Reverse(f.last_seen));
+        flows.extend(self.closed.iter().rev().cloned());
+        flows
+    }
+
+    // Solo los cerrados, en orden de cierre.
+    pub fn closed_flows(&self) -> impl Iterator<Item = &TrafficFlow> {
+        self.closed.iter()
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some(&(_, key)) = self.by_last_seen.first() {
+            self.close(key);
+        }
+    }
+
+    fn close(&mut self, key: FlowKey) {
+        let Some(entry) = self.flows.remove(&key) else {
+            return;
+        };
+        self.by_last_seen.remove(&(entry.last_seen, key));
+        let flow = entry.to_flow(key.protocol, false);
+        if self.closed.len() >= self.max_closed {
+            self.closed.pop_front();
+        }
+        self.closed.push_back(flow.clone());
+        self.closed_pending.push(flow);
+    }
+}
+
+fn flow_packet(ip: &IpFrame) -> FlowPacket {
+    let (src_port, dst_port) = ip.ports();
+    let tcp_flags = match &ip.transport {
+        Transport::Tcp(tcp) => Some(tcp.get_flags()),
+        _ => None,
+    };
+    FlowPacket {
+        protocol: ip.protocol,
+        src: (ip.src, src_port.unwrap_or(0)),
+        dst: (ip.dst, dst_port.unwrap_or(0)),
+        has_ports: src_port.is_some(),
+        tcp_flags,
+        bytes: ip.length as u64,
+    }
+}
+
+// Nombres de `proto` de Zeek (que tampoco distingue ICMP de ICMPv6).
+fn protocol_name(protocol: u8) -> String {
+    match protocol {
+        ip::PROTO_TCP => "tcp".to_string(),
+        ip::PROTO_UDP => "udp".to_string(),
+        ip::PROTO_ICMP | ip::PROTO_ICMPV6 => "icmp".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(table: &mut FlowTable, frame: &[u8], timestamp: u64, service: &str) {
+        table.observe_ip(&ip::parse_frame(frame).unwrap(), timestamp, service)
+    }
+
+    const CLIENT: [u8; 4] = [192, 168, 1, 20];
+    const SERVER: [u8; 4] = [93, 184, 216, 34];
+
+    fn tcp_frame(src: [u8; 4], dst: [u8; 4], src_port: u16, dst_port: u16, flags: u8, payload: usize) -> Vec<u8> {
+        let total_len = (20 + 20 + payload) as u16;
+        let mut frame = vec![0x02, 0, 0, 0, 0, 0x01, 0x02, 0, 0, 0, 0, 0x02, 0x08, 0x00];
+        frame.extend_from_slice(&[0x45, 0, (total_len >> 8) as u8, total_len as u8, 0, 0, 0, 0, 64, 6, 0, 0]);
+        frame.extend_from_slice(&src);
+        frame.extend_from_slice(&dst);
+        frame.extend_from_slice(&src_port.to_be_bytes());
+        frame.extend_from_slice(&dst_port.to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 0, 0x50, flags, 0xFF, 0xFF, 0, 0, 0, 0]);
+        frame.extend(std::iter::repeat_n(0u8, payload));
+        frame
+    }
+
+    #[test]
+    fn merges_both_directions_and_tracks_tcp_handshake_to_close() {
+        let mut table = FlowTable::new();
+        let out = |flags, payload| tcp_frame(CLIENT, SERVER, 50000, 443, flags, payload);
+        let back = |flags, payload| tcp_frame(SERVER, CLIENT, 443, 50000, flags, payload);
+
+        feed(&mut table, &out(TcpFlags::SYN, 0), 1_000, "HTTPS (Web segura)");
+        feed(&mut table, &back(TcpFlags::SYN | TcpFlags::ACK, 0), 1_010, "HTTPS (Web segura)");
+        feed(&mut table, &out(TcpFlags::ACK, 0), 1_020, "HTTPS (Web segura)");
+        feed(&mut table, &out(TcpFlags::ACK | TcpFlags::PSH, 100), 1_030, "HTTPS (Web segura)");
+
+        let updates = table.take_updates();
+        assert_eq!(updates.len(), 1);
+        let flow = &updates[0];
+        assert_eq!((flow.orig_ip.as_str(), flow.orig_port), ("192.168.1.20", Some(50000)));
+        assert_eq!((flow.resp_ip.as_str(), flow.resp_port), ("93.184.216.34", Some(443)));
+        assert_eq!((flow.orig_packets, flow.orig_bytes), (3, 40 + 40 + 140));
+        assert_eq!((flow.resp_packets, flow.resp_bytes), (1, 40));
+        assert_eq!((flow.first_seen, flow.last_seen), (1_000, 1_030));
+        assert_eq!(flow.tcp_state.as_deref(), Some("established"));
+        assert_eq!(flow.conn_state, "S1");
+        assert!(table.take_updates().is_empty());
+
+        feed(&mut table, &out(TcpFlags::FIN | TcpFlags::ACK, 0), 2_000, "");
+        feed(&mut table, &back(TcpFlags::FIN | TcpFlags::ACK, 0), 2_010, "");
+        // Cerrado por FIN en ambos sentidos: caduca a los pocos segundos, no a los 5 minutos.
+        table.expire(2_010 + TCP_CLOSED_IDLE_MS);
+        let snapshot = table.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert!(!snapshot[0].active);
+        assert_eq!(snapshot[0].conn_state, "SF");
+        assert_eq!(snapshot[0].tcp_state.as_deref(), Some("closed"));
+    }
+
+    #[test]
+    fn expires_idle_udp_and_labels_unanswered_and_rejected_connections() {
+        let mut table = FlowTable::new();
+        let mut dns = tcp_frame(CLIENT, [192, 168, 1, 1], 40000, 53, 0, 0);
+        dns[23] = 17; // mismo layout, pero UDP: solo importan los puertos
+        feed(&mut table, &dns, 0, "DNS");
+        feed(&mut table, &tcp_frame(CLIENT, SERVER, 50001, 22, TcpFlags::SYN, 0), 0, "SSH");
+        feed(&mut table, &tcp_frame(CLIENT, SERVER, 50002, 23, TcpFlags::SYN, 0), 0, "Datos TCP");
+        feed(&mut table, &tcp_frame(SERVER, CLIENT, 23, 50002, TcpFlags::RST | TcpFlags::ACK, 0), 5, "");
+
+        table.expire(UDP_IDLE_MS - 1);
+        assert!(table.closed_flows().all(|f| f.resp_port != Some(53)));
+        table.expire(UDP_IDLE_MS);
+        let closed: Vec<&TrafficFlow> = table.closed_flows().collect();
+        assert!(closed.iter().any(|f| f.protocol == "udp" && f.conn_state == "S0"));
+        assert!(closed.iter().any(|f| f.resp_port == Some(23) && f.conn_state == "REJ"));
+
+        table.close_all();
+        let ssh = table.closed_flows().find(|f| f.resp_port == Some(22)).unwrap();
+        assert_eq!((ssh.conn_state.as_str(), ssh.tcp_state.as_deref()), ("S0", Some("syn_sent")));
+        assert_eq!(table.snapshot().len(), 3);
+    }
+
+    #[test]
+    fn full_table_evicts_the_least_recently_seen_flow() {
+        let mut table = FlowTable::new();
+        let syn = |port: u16| tcp_frame(CLIENT, SERVER, port, 443, TcpFlags::SYN, 0);
+        for i in 0..MAX_ACTIVE_FLOWS as u64 {
+            feed(&mut table, &syn(10_000 + i as u16), i, "HTTPS (Web segura)");
+        }
+        // El primero vuelve a tener trafico: el mas antiguo pasa a ser el segundo.
+        feed(&mut table, &syn(10_000), 20_000, "HTTPS (Web segura)");
+        feed(&mut table, &syn(60_000), 20_001, "HTTPS (Web segura)");
+
+        let evicted: Vec<&TrafficFlow> = table.closed_flows().collect();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].orig_port, Some(10_001));
+        assert_eq!(table.by_last_seen.len(), MAX_ACTIVE_FLOWS);
+        assert_eq!(table.by_last_seen.first().map(|(seen, _)| *seen), Some(2));
+    }
+}
@@ -0,0 +1,229 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/http.rs
+
+// Disector HTTP/1.x en claro: linea inicial y cabeceras del primer segmento.
+// Sin reensamblado: las cabeceras de una peticion casi siempre caben en un segmento.
+const METHODS: [&str; 9] = ["GET", "POST", "HEAD", "PUT", "DELETE", "OPTIONS", "PATCH", "CONNECT", "TRACE"];
+const MAX_HEADER_BYTES: usize = 8 * 1024;
+const MAX_FIELD_LEN: usize = 512;
+const MAX_PATH_IN_INFO: usize = 80;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpMessage {
+    Request { method: String, host: Option<String>, path: String, user_agent: Option<String> },
+    Response { status: u16, reason: String, server: Option<String> },
+}
+
+impl HttpMessage {
+    // Texto para `TrafficPacket.info`.
+    pub fn describe(&self) -> String {
+        match self {
+            HttpMessage::Request { method, host, path, .. } => {
+                let mut target = format!("{}{}", host.as_deref().unwrap_or(""), path);
+                if target.chars().count() > MAX_PATH_IN_INFO {
+                    target = target.chars().take(MAX_PATH_IN_INFO).collect::<String>() + "...";
+                }
+                format!("HTTP {} {}", method, target)
+            }
+            HttpMessage::Response { status, reason, .. } => {
+                format!("HTTP {} {}", status, reason).trim_end().to_string()
+            }
+        }
+    }
+}
+
+pub fn parse(payload: &[u8]) -> Option<HttpMessage> {
+    // Descarte rapido antes de convertir a texto: peticion (METODO + espacio) o respuesta ("HTTP/1.").
+    let looks_like_http = payload.starts_with(b"HTTP/1.")
+        || METHODS.iter().any(|m| payload.starts_with(m.as_bytes()) && payload.get(m.len()) == Some(&b' '));
+    if !looks_like_http {
+        return None;
+    }
+    let head = &payload[..payload.len().min(MAX_HEADER_BYTES)];
+    let text = String::from_utf8_lossy(head);
+    let mut lines = text.split("\r\n");
+    let start_line = lines.next()?;
+
+    let headers: Vec<(String, String)> = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.trim().to_ascii_lowercase(), clean(value)))
+        })
+        .collect();
+    let header = |name: &str| headers.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone()).filter(|v| !v.is_empty());
+
+    if let Some(rest) = start_line.strip_prefix("HTTP/1.") {
+        let mut parts = rest.splitn(3, ' ');
+        parts.next()?;
+        let status: u16 = parts.next()?.parse().ok()?;
+        if !(100..600).contains(&status) {
+            return None;
+        }
+        return Some(HttpMessage::Response {
+            status,
+            reason: clean(parts.next().unwrap_or("")),
+            server: header("server"),
+        });
+    }
+
+    let mut parts = start_line.split(' ');
+    let method = parts.next()?.to_string();
+    let path = clean(parts.next()?);
+    if !parts.next()?.starts_with("HTTP/1.") {
+        return None;
+    }
+    Some(HttpMessage::Request {
+        method,
+        host: header("host").map(|h| h.to_ascii_lowercase()),
+        path,
+        user_agent: header("user-agent"),
+    })
+}
+
+// Sin caracteres de control y con longitud acotada: acaba en la UI y en los logs.
+fn clean(value: &str) -> String {
+    value.trim().chars().filter(|c| !c.is_control()).take(MAX_FIELD_LEN).collect()
+}
+
+// Pista de sistema operativo, cliente y tipo de equipo a partir del User-Agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserAgentHint {
+    pub os: Option<String>,
+    pub client: Option<String>,
+    // Vocabulario del clasificador de la UI: PHONE, PC, TV, SPEAKER, IOT.
+    pub device_type: Option<String>,
+}
+
+pub fn user_agent_hint(user_agent: &str) -> UserAgentHint {
+    let ua = user_agent.to_ascii_lowercase();
+    let has = |needle: &str| ua.contains(needle);
+
+    // Orden: lo mas especifico primero (Android incluye "linux"; iPhone incluye "mac os x").
+    let (os, device_type) = if has("iphone") || has("ipad") || has("ipod") {
+        (Some("iOS"), Some("PHONE"))
+    } else if has("tizen")
+        || has("web0s")
+        || has("webos")
+        || has("smarttv")
+        || has("smart-tv")
+        || has("roku")
+        || has("bravia")
+        || has("hbbtv")
+    {
+        (Some("Smart TV"), Some("TV"))
+    } else if (has("aft") && has("android")) || has("firetv") {
+        (Some("Fire OS"), Some("TV"))
+    } else if has("android") {
+        // Las tablets Android tambien cuentan como PHONE: el clasificador no distingue tablets.
+        (Some("Android"), Some("PHONE"))
+    } else if has("windows") || has("microsoft-") {
+        // Incluye agentes del sistema como Microsoft-CryptoAPI o Microsoft-Delivery-Optimization.
+        (Some("Windows"), Some("PC"))
+    } else if has("cros ") {
+        (Some("ChromeOS"), Some("PC"))
+    } else if has("macintosh") || has("mac os x") {
+        (Some("macOS"), Some("PC"))
+    } else if has("playstation") || has("xbox") || has("nintendo") {
+        (Some("Consola"), Some("TV"))
+    } else if has("linux") || has("x11") {
+        (Some("Linux"), Some("PC"))
+    } else {
+        (None, None)
+    };
+
+    let client = if has("edg/") {
+        Some("Edge")
+    } else if has("opr/") {
+        Some("Opera")
+    } else if has("firefox/") {
+        Some("Firefox")
+    } else if has("chrome/") || has("crios/") {
+        Some("Chrome")
+    } else if has("safari/") {
+        Some("Safari")
+    } else if has("curl/") {
+        Some("curl")
+    } else if has("wget/") {
+        Some("Wget")
+    } else if has("python-requests") || has("python-urllib") || has("aiohttp") {
+        Some("Python")
+    } else if has("okhttp") {
+        Some("OkHttp")
+    } else if has("go-http-client") {
+        Some("Go")
+    } else if has("dalvik/") {
+        Some("Android (sistema)")
+    } else {
+        None
+    };
+
+    // Clientes de libreria sin SO reconocible: tipico de firmware IoT.
+    let device_type = device_type
+        .or_else(|| matches!(client, Some("curl") | Some("Wget") | Some("Python") | Some("Go")).then_some("IOT"));
+
+    UserAgentHint {
+        os: os.map(str::to_string),
+        client: client.map(str::to_string),
+        device_type: device_type.map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_requests_and_responses() {
+        let request = b"GET /firmware/check?v=1.2 HTTP/1.1\r\nHost: Update.Vendor.cn\r\nUser-Agent: Wget/1.20.3 (linux-gnu)\r\nAccept: */*\r\n\r\n";
+        let message = parse(request).unwrap();
+        assert_eq!(
+            message,
+            HttpMessage::Request {
+                method: "GET".to_string(),
+                host: Some("update.vendor.cn".to_string()),
+                path: "/firmware/check?v=1.2".to_string(),
+                user_agent: Some("Wget/1.20.3 (linux-gnu)".to_string()),
+            }
+        );
+        assert_eq!(message.describe(), "HTTP GET update.vendor.cn/firmware/check?v=1.2");
+
+        let response = parse(b"HTTP/1.1 404 Not Found\r\nServer: lighttpd/1.4.35\r\n\r\n").unwrap();
+        assert_eq!(response.describe(), "HTTP 404 Not Found");
+        assert!(matches!(response, HttpMessage::Response { server: Some(s), .. } if s == "lighttpd/1.4.35"));
+
+        assert!(parse(b"\x16\x03\x01\x02\x00").is_none());
+        assert!(parse(b"GETTING started").is_none());
+        assert!(parse(b"GET / SSH-2.0\r\n").is_none());
+    }
+
+    #[test]
+    fn user_agent_hints_os_client_and_device_type() {
+        let iphone = user_agent_hint("Mozilla/5.0 (iPhone; CPU iPhone OS 17_4 like Mac OS X) AppleWebKit/605.1.15 Version/17.4 Mobile/15E148 Safari/604.1");
+        assert_eq!(
+            (iphone.os.as_deref(), iphone.client.as_deref(), iphone.device_type.as_deref()),
+            (Some("iOS"), Some("Safari"), Some("PHONE"))
+        );
+
+        let windows = user_agent_hint(
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 Chrome/124.0 Safari/537.36 Edg/124.0",
+        );
+        assert_eq!(
+            (windows.os.as_deref(), windows.client.as_deref(), windows.device_type.as_deref()),
+            (Some("Windows"), Some("Edge"), Some("PC"))
+        );
+
+        let tv = user_agent_hint("Mozilla/5.0 (SMART-TV; Linux; Tizen 6.0) AppleWebKit/537.36 SamsungBrowser/4.0 Chrome/76.0 TV Safari/537.36");
+        assert_eq!((tv.os.as_deref(), tv.device_type.as_deref()), (Some("Smart TV"), Some("TV")));
+
+        let updater = user_agent_hint("Microsoft-CryptoAPI/10.0");
+        assert_eq!((updater.os.as_deref(), updater.device_type.as_deref()), (Some("Windows"), Some("PC")));
+
+        let camera = user_agent_hint("curl/7.58.0");
+        assert_eq!(
+            (camera.os, camera.client.as_deref(), camera.device_type.as_deref()),
+            (None, Some("curl"), Some("IOT"))
+        );
+
+        assert_eq!(user_agent_hint("MyFridge/2.0"), UserAgentHint::default());
+    }
+}
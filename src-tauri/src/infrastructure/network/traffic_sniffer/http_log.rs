@@ -0,0 +1,170 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/http_log.rs
+
+use std::collections::HashMap;
+
+use pnet::packet::Packet;
+
+use crate::domain::entities::{DeviceUserAgents, UserAgentStat};
+
+use super::http::{self, HttpMessage, UserAgentHint};
+use super::ip::{IpFrame, Transport};
+
+const MAX_DEVICES: usize = 512;
+const MAX_AGENTS_PER_DEVICE: usize = 64;
+const MAX_HOSTS_PER_AGENT: usize = 8;
+
+struct AgentEntry {
+    hint: UserAgentHint,
+    requests: u64,
+    first_seen: u64,
+    last_seen: u64,
+    hosts: Vec<String>,
+}
+
+// User-Agents por equipo (la IP que hace la peticion HTTP).
+#[derive(Default)]
+pub struct UserAgentLog {
+    devices: HashMap<String, HashMap<String, AgentEntry>>,
+}
+
+impl UserAgentLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe_ip(&mut self, ip: &IpFrame, timestamp: u64) {
+        let Some((client, HttpMessage::Request { host, user_agent: Some(user_agent), .. })) = http_from_ip(ip) else {
+            return;
+        };
+        self.observe(&client, &user_agent, host.as_deref(), timestamp);
+    }
+
+    pub fn observe(&mut self, client: &str, user_agent: &str, host: Option<&str>, timestamp: u64) {
+        if !self.devices.contains_key(client) && self.devices.len() >= MAX_DEVICES {
+            return;
+        }
+        let agents = self.devices.entry(client.to_string()).or_default();
+        if !agents.contains_key(user_agent) && agents.len() >= MAX_AGENTS_PER_DEVICE {
+            // Se descarta el User-Agent visto hace mas tiempo.
+            if let Some(oldest) = agents.iter().min_by_key(|(_, e)| e.last_seen).map(|(ua, _)| ua.clone()) {
+                agents.remove(&oldest);
+            }
+        }
+        let entry = agents.entry(user_agent.to_string()).or_insert_with(|| AgentEntry {
+            hint: http::user_agent_hint(user_agent),
+            requests: 0,
+            first_seen: timestamp,
+            last_seen: timestamp,
+            hosts: Vec::new(),
+        });
+        entry.requests += 1;
+        entry.last_seen = entry.last_seen.max(timestamp);
+        if let Some(host) = host {
+            if !entry.hosts.iter().any(|h| h == host) {
+                if entry.hosts.len() >= MAX_HOSTS_PER_AGENT {
+                    entry.hosts.remove(0);
+                }
+                entry.hosts.push(host.to_string());
+            }
+        }
+    }
+
+    // `device_ip` limita a un equipo. User-Agents con mas peticiones primero.
+    pub fn snapshot(&self, device_ip: Option<&str>) -> Vec<DeviceUserAgents> {
+        let mut devices: Vec<DeviceUserAgents> = self
+            .devices
+            .iter()
+            .filter(|(ip, _)| device_ip.is_none_or(|wanted| wanted == ip.as_str()))
+            .map(|(ip, agents)| {
+                let mut agents: Vec<UserAgentStat> = agents
+                    .iter()
+                    .map(|(ua, e)| UserAgentStat {
+                        user_agent: ua.clone(),
+                        os: e.hint.os.clone(),
+                        client: e.hint.client.clone(),
+                        device_type: e.hint.device_type.clone(),
+                        requests: e.requests,
+                        first_seen: e.first_seen,
+                        last_seen: e.last_seen,
+                        hosts: e.hosts.clone(),
+                    })
+                    .collect();
+                agents.sort_by(|a, b| b.requests.cmp(&a.requests).then_with(|| a.user_agent.cmp(&b.user_agent)));
+                // El agente dominante manda: un PC con una libreria curl sigue siendo un PC.
+                DeviceUserAgents {
+                    ip: ip.clone(),
+                    os_hint: agents.iter().find_map(|a| a.os.clone()),
+                    device_hint: agents.iter().find_map(|a| a.device_type.clone()),
+                    agents,
+                }
+            })
+            .collect();
+        devices.sort_by(|a, b| a.ip.cmp(&b.ip));
+        devices
+    }
+}
+
+// Mensaje HTTP de un segmento TCP y la IP que lo envia.
+fn http_from_ip(ip: &IpFrame) -> Option<(String, HttpMessage)> {
+    let Transport::Tcp(tcp) = &ip.transport else {
+        return None;
+    };
+    let message = http::parse(tcp.payload())?;
+    Some((ip.src.to_string(), message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ip::parse_frame;
+    use super::*;
+
+    fn feed(log: &mut UserAgentLog, frame: &[u8], timestamp: u64) {
+        log.observe_ip(&parse_frame(frame).unwrap(), timestamp);
+    }
+
+    fn tcp_frame(src: [u8; 4], dst: [u8; 4], dst_port: u16, payload: &[u8]) -> Vec<u8> {
+        let total_len = (20 + 20 + payload.len()) as u16;
+        let mut frame = vec![0x02, 0, 0, 0, 0, 0x01, 0x02, 0, 0, 0, 0, 0x02, 0x08, 0x00];
+        frame.extend_from_slice(&[0x45, 0, (total_len >> 8) as u8, total_len as u8, 0, 0, 0, 0, 64, 6, 0, 0]);
+        frame.extend_from_slice(&src);
+        frame.extend_from_slice(&dst);
+        frame.extend_from_slice(&40000u16.to_be_bytes());
+        frame.extend_from_slice(&dst_port.to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 0, 0x50, 0x18, 0xFF, 0xFF, 0, 0, 0, 0]);
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    fn get(host: &str, user_agent: &str) -> Vec<u8> {
+        format!("GET / HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\n\r\n", host, user_agent).into_bytes()
+    }
+
+    #[test]
+    fn inventories_user_agents_per_requesting_device() {
+        let phone = [192, 168, 1, 30];
+        let server = [93, 184, 216, 34];
+        let safari = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_4 like Mac OS X) Safari/604.1";
+        let mut log = UserAgentLog::new();
+
+        feed(&mut log, &tcp_frame(phone, server, 80, &get("example.com", safari)), 1_000);
+        feed(&mut log, &tcp_frame(phone, server, 8080, &get("captive.apple.com", safari)), 2_000);
+        feed(&mut log, &tcp_frame(phone, server, 80, &get("example.com", "curl/8.4.0")), 3_000);
+        // Respuestas y trafico no HTTP no cuentan.
+        feed(&mut log, &tcp_frame(server, phone, 40000, b"HTTP/1.1 200 OK\r\nServer: nginx\r\n\r\n"), 3_100);
+        feed(&mut log, &tcp_frame(phone, server, 443, &[0x16, 0x03, 0x01, 0x00, 0x10]), 3_200);
+
+        let devices = log.snapshot(None);
+        assert_eq!(devices.len(), 1);
+        let device = &devices[0];
+        assert_eq!(device.ip, "192.168.1.30");
+        assert_eq!((device.os_hint.as_deref(), device.device_hint.as_deref()), (Some("iOS"), Some("PHONE")));
+        assert_eq!(device.agents.len(), 2);
+        assert_eq!(device.agents[0].user_agent, safari);
+        assert_eq!(device.agents[0].requests, 2);
+        assert_eq!((device.agents[0].first_seen, device.agents[0].last_seen), (1_000, 2_000));
+        assert_eq!(device.agents[0].hosts, vec!["example.com", "captive.apple.com"]);
+        assert_eq!(device.agents[1].client.as_deref(), Some("curl"));
+
+        assert!(log.snapshot(Some("93.184.216.34")).is_empty());
+    }
+}
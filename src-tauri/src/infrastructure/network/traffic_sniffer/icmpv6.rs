@@ -0,0 +1,289 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/icmpv6.rs
+
+use std::net::Ipv6Addr;
+
+// ICMPv6 (RFC 4443) y Neighbor Discovery (RFC 4861): el equivalente IPv6 de ARP y DHCP-lite.
+const TYPE_DEST_UNREACHABLE: u8 = 1;
+const TYPE_PACKET_TOO_BIG: u8 = 2;
+const TYPE_TIME_EXCEEDED: u8 = 3;
+const TYPE_PARAMETER_PROBLEM: u8 = 4;
+const TYPE_ECHO_REQUEST: u8 = 128;
+const TYPE_ECHO_REPLY: u8 = 129;
+const TYPE_MLD_QUERY: u8 = 130;
+const TYPE_MLD_REPORT: u8 = 131;
+const TYPE_MLD_DONE: u8 = 132;
+const TYPE_ROUTER_SOLICITATION: u8 = 133;
+const TYPE_ROUTER_ADVERTISEMENT: u8 = 134;
+const TYPE_NEIGHBOR_SOLICITATION: u8 = 135;
+const TYPE_NEIGHBOR_ADVERTISEMENT: u8 = 136;
+const TYPE_REDIRECT: u8 = 137;
+const TYPE_MLDV2_REPORT: u8 = 143;
+
+const OPT_SOURCE_LL: u8 = 1;
+const OPT_TARGET_LL: u8 = 2;
+const OPT_PREFIX_INFO: u8 = 3;
+// Un RA con decenas de prefijos no aporta mas en la linea de info.
+const MAX_PREFIXES: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Icmpv6Message {
+    EchoRequest {
+        id: u16,
+        seq: u16,
+    },
+    EchoReply {
+        id: u16,
+        seq: u16,
+    },
+    DestinationUnreachable {
+        code: u8,
+    },
+    PacketTooBig {
+        mtu: u32,
+    },
+    TimeExceeded,
+    ParameterProblem,
+    Mld,
+    RouterSolicitation {
+        source_mac: Option<String>,
+    },
+    RouterAdvertisement {
+        managed: bool,
+        other_config: bool,
+        lifetime_s: u16,
+        prefixes: Vec<String>,
+        source_mac: Option<String>,
+    },
+    NeighborSolicitation {
+        target: Ipv6Addr,
+        source_mac: Option<String>,
+    },
+    NeighborAdvertisement {
+        target: Ipv6Addr,
+        router: bool,
+        solicited: bool,
+        target_mac: Option<String>,
+    },
+    Redirect {
+        target: Ipv6Addr,
+        destination: Ipv6Addr,
+    },
+    Other {
+        icmp_type: u8,
+        code: u8,
+    },
+}
+
+impl Icmpv6Message {
+    // Neighbor Discovery: RS, RA, NS, NA y Redirect.
+    pub fn is_ndp(&self) -> bool {
+        matches!(
+            self,
+            Icmpv6Message::RouterSolicitation { .. }
+                | Icmpv6Message::RouterAdvertisement { .. }
+                | Icmpv6Message::NeighborSolicitation { .. }
+                | Icmpv6Message::NeighborAdvertisement { .. }
+                | Icmpv6Message::Redirect { .. }
+        )
+    }
+
+    pub fn describe(&self) -> String {
+        let with_mac = |text: String, mac: &Option<String>| match mac {
+            Some(mac) => format!("{} ({})", text, mac),
+            None => text,
+        };
+        match self {
+            Icmpv6Message::EchoRequest { id, seq } => format!("ICMPv6 Echo request id={} seq={}", id, seq),
+            Icmpv6Message::EchoReply { id, seq } => format!("ICMPv6 Echo reply id={} seq={}", id, seq),
+            Icmpv6Message::DestinationUnreachable { code } => {
+                let reason = match code {
+                    0 => "sin ruta",
+                    1 => "prohibido",
+                    3 => "direccion inalcanzable",
+                    4 => "puerto inalcanzable",
+                    _ => "otro",
+                };
+                format!("ICMPv6 Destino inalcanzable ({})", reason)
+            }
+            Icmpv6Message::PacketTooBig { mtu } => format!("ICMPv6 Paquete demasiado grande (MTU {})", mtu),
+            Icmpv6Message::TimeExceeded => "ICMPv6 Tiempo excedido".to_string(),
+            Icmpv6Message::ParameterProblem => "ICMPv6 Problema de parametros".to_string(),
+            Icmpv6Message::Mld => "ICMPv6 MLD (grupos multicast)".to_string(),
+            Icmpv6Message::RouterSolicitation { source_mac } => {
+                with_mac("NDP Router Solicitation".to_string(), source_mac)
+            }
+            Icmpv6Message::RouterAdvertisement { managed, other_config, lifetime_s, prefixes, source_mac } => {
+                let mut text = "NDP Router Advertisement".to_string();
+                if !prefixes.is_empty() {
+                    text.push_str(&format!(" prefijo {}", prefixes.join(", ")));
+                }
+                // M/O: el router pide DHCPv6 para direcciones (M) o solo para otros datos (O).
+                let flags: Vec<&str> = [(*managed, "M"), (*other_config, "O")]
+                    .into_iter()
+                    .filter_map(|(on, flag)| on.then_some(flag))
+                    .collect();
+                if !flags.is_empty() {
+                    text.push_str(&format!(" [{}]", flags.join("/")));
+                }
+                if *lifetime_s == 0 {
+                    text.push_str(" (no es router por defecto)");
+                }
+                with_mac(text, source_mac)
+            }
+            Icmpv6Message::NeighborSolicitation { target, source_mac } => {
+                with_mac(format!("NDP Neighbor Solicitation: quien tiene {}", target), source_mac)
+            }
+            Icmpv6Message::NeighborAdvertisement { target, router, solicited, target_mac } => {
+                let mut text = match target_mac {
+                    Some(mac) => format!("NDP Neighbor Advertisement: {} esta en {}", target, mac),
+                    None => format!("NDP Neighbor Advertisement: {}", target),
+                };
+                let flags: Vec<&str> = [(*router, "router"), (*solicited, "solicitado")]
+                    .into_iter()
+                    .filter_map(|(on, flag)| on.then_some(flag))
+                    .collect();
+                if !flags.is_empty() {
+                    text.push_str(&format!(" ({})", flags.join(", ")));
+                }
+                text
+            }
+            Icmpv6Message::Redirect { target, destination } => {
+                format!("NDP Redirect: {} via {}", destination, target)
+            }
+            Icmpv6Message::Other { icmp_type, code } => format!("ICMPv6 tipo {} codigo {}", icmp_type, code),
+        }
+    }
+}
+
+pub fn parse(payload: &[u8]) -> Option<Icmpv6Message> {
+    let icmp_type = *payload.first()?;
+    let code = *payload.get(1)?;
+    // Cabecera comun: tipo, codigo, checksum y 4 octetos que dependen del tipo.
+    let body = payload.get(4..)?;
+    let u16_at = |i: usize| Some(u16::from_be_bytes([*body.get(i)?, *body.get(i + 1)?]));
+    let address_at = |i: usize| body.get(i..i + 16).and_then(|b| <[u8; 16]>::try_from(b).ok()).map(Ipv6Addr::from);
+
+    let message = match icmp_type {
+        TYPE_ECHO_REQUEST => Icmpv6Message::EchoRequest { id: u16_at(0)?, seq: u16_at(2)? },
+        TYPE_ECHO_REPLY => Icmpv6Message::EchoReply { id: u16_at(0)?, seq: u16_at(2)? },
+        TYPE_DEST_UNREACHABLE => Icmpv6Message::DestinationUnreachable { code },
+        TYPE_PACKET_TOO_BIG => Icmpv6Message::PacketTooBig { mtu: u32::from_be_bytes(body.get(..4)?.try_into().ok()?) },
+        TYPE_TIME_EXCEEDED => Icmpv6Message::TimeExceeded,
+        TYPE_PARAMETER_PROBLEM => Icmpv6Message::ParameterProblem,
+        TYPE_MLD_QUERY | TYPE_MLD_REPORT | TYPE_MLD_DONE | TYPE_MLDV2_REPORT => Icmpv6Message::Mld,
+        TYPE_ROUTER_SOLICITATION => {
+            let options = parse_options(body.get(4..)?);
+            Icmpv6Message::RouterSolicitation { source_mac: options.source_mac }
+        }
+        TYPE_ROUTER_ADVERTISEMENT => {
+            let flags = *body.get(1)?;
+            let options = parse_options(body.get(12..)?);
+            Icmpv6Message::RouterAdvertisement {
+                managed: flags & 0x80 != 0,
+                other_config: flags & 0x40 != 0,
+                lifetime_s: u16_at(2)?,
+                prefixes: options.prefixes,
+                source_mac: options.source_mac,
+            }
+        }
+        TYPE_NEIGHBOR_SOLICITATION => {
+            let options = parse_options(body.get(20..)?);
+            Icmpv6Message::NeighborSolicitation { target: address_at(4)?, source_mac: options.source_mac }
+        }
+        TYPE_NEIGHBOR_ADVERTISEMENT => {
+            let flags = *body.first()?;
+            let options = parse_options(body.get(20..)?);
+            Icmpv6Message::NeighborAdvertisement {
+                target: address_at(4)?,
+                router: flags & 0x80 != 0,
+                solicited: flags & 0x40 != 0,
+                target_mac: options.target_mac,
+            }
+        }
+        TYPE_REDIRECT => Icmpv6Message::Redirect { target: address_at(4)?, destination: address_at(20)? },
+        _ => Icmpv6Message::Other { icmp_type, code },
+    };
+    Some(message)
+}
+
+#[derive(Default)]
+struct NdpOptions {
+    source_mac: Option<String>,
+    target_mac: Option<String>,
+    prefixes: Vec<String>,
+}
+
+// Opciones TLV: tipo, longitud en unidades de 8 octetos (0 invalida la lista entera).
+fn parse_options(mut data: &[u8]) -> NdpOptions {
+    let mut options = NdpOptions::default();
+    while data.len() >= 8 {
+        let len = data[1] as usize * 8;
+        if len == 0 || len > data.len() {
+            break;
+        }
+        let option = &data[..len];
+        match option[0] {
+            OPT_SOURCE_LL => options.source_mac = Some(format_mac(&option[2..8])),
+            OPT_TARGET_LL => options.target_mac = Some(format_mac(&option[2..8])),
+            OPT_PREFIX_INFO if len >= 32 && options.prefixes.len() < MAX_PREFIXES => {
+                let prefix_len = option[2];
+                if let Ok(prefix) = <[u8; 16]>::try_from(&option[16..32]) {
+                    options.prefixes.push(format!("{}/{}", Ipv6Addr::from(prefix), prefix_len));
+                }
+            }
+            _ => {}
+        }
+        data = &data[len..];
+    }
+    options
+}
+
+fn format_mac(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(":")
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn neighbor_advertisement(target: &str, mac: [u8; 6], router: bool) -> Vec<u8> {
+        let mut message = vec![TYPE_NEIGHBOR_ADVERTISEMENT, 0, 0, 0, if router { 0xE0 } else { 0x60 }, 0, 0, 0];
+        message.extend_from_slice(&target.parse::<Ipv6Addr>().unwrap().octets());
+        message.extend_from_slice(&[OPT_TARGET_LL, 1]);
+        message.extend_from_slice(&mac);
+        message
+    }
+
+    #[test]
+    fn decodes_neighbor_discovery() {
+        let na = parse(&neighbor_advertisement("fe80::1", [0xAA, 0xBB, 0xCC, 0, 0, 1], true)).unwrap();
+        assert!(na.is_ndp());
+        assert_eq!(na.describe(), "NDP Neighbor Advertisement: fe80::1 esta en aa:bb:cc:00:00:01 (router, solicitado)");
+
+        let mut ns = vec![TYPE_NEIGHBOR_SOLICITATION, 0, 0, 0, 0, 0, 0, 0];
+        ns.extend_from_slice(&"2001:db8::20".parse::<Ipv6Addr>().unwrap().octets());
+        ns.extend_from_slice(&[OPT_SOURCE_LL, 1, 0x02, 0, 0, 0, 0, 0x20]);
+        assert_eq!(
+            parse(&ns).unwrap().describe(),
+            "NDP Neighbor Solicitation: quien tiene 2001:db8::20 (02:00:00:00:00:20)"
+        );
+
+        // RA: M=0 O=1, vida 1800 s, un prefijo /64.
+        let mut ra = vec![TYPE_ROUTER_ADVERTISEMENT, 0, 0, 0, 64, 0x40, 0x07, 0x08, 0, 0, 0, 0, 0, 0, 0, 0];
+        let mut prefix = vec![OPT_PREFIX_INFO, 4, 64, 0xC0, 0, 0, 0x1C, 0x20, 0, 0, 0x0E, 0x10, 0, 0, 0, 0];
+        prefix.extend_from_slice(&"2001:db8:1::".parse::<Ipv6Addr>().unwrap().octets());
+        ra.extend_from_slice(&prefix);
+        let ra = parse(&ra).unwrap();
+        assert_eq!(ra.describe(), "NDP Router Advertisement prefijo 2001:db8:1::/64 [O]");
+
+        let echo = parse(&[TYPE_ECHO_REQUEST, 0, 0, 0, 0, 7, 0, 1]).unwrap();
+        assert!(!echo.is_ndp());
+        assert_eq!(echo.describe(), "ICMPv6 Echo request id=7 seq=1");
+
+        // Opcion con longitud 0 o truncada: se ignoran las opciones, no el mensaje.
+        let mut broken = neighbor_advertisement("fe80::1", [0; 6], false);
+        broken[25] = 0;
+        assert!(matches!(parse(&broken), Some(Icmpv6Message::NeighborAdvertisement { target_mac: None, .. })));
+        assert!(parse(&[TYPE_NEIGHBOR_SOLICITATION, 0, 0, 0, 0, 0]).is_none());
+    }
+}
@@ -0,0 +1,185 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/ip.rs
+
+use std::net::IpAddr;
+
+use pnet::packet::ethernet::{EtherType, EtherTypes, EthernetPacket};
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::udp::UdpPacket;
+
+// Vista comun IPv4/IPv6 de una trama Ethernet: el bucle de captura la construye una vez por trama
+// y la pasa al clasificador, el filtro, los flujos y los logs por equipo.
+pub const PROTO_ICMP: u8 = 1;
+pub const PROTO_TCP: u8 = 6;
+pub const PROTO_UDP: u8 = 17;
+pub const PROTO_ICMPV6: u8 = 58;
+
+// Cabeceras de extension IPv6 (RFC 8200 4 y registro IANA).
+const EXT_HOP_BY_HOP: u8 = 0;
+const EXT_ROUTING: u8 = 43;
+const EXT_FRAGMENT: u8 = 44;
+const EXT_AUTH: u8 = 51;
+const EXT_DEST_OPTS: u8 = 60;
+const EXT_MOBILITY: u8 = 135;
+const EXT_HIP: u8 = 139;
+const EXT_SHIM6: u8 = 140;
+// Una cadena mas larga que esto es anomala; se para sin recorrerla entera.
+const MAX_EXTENSION_HEADERS: usize = 8;
+const IPV6_HEADER_LEN: usize = 40;
+
+pub struct IpFrame<'a> {
+    pub src: IpAddr,
+    pub dst: IpAddr,
+    // Protocolo de transporte: en IPv6, la cabecera que sigue a las extensiones. Un fragmento
+    // que no es el primero se queda en 44 (Fragment): no lleva cabecera de transporte.
+    pub protocol: u8,
+    pub payload: &'a [u8],
+    // Longitud IP total (cabecera incluida), la que muestra la UI.
+    pub length: usize,
+    pub transport: Transport<'a>,
+}
+
+// Cabecera TCP/UDP ya leida; `Other` si es otro protocolo o viene truncada.
+pub enum Transport<'a> {
+    Tcp(TcpPacket<'a>),
+    Udp(UdpPacket<'a>),
+    Other,
+}
+
+impl<'a> Transport<'a> {
+    fn new(protocol: u8, payload: &'a [u8]) -> Self {
+        let transport = match protocol {
+            PROTO_TCP => TcpPacket::new(payload).map(Transport::Tcp),
+            PROTO_UDP => UdpPacket::new(payload).map(Transport::Udp),
+            _ => None,
+        };
+        transport.unwrap_or(Transport::Other)
+    }
+}
+
+impl IpFrame<'_> {
+    // Puertos de origen/destino si el paquete es TCP o UDP.
+    pub fn ports(&self) -> (Option<u16>, Option<u16>) {
+        match &self.transport {
+            Transport::Tcp(tcp) => (Some(tcp.get_source()), Some(tcp.get_destination())),
+            Transport::Udp(udp) => (Some(udp.get_source()), Some(udp.get_destination())),
+            Transport::Other => (None, None),
+        }
+    }
+}
+
+pub fn parse_frame(frame: &[u8]) -> Option<IpFrame<'_>> {
+    let eth = EthernetPacket::new(frame)?;
+    // Se recorta a mano: `payload()` de pnet queda atado al paquete, no a la trama.
+    let payload = frame.get(EthernetPacket::minimum_packet_size()..)?;
+    parse_payload(eth.get_ethertype().0, payload)
+}
+
+// IPv4/IPv6 tras una cabecera de enlace que ya dio el EtherType (Ethernet, Linux "cooked" SLL/SLL2).
+pub fn parse_payload(ethertype: u16, payload: &[u8]) -> Option<IpFrame<'_>> {
+    match EtherType(ethertype) {
+        EtherTypes::Ipv4 => parse_ipv4(payload),
+        EtherTypes::Ipv6 => parse_ipv6(payload),
+        _ => None,
+    }
+}
+
+fn parse_ipv4(data: &[u8]) -> Option<IpFrame<'_>> {
+    let ipv4 = Ipv4Packet::new(data)?;
+    let header_len = ipv4.get_header_length() as usize * 4;
+    // Relleno Ethernet fuera; una trama recortada (snaplen) se queda con lo que haya.
+    let end = (ipv4.get_total_length() as usize).min(data.len());
+    let protocol = ipv4.get_next_level_protocol().0;
+    let payload = data.get(header_len..end)?;
+    Some(IpFrame {
+        src: IpAddr::V4(ipv4.get_source()),
+        dst: IpAddr::V4(ipv4.get_destination()),
+        protocol,
+        payload,
+        length: ipv4.get_total_length() as usize,
+        transport: Transport::new(protocol, payload),
+    })
+}
+
+fn parse_ipv6(data: &[u8]) -> Option<IpFrame<'_>> {
+    let ipv6 = Ipv6Packet::new(data)?;
+    let payload_len = ipv6.get_payload_length() as usize;
+    let end = (IPV6_HEADER_LEN + payload_len).min(data.len());
+    let mut next = ipv6.get_next_header().0;
+    let mut rest = data.get(IPV6_HEADER_LEN..end)?;
+
+    for _ in 0..MAX_EXTENSION_HEADERS {
+        let header_len = match next {
+            // Siguiente cabecera + longitud en unidades de 8 octetos (sin contar los 8 primeros).
+            EXT_HOP_BY_HOP | EXT_ROUTING | EXT_DEST_OPTS | EXT_MOBILITY | EXT_HIP | EXT_SHIM6 => {
+                (*rest.get(1)? as usize + 1) * 8
+            }
+            // AH: longitud en unidades de 4 octetos, menos 2.
+            EXT_AUTH => (*rest.get(1)? as usize + 2) * 4,
+            EXT_FRAGMENT => {
+                let offset = u16::from_be_bytes([*rest.get(2)?, *rest.get(3)?]) >> 3;
+                if offset != 0 {
+                    break;
+                }
+                8
+            }
+            _ => break,
+        };
+        next = *rest.first()?;
+        rest = rest.get(header_len..)?;
+    }
+
+    Some(IpFrame {
+        src: IpAddr::V6(ipv6.get_source()),
+        dst: IpAddr::V6(ipv6.get_destination()),
+        protocol: next,
+        payload: rest,
+        length: IPV6_HEADER_LEN + payload_len,
+        transport: Transport::new(next, rest),
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    // Ethernet + IPv6 con las cabeceras de extension dadas ya serializadas en `body`.
+    pub fn ipv6_frame(src: &str, dst: &str, next_header: u8, body: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0x33, 0x33, 0, 0, 0, 1, 0x02, 0, 0, 0, 0, 0x02, 0x86, 0xDD];
+        frame.extend_from_slice(&[0x60, 0, 0, 0]);
+        frame.extend_from_slice(&(body.len() as u16).to_be_bytes());
+        frame.extend_from_slice(&[next_header, 255]);
+        frame.extend_from_slice(&src.parse::<std::net::Ipv6Addr>().unwrap().octets());
+        frame.extend_from_slice(&dst.parse::<std::net::Ipv6Addr>().unwrap().octets());
+        frame.extend_from_slice(body);
+        frame
+    }
+
+    #[test]
+    fn walks_the_ipv6_extension_header_chain() {
+        // Hop-by-Hop (8 octetos) -> Destination Options (16) -> UDP.
+        let mut body = vec![EXT_DEST_OPTS, 0, 1, 4, 0, 0, 0, 0];
+        body.extend_from_slice(&[PROTO_UDP, 1, 1, 12, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        body.extend_from_slice(&[0xD4, 0x31, 0, 53, 0, 8, 0, 0]);
+        let frame = ipv6_frame("2001:db8::10", "2001:db8::1", EXT_HOP_BY_HOP, &body);
+        let ip = parse_frame(&frame).unwrap();
+        assert!(ip.src.is_ipv6());
+        assert_eq!(ip.src.to_string(), "2001:db8::10");
+        assert_eq!((ip.protocol, ip.length), (PROTO_UDP, 40 + body.len()));
+        assert_eq!(&ip.payload[2..4], &[0, 53]);
+        assert_eq!(ip.ports(), (Some(0xD431), Some(53)));
+
+        // Primer fragmento: se sigue hasta TCP. Fragmento posterior: sin transporte.
+        let first = [PROTO_TCP, 0, 0x00, 0x01, 0, 0, 0, 7, 0xAA];
+        let frame = ipv6_frame("fe80::1", "fe80::2", EXT_FRAGMENT, &first);
+        let ip = parse_frame(&frame).unwrap();
+        assert_eq!((ip.protocol, ip.payload), (PROTO_TCP, &[0xAA][..]));
+        let later = [PROTO_TCP, 0, 0x05, 0x00, 0, 0, 0, 7, 0xAA];
+        let frame = ipv6_frame("fe80::1", "fe80::2", EXT_FRAGMENT, &later);
+        assert_eq!(parse_frame(&frame).unwrap().protocol, EXT_FRAGMENT);
+
+        // Cabecera de extension truncada: trama descartada, sin panics.
+        assert!(parse_frame(&ipv6_frame("fe80::1", "fe80::2", EXT_ROUTING, &[PROTO_UDP, 3, 0])).is_none());
+    }
+}
@@ -41,6 +41,16 @@ fn get_first_ipv4(interface: &NetworkInterface) -> Option<String> {
         .map(|ip| ip.ip().to_string())
 }
 
+// Todas las IPv6 de la interfaz: un equipo suele tener link-local, global y temporales a la vez.
+pub fn ipv6_addresses(interface: &NetworkInterface) -> Vec<String> {
+    interface
+        .ips
+        .iter()
+        .filter(|ip| ip.is_ipv6())
+        .map(|ip| ip.ip().to_string())
+        .collect()
+}
+
 pub fn open_ethernet_rx(interface: &NetworkInterface) -> Result<Box<dyn DataLinkReceiver>, String> {
     let mut config = datalink::Config::default();
     // Intentamos evitar bloqueos largos al parar el sniffer.
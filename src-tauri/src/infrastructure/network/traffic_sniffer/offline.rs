@@ -0,0 +1,262 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/offline.rs
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use crate::domain::entities::{CaptureAnalysis, CaptureProtocolStat, CaptureTalkerStat, TrafficPacket};
+
+use super::filter::CaptureFilter;
+use super::flows::FlowTable;
+use super::ip;
+use super::protocol;
+use super::tls_log::TlsLog;
+use super::map_packet;
+use super::pcap_reader::{CaptureReader, LINKTYPE_ETHERNET, LINKTYPE_LINUX_SLL, LINKTYPE_LINUX_SLL2};
+
+// La UI pinta la lista como el monitor en vivo; el resto queda en los agregados.
+const MAX_PACKETS_RETURNED: usize = 5_000;
+const MAX_TOP_TALKERS: usize = 20;
+const MAX_FLOWS_RETURNED: usize = 5_000;
+// Cada cuanto (tiempo del fichero) se caducan flujos inactivos.
+const FLOW_EXPIRY_STEP_MS: u64 = 1_000;
+
+#[derive(Default)]
+struct Counter {
+    packets: u64,
+    bytes: u64,
+}
+
+impl Counter {
+    fn add(&mut self, bytes: usize) {
+        self.packets += 1;
+        self.bytes += bytes as u64;
+    }
+}
+
+pub fn analyze<R: Read>(
+    mut reader: CaptureReader<R>,
+    focus_ip: Option<&str>,
+    filter: Option<&CaptureFilter>,
+    file_name: String,
+) -> CaptureAnalysis {
+    let mut analysis = CaptureAnalysis {
+        file_name,
+        format: reader.format().as_str().to_string(),
+        ..Default::default()
+    };
+    let mut protocols: HashMap<(String, String), Counter> = HashMap::new();
+    let mut talkers: HashMap<String, Counter> = HashMap::new();
+    let mut flows = FlowTable::with_history(MAX_FLOWS_RETURNED);
+    let mut last_expiry = 0u64;
+    // Solo para etiquetar los ClientHello igual que en vivo; el log por equipo no se devuelve.
+    let mut tls = TlsLog::new();
+
+    loop {
+        let frame = match reader.next_frame() {
+            Ok(Some(frame)) => frame,
+            Ok(None) => break,
+            Err(e) => {
+                println!("⚠️ [SNIFFER] Captura leida solo en parte: {e}");
+                analysis.read_error = Some(e);
+                break;
+            }
+        };
+        analysis.total_frames += 1;
+
+        // Una sola lectura de enlace/IP por trama, compartida con TLS y flujos.
+        let ip_frame = parse_link_frame(frame.linktype, &frame.data);
+        let decoded = analysis.decoded_packets as usize;
+        let packet = ip_frame
+            .as_ref()
+            .and_then(|ip| map_packet(ip, protocol::analyze(ip), None, filter, decoded, frame.timestamp_us / 1000))
+            .filter(|p| focus_ip.is_none_or(|ip| p.source_ip == ip || p.destination_ip == ip));
+        let (Some(ip_frame), Some(mut packet)) = (ip_frame, packet) else {
+            analysis.skipped_frames += 1;
+            continue;
+        };
+        if let Some(hello) = tls.observe_ip(&ip_frame, packet.timestamp) {
+            packet.info = hello.describe();
+        }
+
+        if packet.timestamp.saturating_sub(last_expiry) >= FLOW_EXPIRY_STEP_MS {
+            flows.expire(packet.timestamp);
+            last_expiry = packet.timestamp;
+        }
+        flows.observe_ip(&ip_frame, packet.timestamp, &packet.info);
+
+        analysis.decoded_packets += 1;
+        analysis.first_timestamp.get_or_insert(packet.timestamp);
+        analysis.last_timestamp = Some(packet.timestamp);
+        protocols
+            .entry((packet.protocol.clone(), packet.info.clone()))
+            .or_default()
+            .add(packet.length);
+        talkers.entry(packet.source_ip.clone()).or_default().add(packet.length);
+        talkers.entry(packet.destination_ip.clone()).or_default().add(packet.length);
+        push_packet(&mut analysis, packet);
+    }
+
+    analysis.protocols = protocols
+        .into_iter()
+        .map(|((protocol, info), c)| CaptureProtocolStat {
+            protocol,
+            info,
+            packets: c.packets,
+            bytes: c.bytes,
+        })
+        .collect();
+    analysis
+        .protocols
+        .sort_by(|a, b| b.packets.cmp(&a.packets).then_with(|| a.protocol.cmp(&b.protocol)));
+
+    analysis.top_talkers = talkers
+        .into_iter()
+        .map(|(ip, c)| CaptureTalkerStat {
+            ip,
+            packets: c.packets,
+            bytes: c.bytes,
+        })
+        .collect();
+    analysis
+        .top_talkers
+        .sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.ip.cmp(&b.ip)));
+    analysis.top_talkers.truncate(MAX_TOP_TALKERS);
+
+    flows.close_all();
+    analysis.flows = flows.closed_flows().cloned().collect();
+    analysis.flows.sort_by(|a, b| a.first_seen.cmp(&b.first_seen).then_with(|| a.uid.cmp(&b.uid)));
+    analysis
+}
+
+// Ethernet o Linux "cooked"; el resto de tipos de enlace cuenta como tramas saltadas.
+fn parse_link_frame(linktype: u16, data: &[u8]) -> Option<ip::IpFrame<'_>> {
+    let ethertype_at = |offset: usize| Some(u16::from_be_bytes([*data.get(offset)?, *data.get(offset + 1)?]));
+    match linktype {
+        LINKTYPE_ETHERNET => ip::parse_frame(data),
+        // Tipo de paquete, ARPHRD, longitud y direccion (8) y, al final, el protocolo.
+        LINKTYPE_LINUX_SLL => ip::parse_payload(ethertype_at(14)?, data.get(16..)?),
+        // Protocolo primero; despues reservado, interfaz, ARPHRD, tipo, longitud y direccion.
+        LINKTYPE_LINUX_SLL2 => ip::parse_payload(ethertype_at(0)?, data.get(20..)?),
+        _ => None,
+    }
+}
+
+fn push_packet(analysis: &mut CaptureAnalysis, packet: TrafficPacket) {
+    if analysis.packets.len() < MAX_PACKETS_RETURNED {
+        analysis.packets.push(packet);
+    } else {
+        analysis.packets_truncated = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::pcapng::{PcapngWriter, DEFAULT_SNAPLEN};
+    use super::*;
+
+    // Ethernet + IPv4 (sin opciones) + UDP con puertos dados.
+    fn udp_frame(src: [u8; 4], dst: [u8; 4], src_port: u16, dst_port: u16) -> Vec<u8> {
+        let mut frame = vec![0x02, 0, 0, 0, 0, 0x01, 0x02, 0, 0, 0, 0, 0x02, 0x08, 0x00];
+        let total_len: u16 = 20 + 8 + 4;
+        frame.extend_from_slice(&[0x45, 0, (total_len >> 8) as u8, total_len as u8, 0, 0, 0, 0, 64, 17, 0, 0]);
+        frame.extend_from_slice(&src);
+        frame.extend_from_slice(&dst);
+        frame.extend_from_slice(&src_port.to_be_bytes());
+        frame.extend_from_slice(&dst_port.to_be_bytes());
+        frame.extend_from_slice(&[0, 12, 0, 0, 0xDE, 0xAD, 0xBE, 0xEF]);
+        frame
+    }
+
+    // pcap clasico (microsegundos, little endian) con el tipo de enlace dado.
+    fn classic_pcap(linktype: u16, frames: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = vec![0xD4, 0xC3, 0xB2, 0xA1, 2, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xFF, 0xFF, 0, 0];
+        out.extend_from_slice(&(linktype as u32).to_le_bytes());
+        for (i, frame) in frames.iter().enumerate() {
+            out.extend_from_slice(&(1_600_000_000 + i as u32).to_le_bytes());
+            out.extend_from_slice(&0u32.to_le_bytes());
+            out.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            out.extend_from_slice(frame);
+        }
+        out
+    }
+
+    fn capture(frames: &[(u64, Vec<u8>)]) -> Vec<u8> {
+        let mut writer = PcapngWriter::new(Vec::new(), "eth0", DEFAULT_SNAPLEN).unwrap();
+        for (ts, frame) in frames {
+            writer.write_packet(*ts, frame).unwrap();
+        }
+        writer.get_ref().clone()
+    }
+
+    #[test]
+    fn summarises_frames_with_original_timestamps() {
+        let client = [192, 168, 1, 20];
+        let bytes = capture(&[
+            (1_600_000_000_000_000, udp_frame(client, [192, 168, 1, 1], 5353, 53)),
+            (1_600_000_000_250_000, udp_frame(client, [192, 168, 1, 1], 5354, 53)),
+            (1_600_000_001_000_000, udp_frame([192, 168, 1, 30], [192, 168, 1, 1], 40000, 123)),
+            // ARP: no es IPv4, cuenta como saltada.
+            (1_600_000_002_000_000, vec![0xFF; 12].into_iter().chain([0x08, 0x06, 0, 1]).collect()),
+        ]);
+
+        let reader = CaptureReader::open(bytes.as_slice()).unwrap();
+        let analysis = analyze(reader, None, None, "cliente.pcapng".to_string());
+        assert_eq!(analysis.format, "pcapng");
+        assert_eq!((analysis.total_frames, analysis.decoded_packets, analysis.skipped_frames), (4, 3, 1));
+        assert_eq!(analysis.first_timestamp, Some(1_600_000_000_000));
+        assert_eq!(analysis.last_timestamp, Some(1_600_000_001_000));
+        assert_eq!(analysis.packets[1].timestamp, 1_600_000_000_250);
+        assert!(analysis.packets.iter().all(|p| !p.is_intercepted));
+
+        assert_eq!(analysis.protocols[0].info, "DNS");
+        assert_eq!(analysis.protocols[0].packets, 2);
+        assert_eq!(analysis.protocols[1].info, "NTP");
+        assert_eq!(analysis.top_talkers[0].ip, "192.168.1.1");
+        assert_eq!(analysis.top_talkers[0].packets, 3);
+        assert!(analysis.read_error.is_none());
+        // Cada consulta DNS usa otro puerto de origen: dos conversaciones, mas la de NTP.
+        assert_eq!(analysis.flows.len(), 3);
+        assert!(analysis.flows.iter().all(|f| !f.active && f.protocol == "udp" && f.conn_state == "S0"));
+        assert_eq!(analysis.flows[0].orig_port, Some(5353));
+
+        let reader = CaptureReader::open(bytes.as_slice()).unwrap();
+        let focused = analyze(reader, Some("192.168.1.30"), None, String::new());
+        assert_eq!(focused.decoded_packets, 1);
+        assert_eq!(focused.packets[0].info, "NTP");
+
+        let ntp = CaptureFilter::compile("udp dst port 123").unwrap();
+        let reader = CaptureReader::open(bytes.as_slice()).unwrap();
+        let filtered = analyze(reader, None, Some(&ntp), String::new());
+        assert_eq!((filtered.decoded_packets, filtered.skipped_frames), (1, 3));
+
+        // Captura cortada: se conserva lo leido y se informa del error.
+        let reader = CaptureReader::open(&bytes[..bytes.len() - 6]).unwrap();
+        let partial = analyze(reader, None, None, String::new());
+        assert_eq!(partial.decoded_packets, 3);
+        assert!(partial.read_error.is_some());
+    }
+
+    #[test]
+    fn decodes_linux_cooked_captures() {
+        // La cabecera IP de `udp_frame` empieza tras los 14 octetos de Ethernet.
+        let ip_packet = udp_frame([10, 0, 0, 5], [10, 0, 0, 1], 40000, 53)[14..].to_vec();
+        let mut sll = vec![0, 4, 0, 1, 0, 6, 2, 0, 0, 0, 0, 1, 0, 0, 0x08, 0x00];
+        sll.extend_from_slice(&ip_packet);
+        let mut sll2 = vec![0x08, 0x00, 0, 0, 0, 0, 0, 2, 0, 1, 4, 6, 2, 0, 0, 0, 0, 1, 0, 0];
+        sll2.extend_from_slice(&ip_packet);
+
+        for (linktype, frame) in [(LINKTYPE_LINUX_SLL, sll), (LINKTYPE_LINUX_SLL2, sll2)] {
+            let bytes = classic_pcap(linktype, &[frame]);
+            let analysis = analyze(CaptureReader::open(bytes.as_slice()).unwrap(), None, None, String::new());
+            assert_eq!((analysis.decoded_packets, analysis.skipped_frames), (1, 0), "linktype {linktype}");
+            assert_eq!(analysis.packets[0].source_ip, "10.0.0.5");
+            assert_eq!(analysis.packets[0].info, "DNS");
+        }
+
+        // Un enlace desconocido (802.11 + radiotap) sigue contando como saltado.
+        let bytes = classic_pcap(127, &[vec![0; 40]]);
+        let analysis = analyze(CaptureReader::open(bytes.as_slice()).unwrap(), None, None, String::new());
+        assert_eq!((analysis.decoded_packets, analysis.skipped_frames), (0, 1));
+    }
+}
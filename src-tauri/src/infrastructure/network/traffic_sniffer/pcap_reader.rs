@@ -0,0 +1,387 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/pcap_reader.rs
+
+use std::io::{self, Read};
+
+// Lector en streaming de pcap clasico (micro/nanosegundos, ambos endianness) y PCAPNG
+// (SHB/IDB/EPB/SPB/PB, varias secciones e interfaces). Solo entrega tramas; el analisis
+// lo hace el mismo pipeline que el monitor en vivo.
+pub const LINKTYPE_ETHERNET: u16 = 1;
+// Linux "cooked" (`tcpdump -i any`): v1 con cabecera de 16 octetos, v2 con 20.
+pub const LINKTYPE_LINUX_SLL: u16 = 113;
+pub const LINKTYPE_LINUX_SLL2: u16 = 276;
+
+const PCAP_MAGIC_MICROS: u32 = 0xA1B2_C3D4;
+const PCAP_MAGIC_NANOS: u32 = 0xA1B2_3C4D;
+const PCAPNG_SHB: u32 = 0x0A0D_0D0A;
+const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+const BLOCK_IDB: u32 = 1;
+const BLOCK_PB: u32 = 2;
+const BLOCK_SPB: u32 = 3;
+const BLOCK_EPB: u32 = 6;
+const IF_TSRESOL: u16 = 9;
+// Ninguna trama Ethernet razonable se acerca; protege de ficheros corruptos.
+const MAX_BLOCK_LEN: usize = 16 * 1024 * 1024;
+const MICROS_PER_SEC: u64 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureFormat {
+    Pcap,
+    Pcapng,
+}
+
+impl CaptureFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pcap => "pcap",
+            Self::Pcapng => "pcapng",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedFrame {
+    // Microsegundos desde epoch (0 si el bloque no lleva marca, ej: SPB).
+    pub timestamp_us: u64,
+    pub linktype: u16,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Copy)]
+struct Interface {
+    linktype: u16,
+    // Unidades de la marca de tiempo por segundo (if_tsresol; por defecto microsegundos).
+    units_per_sec: u64,
+}
+
+pub struct CaptureReader<R: Read> {
+    input: R,
+    format: CaptureFormat,
+    big_endian: bool,
+    // pcap clasico: cabecera global unica.
+    pcap_interface: Interface,
+    // PCAPNG: interfaces de la seccion en curso (el id del bloque es el indice).
+    interfaces: Vec<Interface>,
+}
+
+impl<R: Read> CaptureReader<R> {
+    pub fn open(mut input: R) -> Result<Self, String> {
+        let mut magic = [0u8; 4];
+        input
+            .read_exact(&mut magic)
+            .map_err(|_| "Fichero vacio o truncado".to_string())?;
+
+        let mut reader = Self {
+            input,
+            format: CaptureFormat::Pcap,
+            big_endian: false,
+            pcap_interface: Interface {
+                linktype: LINKTYPE_ETHERNET,
+                units_per_sec: MICROS_PER_SEC,
+            },
+            interfaces: Vec::new(),
+        };
+
+        if u32::from_le_bytes(magic) == PCAPNG_SHB {
+            reader.format = CaptureFormat::Pcapng;
+            reader.read_section_header()?;
+            return Ok(reader);
+        }
+
+        let (big_endian, units_per_sec) = match (u32::from_le_bytes(magic), u32::from_be_bytes(magic)) {
+            (PCAP_MAGIC_MICROS, _) => (false, MICROS_PER_SEC),
+            (PCAP_MAGIC_NANOS, _) => (false, 1_000_000_000),
+            (_, PCAP_MAGIC_MICROS) => (true, MICROS_PER_SEC),
+            (_, PCAP_MAGIC_NANOS) => (true, 1_000_000_000),
+            _ => return Err("Formato no reconocido (ni pcap ni pcapng)".to_string()),
+        };
+        reader.big_endian = big_endian;
+        // version(2+2) + thiszone(4) + sigfigs(4) + snaplen(4) + linktype(4).
+        let header = reader.read_vec(20)?;
+        reader.pcap_interface = Interface {
+            linktype: reader.u32_at(&header, 16) as u16,
+            units_per_sec,
+        };
+        Ok(reader)
+    }
+
+    pub fn format(&self) -> CaptureFormat {
+        self.format
+    }
+
+    pub fn next_frame(&mut self) -> Result<Option<CapturedFrame>, String> {
+        match self.format {
+            CaptureFormat::Pcap => self.next_pcap_record(),
+            CaptureFormat::Pcapng => self.next_pcapng_packet(),
+        }
+    }
+
+    fn next_pcap_record(&mut self) -> Result<Option<CapturedFrame>, String> {
+        let mut header = [0u8; 16];
+        if !self.read_or_eof(&mut header)? {
+            return Ok(None);
+        }
+        let seconds = self.u32_at(&header, 0) as u64;
+        let fraction = self.u32_at(&header, 4) as u64;
+        let captured = self.u32_at(&header, 8) as usize;
+        let data = self.read_vec(captured)?;
+        let Interface { linktype, units_per_sec } = self.pcap_interface;
+        Ok(Some(CapturedFrame {
+            timestamp_us: seconds * MICROS_PER_SEC + fraction * MICROS_PER_SEC / units_per_sec,
+            linktype,
+            data,
+        }))
+    }
+
+    fn next_pcapng_packet(&mut self) -> Result<Option<CapturedFrame>, String> {
+        loop {
+            let mut block_type = [0u8; 4];
+            if !self.read_or_eof(&mut block_type)? {
+                return Ok(None);
+            }
+            // El tipo SHB es palindromo: se reconoce antes de saber el endianness de la nueva seccion.
+            if u32::from_le_bytes(block_type) == PCAPNG_SHB {
+                self.read_section_header()?;
+                continue;
+            }
+            let block_type = self.u32_at(&block_type, 0);
+            let body = self.read_block_body()?;
+            match block_type {
+                BLOCK_IDB => self.interfaces.push(self.parse_interface(&body)?),
+                BLOCK_EPB => return self.enhanced_packet(&body).map(Some),
+                BLOCK_SPB => return self.simple_packet(&body).map(Some),
+                BLOCK_PB => return self.obsolete_packet(&body).map(Some),
+                // NRB, ISB, DSB y bloques propietarios: no aportan tramas.
+                _ => {}
+            }
+        }
+    }
+
+    // Tras el tipo: longitud + byte-order magic fijan el endianness de toda la seccion.
+    fn read_section_header(&mut self) -> Result<(), String> {
+        let head = self.read_vec(8)?;
+        let magic = [head[4], head[5], head[6], head[7]];
+        self.big_endian = match (u32::from_le_bytes(magic), u32::from_be_bytes(magic)) {
+            (PCAPNG_BYTE_ORDER_MAGIC, _) => false,
+            (_, PCAPNG_BYTE_ORDER_MAGIC) => true,
+            _ => return Err("Cabecera de seccion PCAPNG invalida".to_string()),
+        };
+        let total = self.u32_at(&head, 0) as usize;
+        if !(28..=MAX_BLOCK_LEN).contains(&total) || !total.is_multiple_of(4) {
+            return Err(format!("Longitud de SHB invalida: {}", total));
+        }
+        // Ya leidos: tipo + longitud + magic.
+        self.read_vec(total - 12)?;
+        self.interfaces.clear();
+        Ok(())
+    }
+
+    // Cuerpo del bloque sin la longitud final repetida.
+    fn read_block_body(&mut self) -> Result<Vec<u8>, String> {
+        let mut len = [0u8; 4];
+        self.input.read_exact(&mut len).map_err(truncated)?;
+        let total = self.u32_at(&len, 0) as usize;
+        if !(12..=MAX_BLOCK_LEN).contains(&total) {
+            return Err(format!("Longitud de bloque PCAPNG invalida: {}", total));
+        }
+        let mut body = self.read_vec(total - 8)?;
+        body.truncate(total - 12);
+        Ok(body)
+    }
+
+    fn parse_interface(&self, body: &[u8]) -> Result<Interface, String> {
+        if body.len() < 8 {
+            return Err("IDB truncado".to_string());
+        }
+        let mut interface = Interface {
+            linktype: self.u16_at(body, 0),
+            units_per_sec: MICROS_PER_SEC,
+        };
+        let mut offset = 8;
+        while offset + 4 <= body.len() {
+            let code = self.u16_at(body, offset);
+            let len = self.u16_at(body, offset + 2) as usize;
+            let value = body.get(offset + 4..offset + 4 + len).ok_or("Opcion de IDB truncada")?;
+            if code == 0 {
+                break;
+            }
+            if code == IF_TSRESOL && len == 1 {
+                // Bit alto a 0: potencia de 10; a 1: potencia de 2.
+                let exp = (value[0] & 0x7F) as u32;
+                interface.units_per_sec = if value[0] & 0x80 == 0 {
+                    10u64.checked_pow(exp)
+                } else {
+                    1u64.checked_shl(exp)
+                }
+                .filter(|units| *units > 0)
+                .ok_or("if_tsresol fuera de rango")?;
+            }
+            offset += 4 + ((len + 3) & !3);
+        }
+        Ok(interface)
+    }
+
+    fn enhanced_packet(&self, body: &[u8]) -> Result<CapturedFrame, String> {
+        if body.len() < 20 {
+            return Err("EPB truncado".to_string());
+        }
+        let interface = self.interface(self.u32_at(body, 0) as usize)?;
+        let raw_ts = ((self.u32_at(body, 4) as u64) << 32) | self.u32_at(body, 8) as u64;
+        let captured = self.u32_at(body, 12) as usize;
+        let data = body.get(20..20 + captured).ok_or("EPB con longitud capturada invalida")?;
+        Ok(CapturedFrame {
+            timestamp_us: scale_to_micros(raw_ts, interface.units_per_sec),
+            linktype: interface.linktype,
+            data: data.to_vec(),
+        })
+    }
+
+    fn simple_packet(&self, body: &[u8]) -> Result<CapturedFrame, String> {
+        if body.len() < 4 {
+            return Err("SPB truncado".to_string());
+        }
+        let interface = self.interface(0)?;
+        let original = self.u32_at(body, 0) as usize;
+        let data = &body[4..];
+        Ok(CapturedFrame {
+            timestamp_us: 0,
+            linktype: interface.linktype,
+            data: data[..original.min(data.len())].to_vec(),
+        })
+    }
+
+    fn obsolete_packet(&self, body: &[u8]) -> Result<CapturedFrame, String> {
+        if body.len() < 20 {
+            return Err("PB truncado".to_string());
+        }
+        let interface = self.interface(self.u16_at(body, 0) as usize)?;
+        let raw_ts = ((self.u32_at(body, 4) as u64) << 32) | self.u32_at(body, 8) as u64;
+        let captured = self.u32_at(body, 12) as usize;
+        let data = body.get(20..20 + captured).ok_or("PB con longitud capturada invalida")?;
+        Ok(CapturedFrame {
+            timestamp_us: scale_to_micros(raw_ts, interface.units_per_sec),
+            linktype: interface.linktype,
+            data: data.to_vec(),
+        })
+    }
+
+    fn interface(&self, id: usize) -> Result<Interface, String> {
+        self.interfaces
+            .get(id)
+            .copied()
+            .ok_or_else(|| format!("Paquete de una interfaz no declarada ({})", id))
+    }
+
+    fn read_vec(&mut self, len: usize) -> Result<Vec<u8>, String> {
+        if len > MAX_BLOCK_LEN {
+            return Err(format!("Registro demasiado grande: {} bytes", len));
+        }
+        let mut buf = vec![0u8; len];
+        self.input.read_exact(&mut buf).map_err(truncated)?;
+        Ok(buf)
+    }
+
+    // false si el fichero termina limpio justo antes del siguiente registro.
+    fn read_or_eof(&mut self, buf: &mut [u8]) -> Result<bool, String> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match self.input.read(&mut buf[filled..]) {
+                Ok(0) if filled == 0 => return Ok(false),
+                Ok(0) => return Err("Fichero truncado".to_string()),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+        Ok(true)
+    }
+
+    fn u16_at(&self, bytes: &[u8], offset: usize) -> u16 {
+        let raw = [bytes[offset], bytes[offset + 1]];
+        if self.big_endian {
+            u16::from_be_bytes(raw)
+        } else {
+            u16::from_le_bytes(raw)
+        }
+    }
+
+    fn u32_at(&self, bytes: &[u8], offset: usize) -> u32 {
+        let raw = [bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]];
+        if self.big_endian {
+            u32::from_be_bytes(raw)
+        } else {
+            u32::from_le_bytes(raw)
+        }
+    }
+}
+
+fn scale_to_micros(raw: u64, units_per_sec: u64) -> u64 {
+    (raw as u128 * MICROS_PER_SEC as u128 / units_per_sec as u128) as u64
+}
+
+fn truncated(e: io::Error) -> String {
+    if e.kind() == io::ErrorKind::UnexpectedEof {
+        "Fichero truncado".to_string()
+    } else {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::pcapng::{PcapngWriter, DEFAULT_SNAPLEN};
+    use super::*;
+
+    fn read_all(bytes: &[u8]) -> (CaptureFormat, Vec<CapturedFrame>) {
+        let mut reader = CaptureReader::open(bytes).unwrap();
+        let mut frames = Vec::new();
+        while let Some(frame) = reader.next_frame().unwrap() {
+            frames.push(frame);
+        }
+        (reader.format(), frames)
+    }
+
+    #[test]
+    fn reads_back_pcapng_written_by_the_recorder() {
+        let mut writer = PcapngWriter::new(Vec::new(), "eth0", DEFAULT_SNAPLEN).unwrap();
+        writer.write_packet(1_700_000_000_000_001, &[1, 2, 3, 4, 5]).unwrap();
+        writer.write_packet(1_700_000_000_500_000, &[9; 64]).unwrap();
+        let bytes = writer.get_ref().clone();
+
+        let (format, frames) = read_all(&bytes);
+        assert_eq!(format, CaptureFormat::Pcapng);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].timestamp_us, 1_700_000_000_000_001);
+        assert_eq!(frames[0].data, vec![1, 2, 3, 4, 5]);
+        assert_eq!(frames[1].linktype, LINKTYPE_ETHERNET);
+        assert_eq!(frames[1].data.len(), 64);
+
+        // Fichero cortado a mitad de un bloque: error, no una trama a medias.
+        let mut reader = CaptureReader::open(&bytes[..bytes.len() - 10]).unwrap();
+        assert!(reader.next_frame().unwrap().is_some());
+        assert!(reader.next_frame().is_err());
+    }
+
+    #[test]
+    fn reads_big_endian_nanosecond_pcap() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&PCAP_MAGIC_NANOS.to_be_bytes());
+        bytes.extend_from_slice(&[0, 2, 0, 4]);
+        bytes.extend_from_slice(&[0; 8]);
+        bytes.extend_from_slice(&65_535u32.to_be_bytes());
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        // Registro: 10 s + 250 ms (en ns), 3 bytes capturados de 60.
+        bytes.extend_from_slice(&10u32.to_be_bytes());
+        bytes.extend_from_slice(&250_000_000u32.to_be_bytes());
+        bytes.extend_from_slice(&3u32.to_be_bytes());
+        bytes.extend_from_slice(&60u32.to_be_bytes());
+        bytes.extend_from_slice(&[0xAA, 0xBB, 0xCC]);
+
+        let (format, frames) = read_all(&bytes);
+        assert_eq!(format, CaptureFormat::Pcap);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].timestamp_us, 10_250_000);
+        assert_eq!(frames[0].data, vec![0xAA, 0xBB, 0xCC]);
+
+        assert!(CaptureReader::open(&b"GIF89a.."[..]).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/pcapng.rs
+
+use std::io::{self, Write};
+
+// Escritor PCAPNG minimo (SHB + IDB + EPB): lo justo para abrir la sesion en Wireshark/tshark.
+// Todo en little-endian; el "byte-order magic" del SHB lo indica al lector.
+pub const LINKTYPE_ETHERNET: u16 = 1;
+pub const DEFAULT_SNAPLEN: u32 = 65_535;
+
+const BLOCK_SHB: u32 = 0x0A0D_0D0A;
+const BLOCK_IDB: u32 = 0x0000_0001;
+const BLOCK_EPB: u32 = 0x0000_0006;
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+const OPT_END: u16 = 0;
+const SHB_USERAPPL: u16 = 4;
+const IF_NAME: u16 = 2;
+const IF_TSRESOL: u16 = 9;
+// 10^-6: marcas de tiempo en microsegundos (valor por defecto, pero explicito para otros lectores).
+const TSRESOL_MICROS: u8 = 6;
+
+pub struct PcapngWriter<W: Write> {
+    out: W,
+    snaplen: u32,
+    bytes_written: u64,
+}
+
+impl<W: Write> PcapngWriter<W> {
+    // Escribe la cabecera de seccion y la descripcion de la unica interfaz (id 0).
+    pub fn new(out: W, interface_name: &str, snaplen: u32) -> io::Result<Self> {
+        let mut writer = Self {
+            out,
+            snaplen,
+            bytes_written: 0,
+        };
+        writer.write_block(BLOCK_SHB, &section_header_body())?;
+        writer.write_block(BLOCK_IDB, &interface_description_body(interface_name, snaplen))?;
+        Ok(writer)
+    }
+
+    // `timestamp_us`: microsegundos desde epoch. Las tramas mayores que el snaplen se recortan
+    // (se conserva la longitud original en el bloque).
+    pub fn write_packet(&mut self, timestamp_us: u64, frame: &[u8]) -> io::Result<()> {
+        let captured = &frame[..frame.len().min(self.snaplen as usize)];
+        let mut body = Vec::with_capacity(20 + padded_len(captured.len()));
+        body.extend_from_slice(&0u32.to_le_bytes());
+        body.extend_from_slice(&((timestamp_us >> 32) as u32).to_le_bytes());
+        body.extend_from_slice(&(timestamp_us as u32).to_le_bytes());
+        body.extend_from_slice(&(captured.len() as u32).to_le_bytes());
+        body.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        body.extend_from_slice(captured);
+        pad_to_u32(&mut body);
+        self.write_block(BLOCK_EPB, &body)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    #[cfg(test)]
+    pub fn get_ref(&self) -> &W {
+        &self.out
+    }
+
+    // Tipo + longitud total + cuerpo (ya alineado a 4) + longitud total repetida.
+    fn write_block(&mut self, block_type: u32, body: &[u8]) -> io::Result<()> {
+        let total = (12 + body.len()) as u32;
+        self.out.write_all(&block_type.to_le_bytes())?;
+        self.out.write_all(&total.to_le_bytes())?;
+        self.out.write_all(body)?;
+        self.out.write_all(&total.to_le_bytes())?;
+        self.bytes_written += total as u64;
+        Ok(())
+    }
+}
+
+fn section_header_body() -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+    body.extend_from_slice(&1u16.to_le_bytes());
+    body.extend_from_slice(&0u16.to_le_bytes());
+    // Longitud de seccion desconocida (-1): se escribe en streaming.
+    body.extend_from_slice(&(-1i64).to_le_bytes());
+    push_option(&mut body, SHB_USERAPPL, b"NetSentinel");
+    push_option(&mut body, OPT_END, &[]);
+    body
+}
+
+fn interface_description_body(interface_name: &str, snaplen: u32) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&LINKTYPE_ETHERNET.to_le_bytes());
+    body.extend_from_slice(&0u16.to_le_bytes());
+    body.extend_from_slice(&snaplen.to_le_bytes());
+    if !interface_name.is_empty() {
+        push_option(&mut body, IF_NAME, interface_name.as_bytes());
+    }
+    push_option(&mut body, IF_TSRESOL, &[TSRESOL_MICROS]);
+    push_option(&mut body, OPT_END, &[]);
+    body
+}
+
+fn push_option(body: &mut Vec<u8>, code: u16, value: &[u8]) {
+    body.extend_from_slice(&code.to_le_bytes());
+    body.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    body.extend_from_slice(value);
+    pad_to_u32(body);
+}
+
+fn padded_len(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+fn pad_to_u32(body: &mut Vec<u8>) {
+    body.resize(padded_len(body.len()), 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn headers_describe_section_and_ethernet_interface() {
+        let writer = PcapngWriter::new(Vec::new(), "eth0", DEFAULT_SNAPLEN).unwrap();
+        let bytes = writer.get_ref().clone();
+
+        // SHB: tipo, magic de orden, version 1.0 y longitud repetida al final del bloque.
+        assert_eq!(u32_at(&bytes, 0), BLOCK_SHB);
+        let shb_len = u32_at(&bytes, 4) as usize;
+        assert_eq!(shb_len % 4, 0);
+        assert_eq!(u32_at(&bytes, 8), BYTE_ORDER_MAGIC);
+        assert_eq!(&bytes[12..16], &[1, 0, 0, 0]);
+        assert_eq!(&bytes[16..24], &[0xFF; 8]);
+        assert_eq!(u32_at(&bytes, shb_len - 4) as usize, shb_len);
+
+        // IDB: linktype Ethernet, snaplen y opciones if_name + if_tsresol.
+        let idb = &bytes[shb_len..];
+        assert_eq!(u32_at(idb, 0), BLOCK_IDB);
+        let idb_len = u32_at(idb, 4) as usize;
+        assert_eq!(u16::from_le_bytes([idb[8], idb[9]]), LINKTYPE_ETHERNET);
+        assert_eq!(u32_at(idb, 12), DEFAULT_SNAPLEN);
+        assert_eq!(&idb[16..24], &[2, 0, 4, 0, b'e', b't', b'h', b'0']);
+        assert_eq!(&idb[24..29], &[9, 0, 1, 0, TSRESOL_MICROS]);
+        assert_eq!(u32_at(idb, idb_len - 4) as usize, idb_len);
+        assert_eq!(shb_len + idb_len, bytes.len());
+        assert_eq!(writer.bytes_written() as usize, bytes.len());
+    }
+
+    #[test]
+    fn packets_carry_split_timestamp_padding_and_original_length() {
+        let mut writer = PcapngWriter::new(Vec::new(), "", 8).unwrap();
+        let start = writer.get_ref().len();
+        let ts: u64 = 1_700_000_000_123_456;
+        writer.write_packet(ts, &[0xAB; 10]).unwrap();
+
+        let epb = &writer.get_ref()[start..];
+        assert_eq!(u32_at(epb, 0), BLOCK_EPB);
+        // 12 de marco + 20 de cabecera + 8 bytes capturados (recortados por el snaplen).
+        assert_eq!(u32_at(epb, 4), 40);
+        assert_eq!(u32_at(epb, 8), 0);
+        assert_eq!(((u32_at(epb, 12) as u64) << 32) | u32_at(epb, 16) as u64, ts);
+        assert_eq!(u32_at(epb, 20), 8);
+        assert_eq!(u32_at(epb, 24), 10);
+        assert_eq!(u32_at(epb, 36), 40);
+
+        // Trama de longitud no multiplo de 4: se rellena con ceros.
+        let mut writer = PcapngWriter::new(Vec::new(), "", DEFAULT_SNAPLEN).unwrap();
+        let start = writer.get_ref().len();
+        writer.write_packet(0, &[1, 2, 3, 4, 5]).unwrap();
+        let epb = &writer.get_ref()[start..];
+        assert_eq!(u32_at(epb, 4), 12 + 20 + 8);
+        assert_eq!(&epb[28..36], &[1, 2, 3, 4, 5, 0, 0, 0]);
+    }
+}
@@ -1,12 +1,14 @@
 // src-tauri/src/infrastructure/network/traffic_sniffer/protocol.rs
 
-use pnet::packet::ipv4::Ipv4Packet;
-use pnet::packet::tcp::TcpPacket;
-use pnet::packet::udp::UdpPacket;
 use pnet::packet::Packet;
 
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::ip::{self, IpFrame, Transport};
+use super::{dns, http, icmpv6};
+
+const DNS_PORT: u16 = 53;
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum ProtoKind {
     Tcp,
@@ -23,58 +25,82 @@ pub fn unix_ms() -> u64 {
 
 pub fn is_intercepted(my_ip: &str, src: &str, dst: &str) -> bool {
     // Si el paquete NO viene de mi, y NO va para mi, y NO es broadcast/multicast,
-    // entonces es probable que sea un paquete interceptado (p. ej. por ARP spoofing).
+    // entonces es probable que sea un paquete interceptado (p. ej. por ARP spoofing o NDP spoofing).
     src != my_ip
         && dst != my_ip
         && dst != "255.255.255.255"
         && !dst.starts_with("224.0")
         && !dst.starts_with("239.")
+        && !is_ipv6_multicast(dst)
 }
 
-pub fn analyze_ipv4(ipv4: &Ipv4Packet) -> (String, String) {
-    // Primero: heuristicas por destino (broadcast/multicast/dns publico, etc.).
-    let dst_ip = ipv4.get_destination().to_string();
+// ff00::/8 (en forma canonica siempre empieza por "ff" seguido de un digito y ':').
+fn is_ipv6_multicast(ip: &str) -> bool {
+    ip.contains(':') && ip.starts_with("ff")
+}
 
-    match ipv4.get_next_level_protocol() {
-        pnet::packet::ip::IpNextHeaderProtocols::Tcp => {
-            if let Some(tcp) = TcpPacket::new(ipv4.payload()) {
-                let (proto, info) = classify(&dst_ip, ProtoKind::Tcp, tcp.get_source(), tcp.get_destination());
+pub fn analyze(ip: &IpFrame) -> (String, String) {
+    // Primero: heuristicas por destino (broadcast/multicast/dns publico, etc.).
+    let dst_ip = ip.dst.to_string();
 
-                // Fingerprinting best-effort solo en debug.
-                #[cfg(debug_assertions)]
-                {
-                    if let Some(ua) = try_extract_user_agent(tcp.payload()) {
-                        println!("🕵️ [FINGERPRINT] {} -> {}", ipv4.get_source(), ua);
-                    }
+    match (&ip.transport, ip.protocol) {
+        (Transport::Tcp(tcp), _) => {
+            let (proto, mut info) = classify(&dst_ip, ProtoKind::Tcp, tcp.get_source(), tcp.get_destination());
+            if is_dns(tcp.get_source(), tcp.get_destination()) {
+                if let Some(message) = dns::parse_tcp(tcp.payload()) {
+                    info = message.describe();
                 }
-
-                return (proto, info);
+            } else if let Some(message) = http::parse(tcp.payload()) {
+                // HTTP en claro se detecta por contenido: tambien en puertos no estandar (8080, camaras...).
+                info = message.describe();
             }
-            classify(&dst_ip, ProtoKind::Tcp, 0, 0)
+            (proto, info)
         }
-        pnet::packet::ip::IpNextHeaderProtocols::Udp => {
-            if let Some(udp) = UdpPacket::new(ipv4.payload()) {
-                return classify(&dst_ip, ProtoKind::Udp, udp.get_source(), udp.get_destination());
+        (Transport::Udp(udp), _) => {
+            let (proto, info) = classify(&dst_ip, ProtoKind::Udp, udp.get_source(), udp.get_destination());
+            // DNS decodificado: dominio, tipo y respuesta en lugar de la etiqueta generica.
+            if is_dns(udp.get_source(), udp.get_destination()) {
+                if let Some(message) = dns::parse(udp.payload()) {
+                    return (proto, message.describe());
+                }
             }
-            classify(&dst_ip, ProtoKind::Udp, 0, 0)
+            (proto, info)
         }
+        // Cabecera de transporte truncada.
+        (_, ip::PROTO_TCP) => classify(&dst_ip, ProtoKind::Tcp, 0, 0),
+        (_, ip::PROTO_UDP) => classify(&dst_ip, ProtoKind::Udp, 0, 0),
+        // NDP va aparte del resto de ICMPv6: es el ARP de IPv6 (y donde se hace el spoofing).
+        (_, ip::PROTO_ICMPV6) => match icmpv6::parse(ip.payload) {
+            Some(message) if message.is_ndp() => ("NDP".to_string(), message.describe()),
+            Some(message) => ("ICMPv6".to_string(), message.describe()),
+            None => ("ICMPv6".to_string(), "ICMPv6 truncado".to_string()),
+        },
         _ => classify(&dst_ip, ProtoKind::Other, 0, 0),
     }
 }
 
+fn is_dns(src_port: u16, dst_port: u16) -> bool {
+    src_port == DNS_PORT || dst_port == DNS_PORT
+}
+
 pub fn classify(dst_ip: &str, kind: ProtoKind, src_port: u16, dst_port: u16) -> (String, String) {
     if dst_ip == "255.255.255.255" {
         return ("BCAST".to_string(), "Broadcast".to_string());
     }
-    if dst_ip.starts_with("224.0.0") {
+    if dst_ip.starts_with("224.0.0") || dst_ip == "ff02::fb" {
         return ("MDNS".to_string(), "Bonjour/Avahi".to_string());
     }
-    if dst_ip.starts_with("239.255") {
+    // ff0X::c es el grupo SSDP en IPv6 (X = ambito: enlace, sitio...).
+    if dst_ip.starts_with("239.255") || (is_ipv6_multicast(dst_ip) && dst_ip.ends_with("::c")) {
         return ("SSDP".to_string(), "UPnP / IoT".to_string());
     }
-    if dst_ip == "8.8.8.8" || dst_ip == "1.1.1.1" {
+    if matches!(dst_ip, "8.8.8.8" | "1.1.1.1" | "2001:4860:4860::8888" | "2606:4700:4700::1111") {
         return ("DNS".to_string(), "DNS publico".to_string());
     }
+    // Resto de multicast IPv6 (todos los nodos, routers, MLD...): IPv6 no tiene broadcast.
+    if is_ipv6_multicast(dst_ip) && kind == ProtoKind::Other {
+        return ("MCAST".to_string(), "Multicast IPv6".to_string());
+    }
 
     match kind {
         ProtoKind::Tcp => {
@@ -103,15 +129,3 @@ pub fn classify(dst_ip: &str, kind: ProtoKind, src_port: u16, dst_port: u16) ->
         ProtoKind::Other => ("OTHER".to_string(), "Protocolo desconocido".to_string()),
     }
 }
-
-fn try_extract_user_agent(payload: &[u8]) -> Option<String> {
-    if payload.len() <= 10 {
-        return None;
-    }
-    let text = std::str::from_utf8(payload).ok()?;
-    let idx = text.find("User-Agent: ")?;
-    let start = idx + "User-Agent: ".len();
-    let end = text[start..].find("\r\n")?;
-    Some(text[start..start + end].to_string())
-}
-
@@ -0,0 +1,267 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/quic.rs
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::Aes128;
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+// Paquetes Initial de QUIC v1 (RFC 9000/9001). Sus claves salen del DCID elegido por el cliente
+// y de una sal publica, asi que cualquier observador puede leer el ClientHello que transportan.
+// Solo se descifra (AES-CTR); la etiqueta GCM no se verifica: es lectura pasiva, no un endpoint.
+const QUIC_V1: u32 = 0x0000_0001;
+const INITIAL_SALT_V1: [u8; 20] = [
+    0x38, 0x76, 0x2c, 0xf7, 0xf5, 0x59, 0x34, 0xb3, 0x4d, 0x17, 0x9a, 0xe6, 0xa4, 0xc8, 0x0c, 0xad, 0xcc, 0xbb, 0x7f, 0x0a,
+];
+const MAX_CID_LEN: usize = 20;
+const TAG_LEN: usize = 16;
+const SAMPLE_LEN: usize = 16;
+
+const FRAME_PADDING: u64 = 0x00;
+const FRAME_PING: u64 = 0x01;
+const FRAME_ACK: u64 = 0x02;
+const FRAME_ACK_ECN: u64 = 0x03;
+const FRAME_CRYPTO: u64 = 0x06;
+
+// Fragmento CRYPTO de un Initial del cliente: `offset` dentro del flujo de handshake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CryptoFragment {
+    pub offset: u64,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InitialPacket {
+    pub dcid: Vec<u8>,
+    pub fragments: Vec<CryptoFragment>,
+}
+
+struct InitialKeys {
+    key: [u8; 16],
+    iv: [u8; 12],
+    hp: [u8; 16],
+}
+
+// Primer paquete (largo, tipo Initial) del datagrama UDP. `None` si no es un Initial v1 legible.
+pub fn parse_client_initial(datagram: &[u8]) -> Option<InitialPacket> {
+    let first = *datagram.first()?;
+    // Cabecera larga con bit fijo, tipo 0 (Initial).
+    if first & 0xC0 != 0xC0 || (first >> 4) & 0x03 != 0 {
+        return None;
+    }
+    let version = u32::from_be_bytes(datagram.get(1..5)?.try_into().ok()?);
+    if version != QUIC_V1 {
+        return None;
+    }
+    let mut pos = 5;
+    let dcid_len = *datagram.get(pos)? as usize;
+    if dcid_len > MAX_CID_LEN {
+        return None;
+    }
+    let dcid = datagram.get(pos + 1..pos + 1 + dcid_len)?.to_vec();
+    pos += 1 + dcid_len;
+    let scid_len = *datagram.get(pos)? as usize;
+    pos += 1 + scid_len;
+    let (token_len, n) = read_varint(datagram.get(pos..)?)?;
+    pos += n + token_len as usize;
+    let (length, n) = read_varint(datagram.get(pos..)?)?;
+    pos += n;
+    let pn_offset = pos;
+    let packet_end = pn_offset.checked_add(length as usize)?;
+    if packet_end > datagram.len() || (length as usize) < 4 + SAMPLE_LEN {
+        return None;
+    }
+
+    let keys = client_initial_keys(&dcid);
+    let hp = Aes128::new(GenericArray::from_slice(&keys.hp));
+    let mut mask = GenericArray::clone_from_slice(datagram.get(pn_offset + 4..pn_offset + 4 + SAMPLE_LEN)?);
+    hp.encrypt_block(&mut mask);
+
+    let first = first ^ (mask[0] & 0x0F);
+    let pn_len = (first & 0x03) as usize + 1;
+    let mut packet_number: u64 = 0;
+    for i in 0..pn_len {
+        packet_number = (packet_number << 8) | (datagram[pn_offset + i] ^ mask[1 + i]) as u64;
+    }
+
+    let ciphertext = datagram.get(pn_offset + pn_len..packet_end.checked_sub(TAG_LEN)?)?;
+    let mut nonce = keys.iv;
+    for (i, b) in packet_number.to_be_bytes().iter().enumerate() {
+        nonce[4 + i] ^= b;
+    }
+    let plaintext = aes_gcm_keystream_xor(&keys.key, &nonce, ciphertext);
+
+    Some(InitialPacket {
+        dcid,
+        fragments: crypto_fragments(&plaintext)?,
+    })
+}
+
+fn client_initial_keys(dcid: &[u8]) -> InitialKeys {
+    let (initial_secret, _) = Hkdf::<Sha256>::extract(Some(&INITIAL_SALT_V1), dcid);
+    let client_secret = expand_label(&initial_secret, b"client in", 32);
+    let mut keys = InitialKeys {
+        key: [0; 16],
+        iv: [0; 12],
+        hp: [0; 16],
+    };
+    keys.key.copy_from_slice(&expand_label(&client_secret, b"quic key", 16));
+    keys.iv.copy_from_slice(&expand_label(&client_secret, b"quic iv", 12));
+    keys.hp.copy_from_slice(&expand_label(&client_secret, b"quic hp", 16));
+    keys
+}
+
+// HKDF-Expand-Label de TLS 1.3 con contexto vacio.
+fn expand_label(secret: &[u8], label: &[u8], len: usize) -> Vec<u8> {
+    let mut info = (len as u16).to_be_bytes().to_vec();
+    info.push((6 + label.len()) as u8);
+    info.extend_from_slice(b"tls13 ");
+    info.extend_from_slice(label);
+    info.push(0);
+    let mut out = vec![0u8; len];
+    // Las longitudes (<= 32) estan muy por debajo del maximo de HKDF-SHA256.
+    if let Ok(hk) = Hkdf::<Sha256>::from_prk(secret) {
+        let _ = hk.expand(&info, &mut out);
+    }
+    out
+}
+
+// Parte CTR de AES-GCM: el primer bloque de datos usa el contador 2 (el 1 cifra la etiqueta).
+fn aes_gcm_keystream_xor(key: &[u8; 16], nonce: &[u8; 12], data: &[u8]) -> Vec<u8> {
+    let cipher = Aes128::new(GenericArray::from_slice(key));
+    let mut out = Vec::with_capacity(data.len());
+    for (i, chunk) in data.chunks(16).enumerate() {
+        let mut block = [0u8; 16];
+        block[..12].copy_from_slice(nonce);
+        block[12..].copy_from_slice(&(i as u32 + 2).to_be_bytes());
+        let mut block = GenericArray::from(block);
+        cipher.encrypt_block(&mut block);
+        out.extend(chunk.iter().zip(block.iter()).map(|(d, k)| d ^ k));
+    }
+    out
+}
+
+// Tramas de un Initial del cliente; se para en la primera que no se espera ahi.
+fn crypto_fragments(payload: &[u8]) -> Option<Vec<CryptoFragment>> {
+    let mut fragments = Vec::new();
+    let mut pos = 0;
+    while pos < payload.len() {
+        let (frame_type, n) = read_varint(&payload[pos..])?;
+        pos += n;
+        match frame_type {
+            FRAME_PADDING | FRAME_PING => {}
+            FRAME_CRYPTO => {
+                let (offset, n) = read_varint(payload.get(pos..)?)?;
+                pos += n;
+                let (len, n) = read_varint(payload.get(pos..)?)?;
+                pos += n;
+                let data = payload.get(pos..pos + len as usize)?;
+                fragments.push(CryptoFragment {
+                    offset,
+                    data: data.to_vec(),
+                });
+                pos += len as usize;
+            }
+            FRAME_ACK | FRAME_ACK_ECN => {
+                // largest, delay, rangos (+ contadores ECN).
+                for _ in 0..2 {
+                    pos += read_varint(payload.get(pos..)?)?.1;
+                }
+                let (ranges, n) = read_varint(payload.get(pos..)?)?;
+                pos += n;
+                for _ in 0..(1 + ranges * 2 + if frame_type == FRAME_ACK_ECN { 3 } else { 0 }) {
+                    pos += read_varint(payload.get(pos..)?)?.1;
+                }
+            }
+            _ => break,
+        }
+    }
+    (!fragments.is_empty()).then_some(fragments)
+}
+
+// Entero de longitud variable (RFC 9000 16): los 2 bits altos indican 1, 2, 4 u 8 bytes.
+fn read_varint(data: &[u8]) -> Option<(u64, usize)> {
+    let first = *data.first()?;
+    let len = 1usize << (first >> 6);
+    let bytes = data.get(..len)?;
+    let mut value = (first & 0x3F) as u64;
+    for b in &bytes[1..] {
+        value = (value << 8) | *b as u64;
+    }
+    Some((value, len))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    // Initial del cliente con un CRYPTO por fragmento, cifrado y con proteccion de cabecera
+    // (el proceso inverso de `parse_client_initial`; etiqueta a ceros, que no se verifica).
+    pub fn client_initial(dcid: &[u8], packet_number: u8, fragments: &[(u64, &[u8])]) -> Vec<u8> {
+        let mut plaintext = Vec::new();
+        for (offset, data) in fragments {
+            plaintext.push(FRAME_CRYPTO as u8);
+            plaintext.extend_from_slice(&[0x40 | (*offset >> 8) as u8, *offset as u8]);
+            plaintext.extend_from_slice(&[0x40 | (data.len() >> 8) as u8, data.len() as u8]);
+            plaintext.extend_from_slice(data);
+        }
+        plaintext.resize(plaintext.len().max(1162), FRAME_PADDING as u8);
+
+        let keys = client_initial_keys(dcid);
+        let mut nonce = keys.iv;
+        nonce[11] ^= packet_number;
+        let mut protected = aes_gcm_keystream_xor(&keys.key, &nonce, &plaintext);
+        protected.extend_from_slice(&[0; TAG_LEN]);
+
+        // pn de 1 byte: cabecera 0xC0, longitud = pn + cuerpo + etiqueta.
+        let mut packet = vec![0xC0];
+        packet.extend_from_slice(&QUIC_V1.to_be_bytes());
+        packet.push(dcid.len() as u8);
+        packet.extend_from_slice(dcid);
+        packet.extend_from_slice(&[0, 0]);
+        let length = 1 + protected.len();
+        packet.extend_from_slice(&[0x40 | (length >> 8) as u8, length as u8]);
+        let pn_offset = packet.len();
+        packet.push(packet_number);
+        packet.extend(protected);
+
+        let hp = Aes128::new(GenericArray::from_slice(&keys.hp));
+        let mut mask = GenericArray::clone_from_slice(&packet[pn_offset + 4..pn_offset + 4 + SAMPLE_LEN]);
+        hp.encrypt_block(&mut mask);
+        packet[0] ^= mask[0] & 0x0F;
+        packet[pn_offset] ^= mask[1];
+        packet
+    }
+
+    #[test]
+    fn derives_the_rfc9001_client_initial_keys() {
+        let keys = client_initial_keys(&[0x83, 0x94, 0xc8, 0xf0, 0x3e, 0x51, 0x57, 0x08]);
+        let hex = |b: &[u8]| b.iter().map(|x| format!("{:02x}", x)).collect::<String>();
+        assert_eq!(hex(&keys.key), "1f369613dd76d5467730efcbe3b1a22d");
+        assert_eq!(hex(&keys.iv), "fa044b2f42a3fd3b46fb255c");
+        assert_eq!(hex(&keys.hp), "9f50449e04a0e810283a1e9933adedd2");
+    }
+
+    #[test]
+    fn decrypts_crypto_frames_from_a_protected_initial() {
+        let dcid = [0xAB; 8];
+        let packet = client_initial(&dcid, 3, &[(0, b"hello"), (5, b" world")]);
+        let initial = parse_client_initial(&packet).unwrap();
+        assert_eq!(initial.dcid, dcid);
+        assert_eq!(
+            initial.fragments,
+            vec![
+                CryptoFragment { offset: 0, data: b"hello".to_vec() },
+                CryptoFragment { offset: 5, data: b" world".to_vec() },
+            ]
+        );
+
+        // Cabecera corta (1-RTT) o version desconocida: no es un Initial legible.
+        let mut short = packet.clone();
+        short[0] = 0x40;
+        assert!(parse_client_initial(&short).is_none());
+        let mut other_version = packet;
+        other_version[4] = 2;
+        assert!(parse_client_initial(&other_version).is_none());
+    }
+}
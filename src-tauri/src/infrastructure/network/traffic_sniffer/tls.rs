@@ -0,0 +1,375 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/tls.rs
+
+// Lectura pasiva de ClientHello TLS: SNI, ALPN, version y huella JA3.
+// No se descifra nada: el ClientHello viaja en claro (en QUIC, con claves derivables del DCID).
+
+const CONTENT_HANDSHAKE: u8 = 0x16;
+const HANDSHAKE_CLIENT_HELLO: u8 = 0x01;
+// Un ClientHello real no pasa de unos pocos KB (claves post-cuanticas incluidas).
+pub const MAX_HELLO_LEN: usize = 16 * 1024;
+
+const EXT_SERVER_NAME: u16 = 0;
+const EXT_SUPPORTED_GROUPS: u16 = 10;
+const EXT_EC_POINT_FORMATS: u16 = 11;
+const EXT_ALPN: u16 = 16;
+const EXT_SUPPORTED_VERSIONS: u16 = 43;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientHello {
+    pub server_name: Option<String>,
+    pub alpn: Vec<String>,
+    // Version negociable mas alta (supported_versions si existe; si no, legacy_version).
+    pub version: u16,
+    pub ja3: String,
+    pub ja3_hash: String,
+}
+
+impl ClientHello {
+    pub fn version_name(&self) -> String {
+        version_name(self.version)
+    }
+
+    // Texto para `TrafficPacket.info`; `transport` es "TLS" o "QUIC".
+    pub fn describe(&self, transport: &str) -> String {
+        let target = self.server_name.as_deref().unwrap_or("(sin SNI)");
+        let mut details = vec![self.version_name()];
+        if !self.alpn.is_empty() {
+            details.push(self.alpn.join("/"));
+        }
+        format!("{} ClientHello {} ({})", transport, target, details.join(", "))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum HelloParse {
+    Complete(ClientHello),
+    // Parece un ClientHello pero faltan bytes (sigue en el siguiente segmento/paquete).
+    Incomplete,
+    NotHello,
+}
+
+// Flujo TCP desde su primer byte: uno o varios registros TLS de handshake.
+pub fn parse_records(stream: &[u8]) -> HelloParse {
+    if stream.len() < 6 || stream[0] != CONTENT_HANDSHAKE || stream[1] != 0x03 || stream[5] != HANDSHAKE_CLIENT_HELLO {
+        return HelloParse::NotHello;
+    }
+    // El mensaje de handshake puede repartirse en varios registros: se concatenan los fragmentos.
+    let mut handshake = Vec::new();
+    let mut pos = 0;
+    while pos + 5 <= stream.len() {
+        if stream[pos] != CONTENT_HANDSHAKE {
+            break;
+        }
+        let len = u16::from_be_bytes([stream[pos + 3], stream[pos + 4]]) as usize;
+        let Some(fragment) = stream.get(pos + 5..pos + 5 + len) else {
+            handshake.extend_from_slice(&stream[pos + 5..]);
+            break;
+        };
+        handshake.extend_from_slice(fragment);
+        pos += 5 + len;
+    }
+    parse_handshake(&handshake)
+}
+
+// Mensaje de handshake (tipo + longitud de 24 bits + cuerpo), como llega en los CRYPTO de QUIC.
+pub fn parse_handshake(handshake: &[u8]) -> HelloParse {
+    if handshake.first() != Some(&HANDSHAKE_CLIENT_HELLO) {
+        return HelloParse::NotHello;
+    }
+    if handshake.len() < 4 {
+        return HelloParse::Incomplete;
+    }
+    let len = u32::from_be_bytes([0, handshake[1], handshake[2], handshake[3]]) as usize;
+    if len > MAX_HELLO_LEN {
+        return HelloParse::NotHello;
+    }
+    match handshake.get(4..4 + len) {
+        Some(body) => parse_client_hello(body).map_or(HelloParse::NotHello, HelloParse::Complete),
+        None => HelloParse::Incomplete,
+    }
+}
+
+fn parse_client_hello(body: &[u8]) -> Option<ClientHello> {
+    let mut r = Reader::new(body);
+    let legacy_version = r.u16()?;
+    r.skip(32)?;
+    let session_len = r.u8()? as usize;
+    r.skip(session_len)?;
+    let ciphers_len = r.u16()? as usize;
+    let mut ciphers = Reader::new(r.take(ciphers_len)?);
+    let compression_len = r.u8()? as usize;
+    r.skip(compression_len)?;
+
+    let mut cipher_list = Vec::new();
+    while let Some(cipher) = ciphers.u16() {
+        cipher_list.push(cipher);
+    }
+
+    let mut hello = ClientHello {
+        server_name: None,
+        alpn: Vec::new(),
+        version: legacy_version,
+        ja3: String::new(),
+        ja3_hash: String::new(),
+    };
+    let mut extensions = Vec::new();
+    let mut groups = Vec::new();
+    let mut point_formats = Vec::new();
+
+    // Sin extensiones es valido (clientes TLS 1.0 muy antiguos).
+    if let Some(ext_len) = r.u16() {
+        let mut exts = Reader::new(r.take(ext_len as usize)?);
+        while let Some(ext_type) = exts.u16() {
+            let len = exts.u16()? as usize;
+            let mut data = Reader::new(exts.take(len)?);
+            extensions.push(ext_type);
+            match ext_type {
+                EXT_SERVER_NAME => hello.server_name = read_server_name(&mut data),
+                EXT_ALPN => hello.alpn = read_alpn(&mut data),
+                EXT_SUPPORTED_GROUPS => {
+                    let len = data.u16()? as usize;
+                    let mut list = Reader::new(data.take(len)?);
+                    while let Some(group) = list.u16() {
+                        groups.push(group);
+                    }
+                }
+                EXT_EC_POINT_FORMATS => {
+                    let n = data.u8()? as usize;
+                    point_formats.extend_from_slice(data.take(n)?);
+                }
+                EXT_SUPPORTED_VERSIONS => {
+                    let len = data.u8()? as usize;
+                    let mut list = Reader::new(data.take(len)?);
+                    while let Some(version) = list.u16() {
+                        if !is_grease(version) && version > hello.version {
+                            hello.version = version;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // JA3: version,cifrados,extensiones,grupos,formatos (decimal, '-' dentro de cada campo, sin GREASE).
+    let join = |values: &[u16]| {
+        values
+            .iter()
+            .filter(|v| !is_grease(**v))
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join("-")
+    };
+    let formats: Vec<u16> = point_formats.iter().map(|f| *f as u16).collect();
+    hello.ja3 = format!(
+        "{},{},{},{},{}",
+        legacy_version,
+        join(&cipher_list),
+        join(&extensions),
+        join(&groups),
+        join(&formats)
+    );
+    hello.ja3_hash = md5_hex(hello.ja3.as_bytes());
+    Some(hello)
+}
+
+fn read_server_name(data: &mut Reader) -> Option<String> {
+    let len = data.u16()? as usize;
+    let mut list = Reader::new(data.take(len)?);
+    while let Some(kind) = list.u8() {
+        let name_len = list.u16()? as usize;
+        let name = list.take(name_len)?;
+        // Tipo 0 = host_name; el nombre debe ser ASCII imprimible.
+        if kind == 0 && !name.is_empty() && name.iter().all(|b| b.is_ascii_graphic()) {
+            return Some(String::from_utf8_lossy(name).to_ascii_lowercase());
+        }
+    }
+    None
+}
+
+fn read_alpn(data: &mut Reader) -> Vec<String> {
+    let mut protocols = Vec::new();
+    let Some(list_len) = data.u16() else {
+        return protocols;
+    };
+    let Some(list) = data.take(list_len as usize) else {
+        return protocols;
+    };
+    let mut list = Reader::new(list);
+    while let Some(len) = list.u8() {
+        let Some(proto) = list.take(len as usize) else {
+            break;
+        };
+        protocols.push(String::from_utf8_lossy(proto).into_owned());
+    }
+    protocols
+}
+
+// Valores GREASE (RFC 8701): 0x0a0a, 0x1a1a ... 0xfafa. Los clientes los insertan al azar.
+fn is_grease(value: u16) -> bool {
+    value & 0x0F0F == 0x0A0A && (value >> 8) == (value & 0xFF)
+}
+
+pub fn version_name(version: u16) -> String {
+    match version {
+        0x0304 => "TLS 1.3".to_string(),
+        0x0303 => "TLS 1.2".to_string(),
+        0x0302 => "TLS 1.1".to_string(),
+        0x0301 => "TLS 1.0".to_string(),
+        0x0300 => "SSL 3.0".to_string(),
+        other => format!("0x{:04x}", other),
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let slice = self.data.get(self.pos..self.pos + n)?;
+        self.pos += n;
+        Some(slice)
+    }
+
+    fn skip(&mut self, n: usize) -> Option<()> {
+        self.take(n).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+}
+
+// MD5 (RFC 1321) solo para el hash JA3, que por definicion es MD5; no se usa como primitiva de seguridad.
+fn md5_hex(input: &[u8]) -> String {
+    const S: [u32; 64] = [
+        7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14,
+        20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6,
+        10, 15, 21,
+    ];
+    let k: Vec<u32> = (0..64)
+        .map(|i| ((i as f64 + 1.0).sin().abs() * 4_294_967_296.0) as u32)
+        .collect();
+
+    let mut message = input.to_vec();
+    let bit_len = (input.len() as u64).wrapping_mul(8);
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_le_bytes());
+
+    let mut state: [u32; 4] = [0x6745_2301, 0xEFCD_AB89, 0x98BA_DCFE, 0x1032_5476];
+    for chunk in message.chunks(64) {
+        let m: Vec<u32> = chunk
+            .chunks(4)
+            .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+            .collect();
+        let [mut a, mut b, mut c, mut d] = state;
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let rotated = a.wrapping_add(f).wrapping_add(k[i]).wrapping_add(m[g]).rotate_left(S[i]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(rotated);
+        }
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+    state.iter().flat_map(|w| w.to_le_bytes()).map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn ext(ext_type: u16, data: &[u8]) -> Vec<u8> {
+        let mut out = ext_type.to_be_bytes().to_vec();
+        out.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        out.extend_from_slice(data);
+        out
+    }
+
+    // Mensaje de handshake ClientHello (sin cabecera de registro) con GREASE, SNI, ALPN y TLS 1.3.
+    pub fn client_hello(server_name: &str) -> Vec<u8> {
+        let mut body = vec![0x03, 0x03];
+        body.extend_from_slice(&[0x11; 32]);
+        body.push(0);
+        body.extend_from_slice(&[0, 6, 0x1A, 0x1A, 0x13, 0x01, 0xC0, 0x2F]);
+        body.extend_from_slice(&[1, 0]);
+
+        let mut sni = ((server_name.len() + 3) as u16).to_be_bytes().to_vec();
+        sni.push(0);
+        sni.extend_from_slice(&(server_name.len() as u16).to_be_bytes());
+        sni.extend_from_slice(server_name.as_bytes());
+        let mut exts = ext(0x2A2A, &[]);
+        exts.extend(ext(EXT_SERVER_NAME, &sni));
+        exts.extend(ext(EXT_SUPPORTED_GROUPS, &[0, 6, 0x3A, 0x3A, 0, 0x1D, 0, 0x17]));
+        exts.extend(ext(EXT_EC_POINT_FORMATS, &[1, 0]));
+        exts.extend(ext(EXT_ALPN, &[0, 12, 2, b'h', b'2', 8, b'h', b't', b't', b'p', b'/', b'1', b'.', b'1']));
+        exts.extend(ext(EXT_SUPPORTED_VERSIONS, &[4, 0x4A, 0x4A, 0x03, 0x04]));
+        body.extend_from_slice(&(exts.len() as u16).to_be_bytes());
+        body.extend(exts);
+
+        let mut handshake = vec![HANDSHAKE_CLIENT_HELLO, 0];
+        handshake.extend_from_slice(&(body.len() as u16).to_be_bytes());
+        handshake.extend(body);
+        handshake
+    }
+
+    pub fn record(handshake: &[u8]) -> Vec<u8> {
+        let mut out = vec![CONTENT_HANDSHAKE, 0x03, 0x01];
+        out.extend_from_slice(&(handshake.len() as u16).to_be_bytes());
+        out.extend_from_slice(handshake);
+        out
+    }
+
+    #[test]
+    fn extracts_sni_alpn_version_and_grease_free_ja3() {
+        let stream = record(&client_hello("Api.Example.com"));
+        let HelloParse::Complete(hello) = parse_records(&stream) else {
+            panic!("ClientHello no reconocido");
+        };
+        assert_eq!(hello.server_name.as_deref(), Some("api.example.com"));
+        assert_eq!(hello.alpn, vec!["h2", "http/1.1"]);
+        assert_eq!(hello.version_name(), "TLS 1.3");
+        assert_eq!(hello.ja3, "771,4865-49199,0-10-11-16-43,29-23,0");
+        assert_eq!(hello.ja3_hash, md5_hex(hello.ja3.as_bytes()));
+        assert_eq!(hello.describe("TLS"), "TLS ClientHello api.example.com (TLS 1.3, h2/http/1.1)");
+
+        // Cortado a mitad: hay que esperar al siguiente segmento. Otro contenido: no es TLS.
+        assert_eq!(parse_records(&stream[..stream.len() - 10]), HelloParse::Incomplete);
+        assert_eq!(parse_records(b"GET / HTTP/1.1\r\n"), HelloParse::NotHello);
+
+        // El mismo handshake partido en dos registros.
+        let handshake = client_hello("a.example");
+        let mut split = record(&handshake[..20]);
+        split.extend(record(&handshake[20..]));
+        assert!(matches!(parse_records(&split), HelloParse::Complete(h) if h.server_name.as_deref() == Some("a.example")));
+    }
+
+    #[test]
+    fn md5_matches_reference_vectors() {
+        assert_eq!(md5_hex(b""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(md5_hex(b"The quick brown fox jumps over the lazy dog"), "9e107d9d372bb6826bd81d3542a419d6");
+        assert_eq!(md5_hex(&[b'a'; 64]), "014842d480b571495a4a0363793f7367");
+    }
+}
@@ -0,0 +1,332 @@
+// src-tauri/src/infrastructure/network/traffic_sniffer/tls_log.rs
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+
+use pnet::packet::Packet;
+
+use crate::domain::entities::{DeviceTlsLog, TlsClientStat};
+
+use super::ip::{IpFrame, Transport};
+use super::quic;
+use super::tls::{self, ClientHello, HelloParse, MAX_HELLO_LEN};
+
+const QUIC_PORT: u16 = 443;
+// ClientHello repartidos en varios segmentos/paquetes a la espera del resto.
+const MAX_PENDING: usize = 256;
+const PENDING_TTL_MS: u64 = 10_000;
+const MAX_DEVICES: usize = 512;
+const MAX_CLIENTS_PER_DEVICE: usize = 1_000;
+
+type Endpoint = (IpAddr, u16);
+// SNI, hash JA3 y transporte.
+type ClientKey = (Option<String>, String, &'static str);
+
+pub struct HelloEvent {
+    pub hello: ClientHello,
+    pub transport: &'static str,
+}
+
+impl HelloEvent {
+    pub fn describe(&self) -> String {
+        self.hello.describe(self.transport)
+    }
+}
+
+// Segmentos TCP consecutivos desde el inicio del ClientHello.
+struct PendingTcp {
+    next_seq: u32,
+    data: Vec<u8>,
+    updated: u64,
+}
+
+// Fragmentos CRYPTO de los Initial de una conexion QUIC, por offset.
+struct PendingQuic {
+    fragments: BTreeMap<u64, Vec<u8>>,
+    updated: u64,
+}
+
+struct ClientEntry {
+    server_name: Option<String>,
+    alpn: Vec<String>,
+    tls_version: String,
+    ja3: String,
+    ja3_hash: String,
+    transport: String,
+    connections: u64,
+    first_seen: u64,
+    last_seen: u64,
+    last_server_ip: String,
+}
+
+// Huellas TLS por equipo. La clave agrupa conexiones iguales: mismo destino, misma huella, mismo transporte.
+#[derive(Default)]
+pub struct TlsLog {
+    pending_tcp: HashMap<(Endpoint, Endpoint), PendingTcp>,
+    pending_quic: HashMap<(Endpoint, Vec<u8>), PendingQuic>,
+    devices: HashMap<String, HashMap<ClientKey, ClientEntry>>,
+}
+
+impl TlsLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Devuelve el ClientHello cuando esta trama lo completa.
+    pub fn observe_ip(&mut self, ip: &IpFrame, timestamp: u64) -> Option<HelloEvent> {
+        let (src, dst) = (ip.src, ip.dst);
+        self.expire(timestamp);
+
+        let event = match &ip.transport {
+            Transport::Tcp(tcp) => {
+                let key = ((src, tcp.get_source()), (dst, tcp.get_destination()));
+                self.observe_tcp(key, tcp.get_sequence(), tcp.payload(), timestamp)?
+            }
+            Transport::Udp(udp) => {
+                if udp.get_destination() != QUIC_PORT {
+                    return None;
+                }
+                self.observe_quic((src, udp.get_source()), udp.payload(), timestamp)?
+            }
+            Transport::Other => return None,
+        };
+        self.record(&src.to_string(), &dst.to_string(), &event, timestamp);
+        Some(event)
+    }
+
+    fn observe_tcp(&mut self, key: (Endpoint, Endpoint), seq: u32, payload: &[u8], timestamp: u64) -> Option<HelloEvent> {
+        if payload.is_empty() {
+            return None;
+        }
+        let stream = match self.pending_tcp.get_mut(&key) {
+            // Solo se acepta el segmento que continua exactamente (sin reordenar ni retransmisiones).
+            Some(pending) if pending.next_seq == seq => {
+                pending.data.extend_from_slice(payload);
+                pending.next_seq = seq.wrapping_add(payload.len() as u32);
+                pending.updated = timestamp;
+                pending.data.clone()
+            }
+            Some(_) => return None,
+            // Atajo: casi todo el trafico TCP no empieza por un registro de handshake.
+            None if payload[0] != 0x16 => return None,
+            None => payload.to_vec(),
+        };
+
+        match tls::parse_records(&stream) {
+            HelloParse::Complete(hello) => {
+                self.pending_tcp.remove(&key);
+                Some(HelloEvent { hello, transport: "TLS" })
+            }
+            HelloParse::Incomplete if stream.len() < MAX_HELLO_LEN => {
+                if !self.pending_tcp.contains_key(&key) && self.pending_tcp.len() < MAX_PENDING {
+                    self.pending_tcp.insert(
+                        key,
+                        PendingTcp {
+                            next_seq: seq.wrapping_add(payload.len() as u32),
+                            data: stream,
+                            updated: timestamp,
+                        },
+                    );
+                }
+                None
+            }
+            _ => {
+                self.pending_tcp.remove(&key);
+                None
+            }
+        }
+    }
+
+    fn observe_quic(&mut self, client: Endpoint, datagram: &[u8], timestamp: u64) -> Option<HelloEvent> {
+        let initial = quic::parse_client_initial(datagram)?;
+        let key = (client, initial.dcid);
+        if !self.pending_quic.contains_key(&key) && self.pending_quic.len() >= MAX_PENDING {
+            return None;
+        }
+        let pending = self.pending_quic.entry(key.clone()).or_insert_with(|| PendingQuic {
+            fragments: BTreeMap::new(),
+            updated: timestamp,
+        });
+        pending.updated = timestamp;
+        for fragment in initial.fragments {
+            pending.fragments.entry(fragment.offset).or_insert(fragment.data);
+        }
+
+        // Prefijo contiguo desde el offset 0.
+        let mut handshake = Vec::new();
+        for (offset, data) in &pending.fragments {
+            let offset = *offset as usize;
+            if offset > handshake.len() || handshake.len() > MAX_HELLO_LEN {
+                break;
+            }
+            let overlap = handshake.len() - offset;
+            if overlap < data.len() {
+                handshake.extend_from_slice(&data[overlap..]);
+            }
+        }
+
+        match tls::parse_handshake(&handshake) {
+            HelloParse::Complete(hello) => {
+                self.pending_quic.remove(&key);
+                Some(HelloEvent { hello, transport: "QUIC" })
+            }
+            HelloParse::Incomplete if handshake.len() < MAX_HELLO_LEN => None,
+            _ => {
+                self.pending_quic.remove(&key);
+                None
+            }
+        }
+    }
+
+    fn expire(&mut self, now: u64) {
+        self.pending_tcp.retain(|_, p| now.saturating_sub(p.updated) < PENDING_TTL_MS);
+        self.pending_quic.retain(|_, p| now.saturating_sub(p.updated) < PENDING_TTL_MS);
+    }
+
+    fn record(&mut self, client: &str, server: &str, event: &HelloEvent, timestamp: u64) {
+        if !self.devices.contains_key(client) && self.devices.len() >= MAX_DEVICES {
+            return;
+        }
+        let clients = self.devices.entry(client.to_string()).or_default();
+        let hello = &event.hello;
+        let key = (hello.server_name.clone(), hello.ja3_hash.clone(), event.transport);
+        if !clients.contains_key(&key) && clients.len() >= MAX_CLIENTS_PER_DEVICE {
+            if let Some(oldest) = clients.iter().min_by_key(|(_, e)| e.last_seen).map(|(k, _)| k.clone()) {
+                clients.remove(&oldest);
+            }
+        }
+        let entry = clients.entry(key).or_insert_with(|| ClientEntry {
+            server_name: hello.server_name.clone(),
+            alpn: hello.alpn.clone(),
+            tls_version: hello.version_name(),
+            ja3: hello.ja3.clone(),
+            ja3_hash: hello.ja3_hash.clone(),
+            transport: event.transport.to_string(),
+            connections: 0,
+            first_seen: timestamp,
+            last_seen: timestamp,
+            last_server_ip: server.to_string(),
+        });
+        entry.connections += 1;
+        entry.last_seen = entry.last_seen.max(timestamp);
+        entry.last_server_ip = server.to_string();
+    }
+
+    // `device_ip` limita a un equipo. Destinos mas recientes primero.
+    pub fn snapshot(&self, device_ip: Option<&str>) -> Vec<DeviceTlsLog> {
+        let mut devices: Vec<DeviceTlsLog> = self
+            .devices
+            .iter()
+            .filter(|(ip, _)| device_ip.is_none_or(|wanted| wanted == ip.as_str()))
+            .map(|(ip, clients)| {
+                let mut clients: Vec<TlsClientStat> = clients
+                    .values()
+                    .map(|e| TlsClientStat {
+                        server_name: e.server_name.clone(),
+                        alpn: e.alpn.clone(),
+                        tls_version: e.tls_version.clone(),
+                        ja3: e.ja3.clone(),
+                        ja3_hash: e.ja3_hash.clone(),
+                        transport: e.transport.clone(),
+                        connections: e.connections,
+                        first_seen: e.first_seen,
+                        last_seen: e.last_seen,
+                        last_server_ip: e.last_server_ip.clone(),
+                    })
+                    .collect();
+                clients.sort_by(|a, b| b.last_seen.cmp(&a.last_seen).then_with(|| a.server_name.cmp(&b.server_name)));
+                let mut fingerprints: Vec<String> = clients.iter().map(|c| c.ja3_hash.clone()).collect();
+                fingerprints.sort();
+                fingerprints.dedup();
+                DeviceTlsLog {
+                    ip: ip.clone(),
+                    fingerprints,
+                    clients,
+                }
+            })
+            .collect();
+        devices.sort_by(|a, b| a.ip.cmp(&b.ip));
+        devices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ip::parse_frame;
+    use super::super::quic::tests::client_initial;
+    use super::super::tls::tests::{client_hello, record};
+    use super::*;
+
+    fn feed(log: &mut TlsLog, frame: &[u8], timestamp: u64) -> Option<HelloEvent> {
+        log.observe_ip(&parse_frame(frame).unwrap(), timestamp)
+    }
+
+    const CLIENT: [u8; 4] = [192, 168, 1, 20];
+    const SERVER: [u8; 4] = [93, 184, 216, 34];
+
+    fn ipv4_frame(protocol: u8, transport: &[u8]) -> Vec<u8> {
+        let total_len = (20 + transport.len()) as u16;
+        let mut frame = vec![0x02, 0, 0, 0, 0, 0x01, 0x02, 0, 0, 0, 0, 0x02, 0x08, 0x00];
+        frame.extend_from_slice(&[0x45, 0, (total_len >> 8) as u8, total_len as u8, 0, 0, 0, 0, 64, protocol, 0, 0]);
+        frame.extend_from_slice(&CLIENT);
+        frame.extend_from_slice(&SERVER);
+        frame.extend_from_slice(transport);
+        frame
+    }
+
+    fn tcp_segment(seq: u32, payload: &[u8]) -> Vec<u8> {
+        let mut tcp = 50000u16.to_be_bytes().to_vec();
+        tcp.extend_from_slice(&443u16.to_be_bytes());
+        tcp.extend_from_slice(&seq.to_be_bytes());
+        tcp.extend_from_slice(&[0, 0, 0, 0, 0x50, 0x18, 0xFF, 0xFF, 0, 0, 0, 0]);
+        tcp.extend_from_slice(payload);
+        ipv4_frame(6, &tcp)
+    }
+
+    fn udp_datagram(payload: &[u8]) -> Vec<u8> {
+        let mut udp = 50000u16.to_be_bytes().to_vec();
+        udp.extend_from_slice(&443u16.to_be_bytes());
+        udp.extend_from_slice(&((8 + payload.len()) as u16).to_be_bytes());
+        udp.extend_from_slice(&[0, 0]);
+        udp.extend_from_slice(payload);
+        ipv4_frame(17, &udp)
+    }
+
+    #[test]
+    fn reassembles_client_hello_split_across_tcp_segments() {
+        let mut log = TlsLog::new();
+        let stream = record(&client_hello("video.example.com"));
+        let (head, tail) = stream.split_at(40);
+
+        assert!(feed(&mut log, &tcp_segment(1000, head), 1_000).is_none());
+        // Un segmento fuera de orden no se mezcla.
+        assert!(feed(&mut log, &tcp_segment(5000, tail), 1_001).is_none());
+        let event = feed(&mut log, &tcp_segment(1040, tail), 1_002).unwrap();
+        assert_eq!(event.describe(), "TLS ClientHello video.example.com (TLS 1.3, h2/http/1.1)");
+
+        // Segunda conexion igual: se agrupa y cuenta.
+        feed(&mut log, &tcp_segment(9000, &stream), 2_000).unwrap();
+        let devices = log.snapshot(Some("192.168.1.20"));
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].clients.len(), 1);
+        let client = &devices[0].clients[0];
+        assert_eq!((client.connections, client.first_seen, client.last_seen), (2, 1_002, 2_000));
+        assert_eq!((client.transport.as_str(), client.last_server_ip.as_str()), ("TLS", "93.184.216.34"));
+        assert_eq!(devices[0].fingerprints, vec![client.ja3_hash.clone()]);
+        assert!(log.snapshot(Some("192.168.1.99")).is_empty());
+    }
+
+    #[test]
+    fn reads_sni_from_quic_initials_split_across_datagrams() {
+        let mut log = TlsLog::new();
+        let hello = client_hello("quic.example.net");
+        let dcid = [0x5A; 8];
+        let (head, tail) = hello.split_at(30);
+
+        assert!(feed(&mut log, &udp_datagram(&client_initial(&dcid, 0, &[(0, head)])), 1_000).is_none());
+        let event = feed(&mut log, &udp_datagram(&client_initial(&dcid, 1, &[(30, tail)])), 1_010).unwrap();
+        assert_eq!(event.transport, "QUIC");
+        assert_eq!(event.hello.server_name.as_deref(), Some("quic.example.net"));
+        assert_eq!(log.snapshot(None)[0].clients[0].transport, "QUIC");
+    }
+}
@@ -0,0 +1,161 @@
+// src-tauri/src/infrastructure/network/upnp_client.rs
+
+use std::net::{SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+use reqwest::blocking::Client;
+use reqwest::Url;
+
+// Submodulos para separar responsabilidades (SOLID) sin cambiar la API publica del modulo.
+#[path = "upnp_client/xml.rs"]
+pub mod xml;
+
+pub const SSDP_PORT: u16 = 1900;
+const SSDP_MULTICAST: &str = "239.255.255.250:1900";
+const IGD_SEARCH_TARGET: &str = "urn:schemas-upnp-org:device:InternetGatewayDevice:1";
+
+// Fault UPnP: "SpecifiedArrayIndexInvalid" (fin de la tabla de mapeos).
+const FAULT_ARRAY_INDEX_INVALID: u32 = 713;
+
+// Servicio SOAP anunciado en la descripcion: WAN del IGD (WANIPConnection/WANPPPConnection)
+// o un servicio TR-064 (ej: Hosts).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IgdService {
+    pub service_type: String,
+    pub control_url: Url,
+}
+
+// Una fila de `GetGenericPortMappingEntry` tal cual la devuelve el router.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PortMappingEntry {
+    pub remote_host: String,
+    pub external_port: u16,
+    pub protocol: String,
+    pub internal_port: u16,
+    pub internal_client: String,
+    pub enabled: bool,
+    pub description: String,
+    pub lease_duration: u32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SoapError {
+    Fault(u32),
+    Transport(String),
+}
+
+// SSDP M-SEARCH (unicast al gateway + multicast) y devuelve el LOCATION del IGD.
+// Solo se aceptan respuestas del propio gateway y descripciones servidas por el.
+pub fn discover_location(target: SocketAddr, timeout: Duration) -> Option<Url> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    let request = format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: {}\r\nMAN: \"ssdp:discover\"\r\nMX: 2\r\nST: {}\r\n\r\n",
+        SSDP_MULTICAST, IGD_SEARCH_TARGET
+    );
+    socket.send_to(request.as_bytes(), target).ok()?;
+    if target.port() == SSDP_PORT {
+        // Algunos routers solo contestan a la busqueda multicast. Best-effort.
+        let _ = socket.send_to(request.as_bytes(), SSDP_MULTICAST);
+    }
+
+    let deadline = Instant::now() + timeout;
+    let mut buf = [0u8; 2048];
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        if remaining.is_zero() || socket.set_read_timeout(Some(remaining)).is_err() {
+            break;
+        }
+        let Ok((len, from)) = socket.recv_from(&mut buf) else {
+            break;
+        };
+        if from.ip() != target.ip() {
+            continue;
+        }
+        let text = String::from_utf8_lossy(&buf[..len]);
+        let location = text
+            .lines()
+            .filter_map(|l| l.split_once(':'))
+            .find(|(k, _)| k.trim().eq_ignore_ascii_case("location"))
+            .and_then(|(_, v)| Url::parse(v.trim()).ok());
+        if let Some(url) = location.filter(|u| u.host_str() == Some(target.ip().to_string().as_str())) {
+            return Some(url);
+        }
+    }
+    None
+}
+
+// Cliente SOAP minimo del servicio WAN (bloqueante, pensado para `spawn_blocking`).
+pub struct IgdClient {
+    http: Client,
+    service: IgdService,
+}
+
+impl IgdClient {
+    // Descarga la descripcion del dispositivo y localiza el servicio WAN.
+    pub fn connect(location: &Url, timeout: Duration) -> Result<Self, String> {
+        let http = Client::builder()
+            .timeout(timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| format!("No se pudo crear el cliente HTTP: {e}"))?;
+        let description = http
+            .get(location.clone())
+            .send()
+            .and_then(|r| r.text())
+            .map_err(|e| format!("Descripcion UPnP no accesible: {e}"))?;
+        let service = xml::find_wan_service(&description, location)
+            .ok_or("El IGD no expone WANIPConnection/WANPPPConnection")?;
+        Ok(Self { http, service })
+    }
+
+    pub fn service(&self) -> &IgdService {
+        &self.service
+    }
+
+    pub fn external_ip(&self) -> Result<Option<String>, SoapError> {
+        let body = self.call("GetExternalIPAddress", &[])?;
+        Ok(xml::tag_text(&body, "NewExternalIPAddress").filter(|ip| !ip.is_empty()))
+    }
+
+    // `Ok(None)` al llegar al final de la tabla (fault 713).
+    pub fn port_mapping(&self, index: u32) -> Result<Option<PortMappingEntry>, SoapError> {
+        let body = match self.call("GetGenericPortMappingEntry", &[("NewPortMappingIndex", index.to_string())]) {
+            Ok(body) => body,
+            Err(SoapError::Fault(FAULT_ARRAY_INDEX_INVALID)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let text = |tag: &str| xml::tag_text(&body, tag).unwrap_or_default();
+        Ok(Some(PortMappingEntry {
+            remote_host: text("NewRemoteHost"),
+            external_port: text("NewExternalPort").parse().unwrap_or(0),
+            protocol: text("NewProtocol").to_ascii_uppercase(),
+            internal_port: text("NewInternalPort").parse().unwrap_or(0),
+            internal_client: text("NewInternalClient"),
+            enabled: matches!(text("NewEnabled").as_str(), "1" | "true" | "True"),
+            description: text("NewPortMappingDescription"),
+            lease_duration: text("NewLeaseDuration").parse().unwrap_or(0),
+        }))
+    }
+
+    fn call(&self, action: &str, args: &[(&str, String)]) -> Result<String, SoapError> {
+        let envelope = xml::soap_envelope(&self.service.service_type, action, args);
+
+        let response = self
+            .http
+            .post(self.service.control_url.clone())
+            .header("Content-Type", "text/xml; charset=\"utf-8\"")
+            .header("SOAPAction", format!("\"{}#{}\"", self.service.service_type, action))
+            .body(envelope)
+            .send()
+            .map_err(|e| SoapError::Transport(e.to_string()))?;
+        let status = response.status();
+        let body = response.text().map_err(|e| SoapError::Transport(e.to_string()))?;
+
+        if let Some(code) = xml::tag_text(&body, "errorCode").and_then(|c| c.parse().ok()) {
+            return Err(SoapError::Fault(code));
+        }
+        if !status.is_success() {
+            return Err(SoapError::Transport(format!("HTTP {}", status)));
+        }
+        Ok(body)
+    }
+}
@@ -0,0 +1,145 @@
+// src-tauri/src/infrastructure/network/upnp_client/xml.rs
+
+use regex::Regex;
+use reqwest::Url;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use super::IgdService;
+
+// Orden de preferencia: IP directa primero (lo habitual), PPPoE como alternativa.
+const WAN_SERVICE_TYPES: &[&str] = &["WANIPConnection", "WANPPPConnection"];
+
+// Un regex por nombre de etiqueta; los nombres salen del codigo (pocos y fijos), asi que la cache no crece.
+static TAG_RES: OnceLock<Mutex<HashMap<String, Regex>>> = OnceLock::new();
+static SERVICE_BLOCK_RE: OnceLock<Regex> = OnceLock::new();
+
+// Texto del primer `<tag>` (con o sin prefijo de namespace). Suficiente para descripciones y
+// respuestas SOAP de IGD; no es un parser XML general.
+pub fn tag_text(xml: &str, tag: &str) -> Option<String> {
+    let re = tag_regex(tag)?;
+    let text = re.captures(xml).map(|c| unescape(c[1].trim()));
+    text
+}
+
+fn tag_regex(tag: &str) -> Option<Regex> {
+    let mut cache = TAG_RES.get_or_init(Default::default).lock().ok()?;
+    if let Some(re) = cache.get(tag) {
+        return Some(re.clone());
+    }
+    let pattern = format!(r"(?is)<(?:[\w-]+:)?{tag}\b[^>]*>(.*?)</(?:[\w-]+:)?{tag}>", tag = regex::escape(tag));
+    let re = Regex::new(&pattern).ok()?;
+    cache.insert(tag.to_string(), re.clone());
+    Some(re)
+}
+
+pub fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// Busca el servicio WAN en la descripcion del dispositivo (`rootDesc.xml`).
+pub fn find_wan_service(description: &str, location: &Url) -> Option<IgdService> {
+    find_service(description, location, WAN_SERVICE_TYPES)
+}
+
+// Primer servicio cuyo serviceType contiene alguno de `wanted` (en orden de preferencia).
+// El controlURL se resuelve contra URLBase/LOCATION y debe quedarse en el mismo host.
+pub fn find_service(description: &str, location: &Url, wanted: &[&str]) -> Option<IgdService> {
+    let base = tag_text(description, "URLBase")
+        .and_then(|b| Url::parse(&b).ok())
+        .filter(|b| b.host_str() == location.host_str())
+        .unwrap_or_else(|| location.clone());
+
+    let block_re = SERVICE_BLOCK_RE
+        .get_or_init(|| Regex::new(r"(?is)<(?:[\w-]+:)?service\b[^>]*>(.*?)</(?:[\w-]+:)?service>").unwrap());
+    let services: Vec<(String, String)> = block_re
+        .captures_iter(description)
+        .filter_map(|c| Some((tag_text(&c[1], "serviceType")?, tag_text(&c[1], "controlURL")?)))
+        .collect();
+
+    wanted.iter().find_map(|wanted| {
+        let (service_type, control) = services.iter().find(|(st, _)| st.contains(wanted))?;
+        let control_url = base.join(control).ok()?;
+        if control_url.host_str() != location.host_str() {
+            return None;
+        }
+        Some(IgdService {
+            service_type: service_type.clone(),
+            control_url,
+        })
+    })
+}
+
+// Sobre SOAP 1.1 de una accion UPnP/TR-064 (argumentos ya en texto plano, se escapan aqui).
+pub fn soap_envelope(service_type: &str, action: &str, args: &[(&str, String)]) -> String {
+    let args_xml: String = args
+        .iter()
+        .map(|(k, v)| format!("<{k}>{}</{k}>", escape(v)))
+        .collect();
+    format!(
+        "<?xml version=\"1.0\"?>\r\n<s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+         s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\"><s:Body>\
+         <u:{action} xmlns:u=\"{st}\">{args_xml}</u:{action}></s:Body></s:Envelope>",
+        st = escape(service_type)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESCRIPTION: &str = r#"<?xml version="1.0"?>
+        <root xmlns="urn:schemas-upnp-org:device-1-0">
+          <device><deviceType>urn:schemas-upnp-org:device:InternetGatewayDevice:1</deviceType>
+            <serviceList><service>
+              <serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>
+              <controlURL>/ctl/L3F</controlURL>
+            </service></serviceList>
+            <deviceList><device><deviceList><device><serviceList>
+              <service>
+                <serviceType>urn:schemas-upnp-org:service:WANPPPConnection:1</serviceType>
+                <controlURL>/ctl/PPP</controlURL>
+              </service>
+              <service>
+                <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>
+                <controlURL>/ctl/IPConn</controlURL>
+              </service>
+            </serviceList></device></deviceList></device></deviceList>
+          </device>
+        </root>"#;
+
+    #[test]
+    fn find_wan_service_prefers_ip_connection_and_resolves_control_url() {
+        let location = Url::parse("http://192.168.1.1:5000/rootDesc.xml").unwrap();
+        let service = find_wan_service(DESCRIPTION, &location).unwrap();
+        assert_eq!(service.service_type, "urn:schemas-upnp-org:service:WANIPConnection:1");
+        assert_eq!(service.control_url.as_str(), "http://192.168.1.1:5000/ctl/IPConn");
+
+        // controlURL absoluto hacia otro host: se descarta.
+        let foreign = DESCRIPTION.replace("/ctl/IPConn", "http://10.0.0.9/ctl").replace("/ctl/PPP", "http://10.0.0.9/ppp");
+        assert!(find_wan_service(&foreign, &location).is_none());
+    }
+
+    #[test]
+    fn tag_text_handles_namespaces_and_entities() {
+        let soap = "<s:Body><u:Resp><NewPortMappingDescription>A &amp; B</NewPortMappingDescription>\
+                    <m:errorCode>713</m:errorCode></u:Resp></s:Body>";
+        assert_eq!(tag_text(soap, "NewPortMappingDescription").as_deref(), Some("A & B"));
+        assert_eq!(tag_text(soap, "errorCode").as_deref(), Some("713"));
+        assert_eq!(escape("<a&'\">"), "&lt;a&amp;&apos;&quot;&gt;");
+    }
+}
@@ -0,0 +1,109 @@
+// src-tauri/src/infrastructure/port_audit_repository.rs
+
+use async_trait::async_trait;
+use directories::ProjectDirs;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tokio::sync::Mutex;
+
+use crate::domain::entities::PortAuditRecord;
+use crate::domain::ports::PortAuditRepositoryPort;
+
+// Suficiente para ver la evolucion de un equipo sin que el JSON crezca sin limite.
+const MAX_RECORDS_PER_DEVICE: usize = 50;
+
+// Historial de auditorias de puertos en AppData (`port_audits.json`): device_key -> registros (recientes primero).
+pub struct FilePortAuditRepository {
+    path: PathBuf,
+    // Serializa lectura-modificacion-escritura entre auditorias concurrentes.
+    write_lock: Mutex<()>,
+}
+
+impl FilePortAuditRepository {
+    pub fn new() -> Self {
+        Self::with_path(Self::default_path())
+    }
+
+    pub fn with_path(path: PathBuf) -> Self {
+        Self {
+            path,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    fn default_path() -> PathBuf {
+        if let Some(proj_dirs) = ProjectDirs::from("com", "netsentinel", "app") {
+            let data_dir = proj_dirs.data_dir();
+            if !data_dir.exists() {
+                let _ = fs::create_dir_all(data_dir);
+            }
+            return data_dir.join("port_audits.json");
+        }
+        PathBuf::from("netsentinel_port_audits.json")
+    }
+
+    fn load_all(&self) -> BTreeMap<String, Vec<PortAuditRecord>> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl PortAuditRepositoryPort for FilePortAuditRepository {
+    async fn save_record(&self, record: PortAuditRecord) -> Result<(), String> {
+        let _guard = self.write_lock.lock().await;
+
+        let mut all = self.load_all();
+        let records = all.entry(record.device_key.clone()).or_default();
+        records.insert(0, record);
+        records.truncate(MAX_RECORDS_PER_DEVICE);
+
+        let json = serde_json::to_string_pretty(&all).map_err(|e| e.to_string())?;
+        fs::write(&self.path, json).map_err(|e| e.to_string())
+    }
+
+    async fn list_records(&self, device_key: &str) -> Result<Vec<PortAuditRecord>, String> {
+        Ok(self.load_all().remove(device_key).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::PortScanMode;
+
+    fn record(key: &str, timestamp: u64) -> PortAuditRecord {
+        PortAuditRecord {
+            device_key: key.to_string(),
+            ip: "192.168.1.20".to_string(),
+            mac: Some(key.to_string()),
+            timestamp,
+            scan_mode: PortScanMode::Connect,
+            risk_level: "LOW".to_string(),
+            open_ports: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn save_and_list_keep_newest_first_and_cap_per_device() {
+        let path = std::env::temp_dir().join(format!("netsentinel_port_audits_test_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let repo = FilePortAuditRepository::with_path(path.clone());
+
+        for ts in 0..(MAX_RECORDS_PER_DEVICE as u64 + 5) {
+            repo.save_record(record("AA:BB:CC:DD:EE:01", ts)).await.unwrap();
+        }
+        repo.save_record(record("AA:BB:CC:DD:EE:02", 1)).await.unwrap();
+
+        let records = repo.list_records("AA:BB:CC:DD:EE:01").await.unwrap();
+        assert_eq!(records.len(), MAX_RECORDS_PER_DEVICE);
+        assert_eq!(records[0].timestamp, MAX_RECORDS_PER_DEVICE as u64 + 4);
+        assert_eq!(repo.list_records("AA:BB:CC:DD:EE:02").await.unwrap().len(), 1);
+        assert!(repo.list_records("missing").await.unwrap().is_empty());
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,455 @@
+// src-tauri/src/infrastructure/router_audit/attempts.rs
+
+use crate::domain::entities::{RouterAuditPhase, RouterAuditProgress};
+use crate::domain::ports::RouterAuditObserver;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Politica comun de intentos de login (HTTP y Chrome):
+// - tope de intentos por auditoria
+// - pausa entre intentos (no disparar credenciales seguidas)
+// - parada inmediata si el router indica bloqueo (429 / "too many attempts")
+// - rastro de cada intento en AppData (`router_login_attempts.json`), sin guardar claves
+// - cancelacion cooperativa y progreso tipado via `RouterAuditObserver` (entre intentos)
+
+pub const MAX_GATEWAY_ATTEMPTS: usize = 10;
+const ATTEMPT_DELAY: Duration = Duration::from_millis(1500);
+const MAX_TRAIL_RECORDS: usize = 1000;
+// El rastro es un fichero compartido (HTTP y Chrome, varias auditorias): leer y reescribir es una sola operacion.
+static TRAIL_LOCK: Mutex<()> = Mutex::new(());
+
+// Textos de bloqueo habituales en firmwares (comparacion en minusculas).
+pub const LOCKOUT_MARKERS: &[&str] = &[
+    "too many attempts",
+    "too many login",
+    "too many failed",
+    "account is locked",
+    "account locked",
+    "locked out",
+    "try again later",
+    "demasiados intentos",
+    "cuenta bloqueada",
+    "zu viele",
+    "trop de tentatives",
+];
+
+// El resultado de cada intento viaja tambien en los eventos de progreso (dominio).
+pub use crate::domain::entities::RouterLoginOutcome as AttemptOutcome;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginAttemptRecord {
+    pub timestamp: u64,
+    pub target_ip: String,
+    pub backend: String,
+    pub profile_id: String,
+    pub user: String,
+    pub outcome: AttemptOutcome,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AttemptPolicy {
+    pub max_attempts: usize,
+    pub delay: Duration,
+}
+
+impl Default for AttemptPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: MAX_GATEWAY_ATTEMPTS,
+            delay: ATTEMPT_DELAY,
+        }
+    }
+}
+
+pub fn is_lockout(status: u16, body: &str) -> bool {
+    if status == 429 {
+        return true;
+    }
+    let text = body.to_lowercase();
+    LOCKOUT_MARKERS.iter().any(|m| text.contains(m))
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AttemptsSummary {
+    pub found: Option<(String, String)>,
+    pub attempts: usize,
+    pub locked_out: bool,
+    pub cancelled: bool,
+}
+
+impl AttemptsSummary {
+    // Mensaje para `RouterAuditResult` cuando no hay credenciales validas.
+    pub fn failure_message(&self) -> String {
+        if self.locked_out {
+            format!("Stopped: lockout detected after {} attempts", self.attempts)
+        } else if self.cancelled {
+            format!("Cancelled after {} attempts", self.attempts)
+        } else {
+            "Failed".to_string()
+        }
+    }
+}
+
+// Contexto fijo de una auditoria (rastro + eventos de progreso).
+pub struct AttemptContext<'a> {
+    pub target_ip: &'a str,
+    pub backend: &'a str,
+    pub profile_id: &'a str,
+    pub observer: &'a dyn RouterAuditObserver,
+}
+
+impl AttemptContext<'_> {
+    fn progress(&self, phase: RouterAuditPhase, index: usize, total: usize, user: &str, outcome: Option<AttemptOutcome>) {
+        self.observer.on_progress(RouterAuditProgress {
+            phase,
+            backend: self.backend.to_string(),
+            profile_id: Some(self.profile_id.to_string()),
+            attempt_index: Some(index),
+            attempt_total: Some(total),
+            user: Some(user.to_string()),
+            outcome,
+        });
+    }
+}
+
+// Evento inicial comun a los backends (aun sin perfil ni diccionario).
+pub fn report_detecting(observer: &dyn RouterAuditObserver, backend: &str) {
+    observer.on_progress(RouterAuditProgress {
+        phase: RouterAuditPhase::Detecting,
+        backend: backend.to_string(),
+        profile_id: None,
+        attempt_index: None,
+        attempt_total: None,
+        user: None,
+        outcome: None,
+    });
+}
+
+// Prueba credenciales en orden respetando la politica; para en el primer exito o bloqueo.
+pub fn run_credential_attempts<F>(
+    ctx: &AttemptContext,
+    credentials: &[(String, String)],
+    policy: &AttemptPolicy,
+    trail: &LoginAttemptTrail,
+    log: &dyn Fn(&str),
+    mut try_login: F,
+) -> AttemptsSummary
+where
+    F: FnMut(&str, &str) -> AttemptOutcome,
+{
+    if credentials.len() > policy.max_attempts {
+        log(&format!(
+            "   ✂️ Diccionario recortado a {} intentos (de {}).",
+            policy.max_attempts,
+            credentials.len()
+        ));
+    }
+
+    let total = credentials.len().min(policy.max_attempts);
+    let mut summary = AttemptsSummary::default();
+    for (idx, (user, pass)) in credentials.iter().take(policy.max_attempts).enumerate() {
+        if idx > 0 && !policy.delay.is_zero() {
+            thread::sleep(policy.delay);
+        }
+        if ctx.observer.is_cancelled() {
+            log("⏹️ Auditoria cancelada por el usuario.");
+            summary.cancelled = true;
+            break;
+        }
+
+        log(&format!("👉 Probando: {} / {}", user, pass));
+        ctx.progress(RouterAuditPhase::Attempting, idx + 1, total, user, None);
+        let outcome = try_login(user, pass);
+        ctx.progress(RouterAuditPhase::Attempted, idx + 1, total, user, Some(outcome));
+        summary.attempts += 1;
+        trail.record(LoginAttemptRecord {
+            timestamp: now_millis(),
+            target_ip: ctx.target_ip.to_string(),
+            backend: ctx.backend.to_string(),
+            profile_id: ctx.profile_id.to_string(),
+            user: user.clone(),
+            outcome,
+        });
+
+        match outcome {
+            AttemptOutcome::Success => {
+                summary.found = Some((user.clone(), pass.clone()));
+                break;
+            }
+            AttemptOutcome::LockedOut => {
+                log("🛑 El router indica bloqueo por intentos: auditoria detenida para no bloquear la cuenta admin.");
+                summary.locked_out = true;
+                break;
+            }
+            AttemptOutcome::Rejected | AttemptOutcome::Error => {}
+        }
+    }
+    summary
+}
+
+// Rastro persistente de intentos (recientes primero, acotado).
+pub struct LoginAttemptTrail {
+    path: PathBuf,
+}
+
+impl LoginAttemptTrail {
+    pub fn new() -> Self {
+        Self::with_path(Self::default_path())
+    }
+
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn default_path() -> PathBuf {
+        if let Some(proj_dirs) = ProjectDirs::from("com", "netsentinel", "app") {
+            let data_dir = proj_dirs.data_dir();
+            if !data_dir.exists() {
+                let _ = fs::create_dir_all(data_dir);
+            }
+            return data_dir.join("router_login_attempts.json");
+        }
+        PathBuf::from("netsentinel_router_login_attempts.json")
+    }
+
+    pub fn load(&self) -> Vec<LoginAttemptRecord> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    // Best-effort: un fallo de disco no debe interrumpir la auditoria.
+    pub fn record(&self, record: LoginAttemptRecord) {
+        let _guard = TRAIL_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut records = self.load();
+        records.insert(0, record);
+        records.truncate(MAX_TRAIL_RECORDS);
+        match serde_json::to_string_pretty(&records) {
+            Ok(json) => {
+                if let Err(e) = self.write_atomically(&json) {
+                    println!("⚠️ [ROUTER] No se pudo guardar el rastro de intentos: {}", e);
+                }
+            }
+            Err(e) => println!("⚠️ [ROUTER] Rastro de intentos no serializable: {}", e),
+        }
+    }
+
+    // Fichero temporal + rename: un cierre a medias no deja un JSON truncado (que `load` leeria vacio).
+    fn write_atomically(&self, json: &str) -> std::io::Result<()> {
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &self.path)
+    }
+}
+
+impl Default for LoginAttemptTrail {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+pub mod test_support {
+    use super::*;
+    use crate::domain::entities::RouterEvidence;
+
+    // Observador de tests: guarda eventos y evidencias, y cancela tras `cancel_after` eventos `Attempted`.
+    #[derive(Default)]
+    pub struct RecordingObserver {
+        pub events: std::sync::Mutex<Vec<RouterAuditProgress>>,
+        pub evidence: std::sync::Mutex<Vec<RouterEvidence>>,
+        pub cancel_after: Option<usize>,
+    }
+
+    impl RouterAuditObserver for RecordingObserver {
+        fn on_progress(&self, progress: RouterAuditProgress) {
+            self.events.lock().unwrap().push(progress);
+        }
+
+        fn on_evidence(&self, evidence: RouterEvidence) {
+            self.evidence.lock().unwrap().push(evidence);
+        }
+
+        fn is_cancelled(&self) -> bool {
+            let attempted = self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| e.phase == RouterAuditPhase::Attempted)
+                .count();
+            self.cancel_after.is_some_and(|n| attempted >= n)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::RecordingObserver;
+    use super::*;
+
+    fn creds(n: usize) -> Vec<(String, String)> {
+        (0..n).map(|i| (format!("user{i}"), format!("pass{i}"))).collect()
+    }
+
+    fn temp_trail(name: &str) -> (LoginAttemptTrail, PathBuf) {
+        let path = std::env::temp_dir().join(format!("netsentinel_{}_{}.json", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        (LoginAttemptTrail::with_path(path.clone()), path)
+    }
+
+    #[test]
+    fn attempts_stop_on_lockout_and_are_recorded_without_passwords() {
+        let (trail, path) = temp_trail("attempts_lockout");
+        let ctx = AttemptContext {
+            target_ip: "192.168.1.1",
+            backend: "http",
+            profile_id: "generic",
+            observer: &RecordingObserver::default(),
+        };
+        let policy = AttemptPolicy {
+            max_attempts: 10,
+            delay: Duration::ZERO,
+        };
+
+        let mut calls = 0;
+        let summary = run_credential_attempts(&ctx, &creds(5), &policy, &trail, &|_| {}, |_, _| {
+            calls += 1;
+            if calls == 2 {
+                AttemptOutcome::LockedOut
+            } else {
+                AttemptOutcome::Rejected
+            }
+        });
+
+        assert!(summary.found.is_none());
+        assert!(summary.locked_out);
+        assert_eq!(calls, 2);
+        assert_eq!(summary.failure_message(), "Stopped: lockout detected after 2 attempts");
+        let records = trail.load();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].outcome, AttemptOutcome::LockedOut);
+        assert_eq!(records[1].user, "user0");
+        assert!(!fs::read_to_string(&path).unwrap().contains("pass0"));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn attempts_are_capped_and_return_first_success() {
+        let (trail, path) = temp_trail("attempts_cap");
+        let ctx = AttemptContext {
+            target_ip: "192.168.1.1",
+            backend: "chrome",
+            profile_id: "tp-link",
+            observer: &RecordingObserver::default(),
+        };
+        let policy = AttemptPolicy {
+            max_attempts: 3,
+            delay: Duration::ZERO,
+        };
+
+        let mut tried = Vec::new();
+        let summary = run_credential_attempts(&ctx, &creds(6), &policy, &trail, &|_| {}, |user, _| {
+            tried.push(user.to_string());
+            AttemptOutcome::Rejected
+        });
+        assert_eq!(summary.attempts, 3);
+        assert_eq!(summary.failure_message(), "Failed");
+        assert_eq!(tried, vec!["user0", "user1", "user2"]);
+
+        let summary = run_credential_attempts(&ctx, &creds(6), &policy, &trail, &|_| {}, |user, _| {
+            if user == "user1" {
+                AttemptOutcome::Success
+            } else {
+                AttemptOutcome::Rejected
+            }
+        });
+        assert_eq!(summary.found, Some(("user1".to_string(), "pass1".to_string())));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn attempts_emit_typed_progress_and_stop_when_cancelled() {
+        let (trail, path) = temp_trail("attempts_cancel");
+        let observer = RecordingObserver {
+            cancel_after: Some(2),
+            ..RecordingObserver::default()
+        };
+        let ctx = AttemptContext {
+            target_ip: "192.168.1.1",
+            backend: "chrome",
+            profile_id: "generic",
+            observer: &observer,
+        };
+        let policy = AttemptPolicy {
+            max_attempts: 10,
+            delay: Duration::ZERO,
+        };
+
+        let summary = run_credential_attempts(&ctx, &creds(5), &policy, &trail, &|_| {}, |_, _| AttemptOutcome::Rejected);
+        assert!(summary.cancelled);
+        assert_eq!(summary.attempts, 2);
+        assert_eq!(summary.failure_message(), "Cancelled after 2 attempts");
+
+        let events = observer.events.lock().unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[2].phase, RouterAuditPhase::Attempting);
+        assert_eq!(events[3].attempt_index, Some(2));
+        assert_eq!(events[3].attempt_total, Some(5));
+        assert_eq!(events[3].user.as_deref(), Some("user1"));
+        assert_eq!(events[3].outcome, Some(AttemptOutcome::Rejected));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn concurrent_records_are_not_lost() {
+        let (trail, path) = temp_trail("attempts_concurrent");
+        let trail = std::sync::Arc::new(trail);
+        let writers: Vec<_> = (0..4)
+            .map(|writer| {
+                let trail = std::sync::Arc::clone(&trail);
+                thread::spawn(move || {
+                    for i in 0..10 {
+                        trail.record(LoginAttemptRecord {
+                            timestamp: now_millis(),
+                            target_ip: "192.168.1.1".to_string(),
+                            backend: "http".to_string(),
+                            profile_id: "generic".to_string(),
+                            user: format!("w{writer}-{i}"),
+                            outcome: AttemptOutcome::Rejected,
+                        });
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(trail.load().len(), 40);
+        assert!(!path.with_extension("json.tmp").exists());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn is_lockout_detects_429_and_known_texts() {
+        assert!(is_lockout(429, ""));
+        assert!(is_lockout(200, "<p>Too many attempts. Please wait 60 seconds.</p>"));
+        assert!(is_lockout(200, "Demasiados intentos fallidos"));
+        assert!(!is_lockout(200, "Invalid password"));
+    }
+}
@@ -0,0 +1,196 @@
+// src-tauri/src/infrastructure/router_audit/auto_auditor.rs
+
+use crate::domain::{
+    entities::{Device, RouterAuditResult, RouterCredentials},
+    ports::{RouterAuditObserver, RouterAuditorPort},
+};
+use async_trait::async_trait;
+use std::env;
+use std::sync::Arc;
+
+use super::chrome_auditor::ChromeAuditor;
+use super::http_auditor::HttpAuditor;
+use super::tr064::Tr064Auditor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditorBackend {
+    Http,
+    Chrome,
+    // Solo lista de clientes: TR-064 no sirve para auditar el login web.
+    Tr064,
+}
+
+// Elige backend por router:
+// - TR-064 para la lista de clientes si el router anuncia el servicio Hosts (datos estructurados).
+// - HTTP (reqwest) si el login es Basic/Digest o un formulario HTML sin JS: rapido y sin navegador.
+// - Chrome si el login solo existe tras ejecutar JS (SPAs, hash de clave en cliente...).
+// `NETSENTINEL_ROUTER_AUDITOR=http|chrome|tr064` fuerza un backend (agentes de build sin Chrome, debug).
+pub struct AutoRouterAuditor {
+    http: Arc<HttpAuditor>,
+    chrome: Arc<ChromeAuditor>,
+    tr064: Arc<Tr064Auditor>,
+}
+
+impl AutoRouterAuditor {
+    pub fn new(logger: Arc<dyn Fn(String) + Send + Sync>) -> Self {
+        Self {
+            http: Arc::new(HttpAuditor::new(logger.clone())),
+            chrome: Arc::new(ChromeAuditor::new(logger.clone())),
+            tr064: Arc::new(Tr064Auditor::new(logger)),
+        }
+    }
+
+    fn forced_backend() -> Option<AuditorBackend> {
+        parse_backend(&env::var("NETSENTINEL_ROUTER_AUDITOR").ok()?)
+    }
+
+    // `for_devices`: ademas del login, la lista de clientes debe ser accesible sin navegador.
+    async fn choose(&self, ip: &str, for_devices: bool) -> AuditorBackend {
+        // Forzar TR-064 solo afecta a la lista de clientes; el login se sigue autodetectando.
+        if let Some(forced) = Self::forced_backend().filter(|b| for_devices || *b != AuditorBackend::Tr064) {
+            return forced;
+        }
+
+        let ip = ip.to_string();
+        if for_devices {
+            let tr064 = self.tr064.clone();
+            let probe_ip = ip.clone();
+            let available = tauri::async_runtime::spawn_blocking(move || tr064.is_available(&probe_ip))
+                .await
+                .unwrap_or(false);
+            if available {
+                return AuditorBackend::Tr064;
+            }
+        }
+        self.choose_web(&ip, for_devices).await
+    }
+
+    // HTTP o Chrome segun el login web (tambien el fallback cuando TR-064 falla).
+    async fn choose_web(&self, ip: &str, for_devices: bool) -> AuditorBackend {
+        let ip = ip.to_string();
+        let http = self.http.clone();
+        let plan = tauri::async_runtime::spawn_blocking(move || http.detect_login(&ip))
+            .await
+            .ok()
+            .flatten();
+
+        match plan {
+            Some(plan) if !for_devices || plan.can_fetch_devices() => AuditorBackend::Http,
+            _ => AuditorBackend::Chrome,
+        }
+    }
+}
+
+pub fn parse_backend(value: &str) -> Option<AuditorBackend> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "http" => Some(AuditorBackend::Http),
+        "chrome" => Some(AuditorBackend::Chrome),
+        "tr064" => Some(AuditorBackend::Tr064),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl RouterAuditorPort for AutoRouterAuditor {
+    async fn audit_gateway(&self, ip: &str, observer: Arc<dyn RouterAuditObserver>) -> RouterAuditResult {
+        match self.choose(ip, false).await {
+            // `choose(_, false)` nunca devuelve Tr064; por exhaustividad se audita por HTTP.
+            AuditorBackend::Http | AuditorBackend::Tr064 => self.http.audit_gateway(ip, observer).await,
+            AuditorBackend::Chrome => self.chrome.audit_gateway(ip, observer).await,
+        }
+    }
+
+    async fn fetch_connected_devices(&self, ip: &str, creds: &RouterCredentials) -> Vec<Device> {
+        match self.choose(ip, true).await {
+            AuditorBackend::Http => self.http.fetch_connected_devices(ip, creds).await,
+            AuditorBackend::Chrome => self.chrome.fetch_connected_devices(ip, creds).await,
+            AuditorBackend::Tr064 => {
+                let tr064 = self.tr064.clone();
+                let task_ip = ip.to_string();
+                let task_creds = creds.clone();
+                let fetched = tauri::async_runtime::spawn_blocking(move || {
+                    tr064.fetch_connected_devices_blocking(&task_ip, &task_creds)
+                })
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|r| r);
+                match fetched {
+                    Ok(devices) => devices,
+                    // Descripcion anonima pero SOAP rechazado/caido: se sincroniza por la web del router.
+                    Err(_) => match self.choose_web(ip, true).await {
+                        AuditorBackend::Chrome => self.chrome.fetch_connected_devices(ip, creds).await,
+                        _ => self.http.fetch_connected_devices(ip, creds).await,
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::service_probes::test_support::{response, spawn_stand_in_http};
+
+    #[test]
+    fn parse_backend_accepts_known_values_only() {
+        assert_eq!(parse_backend(" HTTP "), Some(AuditorBackend::Http));
+        assert_eq!(parse_backend("chrome"), Some(AuditorBackend::Chrome));
+        assert_eq!(parse_backend("TR064"), Some(AuditorBackend::Tr064));
+        assert_eq!(parse_backend("auto"), None);
+    }
+
+    #[tokio::test]
+    async fn choose_prefers_http_for_basic_auth_and_chrome_for_js_logins() {
+        if AutoRouterAuditor::forced_backend().is_some() {
+            return;
+        }
+        let auditor = AutoRouterAuditor::new(Arc::new(|_| {}));
+
+        let basic = spawn_stand_in_http(|_| {
+            response("401 Unauthorized", &[("WWW-Authenticate", "Basic realm=\"Router\"")], "")
+        }).to_string();
+        assert_eq!(auditor.choose(&basic, true).await, AuditorBackend::Http);
+
+        let spa = spawn_stand_in_http(|_| response("200 OK", &[], "<div id='app'></div><script src='/app.js'></script>"));
+        let spa = spa.to_string();
+        assert_eq!(auditor.choose(&spa, false).await, AuditorBackend::Chrome);
+    }
+
+    #[tokio::test]
+    async fn rejected_tr064_credentials_fall_back_to_the_web_scrape() {
+        if AutoRouterAuditor::forced_backend().is_some() {
+            return;
+        }
+        // Descripcion TR-064 anonima, pero el usuario de la web no vale para SOAP (401 siempre).
+        let router = spawn_stand_in_http(|req| match (req.method.as_str(), req.path.as_str()) {
+            ("GET", "/tr64desc.xml") => response(
+                "200 OK",
+                &[("Content-Type", "text/xml")],
+                "<root><device><serviceList><service><serviceType>urn:dslforum-org:service:Hosts:1</serviceType>\
+                 <controlURL>/upnp/control/hosts</controlURL></service></serviceList></device></root>",
+            ),
+            ("POST", _) => {
+                let challenge = "Digest realm=\"F!Box SOAP-Auth\", nonce=\"n1\", qop=\"auth\"";
+                response("401 Unauthorized", &[("WWW-Authenticate", challenge)], "")
+            }
+            // "admin:1234" en base64.
+            _ if req.header("authorization") == Some("Basic YWRtaW46MTIzNA==") => response(
+                "200 OK",
+                &[],
+                "<html><body><div>Portatil</div><div>IP: 192.168.1.20</div></body></html>",
+            ),
+            _ => response("401 Unauthorized", &[("WWW-Authenticate", "Basic realm=\"Router\"")], ""),
+        }).to_string();
+        let auditor = AutoRouterAuditor::new(Arc::new(|_| {}));
+        assert_eq!(auditor.choose(&router, true).await, AuditorBackend::Tr064);
+
+        let creds = RouterCredentials {
+            user: "admin".to_string(),
+            pass: "1234".to_string(),
+        };
+        let devices = auditor.fetch_connected_devices(&router, &creds).await;
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].ip, "192.168.1.20");
+    }
+}
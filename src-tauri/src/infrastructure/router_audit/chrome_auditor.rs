@@ -1,20 +1,28 @@
 // src-tauri/src/infrastructure/router_audit/chrome_auditor.rs
 
 use crate::domain::{
-    entities::{Device, RouterAuditResult},
-    ports::RouterAuditorPort,
+    entities::{Device, RouterAuditResult, RouterCredentials, RouterEvidence, RouterEvidenceKind, RouterEvidenceStage},
+    ports::{RouterAuditObserver, RouterAuditorPort},
 };
 use async_trait::async_trait;
+use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
 use headless_chrome::Tab;
 use std::env;
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
+use super::attempts::{
+    report_detecting, run_credential_attempts, AttemptContext, AttemptOutcome, AttemptPolicy, LoginAttemptTrail,
+};
 use super::browser_driver::BrowserDriver;
-use super::credentials::DEFAULT_GATEWAY_CREDENTIALS;
-use super::dom_parser::parse_router_text;
+use super::credentials::load_gateway_credentials;
+use super::dom_parser::{parse_table_rows, ParsedRouterDevice};
 use super::enrichment::enrich_router_devices;
+use super::evidence::sanitize_html;
+use super::fingerprint;
+use super::html_extractor::parse_with_text_fallback;
+use super::profiles::{load_profiles, select_profile, ExtractionRules, PageFingerprint, RouterProfile};
 use super::scripts::ScriptArsenal;
 
 // Auditor de router basado en automatizacion de Chrome.
@@ -44,26 +52,86 @@ impl ChromeAuditor {
         }
     }
 
-    fn audit_gateway_blocking(&self, ip: &str) -> RouterAuditResult {
+    // Abre la raiz del router y elige el perfil de fabricante por titulo/HTML/URL.
+    fn detect_profile(&self, tab: &Arc<Tab>, ip: &str) -> Option<RouterProfile> {
+        let profiles = load_profiles();
+        let _ = BrowserDriver::navigate_and_wait(tab, &format!("http://{}/", ip));
+        thread::sleep(Duration::from_millis(1000));
+
+        let page = tab
+            .evaluate(ScriptArsenal::injection_fingerprint(), false)
+            .ok()
+            .and_then(|res| res.value)
+            .and_then(|v| v.as_str().map(str::to_string))
+            .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+            .map(|v| PageFingerprint {
+                title: v["title"].as_str().unwrap_or("").to_string(),
+                url: v["url"].as_str().unwrap_or("").to_string(),
+                html: v["html"].as_str().unwrap_or("").to_string(),
+            })
+            .unwrap_or_default();
+
+        let profile = select_profile(&profiles, &page).cloned();
+        match &profile {
+            Some(p) => self.log(&format!("🧬 Perfil de router: {} ({})", p.vendor, p.id)),
+            None => self.log("❌ Sin perfiles de router disponibles."),
+        }
+        profile
+    }
+
+    // La cancelacion se comprueba entre intentos: al salir se suelta el `Browser` y se cierra Chrome.
+    fn audit_gateway_blocking(&self, ip: &str, observer: &dyn RouterAuditObserver) -> RouterAuditResult {
         self.log(&format!("⚔️ ROUTER AUDIT: Iniciando brute-force a {}...", ip));
+        report_detecting(observer, "chrome");
+        let mut failure = "Failed".to_string();
+        let mut cancelled = false;
 
         let headless = Self::should_run_chrome_headless();
         match BrowserDriver::launch(headless) {
             Ok(browser) => {
                 if let Ok(tab) = browser.new_tab() {
-                    let url = format!("http://{}/", ip);
-
-                    for (user, pass) in DEFAULT_GATEWAY_CREDENTIALS {
-                        if self.try_credentials(&tab, &url, user, pass) {
+                    if let Some(profile) = self.detect_profile(&tab, ip) {
+                        let url = format!("http://{}{}", ip, profile.login.path);
+                        let credentials = load_gateway_credentials(&profile);
+                        self.capture_login_page(&tab, &url, observer);
+                        let ctx = AttemptContext {
+                            target_ip: ip,
+                            backend: "chrome",
+                            profile_id: &profile.id,
+                            observer,
+                        };
+                        let summary = run_credential_attempts(
+                            &ctx,
+                            &credentials,
+                            &AttemptPolicy::default(),
+                            &LoginAttemptTrail::new(),
+                            &|msg| self.log(msg),
+                            |user, pass| self.try_credentials(&tab, &url, &profile, user, pass),
+                        );
+
+                        if let Some((user, pass)) = summary.found.clone() {
                             self.log(&format!("🔓 ACCESO CONFIRMADO: {}/{}", user, pass));
+                            // La pestaña sigue autenticada: evidencias y estado antes de cerrar Chrome.
+                            self.capture_evidence(&tab, RouterEvidenceStage::Landing, observer);
+                            let pages = self.read_status_pages(&tab, ip, &profile);
+                            self.capture_device_list(&tab, ip, &profile, observer);
+                            let (fingerprint, findings) = fingerprint::fingerprint_router(ip, &pages, &|msg| self.log(msg));
                             self.log("🚀 Credenciales validas. Cerrando auditoria para iniciar sync...");
                             return RouterAuditResult {
+                                audit_id: None,
                                 target_ip: ip.to_string(),
                                 vulnerable: true,
-                                credentials_found: Some(format!("{}:{}", user, pass)),
-                                message: format!("Success: {}:{}", user, pass),
+                                credentials_found: Some(RouterCredentials { user: user.clone(), pass }),
+                                message: format!("Success: credenciales por defecto validas para '{}'", user),
+                                fingerprint: Some(fingerprint),
+                                findings,
                             };
                         }
+                        failure = summary.failure_message();
+                        cancelled = summary.cancelled;
+                        if summary.cancelled {
+                            self.log("⏹️ Sesion de Chrome cerrada tras la cancelacion.");
+                        }
                     }
                 }
             }
@@ -71,12 +139,73 @@ impl ChromeAuditor {
         }
 
         self.log("❌ FINALIZADO: el router resiste.");
+        // Sin login aun se puede identificar por cabeceras/favicon (salvo si el usuario cancelo).
+        let (fingerprint, findings) = if cancelled {
+            (None, Vec::new())
+        } else {
+            let (fingerprint, findings) = fingerprint::fingerprint_router(ip, &[], &|msg| self.log(msg));
+            (Some(fingerprint), findings)
+        };
         RouterAuditResult {
+            audit_id: None,
             target_ip: ip.to_string(),
             vulnerable: false,
             credentials_found: None,
-            message: "Failed".to_string(),
+            message: failure,
+            fingerprint,
+            findings,
+        }
+    }
+
+    // Login sin rellenar: los intentos navegan de nuevo a la misma URL.
+    fn capture_login_page(&self, tab: &Arc<Tab>, url: &str, observer: &dyn RouterAuditObserver) {
+        if BrowserDriver::navigate_and_wait(tab, url).is_ok() {
+            thread::sleep(Duration::from_millis(1500));
+            self.capture_evidence(tab, RouterEvidenceStage::LoginPage, observer);
+        }
+    }
+
+    fn capture_device_list(&self, tab: &Arc<Tab>, ip: &str, profile: &RouterProfile, observer: &dyn RouterAuditObserver) {
+        self.open_device_list(tab, ip, profile);
+        thread::sleep(Duration::from_millis(profile.devices.wait_ms));
+        self.capture_evidence(tab, RouterEvidenceStage::DeviceList, observer);
+    }
+
+    // Pantallazo + HTML saneado de la pagina actual. Best-effort: un fallo no corta la auditoria.
+    fn capture_evidence(&self, tab: &Arc<Tab>, stage: RouterEvidenceStage, observer: &dyn RouterAuditObserver) {
+        match tab.capture_screenshot(CaptureScreenshotFormatOption::Png, None, None, true) {
+            Ok(png) => observer.on_evidence(RouterEvidence {
+                stage,
+                kind: RouterEvidenceKind::Screenshot,
+                content: png,
+            }),
+            Err(e) => self.log(&format!("   ⚠️ Sin captura de {}: {}", stage.as_str(), e)),
+        }
+        if let Some(html) = Self::evaluate_string(tab, ScriptArsenal::injection_extract_html()) {
+            observer.on_evidence(RouterEvidence {
+                stage,
+                kind: RouterEvidenceKind::Html,
+                content: sanitize_html(&html).into_bytes(),
+            });
+        }
+        self.log(&format!("   📸 Evidencia capturada: {}", stage.as_str()));
+    }
+
+    // innerText de la landing tras el login y de las paginas de estado del perfil.
+    fn read_status_pages(&self, tab: &Arc<Tab>, ip: &str, profile: &RouterProfile) -> Vec<(String, String)> {
+        let mut pages = Vec::new();
+        if let Some(text) = Self::evaluate_string(tab, ScriptArsenal::injection_extract_text()) {
+            pages.push(("landing".to_string(), text));
+        }
+        for path in &profile.status_paths {
+            if BrowserDriver::navigate_and_wait(tab, &format!("http://{}{}", ip, path)).is_err() {
+                continue;
+            }
+            if let Some(text) = Self::evaluate_string(tab, ScriptArsenal::injection_extract_text()) {
+                pages.push((format!("page:{}", path), text));
+            }
         }
+        pages
     }
 
     fn fetch_connected_devices_blocking(&self, ip: &str, user: &str, pass: &str, headless: bool) -> Vec<Device> {
@@ -88,31 +217,32 @@ impl ChromeAuditor {
 
         if let Ok(browser) = BrowserDriver::launch(headless) {
             if let Ok(tab) = browser.new_tab() {
-                let url = format!("http://{}/", ip);
+                let Some(profile) = self.detect_profile(&tab, ip) else {
+                    return Vec::new();
+                };
+                let url = format!("http://{}{}", ip, profile.login.path);
 
                 // Login especifico para fetch (ya tenemos creds).
                 let _ = BrowserDriver::navigate_and_wait(&tab, &url);
                 thread::sleep(Duration::from_secs(1));
 
                 self.log("   🔑 Autenticandose...");
-                let _ = tab.evaluate(&ScriptArsenal::injection_login(user, pass), false);
+                let _ = tab.evaluate(&ScriptArsenal::injection_login(&profile.login, user, pass), false);
                 thread::sleep(Duration::from_millis(500));
-                let _ = tab.evaluate(ScriptArsenal::injection_click_submit(), false);
-
-                self.log("   ⏳ Esperando carga de lista (6s)...");
-                thread::sleep(Duration::from_secs(6));
+                let _ = tab.evaluate(&ScriptArsenal::injection_click_submit(&profile.login), false);
+                thread::sleep(Duration::from_secs(2));
 
-                self.log("   📄 Extrayendo datos del DOM...");
-                if let Ok(res) = tab.evaluate(ScriptArsenal::injection_extract_text(), false) {
-                    let text = res.value.as_ref().and_then(|v| v.as_str()).unwrap_or("");
+                self.open_device_list(&tab, ip, &profile);
 
-                    // 1) Parseo puro (sin ARP/vendor).
-                    let parsed = parse_router_text(text);
+                self.log(&format!("   ⏳ Esperando carga de lista ({}ms)...", profile.devices.wait_ms));
+                thread::sleep(Duration::from_millis(profile.devices.wait_ms));
 
-                    // 2) Enriquecimiento local (ARP + vendor).
+                self.log("   📄 Extrayendo datos del DOM...");
+                if let Some(parsed) = Self::extract_devices(&tab, &profile) {
+                    // Enriquecimiento local (ARP + vendor).
                     let devices = enrich_router_devices(parsed);
 
-                    // 3) Logging post-enriquecimiento: aqui el MAC/vendor ya es el "final".
+                    // Logging post-enriquecimiento: aqui el MAC/vendor ya es el "final".
                     for d in devices.iter() {
                         let name = d
                             .name
@@ -133,21 +263,63 @@ impl ChromeAuditor {
         Vec::new()
     }
 
-    fn try_credentials(&self, tab: &Arc<Tab>, url: &str, user: &str, pass: &str) -> bool {
-        self.log(&format!("👉 Probando: {} / {}", user, pass));
+    fn open_device_list(&self, tab: &Arc<Tab>, ip: &str, profile: &RouterProfile) {
+        if let Some(path) = &profile.devices.path {
+            let _ = BrowserDriver::navigate_and_wait(tab, &format!("http://{}{}", ip, path));
+        }
+        for selector in &profile.devices.click_selectors {
+            let _ = tab.evaluate(&ScriptArsenal::injection_click(selector), false);
+            thread::sleep(Duration::from_millis(1000));
+        }
+    }
 
+    // Parseo puro (sin ARP/vendor) segun el modo de extraccion del perfil.
+    fn extract_devices(tab: &Arc<Tab>, profile: &RouterProfile) -> Option<Vec<ParsedRouterDevice>> {
+        match &profile.devices.extraction {
+            ExtractionRules::Text(rules) => {
+                // DOM estructurado (tablas/listas) primero; innerText solo si no hay nada util.
+                let html = Self::evaluate_string(tab, ScriptArsenal::injection_extract_html())?;
+                Some(parse_with_text_fallback(
+                    &html,
+                    || Self::evaluate_string(tab, ScriptArsenal::injection_extract_text()).unwrap_or_default(),
+                    rules,
+                ))
+            }
+            ExtractionRules::Table(rules) => {
+                let res = tab.evaluate(&ScriptArsenal::injection_extract_table_rows(rules), false).ok()?;
+                let raw = res.value.as_ref().and_then(|v| v.as_str()).unwrap_or("[]");
+                let rows: Vec<Vec<String>> = serde_json::from_str(raw).unwrap_or_default();
+                Some(parse_table_rows(&rows, rules))
+            }
+        }
+    }
+
+    fn evaluate_string(tab: &Arc<Tab>, script: &str) -> Option<String> {
+        let res = tab.evaluate(script, false).ok()?;
+        Some(res.value.as_ref().and_then(|v| v.as_str()).unwrap_or("").to_string())
+    }
+
+    fn login_status(tab: &Arc<Tab>, profile: &RouterProfile, url: &str) -> String {
+        tab.evaluate(&ScriptArsenal::injection_login_status(&profile.login.success, url), false)
+            .ok()
+            .and_then(|res| res.value)
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| "WAIT".to_string())
+    }
+
+    fn try_credentials(&self, tab: &Arc<Tab>, url: &str, profile: &RouterProfile, user: &str, pass: &str) -> AttemptOutcome {
         // Limpieza previa.
         let _ = tab.navigate_to("about:blank");
         thread::sleep(Duration::from_millis(100));
 
         // Navegacion.
         if BrowserDriver::navigate_and_wait(tab, url).is_err() {
-            return false;
+            return AttemptOutcome::Error;
         }
         thread::sleep(Duration::from_millis(1500));
 
         // Inyeccion de credenciales.
-        let js_fill = ScriptArsenal::injection_login(user, pass);
+        let js_fill = ScriptArsenal::injection_login(&profile.login, user, pass);
         match tab.evaluate(&js_fill, false) {
             Ok(res) => {
                 let val = res
@@ -155,38 +327,40 @@ impl ChromeAuditor {
                     .as_ref()
                     .and_then(|v| v.as_str())
                     .unwrap_or("ERR");
-                if val == "NO_USER" {
-                    // Check rapido: si no estamos en login, puede que ya haya sesion.
-                    let current = tab.get_url();
-                    if !current.contains("login") && current.len() > url.len() + 5 {
-                        return true;
-                    }
-                    return false;
+                if val == "NO_USER" || val == "NO_PASS" {
+                    // Check rapido: sin formulario puede que ya haya sesion abierta (o un aviso de bloqueo).
+                    return match Self::login_status(tab, profile, url).as_str() {
+                        "OK" => AttemptOutcome::Success,
+                        "LOCKED" => AttemptOutcome::LockedOut,
+                        _ => AttemptOutcome::Rejected,
+                    };
                 }
             }
-            Err(_) => return false,
+            Err(_) => return AttemptOutcome::Error,
         }
 
         // Click submit.
         self.log("   ⚡ Enviando submit...");
         thread::sleep(Duration::from_millis(500));
-        let _ = tab.evaluate(ScriptArsenal::injection_click_submit(), false);
+        let _ = tab.evaluate(&ScriptArsenal::injection_click_submit(&profile.login), false);
 
-        // Verificacion de exito: URL cambia?
+        // Verificacion de exito segun el perfil (selector del panel / URL / texto de error).
         for _ in 0..6 {
             thread::sleep(Duration::from_millis(500));
-            let post_url = tab.get_url();
-            if !post_url.contains("login") && post_url != url {
-                return true;
+            match Self::login_status(tab, profile, url).as_str() {
+                "OK" => return AttemptOutcome::Success,
+                "FAIL" => return AttemptOutcome::Rejected,
+                "LOCKED" => return AttemptOutcome::LockedOut,
+                _ => {}
             }
         }
-        false
+        AttemptOutcome::Rejected
     }
 }
 
 #[async_trait]
 impl RouterAuditorPort for ChromeAuditor {
-    async fn audit_gateway(&self, ip: &str) -> RouterAuditResult {
+    async fn audit_gateway(&self, ip: &str, observer: Arc<dyn RouterAuditObserver>) -> RouterAuditResult {
         // Importante: headless_chrome + sleeps es bloqueante. Lo movemos a un hilo dedicado.
         let ip_for_task = ip.to_string();
         let ip_for_err = ip_for_task.clone();
@@ -194,30 +368,25 @@ impl RouterAuditorPort for ChromeAuditor {
 
         tauri::async_runtime::spawn_blocking(move || {
             let auditor = ChromeAuditor { log_callback };
-            auditor.audit_gateway_blocking(&ip_for_task)
+            auditor.audit_gateway_blocking(&ip_for_task, observer.as_ref())
         })
         .await
         .unwrap_or_else(|_| RouterAuditResult {
+            audit_id: None,
             target_ip: ip_for_err,
             vulnerable: false,
             credentials_found: None,
             message: "JoinError".to_string(),
+            fingerprint: None,
+            findings: Vec::new(),
         })
     }
 
-    async fn fetch_connected_devices(&self, ip: &str, creds: &str) -> Vec<Device> {
-        let parts: Vec<&str> = creds.split(':').collect();
-        let (user, pass) = if parts.len() == 2 {
-            (parts[0], parts[1])
-        } else {
-            // Fallback defensivo: si el formato no es user:pass, usamos admin/admin.
-            ("admin", "admin")
-        };
-
+    async fn fetch_connected_devices(&self, ip: &str, creds: &RouterCredentials) -> Vec<Device> {
         // Importante: el scraping via Chrome es bloqueante. Lo ejecutamos en paralelo.
         let ip = ip.to_string();
-        let user = user.to_string();
-        let pass = pass.to_string();
+        let user = creds.user.clone();
+        let pass = creds.pass.clone();
         let log_callback = self.log_callback.clone();
 
         let headless = ChromeAuditor::should_run_chrome_headless();
@@ -1,10 +1,16 @@
 // src-tauri/src/infrastructure/router_audit/credentials.rs
 
+use directories::ProjectDirs;
+use std::collections::HashSet;
+use std::fs;
+
+use super::profiles::RouterProfile;
+
 // Credenciales por defecto (laboratorio) para auditoria de gateway.
 // Importante:
 // - Esto NO intenta cubrir diccionarios ni fuerza bruta real.
 // - Es una lista pequeña y controlada para entornos educativos autorizados.
-// - Si se necesita ampliar, hacerlo de forma configurable (AppData) y documentar en `docs/SECURITY.md`.
+// - Ampliable via AppData (ver `load_gateway_credentials` y `docs/SECURITY.md`); tope en `AttemptPolicy::max_attempts`.
 
 pub const DEFAULT_GATEWAY_CREDENTIALS: &[(&str, &str)] = &[
     ("admin", "admin"),
@@ -13,3 +19,77 @@ pub const DEFAULT_GATEWAY_CREDENTIALS: &[(&str, &str)] = &[
     ("1234", "1234"),
 ];
 
+// Orden de carga (JSON `[["usuario","clave"], ...]`):
+// 1) AppData `router_credentials/<perfil>.json` (por fabricante)
+// 2) AppData `router_credentials.json` (general)
+// Si no hay ficheros de usuario: credenciales de fabrica del perfil + lista por defecto.
+pub fn load_gateway_credentials(profile: &RouterProfile) -> Vec<(String, String)> {
+    let custom: Vec<(String, String)> = [appdata_credentials(Some(&profile.id)), appdata_credentials(None)]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .collect();
+
+    let candidates = if custom.is_empty() {
+        profile
+            .default_credentials
+            .iter()
+            .cloned()
+            .chain(DEFAULT_GATEWAY_CREDENTIALS.iter().map(|(u, p)| (u.to_string(), p.to_string())))
+            .collect()
+    } else {
+        custom
+    };
+    sanitize_credentials(candidates)
+}
+
+fn appdata_credentials(profile_id: Option<&str>) -> Option<Vec<(String, String)>> {
+    let proj_dirs = ProjectDirs::from("com", "netsentinel", "app")?;
+    let path = match profile_id {
+        // El id viene de ficheros de usuario: nada de rutas relativas.
+        Some(id) if id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') => {
+            proj_dirs.data_dir().join("router_credentials").join(format!("{}.json", id))
+        }
+        Some(_) => return None,
+        None => proj_dirs.data_dir().join("router_credentials.json"),
+    };
+    let content = fs::read_to_string(path).ok()?;
+    parse_credentials(&content)
+}
+
+pub fn parse_credentials(content: &str) -> Option<Vec<(String, String)>> {
+    let raw: Vec<(String, String)> = serde_json::from_str(content).ok()?;
+    Some(sanitize_credentials(raw))
+}
+
+// Sin tope aqui: lo aplica `AttemptPolicy::max_attempts` al probar (y avisa si recorta el diccionario).
+fn sanitize_credentials(raw: Vec<(String, String)>) -> Vec<(String, String)> {
+    let mut seen = HashSet::new();
+    let mut out: Vec<(String, String)> = Vec::new();
+    for (user, pass) in raw {
+        // Usuario vacio permitido (routers con solo clave); clave vacia tambien (routers "sin clave").
+        let valid = |s: &str| s.len() <= 64 && !s.chars().any(|ch| ch.is_control());
+        if !valid(&user) || !valid(&pass) || !seen.insert((user.clone(), pass.clone())) {
+            continue;
+        }
+        out.push((user, pass));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_credentials_dedups_and_drops_invalid_pairs_without_capping() {
+        let list = parse_credentials(r#"[["admin","admin"],["admin","admin"],["", "1234"],["root","a\u0007"]]"#).unwrap();
+        assert_eq!(list, vec![("admin".to_string(), "admin".to_string()), ("".to_string(), "1234".to_string())]);
+
+        let many: Vec<(String, String)> = (0..50).map(|i| ("admin".to_string(), format!("p{i}"))).collect();
+        let json = serde_json::to_string(&many).unwrap();
+        // El recorte es cosa de la politica de intentos, no del diccionario.
+        assert_eq!(parse_credentials(&json).unwrap().len(), 50);
+        assert!(parse_credentials("{}").is_none());
+    }
+}
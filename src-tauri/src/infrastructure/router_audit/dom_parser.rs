@@ -1,6 +1,7 @@
 // src-tauri/src/infrastructure/router_audit/dom_parser.rs
 
 use regex::Regex;
+use serde::Deserialize;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ParsedRouterDevice {
@@ -10,6 +11,46 @@ pub struct ParsedRouterDevice {
     pub signal_strength: Option<String>,
     pub signal_rate: Option<String>,
     pub wifi_band: Option<String>,
+    // Solo backends con API estructurada (TR-064); el DOM no los expone de forma fiable.
+    pub interface_type: Option<String>,
+    pub active: Option<bool>,
+}
+
+// Etiquetas del layout de texto (configurables por perfil de router).
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TextRules {
+    pub ip_label: String,
+    pub signal_label: String,
+    pub rate_label: String,
+}
+
+impl Default for TextRules {
+    fn default() -> Self {
+        Self {
+            ip_label: "IP:".to_string(),
+            signal_label: "Signal strength:".to_string(),
+            rate_label: "Signal rate:".to_string(),
+        }
+    }
+}
+
+// Columnas de una tabla de clientes (indices 0-based sobre las celdas de cada fila).
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableRules {
+    pub row_selector: String,
+    pub ip_column: usize,
+    #[serde(default)]
+    pub mac_column: Option<usize>,
+    #[serde(default)]
+    pub name_column: Option<usize>,
+}
+
+// Layout por defecto (perfil generico); en produccion el auditor pasa las reglas del perfil.
+#[cfg(test)]
+pub fn parse_router_text(text: &str) -> Vec<ParsedRouterDevice> {
+    parse_router_text_with(text, &TextRules::default())
 }
 
 // Parser puro del texto de DOM extraido del panel del router.
@@ -19,7 +60,7 @@ pub struct ParsedRouterDevice {
 // - ruido (lineas vacias, encabezados)
 //
 // Regla: NO resolver vendor ni ARP aqui. Eso es enrichment (otra responsabilidad).
-pub fn parse_router_text(text: &str) -> Vec<ParsedRouterDevice> {
+pub fn parse_router_text_with(text: &str, rules: &TextRules) -> Vec<ParsedRouterDevice> {
     let mut devices = Vec::new();
     let lines: Vec<&str> = text.split('\n').collect();
     let mut current_band = "2.4 GHz".to_string();
@@ -37,8 +78,8 @@ pub fn parse_router_text(text: &str) -> Vec<ParsedRouterDevice> {
             current_band = "2.4 GHz".to_string();
         }
 
-        if line.starts_with("IP:") {
-            let ip = line.replace("IP:", "").trim().to_string();
+        if line.starts_with(rules.ip_label.as_str()) {
+            let ip = line.replace(rules.ip_label.as_str(), "").trim().to_string();
 
             // Nombre/alias del dispositivo (si existe).
             let mut name_found: Option<String> = None;
@@ -47,6 +88,8 @@ pub fn parse_router_text(text: &str) -> Vec<ParsedRouterDevice> {
                 let candidate = lines[i - k].trim();
                 if candidate.is_empty()
                     || candidate.starts_with("Signal")
+                    || candidate.starts_with(rules.signal_label.as_str())
+                    || candidate.starts_with(rules.rate_label.as_str())
                     || candidate.contains("GHz")
                     || candidate.contains("connected devices")
                     || re_ip.is_match(candidate)
@@ -98,14 +141,14 @@ pub fn parse_router_text(text: &str) -> Vec<ParsedRouterDevice> {
             for j in 1..6 {
                 if i + j < lines.len() {
                     let next = lines[i + j].trim();
-                    if next.starts_with("Signal strength:") {
+                    if next.starts_with(rules.signal_label.as_str()) {
                         signal = next
-                            .replace("Signal strength:", "")
+                            .replace(rules.signal_label.as_str(), "")
                             .trim()
                             .to_string();
                     }
-                    if next.starts_with("Signal rate:") {
-                        rate = next.replace("Signal rate:", "").trim().to_string();
+                    if next.starts_with(rules.rate_label.as_str()) {
+                        rate = next.replace(rules.rate_label.as_str(), "").trim().to_string();
                     }
                 }
             }
@@ -117,6 +160,8 @@ pub fn parse_router_text(text: &str) -> Vec<ParsedRouterDevice> {
                 signal_strength: Some(signal),
                 signal_rate: Some(rate),
                 wifi_band: Some(current_band.clone()),
+                interface_type: None,
+                active: None,
             });
         }
 
@@ -126,6 +171,35 @@ pub fn parse_router_text(text: &str) -> Vec<ParsedRouterDevice> {
     devices
 }
 
+// Filas de tabla ya extraidas del DOM (texto por celda). Las filas sin IPv4 valida
+// en `ip_column` (cabeceras, separadores) se descartan.
+pub fn parse_table_rows(rows: &[Vec<String>], rules: &TableRules) -> Vec<ParsedRouterDevice> {
+    let re_ip = Regex::new(r"^(?:\d{1,3}\.){3}\d{1,3}$").unwrap();
+    let re_mac = Regex::new(r"(?i)([0-9a-f]{2}[:-]){5}[0-9a-f]{2}").unwrap();
+    let cell = |row: &Vec<String>, idx: Option<usize>| -> Option<String> {
+        idx.and_then(|i| row.get(i)).map(|c| c.trim().to_string()).filter(|c| !c.is_empty())
+    };
+
+    rows.iter()
+        .filter_map(|row| {
+            let ip = cell(row, Some(rules.ip_column)).filter(|ip| re_ip.is_match(ip))?;
+            let mac = cell(row, rules.mac_column)
+                .and_then(|m| re_mac.find(&m).map(|f| f.as_str().replace('-', ":").to_uppercase()));
+            let name = cell(row, rules.name_column).filter(|n| *n != ip && n != "-");
+            Some(ParsedRouterDevice {
+                ip,
+                name,
+                mac,
+                signal_strength: None,
+                signal_rate: None,
+                wifi_band: None,
+                interface_type: None,
+                active: None,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,4 +265,45 @@ Signal rate: 390 Mbps
         assert_eq!(d[1].name.as_deref(), Some("Laptop-Office"));
         assert_eq!(d[1].mac, None);
     }
+
+    #[test]
+    fn parse_router_text_with_acepta_etiquetas_de_otro_firmware() {
+        let sample = r#"
+Movil-Ana
+Direccion IP: 192.168.0.23
+Intensidad: -51 dBm
+Velocidad: 144 Mbps
+"#;
+        let rules = TextRules {
+            ip_label: "Direccion IP:".to_string(),
+            signal_label: "Intensidad:".to_string(),
+            rate_label: "Velocidad:".to_string(),
+        };
+        let d = parse_router_text_with(sample, &rules);
+        assert_eq!(d.len(), 1);
+        assert_eq!(d[0].ip, "192.168.0.23");
+        assert_eq!(d[0].name.as_deref(), Some("Movil-Ana"));
+        assert_eq!(d[0].signal_rate.as_deref(), Some("144 Mbps"));
+    }
+
+    #[test]
+    fn parse_table_rows_descarta_cabeceras_y_normaliza_mac() {
+        let rows = vec![
+            vec!["Nombre".to_string(), "IP".to_string(), "MAC".to_string()],
+            vec!["NAS".to_string(), "192.168.1.5".to_string(), "00-11-32-aa-bb-cc".to_string()],
+            vec!["-".to_string(), "192.168.1.6".to_string(), "".to_string()],
+        ];
+        let rules = TableRules {
+            row_selector: "tr".to_string(),
+            ip_column: 1,
+            mac_column: Some(2),
+            name_column: Some(0),
+        };
+        let d = parse_table_rows(&rows, &rules);
+        assert_eq!(d.len(), 2);
+        assert_eq!(d[0].mac.as_deref(), Some("00:11:32:AA:BB:CC"));
+        assert_eq!(d[0].name.as_deref(), Some("NAS"));
+        assert_eq!(d[1].name, None);
+        assert_eq!(d[1].mac, None);
+    }
 }
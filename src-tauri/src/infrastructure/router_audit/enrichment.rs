@@ -45,6 +45,10 @@ fn enrich_router_devices_with_arp(
                 signal_rate: p.signal_rate,
                 wifi_band: p.wifi_band,
                 open_ports: None,
+                model: None,
+                firmware: None,
+                interface_type: p.interface_type,
+                active: p.active,
             }
         })
         .collect()
@@ -0,0 +1,146 @@
+// src-tauri/src/infrastructure/router_audit/evidence.rs
+
+use regex::{Captures, Regex};
+use std::sync::OnceLock;
+
+const REDACTED: &str = "[REDACTED]";
+// Campos ocultos cuyo valor es un secreto de sesion aunque el `type` no lo diga; las claves WiFi
+// (psk, wpa_key, wep...) suelen ir en inputs de texto de la pagina de red inalambrica.
+const SECRET_FIELD_HINTS: [&str; 10] =
+    ["csrf", "token", "nonce", "session", "sid", "pass", "psk", "wpa_key", "key", "wep"];
+
+static EMBEDDED_RE: OnceLock<Regex> = OnceLock::new();
+static EMBEDDED_TAG_RE: OnceLock<Regex> = OnceLock::new();
+static TAG_RE: OnceLock<Regex> = OnceLock::new();
+static HANDLER_RE: OnceLock<Regex> = OnceLock::new();
+static JS_URL_RE: OnceLock<Regex> = OnceLock::new();
+static INPUT_RE: OnceLock<Regex> = OnceLock::new();
+static META_RE: OnceLock<Regex> = OnceLock::new();
+static ATTR_RE: OnceLock<Regex> = OnceLock::new();
+
+// HTML apto para adjuntar al informe: sin scripts, iframes, objetos, manejadores inline ni URLs
+// `javascript:`, y con contrasenas, tokens CSRF, claves WiFi y campos ocultos de sesion sustituidos
+// por "[REDACTED]".
+pub fn sanitize_html(html: &str) -> String {
+    let embedded = EMBEDDED_RE.get_or_init(|| {
+        Regex::new(r"(?is)<script\b[^>]*>.*?</script\s*>|<iframe\b[^>]*>.*?</iframe\s*>|<object\b[^>]*>.*?</object\s*>")
+            .unwrap()
+    });
+    // Aperturas o cierres sueltos (sin pareja) de los mismos elementos.
+    let embedded_tags =
+        EMBEDDED_TAG_RE.get_or_init(|| Regex::new(r"(?i)</?(?:script|iframe|object)\b[^>]*>").unwrap());
+    let tags = TAG_RE.get_or_init(|| Regex::new(r"<[a-zA-Z][^>]*>").unwrap());
+    let handlers =
+        HANDLER_RE.get_or_init(|| Regex::new(r#"(?i)\s+on[a-z]+\s*=\s*("[^"]*"|'[^']*'|[^\s>]+)"#).unwrap());
+    let js_urls = JS_URL_RE.get_or_init(|| {
+        Regex::new(r#"(?i)\s+[a-z:-]+\s*=\s*("\s*javascript:[^"]*"|'\s*javascript:[^']*'|javascript:[^\s>]*)"#).unwrap()
+    });
+    let inputs = INPUT_RE.get_or_init(|| Regex::new(r"(?i)<input\b[^>]*>").unwrap());
+    let metas = META_RE.get_or_init(|| Regex::new(r"(?i)<meta\b[^>]*>").unwrap());
+
+    let html = embedded.replace_all(html, "");
+    let html = embedded_tags.replace_all(&html, "");
+    // Solo dentro de etiquetas: un "online = ..." en el texto visible no es un manejador.
+    let html = tags.replace_all(&html, |caps: &Captures| {
+        let tag = handlers.replace_all(&caps[0], "");
+        js_urls.replace_all(&tag, "").into_owned()
+    });
+    let html = inputs.replace_all(&html, |caps: &Captures| {
+        let tag = &caps[0];
+        if is_secret_input(tag) {
+            redact_attr(tag, "value")
+        } else {
+            tag.to_string()
+        }
+    });
+    let sanitized = metas.replace_all(&html, |caps: &Captures| {
+        let tag = &caps[0];
+        if has_secret_hint(attr(tag, "name").as_deref().unwrap_or("")) {
+            redact_attr(tag, "content")
+        } else {
+            tag.to_string()
+        }
+    });
+    sanitized.into_owned()
+}
+
+fn is_secret_input(tag: &str) -> bool {
+    let kind = attr(tag, "type").unwrap_or_default().to_lowercase();
+    kind == "password" || (kind == "hidden" && attr(tag, "value").is_some()) || {
+        let name = attr(tag, "name").or_else(|| attr(tag, "id")).unwrap_or_default();
+        has_secret_hint(&name)
+    }
+}
+
+fn has_secret_hint(name: &str) -> bool {
+    let name = name.to_lowercase();
+    SECRET_FIELD_HINTS.iter().any(|hint| name.contains(hint))
+}
+
+// Cualquier atributo `nombre=valor` de una etiqueta; el nombre se compara fuera del regex.
+fn attr_regex() -> &'static Regex {
+    ATTR_RE.get_or_init(|| Regex::new(r#"\s([a-zA-Z_:][-a-zA-Z0-9_:.]*)\s*=\s*("[^"]*"|'[^']*'|[^\s>]+)"#).unwrap())
+}
+
+fn attr(tag: &str, name: &str) -> Option<String> {
+    let caps = attr_regex().captures_iter(tag).find(|caps| caps[1].eq_ignore_ascii_case(name))?;
+    Some(caps[2].trim_matches(|c| c == '"' || c == '\'').to_string())
+}
+
+fn redact_attr(tag: &str, name: &str) -> String {
+    let redacted = attr_regex().replace_all(tag, |caps: &Captures| {
+        if caps[1].eq_ignore_ascii_case(name) {
+            format!(r#" {}="{}""#, name, REDACTED)
+        } else {
+            caps[0].to_string()
+        }
+    });
+    redacted.into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_scripts_handlers_passwords_and_csrf_tokens() {
+        let html = r#"<html><head><meta name="csrf-token" content="abc123"><meta charset="utf-8">
+            <script>var pwd = "admin";</script></head>
+            <body onload="init()"><form>
+            <input type="text" name="username" value="admin">
+            <input type='password' name='pwd' value='hunter2'>
+            <input type="hidden" name="stok" value="f00dfeed">
+            <input name="csrf_token" value="zzz">
+            <button onclick='go()'>Login</button></form></body></html>"#;
+
+        let clean = sanitize_html(html);
+        for secret in ["abc123", "hunter2", "f00dfeed", "zzz", "var pwd", "init()", "go()"] {
+            assert!(!clean.contains(secret), "{} sigue en el HTML", secret);
+        }
+        assert!(clean.contains(r#"name="username" value="admin""#));
+        assert!(clean.contains(r#"<meta charset="utf-8">"#));
+        assert!(clean.contains("<button>Login</button>"));
+        assert_eq!(clean.matches(REDACTED).count(), 4);
+    }
+
+    #[test]
+    fn strips_wifi_keys_javascript_urls_and_embedded_frames() {
+        let html = r#"<form action="javascript:save()">
+            <input type="text" name="wpa_psk" value="CasaWifi2024">
+            <input type="text" name="wep_key1" value="0A1B2C3D4E">
+            <input type="text" name="channel" value="11">
+            <a href=" JavaScript:alert(1)">Guardar</a><a href="/status.htm">Estado</a>
+            <iframe src="http://evil.example/"><p>frame</p></iframe>
+            <object data="applet.swf"><param name="x" value="y"></object>
+            <iframe src="http://evil.example/unclosed"></form>"#;
+
+        let clean = sanitize_html(html);
+        for secret in ["CasaWifi2024", "0A1B2C3D4E", "save()", "alert(1)", "evil.example", "applet.swf", "<iframe"] {
+            assert!(!clean.contains(secret), "{} sigue en el HTML", secret);
+        }
+        assert!(clean.contains(r#"name="channel" value="11""#));
+        assert!(clean.contains("<a>Guardar</a>"));
+        assert!(clean.contains(r#"<a href="/status.htm">Estado</a>"#));
+        assert_eq!(clean.matches(REDACTED).count(), 2);
+    }
+}
@@ -0,0 +1,303 @@
+// src-tauri/src/infrastructure/router_audit/fingerprint.rs
+
+use regex::Regex;
+use reqwest::blocking::Client;
+use reqwest::{header, redirect, Url};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::domain::entities::{RouterFingerprint, Vulnerability};
+
+use super::firmware_db;
+use super::html_extractor::normalize_label;
+use super::html_login::page_title;
+use super::http_auth::md5_hex;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(4);
+// Un favicon real pesa unos KB; mas que esto no es un icono.
+const MAX_FAVICON_BYTES: usize = 256 * 1024;
+const MAX_VALUE_LEN: usize = 64;
+
+// Fabricantes que aparecen en realms, titulos y cabeceras (forma canonica).
+const KNOWN_VENDORS: &[(&str, &str)] = &[
+    ("netgear", "Netgear"),
+    ("tp-link", "TP-Link"),
+    ("tplink", "TP-Link"),
+    ("d-link", "D-Link"),
+    ("dlink", "D-Link"),
+    ("linksys", "Linksys"),
+    ("asus", "ASUS"),
+    ("huawei", "Huawei"),
+    ("zte", "ZTE"),
+    ("sagemcom", "Sagemcom"),
+    ("fritz!box", "AVM"),
+    ("avm", "AVM"),
+    ("zyxel", "Zyxel"),
+    ("technicolor", "Technicolor"),
+    ("mikrotik", "MikroTik"),
+    ("ubiquiti", "Ubiquiti"),
+    ("tenda", "Tenda"),
+    ("xiaomi", "Xiaomi"),
+    ("cisco", "Cisco"),
+];
+
+// Etiquetas de las paginas de estado (normalizadas con `normalize_label`).
+const MODEL_LABELS: &[&str] = &[
+    "model", "model name", "model number", "product name", "device model", "modelo", "nombre del modelo", "modele",
+    "nom du modele", "modello", "modell", "produktname", "model del dispositiu",
+];
+const HARDWARE_LABELS: &[&str] = &[
+    "hardware version", "hardware", "hw version", "version de hardware", "version del hardware", "version materielle",
+    "versione hardware", "hardwareversion", "hardware revision", "versio del maquinari",
+];
+const FIRMWARE_LABELS: &[&str] = &[
+    "firmware version", "firmware", "fw version", "software version", "sw version", "version de firmware",
+    "version del firmware", "version del software", "version du micrologiciel", "version du firmware",
+    "versione firmware", "firmwareversion", "fritz os", "versio del microprogramari",
+];
+
+// Huella sin login (cabecera `Server`, realm Basic/Digest, titulo y favicon) + paginas de estado
+// ya leidas por el auditor tras autenticarse (`(origen, texto)`), y cruce con la lista local de
+// firmwares vulnerables / modelos EOL.
+pub fn fingerprint_router(
+    ip: &str,
+    status_pages: &[(String, String)],
+    log: &dyn Fn(&str),
+) -> (RouterFingerprint, Vec<Vulnerability>) {
+    // Las paginas de estado mandan sobre realm/titulo (que a veces solo nombran la familia).
+    let mut fingerprint = RouterFingerprint::default();
+    for (source, text) in status_pages {
+        merge(&mut fingerprint, from_status_text(text, source));
+    }
+    merge(&mut fingerprint, probe_http(ip));
+    let findings = firmware_db::assess(&mut fingerprint, &firmware_db::load_advisories());
+
+    log(&format!("🧬 Huella del router: {}", summary(&fingerprint)));
+    for finding in &findings {
+        log(&format!("   🚨 {} [{}]: {}", finding.id, finding.severity, finding.description));
+    }
+    (fingerprint, findings)
+}
+
+// Linea de log: "Netgear DGN2200 (hw DGN2200v1, fw V1.0.0.50) via realm, page:/RST_status.htm".
+fn summary(fingerprint: &RouterFingerprint) -> String {
+    let mut text = [fingerprint.vendor.as_deref(), fingerprint.model.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() {
+        text = "modelo desconocido".to_string();
+    }
+    let versions: Vec<String> = [("hw", &fingerprint.hardware_version), ("fw", &fingerprint.firmware_version)]
+        .into_iter()
+        .filter_map(|(label, value)| value.as_ref().map(|v| format!("{} {}", label, v)))
+        .collect();
+    if !versions.is_empty() {
+        text.push_str(&format!(" ({})", versions.join(", ")));
+    }
+    if !fingerprint.sources.is_empty() {
+        text.push_str(&format!(" via {}", fingerprint.sources.join(", ")));
+    }
+    text
+}
+
+// Solo lectura y sin credenciales: GET de la raiz y del favicon.
+fn probe_http(ip: &str) -> RouterFingerprint {
+    let mut fingerprint = RouterFingerprint::default();
+    let Ok(client) = Client::builder()
+        .timeout(PROBE_TIMEOUT)
+        .redirect(redirect::Policy::limited(3))
+        .danger_accept_invalid_certs(true)
+        .build()
+    else {
+        return fingerprint;
+    };
+    let Ok(root) = Url::parse(&format!("http://{}/", ip)) else {
+        return fingerprint;
+    };
+
+    if let Ok(response) = client.get(root.clone()).send() {
+        let header_value = |name: header::HeaderName| {
+            response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string)
+        };
+        let server = header_value(header::SERVER);
+        let realm = header_value(header::WWW_AUTHENTICATE).and_then(|h| realm_of(&h));
+        if let Some(server) = server.map(|s| clip(&s)).filter(|s| !s.is_empty()) {
+            fingerprint.server = Some(server.clone());
+            push_source(&mut fingerprint, "header:server");
+            merge(&mut fingerprint, from_banner(&server, "header:server"));
+        }
+        if let Some(realm) = realm {
+            merge(&mut fingerprint, from_banner(&realm, "realm"));
+        }
+        if let Ok(html) = response.text() {
+            merge(&mut fingerprint, from_banner(&page_title(&html), "title"));
+        }
+    }
+
+    if let Ok(icon) = root.join("/favicon.ico").map(|url| client.get(url).send()) {
+        if let Some(bytes) = icon.ok().filter(|r| r.status().is_success()).and_then(|r| r.bytes().ok()) {
+            if !bytes.is_empty() && bytes.len() <= MAX_FAVICON_BYTES {
+                fingerprint.favicon_md5 = Some(md5_hex(&bytes));
+                push_source(&mut fingerprint, "favicon");
+            }
+        }
+    }
+    fingerprint
+}
+
+// Realm, titulo o `Server`: "NETGEAR DGN2200", "TL-WR841N", "FRITZ!Box 7590"...
+pub fn from_banner(banner: &str, source: &str) -> RouterFingerprint {
+    let mut fingerprint = RouterFingerprint::default();
+    let lower = banner.to_lowercase();
+    let Some((token, vendor)) = KNOWN_VENDORS.iter().find(|(token, _)| lower.contains(token)) else {
+        return fingerprint;
+    };
+    fingerprint.vendor = Some(vendor.to_string());
+
+    // Modelo: primer token alfanumerico con digitos tras la marca ("R7000", "DIR-859", "7590").
+    let start = lower.find(token).map(|i| i + token.len()).unwrap_or(0);
+    static RE_MODEL: OnceLock<Regex> = OnceLock::new();
+    let re_model = RE_MODEL.get_or_init(|| Regex::new(r"\b([A-Za-z]{0,6}-?\d{2,5}[A-Za-z0-9+-]*)\b").unwrap());
+    let model = re_model
+        .captures(banner.get(start..).unwrap_or(banner))
+        .or_else(|| re_model.captures(banner))
+        .map(|c| c[1].to_string())
+        .filter(|m| !m.contains('/'));
+    fingerprint.model = model;
+    push_source(&mut fingerprint, source);
+    fingerprint
+}
+
+// Texto de una pagina de estado: "Etiqueta: valor" o etiqueta y valor en lineas seguidas (tablas).
+pub fn from_status_text(text: &str, source: &str) -> RouterFingerprint {
+    let mut fingerprint = RouterFingerprint::default();
+    let lines: Vec<&str> = text.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    for (i, line) in lines.iter().enumerate() {
+        let (label, value) = match line.split_once(':') {
+            Some((label, value)) if !value.trim().is_empty() => (normalize_label(label), value.trim()),
+            _ => (normalize_label(line), lines.get(i + 1).copied().unwrap_or("")),
+        };
+        let value = clip(value);
+        if value.is_empty() {
+            continue;
+        }
+        let slot = if MODEL_LABELS.contains(&label.as_str()) {
+            &mut fingerprint.model
+        } else if HARDWARE_LABELS.contains(&label.as_str()) {
+            &mut fingerprint.hardware_version
+        } else if FIRMWARE_LABELS.contains(&label.as_str()) {
+            &mut fingerprint.firmware_version
+        } else {
+            continue;
+        };
+        slot.get_or_insert(value);
+    }
+
+    if fingerprint.vendor.is_none() {
+        fingerprint.vendor = fingerprint.model.as_deref().and_then(|m| from_banner(m, source).vendor);
+    }
+    if fingerprint.model.is_some() || fingerprint.firmware_version.is_some() || fingerprint.hardware_version.is_some() {
+        push_source(&mut fingerprint, source);
+    }
+    fingerprint
+}
+
+// Completa huecos: la primera fuente que aporta un dato gana.
+pub fn merge(into: &mut RouterFingerprint, other: RouterFingerprint) {
+    if into.vendor.is_none() {
+        into.vendor = other.vendor;
+    }
+    if into.model.is_none() {
+        into.model = other.model;
+    }
+    if into.hardware_version.is_none() {
+        into.hardware_version = other.hardware_version;
+    }
+    if into.firmware_version.is_none() {
+        into.firmware_version = other.firmware_version;
+    }
+    if into.server.is_none() {
+        into.server = other.server;
+    }
+    if into.favicon_md5.is_none() {
+        into.favicon_md5 = other.favicon_md5;
+    }
+    for source in other.sources {
+        push_source(into, &source);
+    }
+}
+
+fn push_source(fingerprint: &mut RouterFingerprint, source: &str) {
+    if !fingerprint.sources.iter().any(|s| s == source) {
+        fingerprint.sources.push(source.to_string());
+    }
+}
+
+fn realm_of(header: &str) -> Option<String> {
+    static RE_REALM: OnceLock<Regex> = OnceLock::new();
+    let re = RE_REALM.get_or_init(|| Regex::new(r#"(?i)realm="([^"]*)""#).unwrap());
+    re.captures(header).map(|c| c[1].trim().to_string()).filter(|r| !r.is_empty())
+}
+
+// Valores de paginas ajenas: se recortan para no inflar el informe.
+fn clip(value: &str) -> String {
+    value.trim().chars().take(MAX_VALUE_LEN).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::router_audit::html_login::html_to_text;
+
+    #[test]
+    fn from_status_text_lee_tablas_y_pares_en_varios_idiomas() {
+        let html = include_str!("./fixtures/router_status_netgear.html");
+        let fp = from_status_text(&html_to_text(html), "page:/RST_status.htm");
+        assert_eq!(fp.model.as_deref(), Some("DGN2200"));
+        assert_eq!(fp.hardware_version.as_deref(), Some("DGN2200v1"));
+        assert_eq!(fp.firmware_version.as_deref(), Some("V1.0.0.50_7.0.50"));
+        assert_eq!(fp.vendor.as_deref(), None);
+        assert_eq!(fp.sources, vec!["page:/RST_status.htm".to_string()]);
+
+        let es = "Estado del router\nModelo: Archer C7\nVersión de firmware: 3.15.3 Build 180114\nVersión del hardware: Archer C7 v2";
+        let fp = from_status_text(es, "landing");
+        assert_eq!(fp.model.as_deref(), Some("Archer C7"));
+        assert_eq!(fp.firmware_version.as_deref(), Some("3.15.3 Build 180114"));
+        assert_eq!(fp.hardware_version.as_deref(), Some("Archer C7 v2"));
+    }
+
+    #[test]
+    fn from_banner_reconoce_realm_y_titulo() {
+        let fp = from_banner("NETGEAR DGN2200", "realm");
+        assert_eq!(fp.vendor.as_deref(), Some("Netgear"));
+        assert_eq!(fp.model.as_deref(), Some("DGN2200"));
+
+        let fp = from_banner("D-LINK SYSTEMS, INC. | WIRELESS ROUTER | HOME : DIR-859", "title");
+        assert_eq!(fp.vendor.as_deref(), Some("D-Link"));
+        assert_eq!(fp.model.as_deref(), Some("DIR-859"));
+
+        assert_eq!(from_banner("Router login", "title"), RouterFingerprint::default());
+    }
+
+    #[test]
+    fn probe_http_lee_server_realm_y_favicon() {
+        use crate::infrastructure::service_probes::test_support::{response, spawn_stand_in_http};
+        let addr = spawn_stand_in_http(|req| match req.path.as_str() {
+            "/favicon.ico" => response("200 OK", &[("Content-Type", "image/x-icon")], "ICO"),
+            _ => response(
+                "401 Unauthorized",
+                &[("Server", "RomPager/4.07 UPnP/1.0"), ("WWW-Authenticate", "Basic realm=\"NETGEAR DGN2200\"")],
+                "",
+            ),
+        }).to_string();
+
+        let fp = probe_http(&addr);
+        assert_eq!(fp.server.as_deref(), Some("RomPager/4.07 UPnP/1.0"));
+        assert_eq!(fp.vendor.as_deref(), Some("Netgear"));
+        assert_eq!(fp.model.as_deref(), Some("DGN2200"));
+        assert_eq!(fp.favicon_md5.as_deref(), Some(md5_hex(b"ICO").as_str()));
+        assert_eq!(fp.sources, vec!["header:server", "realm", "favicon"]);
+    }
+}
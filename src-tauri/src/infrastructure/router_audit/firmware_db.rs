@@ -0,0 +1,329 @@
+// src-tauri/src/infrastructure/router_audit/firmware_db.rs
+
+use directories::ProjectDirs;
+use regex::Regex;
+use serde::Deserialize;
+use std::fs;
+use std::sync::OnceLock;
+
+use crate::domain::entities::{RouterFingerprint, Vulnerability};
+
+// Lista local de firmwares vulnerables y modelos EOL. La semilla va embebida; AppData
+// `router_firmware_advisories.json` (mismo esquema) la amplia y un `id` repetido la sustituye.
+const BUILTIN_ADVISORIES: &str = include_str!("firmware_advisories.json");
+const USER_ADVISORIES_FILE: &str = "router_firmware_advisories.json";
+const MAX_USER_ENTRIES: usize = 512;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AdvisoryList {
+    pub advisories: Vec<FirmwareAdvisory>,
+    pub favicons: Vec<FaviconEntry>,
+}
+
+// Coincide por fabricante + modelo (y hardware si se indica) o por producto de la cabecera `Server`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FirmwareAdvisory {
+    pub id: String,
+    #[serde(default)]
+    pub vendor: Option<String>,
+    #[serde(default)]
+    pub models: Vec<String>,
+    #[serde(default)]
+    pub hardware: Option<String>,
+    #[serde(default)]
+    pub server: Option<String>,
+    // Primera version corregida; anterior => hallazgo "firmware desactualizado".
+    #[serde(default)]
+    pub fixed_version: Option<String>,
+    #[serde(default)]
+    pub eol: bool,
+    pub severity: String,
+    #[serde(default)]
+    pub references: Vec<String>,
+    pub summary: String,
+}
+
+// Hash MD5 del favicon -> fabricante/modelo (paneles sin nombre en titulo ni cabeceras).
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FaviconEntry {
+    pub md5: String,
+    pub vendor: String,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+pub fn load_advisories() -> AdvisoryList {
+    let mut list = parse_advisories(BUILTIN_ADVISORIES).unwrap_or_default();
+    if let Some(custom) = appdata_advisories() {
+        for advisory in custom.advisories.into_iter().take(MAX_USER_ENTRIES) {
+            match list.advisories.iter().position(|a| a.id == advisory.id) {
+                Some(idx) => list.advisories[idx] = advisory,
+                None => list.advisories.push(advisory),
+            }
+        }
+        list.favicons.extend(custom.favicons.into_iter().take(MAX_USER_ENTRIES));
+    }
+    list
+}
+
+fn appdata_advisories() -> Option<AdvisoryList> {
+    let proj_dirs = ProjectDirs::from("com", "netsentinel", "app")?;
+    let path = proj_dirs.data_dir().join(USER_ADVISORIES_FILE);
+    let content = fs::read_to_string(&path).ok()?;
+    match parse_advisories(&content) {
+        Ok(list) => Some(list),
+        Err(e) => {
+            println!("⚠️ [ROUTER] Lista de firmwares ignorada {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+pub fn parse_advisories(content: &str) -> Result<AdvisoryList, String> {
+    let list: AdvisoryList = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    if let Some(bad) = list
+        .advisories
+        .iter()
+        .find(|a| a.id.trim().is_empty() || (a.server.is_none() && a.models.is_empty()))
+    {
+        return Err(format!("entrada sin id o sin modelos/servidor: '{}'", bad.id));
+    }
+    Ok(list)
+}
+
+// Completa la huella con el favicon conocido y devuelve los hallazgos (EOL / firmware anterior al parche).
+pub fn assess(fingerprint: &mut RouterFingerprint, list: &AdvisoryList) -> Vec<Vulnerability> {
+    if let Some(hash) = fingerprint.favicon_md5.clone() {
+        if let Some(entry) = list.favicons.iter().find(|f| f.md5.eq_ignore_ascii_case(&hash)) {
+            fingerprint.vendor.get_or_insert_with(|| entry.vendor.clone());
+            if fingerprint.model.is_none() {
+                fingerprint.model = entry.model.clone();
+            }
+        }
+    }
+
+    let mut findings = Vec::new();
+    for advisory in &list.advisories {
+        let (matched, version) = match &advisory.server {
+            Some(product) => match server_version(fingerprint, product) {
+                Some(version) => (true, Some(version)),
+                None => (false, None),
+            },
+            None => (model_matches(fingerprint, advisory), fingerprint.firmware_version.clone()),
+        };
+        if !matched {
+            continue;
+        }
+
+        let evidence = Some(evidence_for(advisory));
+        let subject = subject_of(fingerprint, advisory);
+        if advisory.eol {
+            findings.push(Vulnerability {
+                id: "ROUTER-EOL".to_string(),
+                description: format!("{} esta fuera de soporte del fabricante: {}", subject, advisory.summary),
+                severity: advisory.severity.clone(),
+                recommendation: "Sustituir el equipo por un modelo con soporte; no recibira mas parches de seguridad.".to_string(),
+                evidence: evidence.clone(),
+            });
+        }
+        if let (Some(fixed), Some(current)) = (&advisory.fixed_version, version) {
+            if is_older(&current, fixed) {
+                findings.push(Vulnerability {
+                    id: "ROUTER-FIRMWARE-OUTDATED".to_string(),
+                    description: format!(
+                        "{} usa la version {}, anterior a la corregida {}: {}",
+                        subject, current, fixed, advisory.summary
+                    ),
+                    severity: advisory.severity.clone(),
+                    recommendation: format!("Actualizar a {} o superior desde la web oficial del fabricante.", fixed),
+                    evidence,
+                });
+            }
+        }
+    }
+    findings
+}
+
+fn model_matches(fingerprint: &RouterFingerprint, advisory: &FirmwareAdvisory) -> bool {
+    let Some(model) = fingerprint.model.as_deref().map(compact) else {
+        return false;
+    };
+    let vendor_ok = match (&advisory.vendor, &fingerprint.vendor) {
+        (Some(wanted), Some(found)) => compact(wanted) == compact(found),
+        _ => true,
+    };
+    // Si el aviso acota el hardware (ej: "v1") y no lo conocemos, mejor no afirmar nada.
+    let hardware_ok = match &advisory.hardware {
+        Some(wanted) => fingerprint.hardware_version.as_deref().is_some_and(|hw| same_hardware(hw, wanted)),
+        None => true,
+    };
+    vendor_ok && hardware_ok && advisory.models.iter().any(|m| compact(m) == model)
+}
+
+// "RomPager/4.07 UPnP/1.0" + "RomPager" -> "4.07".
+fn server_version(fingerprint: &RouterFingerprint, product: &str) -> Option<String> {
+    fingerprint
+        .server
+        .as_deref()?
+        .split_whitespace()
+        .filter_map(|token| token.split_once('/'))
+        .find(|(name, _)| name.eq_ignore_ascii_case(product))
+        .map(|(_, version)| version.to_string())
+}
+
+// Comparacion numerica por componentes: "V1.0.0.50_7.0.50" -> [1, 0, 0, 50, 7, 0, 50]. Solo cuentan los
+// componentes comunes: "1.0.0.50" frente a "1.0.0.50_7.0.50" no es un prefijo anterior, solo menos detalle.
+fn is_older(current: &str, fixed: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> {
+        v.split(|c: char| !c.is_ascii_digit())
+            .filter(|part| !part.is_empty())
+            .filter_map(|part| part.parse().ok())
+            .collect()
+    };
+    let (current, fixed) = (parse(current), parse(fixed));
+    let shared = current.len().min(fixed.len());
+    shared > 0 && current[..shared] < fixed[..shared]
+}
+
+// Revision pedida frente a la leida, por igualdad: "v2" no vale para "v20" ni para "Archer C7 v2.1".
+fn same_hardware(found: &str, wanted: &str) -> bool {
+    let wanted_revisions = revisions(wanted);
+    if wanted_revisions.is_empty() {
+        let wanted = compact(wanted);
+        return found.split(|c: char| !c.is_ascii_alphanumeric()).any(|token| compact(token) == wanted);
+    }
+    revisions(found).iter().any(|rev| wanted_revisions.contains(rev))
+}
+
+// "Archer C7 v2 00000000" -> ["2"], "DGN2200v1" -> ["1"], "V2.0" -> ["2"].
+fn revisions(value: &str) -> Vec<String> {
+    static RE_REVISION: OnceLock<Regex> = OnceLock::new();
+    let re = RE_REVISION.get_or_init(|| Regex::new(r"(?i)(?:^|[^a-z])v(\d+(?:\.\d+)*)").unwrap());
+    re.captures_iter(value).map(|c| c[1].trim_end_matches(".0").to_string()).collect()
+}
+
+fn compact(value: &str) -> String {
+    value.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_lowercase()
+}
+
+fn subject_of(fingerprint: &RouterFingerprint, advisory: &FirmwareAdvisory) -> String {
+    match (&advisory.server, &fingerprint.model) {
+        (Some(server), _) => format!("El servidor web {}", server),
+        (None, Some(model)) => format!(
+            "El router {}{}",
+            fingerprint.vendor.as_deref().map(|v| format!("{} ", v)).unwrap_or_default(),
+            model
+        ),
+        (None, None) => "El router".to_string(),
+    }
+}
+
+fn evidence_for(advisory: &FirmwareAdvisory) -> String {
+    if advisory.references.is_empty() {
+        advisory.id.clone()
+    } else {
+        format!("{} ({})", advisory.id, advisory.references.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint(vendor: &str, model: &str, firmware: Option<&str>) -> RouterFingerprint {
+        RouterFingerprint {
+            vendor: Some(vendor.to_string()),
+            model: Some(model.to_string()),
+            firmware_version: firmware.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn builtin_list_is_valid_and_flags_eol_models_and_old_rompager() {
+        let list = parse_advisories(BUILTIN_ADVISORIES).unwrap();
+        assert!(!list.advisories.is_empty());
+
+        let mut dgn = fingerprint("Netgear", "DGN2200", Some("V1.0.0.50_7.0.50"));
+        let findings = assess(&mut dgn, &list);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].id, "ROUTER-EOL");
+        assert!(findings[0].evidence.as_deref().unwrap().contains("CVE-2017-6334"));
+
+        let mut rompager = RouterFingerprint {
+            server: Some("RomPager/4.07 UPnP/1.0".to_string()),
+            ..Default::default()
+        };
+        let findings = assess(&mut rompager, &list);
+        assert_eq!(findings[0].id, "ROUTER-FIRMWARE-OUTDATED");
+        assert_eq!(findings[0].severity, "CRITICAL");
+
+        let mut patched = RouterFingerprint {
+            server: Some("RomPager/4.51".to_string()),
+            ..Default::default()
+        };
+        assert!(assess(&mut patched, &list).is_empty());
+    }
+
+    #[test]
+    fn fixed_version_compares_numeric_components_and_respects_hardware() {
+        let list = parse_advisories(
+            r#"{"advisories":[{"id":"X-1","vendor":"TP-Link","models":["Archer C7"],"hardware":"v2",
+                "fixedVersion":"3.15.3 Build 190000","severity":"HIGH","summary":"RCE"}],
+                "favicons":[{"md5":"ABCD","vendor":"TP-Link","model":"Archer C7"}]}"#,
+        )
+        .unwrap();
+
+        let mut old = fingerprint("tp-link", "ArcherC7", Some("3.15.3 Build 180114 Rel.39265n"));
+        old.hardware_version = Some("Archer C7 v2 00000000".to_string());
+        assert_eq!(assess(&mut old, &list)[0].id, "ROUTER-FIRMWARE-OUTDATED");
+
+        let mut new = old.clone();
+        new.firmware_version = Some("3.15.3 Build 200101".to_string());
+        assert!(assess(&mut new, &list).is_empty());
+
+        // Hardware desconocido: el aviso acotado a v2 no se aplica.
+        let mut unknown_hw = fingerprint("TP-Link", "Archer C7", Some("3.15.1"));
+        assert!(assess(&mut unknown_hw, &list).is_empty());
+
+        // Favicon conocido completa fabricante y modelo.
+        let mut by_icon = RouterFingerprint {
+            favicon_md5: Some("abcd".to_string()),
+            ..Default::default()
+        };
+        assess(&mut by_icon, &list);
+        assert_eq!(by_icon.model.as_deref(), Some("Archer C7"));
+
+        assert!(parse_advisories(r#"{"advisories":[{"id":"Y","severity":"LOW","summary":"s"}]}"#).is_err());
+    }
+
+    #[test]
+    fn shorter_versions_are_not_older_and_hardware_revisions_match_exactly() {
+        let list = parse_advisories(
+            r#"{"advisories":[{"id":"N-1","vendor":"Netgear","models":["DGN2200"],"hardware":"v2",
+                "fixedVersion":"V1.0.0.50_7.0.50","severity":"HIGH","summary":"RCE"}]}"#,
+        )
+        .unwrap();
+
+        // Sin el sufijo del parche no se puede afirmar que sea anterior.
+        let mut short = fingerprint("Netgear", "DGN2200", Some("V1.0.0.50"));
+        short.hardware_version = Some("DGN2200v2".to_string());
+        assert!(assess(&mut short, &list).is_empty());
+
+        let mut old = short.clone();
+        old.firmware_version = Some("V1.0.0.46_7.0.46".to_string());
+        assert_eq!(assess(&mut old, &list)[0].id, "ROUTER-FIRMWARE-OUTDATED");
+
+        for other_revision in ["DGN2200v20", "DGN2200 v2.1", "v1"] {
+            let mut other = old.clone();
+            other.hardware_version = Some(other_revision.to_string());
+            assert!(assess(&mut other, &list).is_empty(), "{other_revision}");
+        }
+        let mut same = old.clone();
+        same.hardware_version = Some("DGN2200 V2.0".to_string());
+        assert_eq!(assess(&mut same, &list).len(), 1);
+    }
+}
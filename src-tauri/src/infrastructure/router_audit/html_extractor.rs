@@ -0,0 +1,395 @@
+// src-tauri/src/infrastructure/router_audit/html_extractor.rs
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+use super::dom_parser::{parse_router_text_with, ParsedRouterDevice, TextRules};
+use super::html_login::{html_table_rows, html_to_text};
+
+// Compiladas una vez: el parser corre sobre cada tabla, lista y celda de la pagina de clientes.
+static TABLE_TAG_RE: OnceLock<Regex> = OnceLock::new();
+static LIST_ITEM_RE: OnceLock<Regex> = OnceLock::new();
+static IPV4_RE: OnceLock<Regex> = OnceLock::new();
+static MAC_RE: OnceLock<Regex> = OnceLock::new();
+
+// Columnas que sabemos mapear a `ParsedRouterDevice`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Column {
+    Ip,
+    Mac,
+    Name,
+    Interface,
+    Band,
+    Signal,
+}
+
+// Diccionario multilingue de cabeceras/etiquetas (ya normalizadas: minusculas, sin acentos).
+// Coincidencia exacta; para IP/MAC tambien basta con que aparezca el token ("IPv4 Address (LAN)").
+const LABELS: &[(Column, &[&str])] = &[
+    (
+        Column::Ip,
+        &[
+            "ip", "ipv4", "ip address", "ipv4 address", "lan ip", "direccion ip", "dir ip", "adresse ip", "indirizzo ip",
+            "ip adresse", "endereco ip", "adreca ip", "ip adres",
+        ],
+    ),
+    (
+        Column::Mac,
+        &[
+            "mac", "mac address", "direccion mac", "adresse mac", "indirizzo mac", "mac adresse", "endereco mac",
+            "adreca mac", "mac adres", "hardware address", "direccion fisica", "adresse physique",
+        ],
+    ),
+    (
+        Column::Name,
+        &[
+            "name", "device", "device name", "host", "host name", "hostname", "client", "client name", "nombre",
+            "nombre del dispositivo", "nombre de host", "dispositivo", "equipo", "cliente", "nom", "nom de l appareil",
+            "nom d hote", "appareil", "nome", "nome host", "nome dispositivo", "geratename", "geraet", "gerat",
+            "dispositiu", "nom del dispositiu",
+        ],
+    ),
+    (
+        Column::Interface,
+        &[
+            "interface", "interfaz", "connection", "connection type", "conexion", "tipo de conexion", "connexion",
+            "type de connexion", "connessione", "verbindung", "anschluss", "connexio",
+        ],
+    ),
+    (Column::Band, &["band", "banda", "bande", "frequency", "frecuencia", "frequence", "frequenz", "frequenza"]),
+    (
+        Column::Signal,
+        &[
+            "signal", "signal strength", "rssi", "intensidad", "intensidad de senal", "senal", "puissance du signal",
+            "signal wifi", "potenza segnale", "signalstarke", "senyal",
+        ],
+    ),
+];
+
+// Palabras que pueden acompanar a "ip" en una cabecera de direccion no listada en `LABELS`.
+const IP_QUALIFIERS: &[&str] = &[
+    "address", "addr", "adres", "adresse", "direccion", "dir", "indirizzo", "endereco", "adreca", "lan", "local",
+    "client", "device", "host", "assigned", "reserved", "current", "asignada", "v4",
+];
+
+// Parser estructurado del HTML de la pagina de clientes:
+// - tablas: cabecera mapeada con `LABELS` o, si no hay cabecera reconocible, columnas inferidas
+//   por contenido (IPv4 / MAC)
+// - listas (`<li>`): pares `Etiqueta: valor` o IP/MAC sueltas dentro del mismo elemento
+// Sin resultados => el llamador cae al parser de texto (`parse_with_text_fallback`).
+pub fn parse_router_html(html: &str) -> Vec<ParsedRouterDevice> {
+    let mut devices = Vec::new();
+    for table in table_bodies(html) {
+        merge_devices(&mut devices, parse_table(&html_table_rows(&table)));
+    }
+
+    let re_item = LIST_ITEM_RE.get_or_init(|| Regex::new(r"(?is)<li\b[^>]*>(.*?)</li>").unwrap());
+    let items = re_item.captures_iter(html).filter_map(|item| parse_list_item(&html_to_text(&item[1])));
+    merge_devices(&mut devices, items.collect());
+    devices
+}
+
+// HTML estructurado primero; si no aparece ninguna tabla/lista util, parser de texto del perfil.
+pub fn parse_with_text_fallback<F>(html: &str, text: F, rules: &TextRules) -> Vec<ParsedRouterDevice>
+where
+    F: FnOnce() -> String,
+{
+    let devices = parse_router_html(html);
+    if !devices.is_empty() {
+        return devices;
+    }
+    parse_router_text_with(&text(), rules)
+}
+
+// Contenido propio de cada `<table>`, sin las tablas anidadas (salen aparte), en orden de cierre.
+// Las paginas con tablas de maquetacion envuelven la de clientes: el primer `</table>` no es el suyo.
+fn table_bodies(html: &str) -> Vec<String> {
+    let re_tag = TABLE_TAG_RE.get_or_init(|| Regex::new(r"(?i)<(/?)table\b[^>]*>").unwrap());
+    // Por cada tabla abierta: contenido propio acumulado y donde empieza el tramo actual.
+    let mut open: Vec<(String, usize)> = Vec::new();
+    let mut bodies = Vec::new();
+    for tag in re_tag.captures_iter(html) {
+        let whole = tag.get(0).unwrap();
+        if tag[1].is_empty() {
+            if let Some((body, start)) = open.last_mut() {
+                body.push_str(&html[*start..whole.start()]);
+            }
+            open.push((String::new(), whole.end()));
+        } else if let Some((mut body, start)) = open.pop() {
+            body.push_str(&html[start..whole.start()]);
+            bodies.push(body);
+            if let Some((_, parent_start)) = open.last_mut() {
+                *parent_start = whole.end();
+            }
+        }
+    }
+    bodies
+}
+
+fn parse_table(rows: &[Vec<String>]) -> Vec<ParsedRouterDevice> {
+    let header = rows.iter().position(|row| {
+        let columns = header_columns(row);
+        columns.contains(&Some(Column::Ip))
+    });
+
+    let (columns, data) = match header {
+        Some(index) => (header_columns(&rows[index]), &rows[index + 1..]),
+        None => (infer_columns(rows), rows),
+    };
+    data.iter().filter_map(|row| device_from_cells(row, &columns)).collect()
+}
+
+fn header_columns(row: &[String]) -> Vec<Option<Column>> {
+    row.iter().map(|cell| label_column(cell)).collect()
+}
+
+// Sin cabecera: la columna con mas IPv4 es la IP y la que mas MACs tiene, la MAC.
+fn infer_columns(rows: &[Vec<String>]) -> Vec<Option<Column>> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let hits = |pattern: fn(&str) -> Option<String>, col: usize| {
+        rows.iter().filter(|r| r.get(col).and_then(|c| pattern(c)).is_some()).count()
+    };
+    let best = |pattern: fn(&str) -> Option<String>| {
+        (0..width)
+            .map(|col| (col, hits(pattern, col)))
+            .filter(|(_, n)| *n > 0)
+            .max_by_key(|(col, n)| (*n, std::cmp::Reverse(*col)))
+            .map(|(col, _)| col)
+    };
+
+    let mut columns = vec![None; width];
+    if let Some(col) = best(find_ipv4) {
+        columns[col] = Some(Column::Ip);
+    }
+    if let Some(col) = best(find_mac) {
+        columns[col].get_or_insert(Column::Mac);
+    }
+    columns
+}
+
+fn device_from_cells(row: &[String], columns: &[Option<Column>]) -> Option<ParsedRouterDevice> {
+    let mut device = empty_device();
+    for (cell, column) in row.iter().zip(columns) {
+        if let Some(column) = column {
+            assign(&mut device, *column, cell);
+        }
+    }
+    (!device.ip.is_empty()).then_some(device)
+}
+
+// Un `<li>` por dispositivo: "Etiqueta: valor" por linea; la primera linea libre es el nombre.
+fn parse_list_item(text: &str) -> Option<ParsedRouterDevice> {
+    let mut device = empty_device();
+    let mut loose: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        match line.split_once(':').and_then(|(label, value)| Some((label_column(label)?, value))) {
+            Some((column, value)) => assign(&mut device, column, value),
+            None => loose.push(line),
+        }
+    }
+    for line in &loose {
+        if device.ip.is_empty() {
+            if let Some(ip) = find_ipv4(line) {
+                device.ip = ip;
+                continue;
+            }
+        }
+        if device.mac.is_none() && find_mac(line).is_some() {
+            device.mac = find_mac(line);
+            continue;
+        }
+        if device.name.is_none() && find_ipv4(line).is_none() {
+            device.name = Some(line.to_string());
+        }
+    }
+    if device.ip.is_empty() {
+        return None;
+    }
+    device.name = device.name.filter(|n| *n != device.ip);
+    Some(device)
+}
+
+fn assign(device: &mut ParsedRouterDevice, column: Column, raw: &str) {
+    let value = raw.trim();
+    let text = || Some(value.to_string()).filter(|v| !v.is_empty() && v != "-" && v != "--");
+    match column {
+        Column::Ip => {
+            if device.ip.is_empty() {
+                device.ip = find_ipv4(value).unwrap_or_default();
+            }
+        }
+        Column::Mac => device.mac = device.mac.take().or_else(|| find_mac(value)),
+        Column::Name => {
+            // Algunos firmwares repiten la IP en la columna de nombre si el host no tiene alias.
+            let name = text().filter(|n| find_ipv4(n).as_deref() != Some(n.as_str()));
+            device.name = device.name.take().or(name);
+        }
+        Column::Interface => device.interface_type = device.interface_type.take().or_else(text),
+        Column::Band => device.wifi_band = device.wifi_band.take().or_else(text),
+        Column::Signal => device.signal_strength = device.signal_strength.take().or_else(text),
+    }
+}
+
+// El mismo cliente puede aparecer en varias tablas (WiFi + DHCP): se completan huecos.
+fn merge_devices(devices: &mut Vec<ParsedRouterDevice>, found: Vec<ParsedRouterDevice>) {
+    for device in found {
+        match devices.iter_mut().find(|d| d.ip == device.ip) {
+            Some(existing) => {
+                existing.mac = existing.mac.take().or(device.mac);
+                existing.name = existing.name.take().or(device.name);
+                existing.interface_type = existing.interface_type.take().or(device.interface_type);
+                existing.wifi_band = existing.wifi_band.take().or(device.wifi_band);
+                existing.signal_strength = existing.signal_strength.take().or(device.signal_strength);
+            }
+            None => devices.push(device),
+        }
+    }
+}
+
+fn label_column(label: &str) -> Option<Column> {
+    let label = normalize_label(label);
+    if label.is_empty() {
+        return None;
+    }
+    if let Some((column, _)) = LABELS.iter().find(|(_, names)| names.contains(&label.as_str())) {
+        return Some(*column);
+    }
+    let tokens: Vec<&str> = label.split(' ').collect();
+    if tokens.contains(&"mac") {
+        return Some(Column::Mac);
+    }
+    // "IPv4 Address (LAN)" si; "IP Lease Time" no: el resto de palabras solo puede calificar la direccion.
+    let is_ip = |t: &&str| *t == "ip" || *t == "ipv4";
+    if tokens.iter().any(is_ip) && tokens.iter().all(|t| is_ip(t) || IP_QUALIFIERS.contains(t)) {
+        return Some(Column::Ip);
+    }
+    None
+}
+
+// Minusculas, sin acentos ni puntuacion, espacios colapsados ("Dirección IP:" -> "direccion ip").
+pub fn normalize_label(label: &str) -> String {
+    let folded: String = label
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ä' | 'ã' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'ö' | 'õ' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ñ' => 'n',
+            'ç' => 'c',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn find_ipv4(text: &str) -> Option<String> {
+    let re_ip = IPV4_RE.get_or_init(|| {
+        Regex::new(r"\b(?:(?:25[0-5]|2[0-4]\d|1?\d?\d)\.){3}(?:25[0-5]|2[0-4]\d|1?\d?\d)\b").unwrap()
+    });
+    re_ip.find(text).map(|m| m.as_str().to_string())
+}
+
+fn find_mac(text: &str) -> Option<String> {
+    let re_mac = MAC_RE.get_or_init(|| Regex::new(r"(?i)\b(?:[0-9a-f]{2}[:-]){5}[0-9a-f]{2}\b").unwrap());
+    re_mac.find(text).map(|m| m.as_str().replace('-', ":").to_uppercase())
+}
+
+fn empty_device() -> ParsedRouterDevice {
+    ParsedRouterDevice {
+        ip: String::new(),
+        name: None,
+        mac: None,
+        signal_strength: None,
+        signal_rate: None,
+        wifi_band: None,
+        interface_type: None,
+        active: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_router_html_fixture_tabla_es_con_cabeceras() {
+        let html = include_str!("./fixtures/router_html_table_es.html");
+        let d = parse_router_html(html);
+        assert_eq!(d.len(), 3);
+        assert_eq!(d[0].ip, "192.168.1.33");
+        assert_eq!(d[0].name.as_deref(), Some("Portátil-Ana"));
+        assert_eq!(d[0].mac.as_deref(), Some("3C:22:FB:10:AA:01"));
+        assert_eq!(d[0].interface_type.as_deref(), Some("Wi-Fi 5 GHz"));
+        // Columnas en otro orden y MAC con guiones.
+        assert_eq!(d[2].ip, "192.168.1.50");
+        assert_eq!(d[2].mac.as_deref(), Some("00:11:32:AB:CD:EF"));
+        assert_eq!(d[2].name, None);
+    }
+
+    #[test]
+    fn parse_router_html_fixture_fr_combina_tablas_y_tabla_sin_cabecera() {
+        let html = include_str!("./fixtures/router_html_tables_fr.html");
+        let d = parse_router_html(html);
+        assert_eq!(d.len(), 3);
+        // La tabla WiFi aporta nombre y senal; la de DHCP (sin cabecera) completa la MAC.
+        assert_eq!(d[0].ip, "192.168.0.12");
+        assert_eq!(d[0].name.as_deref(), Some("Téléphone-Léa"));
+        assert_eq!(d[0].signal_strength.as_deref(), Some("-58 dBm"));
+        assert_eq!(d[0].mac.as_deref(), Some("A4:5E:60:01:02:03"));
+        assert_eq!(d[2].ip, "192.168.0.40");
+        assert_eq!(d[2].mac.as_deref(), Some("F0:9F:C2:11:22:33"));
+    }
+
+    #[test]
+    fn parse_router_html_fixture_lista_con_etiquetas_alemanas() {
+        let html = include_str!("./fixtures/router_html_list_de.html");
+        let d = parse_router_html(html);
+        assert_eq!(d.len(), 2);
+        assert_eq!(d[0].ip, "192.168.178.21");
+        assert_eq!(d[0].name.as_deref(), Some("Wohnzimmer-TV"));
+        assert_eq!(d[0].mac.as_deref(), Some("B8:27:EB:AA:BB:CC"));
+        assert_eq!(d[0].interface_type.as_deref(), Some("LAN 1"));
+        assert_eq!(d[1].name.as_deref(), Some("Laptop"));
+        assert_eq!(d[1].mac, None);
+    }
+
+    #[test]
+    fn parse_router_html_fixture_tablas_anidadas_en_maquetacion() {
+        let html = include_str!("./fixtures/router_html_nested_en.html");
+        let d = parse_router_html(html);
+        assert_eq!(d.len(), 3);
+        assert_eq!(d[0].ip, "192.168.1.4");
+        assert_eq!(d[0].name.as_deref(), Some("Galaxy-S21"));
+        assert_eq!(d[1].mac.as_deref(), Some("D4:5D:64:AA:BB:CC"));
+        // La fila "IP Lease Time" no es la cabecera de la segunda tabla.
+        assert_eq!(d[2].ip, "192.168.1.20");
+        assert_eq!(d[2].name.as_deref(), Some("printer"));
+        assert_eq!(d[2].mac.as_deref(), Some("00:1B:A9:01:02:03"));
+    }
+
+    #[test]
+    fn parse_with_text_fallback_usa_el_parser_de_texto_sin_tablas() {
+        let html = "<div><p>Redmi-15</p><p>IP: 192.168.1.130</p><p>Signal strength: -83 dBm</p></div>";
+        let d = parse_with_text_fallback(html, || html_to_text(html), &TextRules::default());
+        assert_eq!(d.len(), 1);
+        assert_eq!(d[0].name.as_deref(), Some("Redmi-15"));
+        assert_eq!(d[0].signal_strength.as_deref(), Some("-83 dBm"));
+    }
+
+    #[test]
+    fn label_column_normaliza_acentos_y_puntuacion() {
+        assert_eq!(label_column("Dirección IP:"), Some(Column::Ip));
+        assert_eq!(label_column("Adresse MAC"), Some(Column::Mac));
+        assert_eq!(label_column("IPv4 Address (LAN)"), Some(Column::Ip));
+        assert_eq!(label_column("Nom de l'appareil"), Some(Column::Name));
+        assert_eq!(label_column("Gerätename"), Some(Column::Name));
+        assert_eq!(label_column("Estado"), None);
+        assert_eq!(label_column("IP Lease Time"), None);
+        assert_eq!(label_column("Reserved IP Address"), Some(Column::Ip));
+    }
+}
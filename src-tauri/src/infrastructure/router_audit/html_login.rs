@@ -0,0 +1,324 @@
+// src-tauri/src/infrastructure/router_audit/html_login.rs
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::profiles::LoginRules;
+
+// Compiladas una vez: el auditor las pasa por cada pagina, formulario y celda del router.
+static FORM_RE: OnceLock<Regex> = OnceLock::new();
+static FIELD_RE: OnceLock<Regex> = OnceLock::new();
+static INPUT_RE: OnceLock<Regex> = OnceLock::new();
+static TITLE_RE: OnceLock<Regex> = OnceLock::new();
+static OPEN_TAG_RE: OnceLock<Regex> = OnceLock::new();
+static BREAK_RE: OnceLock<Regex> = OnceLock::new();
+static ANY_TAG_RE: OnceLock<Regex> = OnceLock::new();
+static ROW_RE: OnceLock<Regex> = OnceLock::new();
+static CELL_RE: OnceLock<Regex> = OnceLock::new();
+static NON_CONTENT_RE: OnceLock<Regex> = OnceLock::new();
+static META_RE: OnceLock<Regex> = OnceLock::new();
+static ATTR_RE: OnceLock<Regex> = OnceLock::new();
+static SELECTOR_RE: OnceLock<Regex> = OnceLock::new();
+static SELECTOR_ATTR_RE: OnceLock<Regex> = OnceLock::new();
+
+// Analisis estatico (sin JS) del HTML del router para el auditor HTTP:
+// - formulario de login, campos ocultos y tokens CSRF
+// - texto/tablas para la lista de clientes
+//
+// Si el login solo existe tras ejecutar JS, devolvemos `None` y el auditor automatico usa Chrome.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoginForm {
+    // Tal cual aparece en el HTML (relativa o vacia = misma pagina).
+    pub action: String,
+    pub method: String,
+    pub user_field: Option<String>,
+    pub password_field: String,
+    // Campos a reenviar sin tocar (ocultos, CSRF, selects...).
+    pub extra_fields: Vec<(String, String)>,
+    // Token de `<meta name="csrf-token">` para frameworks que lo esperan en cabecera.
+    pub csrf_header_token: Option<String>,
+}
+
+impl LoginForm {
+    pub fn csrf_field(&self) -> Option<&str> {
+        self.extra_fields
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .find(|name| looks_like_csrf(name))
+    }
+}
+
+pub fn find_login_form(html: &str, rules: Option<&LoginRules>) -> Option<LoginForm> {
+    let re_form = FORM_RE.get_or_init(|| Regex::new(r"(?is)<form\b([^>]*)>(.*?)</form>").unwrap());
+    let re_input = FIELD_RE.get_or_init(|| Regex::new(r"(?is)<(input|select|textarea)\b([^>]*)>").unwrap());
+
+    for form in re_form.captures_iter(html) {
+        let form_attrs = parse_attrs(&form[1]);
+        let inputs: Vec<(String, HashMap<String, String>)> = re_input
+            .captures_iter(&form[2])
+            .map(|c| (c[1].to_ascii_lowercase(), parse_attrs(&c[2])))
+            .collect();
+
+        let is_password = |attrs: &HashMap<String, String>| attr_eq(attrs, "type", "password");
+        if !inputs.iter().any(|(_, a)| is_password(a)) {
+            continue;
+        }
+        // Validacion/hash de la clave en JS: el POST "en crudo" no reproduciria el login real.
+        if form_attrs.contains_key("onsubmit") {
+            return None;
+        }
+
+        let by_selectors = |selectors: &[String]| -> Option<String> {
+            selectors.iter().find_map(|sel| {
+                inputs
+                    .iter()
+                    .find(|(tag, attrs)| selector_matches(sel, tag, attrs))
+                    .and_then(|(_, attrs)| attrs.get("name").cloned())
+            })
+        };
+
+        let password_field = rules
+            .and_then(|r| by_selectors(&r.password_selectors))
+            .or_else(|| inputs.iter().find(|(_, a)| is_password(a)).and_then(|(_, a)| a.get("name").cloned()))?;
+
+        let user_field = match rules {
+            Some(r) if r.user_selectors.is_empty() => None,
+            _ => rules.and_then(|r| by_selectors(&r.user_selectors)).or_else(|| {
+                inputs
+                    .iter()
+                    .filter(|(tag, a)| tag == "input" && !is_password(a))
+                    .filter(|(_, a)| matches!(a.get("type").map(|t| t.to_ascii_lowercase()).as_deref(), None | Some("text") | Some("email")))
+                    .filter_map(|(_, a)| a.get("name").cloned())
+                    .max_by_key(|name| user_field_score(name))
+            }),
+        };
+
+        let extra_fields = inputs
+            .iter()
+            .filter(|(_, a)| !matches!(a.get("type").map(|t| t.to_ascii_lowercase()).as_deref(), Some("submit" | "button" | "checkbox" | "radio")))
+            .filter_map(|(_, a)| Some((a.get("name")?.clone(), a.get("value").cloned().unwrap_or_default())))
+            .filter(|(name, _)| *name != password_field && Some(name) != user_field.as_ref())
+            .collect();
+
+        return Some(LoginForm {
+            action: form_attrs.get("action").cloned().unwrap_or_default(),
+            method: form_attrs.get("method").map(|m| m.to_ascii_uppercase()).unwrap_or_else(|| "GET".to_string()),
+            user_field,
+            password_field,
+            extra_fields,
+            csrf_header_token: meta_csrf_token(html),
+        });
+    }
+    None
+}
+
+pub fn has_password_input(html: &str) -> bool {
+    let re_input = INPUT_RE.get_or_init(|| Regex::new(r"(?is)<input\b([^>]*)>").unwrap());
+    let found = re_input
+        .captures_iter(html)
+        .any(|c| attr_eq(&parse_attrs(&c[1]), "type", "password"));
+    found
+}
+
+pub fn page_title(html: &str) -> String {
+    let re = TITLE_RE.get_or_init(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap());
+    re.captures(html)
+        .map(|c| decode_entities(c[1].trim()))
+        .unwrap_or_default()
+}
+
+// Solo selectores simples: `tag`, `#id`, `[attr='valor']` y combinaciones (`input#user[name='u']`).
+pub fn selector_present(html: &str, selectors: &[String]) -> bool {
+    let re_tag = OPEN_TAG_RE.get_or_init(|| Regex::new(r"(?is)<([a-z][a-z0-9-]*)\b([^>]*)>").unwrap());
+    let tags: Vec<(String, HashMap<String, String>)> = re_tag
+        .captures_iter(html)
+        .map(|c| (c[1].to_ascii_lowercase(), parse_attrs(&c[2])))
+        .collect();
+    selectors
+        .iter()
+        .any(|sel| tags.iter().any(|(tag, attrs)| selector_matches(sel, tag, attrs)))
+}
+
+pub fn html_to_text(html: &str) -> String {
+    let re_break = BREAK_RE.get_or_init(|| Regex::new(r"(?i)<br\s*/?>|</(p|div|tr|li|h[1-6]|td|th|table)>").unwrap());
+    let re_tag = ANY_TAG_RE.get_or_init(|| Regex::new(r"(?s)<[^>]*>").unwrap());
+
+    let text = strip_non_content(html);
+    let text = re_break.replace_all(&text, "\n");
+    let text = re_tag.replace_all(&text, "");
+    decode_entities(&text)
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Celdas (texto) de cada `<tr>` del documento, en orden.
+pub fn html_table_rows(html: &str) -> Vec<Vec<String>> {
+    let re_row = ROW_RE.get_or_init(|| Regex::new(r"(?is)<tr\b[^>]*>(.*?)</tr>").unwrap());
+    let re_cell = CELL_RE.get_or_init(|| Regex::new(r"(?is)<t[dh]\b[^>]*>(.*?)</t[dh]>").unwrap());
+    re_row
+        .captures_iter(&strip_non_content(html))
+        .map(|row| {
+            re_cell
+                .captures_iter(&row[1])
+                .map(|cell| html_to_text(&cell[1]).replace('\n', " "))
+                .collect()
+        })
+        .collect()
+}
+
+fn strip_non_content(html: &str) -> String {
+    let re_drop = NON_CONTENT_RE.get_or_init(|| {
+        Regex::new(r"(?is)<(script|style|head)\b.*?</(script|style|head)>").unwrap()
+    });
+    re_drop.replace_all(html, "").to_string()
+}
+
+fn meta_csrf_token(html: &str) -> Option<String> {
+    let re_meta = META_RE.get_or_init(|| Regex::new(r"(?is)<meta\b([^>]*)>").unwrap());
+    let token = re_meta.captures_iter(html).find_map(|c| {
+        let attrs = parse_attrs(&c[1]);
+        let name = attrs.get("name")?.to_ascii_lowercase();
+        if looks_like_csrf(&name) {
+            attrs.get("content").cloned()
+        } else {
+            None
+        }
+    });
+    token
+}
+
+fn looks_like_csrf(name: &str) -> bool {
+    let n = name.to_ascii_lowercase();
+    ["csrf", "xsrf", "token", "nonce", "authenticity"].iter().any(|k| n.contains(k))
+}
+
+fn user_field_score(name: &str) -> u8 {
+    let n = name.to_ascii_lowercase();
+    if n.contains("user") || n.contains("login") || n.contains("account") {
+        2
+    } else if n.contains("name") || n.contains("mail") {
+        1
+    } else {
+        0
+    }
+}
+
+fn attr_eq(attrs: &HashMap<String, String>, key: &str, value: &str) -> bool {
+    attrs.get(key).is_some_and(|v| v.eq_ignore_ascii_case(value))
+}
+
+fn parse_attrs(raw: &str) -> HashMap<String, String> {
+    let re = ATTR_RE.get_or_init(|| {
+        Regex::new(r#"(?s)([a-zA-Z_:][-a-zA-Z0-9_:.]*)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+)))?"#).unwrap()
+    });
+    re.captures_iter(raw)
+        .map(|c| {
+            let value = c.get(2).or(c.get(3)).or(c.get(4)).map(|m| decode_entities(m.as_str())).unwrap_or_default();
+            (c[1].to_ascii_lowercase(), value)
+        })
+        .collect()
+}
+
+fn selector_matches(selector: &str, tag: &str, attrs: &HashMap<String, String>) -> bool {
+    let re = SELECTOR_RE.get_or_init(|| {
+        Regex::new(r#"^([a-zA-Z][a-zA-Z0-9-]*)?(?:#([-\w]+))?((?:\[[^\]]+\])*)$"#).unwrap()
+    });
+    let re_attr = SELECTOR_ATTR_RE.get_or_init(|| {
+        Regex::new(r#"\[\s*([-\w]+)\s*=\s*['"]?([^'"\]]*)['"]?\s*\]"#).unwrap()
+    });
+    let Some(c) = re.captures(selector.trim()) else {
+        return false;
+    };
+    if c.get(1).is_none() && c.get(2).is_none() && c[3].is_empty() {
+        return false;
+    }
+    if c.get(1).is_some_and(|t| !t.as_str().eq_ignore_ascii_case(tag)) {
+        return false;
+    }
+    if c.get(2).is_some_and(|id| attrs.get("id").map(String::as_str) != Some(id.as_str())) {
+        return false;
+    }
+    let all_match = re_attr.captures_iter(&c[3]).all(|a| {
+        let key = a[1].to_ascii_lowercase();
+        match attrs.get(&key) {
+            // `type` no distingue mayusculas en HTML; el resto si.
+            Some(v) if key == "type" => v.eq_ignore_ascii_case(&a[2]),
+            Some(v) => *v == a[2],
+            None => false,
+        }
+    });
+    all_match
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOGIN_PAGE: &str = r#"
+        <html><head><title>Router &amp; Co</title><meta name="csrf-token" content="meta-tok"></head>
+        <body>
+          <form id="search" action="/search"><input name="q"></form>
+          <form action="/cgi-bin/login" method="post">
+            <input type="hidden" name="csrf_token" value="abc123">
+            <input type="text" name="remember_label" value="">
+            <input type="text" name="Username" id="user">
+            <input type="PASSWORD" name="Password" id="password">
+            <input type="checkbox" name="remember" value="1">
+            <button type="submit">Entrar</button>
+          </form>
+        </body></html>"#;
+
+    #[test]
+    fn find_login_form_detects_fields_and_csrf_tokens() {
+        let form = find_login_form(LOGIN_PAGE, None).unwrap();
+
+        assert_eq!(form.action, "/cgi-bin/login");
+        assert_eq!(form.method, "POST");
+        assert_eq!(form.user_field.as_deref(), Some("Username"));
+        assert_eq!(form.password_field, "Password");
+        assert_eq!(form.csrf_field(), Some("csrf_token"));
+        assert_eq!(form.csrf_header_token.as_deref(), Some("meta-tok"));
+        assert!(form.extra_fields.contains(&("csrf_token".to_string(), "abc123".to_string())));
+        assert!(!form.extra_fields.iter().any(|(n, _)| n == "remember"));
+        assert_eq!(page_title(LOGIN_PAGE), "Router & Co");
+    }
+
+    #[test]
+    fn find_login_form_prefers_profile_selectors_and_skips_js_forms() {
+        let rules: LoginRules = serde_json::from_str(
+            r##"{"userSelectors":["input[name='remember_label']"],"passwordSelectors":["#password"]}"##,
+        )
+        .unwrap();
+        let form = find_login_form(LOGIN_PAGE, Some(&rules)).unwrap();
+        assert_eq!(form.user_field.as_deref(), Some("remember_label"));
+
+        let js = r#"<form onsubmit="return hashAndSend()"><input type="password" name="p"></form>"#;
+        assert!(find_login_form(js, None).is_none());
+        assert!(has_password_input(js));
+    }
+
+    #[test]
+    fn html_helpers_extract_text_rows_and_selectors() {
+        let html = r#"<div id="logout">Salir</div><table><tr><th>IP</th><th>MAC</th></tr>
+            <tr><td>192.168.1.2</td><td>aa:bb:cc:dd:ee:ff</td></tr></table><script>var x = "<tr>";</script>"#;
+
+        assert_eq!(html_table_rows(html)[1], vec!["192.168.1.2".to_string(), "aa:bb:cc:dd:ee:ff".to_string()]);
+        assert!(html_to_text(html).starts_with("Salir\nIP\nMAC"));
+        assert!(selector_present(html, &["#logout".to_string()]));
+        assert!(!selector_present(html, &[".submit button".to_string()]));
+    }
+}
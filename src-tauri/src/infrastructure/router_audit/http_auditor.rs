@@ -0,0 +1,793 @@
+// src-tauri/src/infrastructure/router_audit/http_auditor.rs
+
+use crate::domain::{
+    entities::{Device, RouterAuditResult, RouterCredentials, RouterEvidence, RouterEvidenceKind, RouterEvidenceStage},
+    ports::{RouterAuditObserver, RouterAuditorPort},
+};
+use async_trait::async_trait;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::{header, redirect, StatusCode, Url};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::attempts::{
+    is_lockout, report_detecting, run_credential_attempts, AttemptContext, AttemptOutcome, AttemptPolicy, LoginAttemptTrail,
+};
+use super::credentials::load_gateway_credentials;
+use super::dom_parser::{parse_table_rows, ParsedRouterDevice};
+use super::enrichment::enrich_router_devices;
+use super::evidence::sanitize_html;
+use super::fingerprint;
+use super::html_extractor::parse_with_text_fallback;
+use super::html_login::{self, LoginForm};
+use super::http_auth::{self, HttpAuthScheme};
+use super::profiles::{load_profiles, select_profile, ExtractionRules, PageFingerprint, RouterProfile};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(6);
+// Algunos paneles rechazan clientes que no parecen un navegador.
+const USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0 Safari/537.36";
+
+// Como se autentica el router sin ejecutar JS.
+#[derive(Clone, Debug)]
+pub enum HttpLoginMethod {
+    Basic,
+    Digest,
+    Form { login_url: Url, form: Box<LoginForm> },
+}
+
+#[derive(Clone, Debug)]
+pub struct HttpLoginPlan {
+    pub method: HttpLoginMethod,
+    pub profile: RouterProfile,
+}
+
+impl HttpLoginPlan {
+    // La lista de clientes de SPAs (clicks en menus) necesita navegador aunque el login sea HTTP.
+    pub fn can_fetch_devices(&self) -> bool {
+        self.profile.devices.path.is_some() || self.profile.devices.click_selectors.is_empty()
+    }
+
+    fn label(&self) -> &'static str {
+        match self.method {
+            HttpLoginMethod::Basic => "HTTP Basic",
+            HttpLoginMethod::Digest => "HTTP Digest",
+            HttpLoginMethod::Form { .. } => "formulario",
+        }
+    }
+}
+
+// Motivo de un login fallido: distingue bloqueo (parar la auditoria) de clave incorrecta.
+struct LoginFailure {
+    outcome: AttemptOutcome,
+    reason: String,
+}
+
+impl LoginFailure {
+    fn rejected() -> Self {
+        Self {
+            outcome: AttemptOutcome::Rejected,
+            reason: "credenciales rechazadas".to_string(),
+        }
+    }
+
+    fn locked_out() -> Self {
+        Self {
+            outcome: AttemptOutcome::LockedOut,
+            reason: "el router indica bloqueo por intentos".to_string(),
+        }
+    }
+}
+
+impl From<String> for LoginFailure {
+    fn from(reason: String) -> Self {
+        Self {
+            outcome: AttemptOutcome::Error,
+            reason,
+        }
+    }
+}
+
+// Sesion autenticada: cookies en el cliente, o cabecera por peticion (Basic/Digest).
+struct HttpSession {
+    client: Client,
+    user: String,
+    pass: String,
+    method: HttpLoginMethod,
+    landing_url: Url,
+}
+
+impl HttpSession {
+    fn get(&self, url: Url) -> Result<Response, String> {
+        match &self.method {
+            HttpLoginMethod::Basic => send(self.client.get(url).basic_auth(&self.user, Some(&self.pass))),
+            HttpLoginMethod::Digest => digest_get(&self.client, url, &self.user, &self.pass),
+            HttpLoginMethod::Form { .. } => send(self.client.get(url)),
+        }
+    }
+}
+
+// Auditor de router sin navegador (reqwest):
+// - Basic/Digest (401 + WWW-Authenticate)
+// - formularios HTML con cookies de sesion y tokens CSRF (campo oculto o `<meta>`)
+// Reutiliza los perfiles de fabricante para reglas de exito y extraccion.
+// Evidencias: solo HTML saneado (login, landing y lista de clientes); sin navegador no hay pantallazos.
+// Si el login depende de JS, `detect_login` devuelve `None` y hay que usar `ChromeAuditor`.
+#[derive(Clone)]
+pub struct HttpAuditor {
+    log_callback: Arc<dyn Fn(String) + Send + Sync>,
+    policy: AttemptPolicy,
+    trail: Arc<LoginAttemptTrail>,
+}
+
+impl HttpAuditor {
+    pub fn new(logger: Arc<dyn Fn(String) + Send + Sync>) -> Self {
+        Self::with_policy(logger, AttemptPolicy::default(), LoginAttemptTrail::new())
+    }
+
+    pub fn with_policy(logger: Arc<dyn Fn(String) + Send + Sync>, policy: AttemptPolicy, trail: LoginAttemptTrail) -> Self {
+        Self {
+            log_callback: logger,
+            policy,
+            trail: Arc::new(trail),
+        }
+    }
+
+    fn log(&self, msg: &str) {
+        (self.log_callback)(msg.to_string());
+    }
+
+    pub fn detect_login(&self, ip: &str) -> Option<HttpLoginPlan> {
+        let client = new_client().ok()?;
+        let response = send(client.get(base_url(ip).ok()?)).ok()?;
+        let profiles = load_profiles();
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            let challenge = www_authenticate(&response)?;
+            let page = PageFingerprint {
+                title: String::new(),
+                url: response.url().to_string(),
+                // El realm suele llevar el modelo ("TP-LINK Wireless N Router").
+                html: challenge.clone(),
+            };
+            let profile = select_profile(&profiles, &page)?.clone();
+            let method = match http_auth::parse_www_authenticate(&challenge)? {
+                HttpAuthScheme::Basic => HttpLoginMethod::Basic,
+                HttpAuthScheme::Digest(_) => HttpLoginMethod::Digest,
+            };
+            return Some(HttpLoginPlan { method, profile });
+        }
+
+        let url = response.url().clone();
+        let html = response.text().ok()?;
+        let page = PageFingerprint {
+            title: html_login::page_title(&html),
+            url: url.to_string(),
+            html: html.clone(),
+        };
+        let profile = select_profile(&profiles, &page)?.clone();
+
+        let (login_url, html) = if profile.login.path == "/" {
+            (url, html)
+        } else {
+            let login_url = router_url(ip, &profile.login.path).ok()?;
+            let response = send(client.get(login_url)).ok()?;
+            (response.url().clone(), response.text().ok()?)
+        };
+
+        let form = html_login::find_login_form(&html, Some(&profile.login))?;
+        Some(HttpLoginPlan {
+            method: HttpLoginMethod::Form {
+                login_url,
+                form: Box::new(form),
+            },
+            profile,
+        })
+    }
+
+    pub fn audit_gateway_blocking(&self, ip: &str, observer: &dyn RouterAuditObserver) -> RouterAuditResult {
+        self.log(&format!("⚔️ ROUTER AUDIT (HTTP): Iniciando brute-force a {}...", ip));
+        report_detecting(observer, "http");
+        let mut failure = "Failed".to_string();
+        let mut cancelled = false;
+
+        if let Some(plan) = self.detect_login(ip) {
+            self.log(&format!(
+                "🧬 Perfil de router: {} ({}) - login por {}",
+                plan.profile.vendor,
+                plan.profile.id,
+                plan.label()
+            ));
+            if let HttpLoginMethod::Form { form, .. } = &plan.method {
+                if let Some(field) = form.csrf_field() {
+                    self.log(&format!("   🛡️ Token CSRF detectado en el formulario ({})", field));
+                }
+            }
+
+            let credentials = load_gateway_credentials(&plan.profile);
+            self.capture_login_page(ip, &plan, observer);
+            let ctx = AttemptContext {
+                target_ip: ip,
+                backend: "http",
+                profile_id: &plan.profile.id,
+                observer,
+            };
+            let mut session = None;
+            let summary = run_credential_attempts(
+                &ctx,
+                &credentials,
+                &self.policy,
+                &self.trail,
+                &|msg| self.log(msg),
+                |user, pass| match self.login(ip, &plan, user, pass) {
+                    Ok(s) => {
+                        session = Some(s);
+                        AttemptOutcome::Success
+                    }
+                    Err(failure) => failure.outcome,
+                },
+            );
+
+            if let Some((user, pass)) = summary.found.clone() {
+                self.log(&format!("🔓 ACCESO CONFIRMADO: {}/{}", user, pass));
+                // Misma sesion autenticada para evidencias y estado (como la pestana de Chrome).
+                let pages = session
+                    .map(|s| {
+                        let pages = self.read_status_pages(ip, &plan.profile, &s, observer);
+                        self.capture_device_list(ip, &plan, &s, observer);
+                        pages
+                    })
+                    .unwrap_or_default();
+                let (fingerprint, findings) = fingerprint::fingerprint_router(ip, &pages, &|msg| self.log(msg));
+                return RouterAuditResult {
+                    audit_id: None,
+                    target_ip: ip.to_string(),
+                    vulnerable: true,
+                    credentials_found: Some(RouterCredentials { user: user.clone(), pass }),
+                    message: format!("Success: credenciales por defecto validas para '{}'", user),
+                    fingerprint: Some(fingerprint),
+                    findings,
+                };
+            }
+            failure = summary.failure_message();
+            cancelled = summary.cancelled;
+        } else {
+            self.log("❌ El login del router no es accesible sin navegador.");
+        }
+
+        self.log("❌ FINALIZADO: el router resiste.");
+        // Sin login aun se puede identificar por cabeceras/favicon (salvo si el usuario cancelo).
+        let (fingerprint, findings) = if cancelled {
+            (None, Vec::new())
+        } else {
+            let (fingerprint, findings) = fingerprint::fingerprint_router(ip, &[], &|msg| self.log(msg));
+            (Some(fingerprint), findings)
+        };
+        RouterAuditResult {
+            audit_id: None,
+            target_ip: ip.to_string(),
+            vulnerable: false,
+            credentials_found: None,
+            message: failure,
+            fingerprint,
+            findings,
+        }
+    }
+
+    // Login sin rellenar (formulario o cuerpo del 401), pedido con un cliente limpio como cada intento.
+    fn capture_login_page(&self, ip: &str, plan: &HttpLoginPlan, observer: &dyn RouterAuditObserver) {
+        let url = match &plan.method {
+            HttpLoginMethod::Form { login_url, .. } => Ok(login_url.clone()),
+            HttpLoginMethod::Basic | HttpLoginMethod::Digest => base_url(ip),
+        };
+        let html = url.and_then(|url| send(new_client()?.get(url))?.text().map_err(|e| e.to_string()));
+        self.capture_html(RouterEvidenceStage::LoginPage, html, observer);
+    }
+
+    fn capture_device_list(
+        &self,
+        ip: &str,
+        plan: &HttpLoginPlan,
+        session: &HttpSession,
+        observer: &dyn RouterAuditObserver,
+    ) {
+        if !plan.can_fetch_devices() {
+            self.log("   ⚠️ Sin captura de device_list: el perfil necesita navegador");
+            return;
+        }
+        let html = device_list_url(ip, &plan.profile, session)
+            .and_then(|url| session.get(url)?.text().map_err(|e| e.to_string()));
+        self.capture_html(RouterEvidenceStage::DeviceList, html, observer);
+    }
+
+    // HTML saneado de la pagina leida. Best-effort: un fallo no corta la auditoria.
+    fn capture_html(
+        &self,
+        stage: RouterEvidenceStage,
+        html: Result<String, String>,
+        observer: &dyn RouterAuditObserver,
+    ) {
+        match html {
+            Ok(html) => {
+                observer.on_evidence(RouterEvidence {
+                    stage,
+                    kind: RouterEvidenceKind::Html,
+                    content: sanitize_html(&html).into_bytes(),
+                });
+                self.log(&format!("   📸 Evidencia capturada: {}", stage.as_str()));
+            }
+            Err(e) => self.log(&format!("   ⚠️ Sin captura de {}: {}", stage.as_str(), e)),
+        }
+    }
+
+    // Landing tras el login (tambien como evidencia) + paginas de estado del perfil, ya como texto.
+    fn read_status_pages(
+        &self,
+        ip: &str,
+        profile: &RouterProfile,
+        session: &HttpSession,
+        observer: &dyn RouterAuditObserver,
+    ) -> Vec<(String, String)> {
+        let read = |url: Url| session.get(url).and_then(|r| r.text().map_err(|e| e.to_string()));
+        let mut pages = Vec::new();
+        let landing = read(session.landing_url.clone());
+        if let Ok(html) = &landing {
+            pages.push(("landing".to_string(), html_login::html_to_text(html)));
+        }
+        self.capture_html(RouterEvidenceStage::Landing, landing, observer);
+        for path in &profile.status_paths {
+            let Ok(url) = router_url(ip, path) else {
+                continue;
+            };
+            if let Ok(html) = read(url) {
+                pages.push((format!("page:{}", path), html_login::html_to_text(&html)));
+            }
+        }
+        pages
+    }
+
+    // Parseo puro (sin ARP/vendor) de la lista de clientes tras autenticarse.
+    pub fn fetch_parsed_devices(&self, ip: &str, user: &str, pass: &str) -> Result<Vec<ParsedRouterDevice>, String> {
+        let plan = self.detect_login(ip).ok_or("login no accesible sin navegador")?;
+        if !plan.can_fetch_devices() {
+            return Err("la lista de clientes de este perfil requiere navegador".to_string());
+        }
+
+        self.log("   🔑 Autenticandose (HTTP)...");
+        let session = self.login(ip, &plan, user, pass).map_err(|f| f.reason)?;
+
+        self.log("   📄 Extrayendo datos del HTML...");
+        let url = device_list_url(ip, &plan.profile, &session)?;
+        let html = session.get(url)?.text().map_err(|e| e.to_string())?;
+
+        Ok(match &plan.profile.devices.extraction {
+            ExtractionRules::Text(rules) => parse_with_text_fallback(&html, || html_login::html_to_text(&html), rules),
+            ExtractionRules::Table(rules) => parse_table_rows(&html_login::html_table_rows(&html), rules),
+        })
+    }
+
+    fn fetch_connected_devices_blocking(&self, ip: &str, user: &str, pass: &str) -> Vec<Device> {
+        self.log(&format!("📡 SYNC (HTTP): Conectando a {}...", ip));
+        match self.fetch_parsed_devices(ip, user, pass) {
+            Ok(parsed) => {
+                let devices = enrich_router_devices(parsed);
+                for d in devices.iter() {
+                    let name = d
+                        .name
+                        .clone()
+                        .or_else(|| d.hostname.clone())
+                        .unwrap_or_else(|| "Unknown".to_string());
+                    self.log(&format!(
+                        "   ✨ DETECTADO: {} ({}) MAC={} VENDOR={}",
+                        name, d.ip, d.mac, d.vendor
+                    ));
+                }
+                devices
+            }
+            Err(e) => {
+                self.log(&format!("❌ SYNC (HTTP): {}", e));
+                Vec::new()
+            }
+        }
+    }
+
+    // Cada intento usa un cliente nuevo: cookies y token CSRF limpios.
+    fn login(&self, ip: &str, plan: &HttpLoginPlan, user: &str, pass: &str) -> Result<HttpSession, LoginFailure> {
+        let client = new_client()?;
+        let root = base_url(ip)?;
+
+        match &plan.method {
+            HttpLoginMethod::Basic | HttpLoginMethod::Digest => {
+                let session = HttpSession {
+                    client,
+                    user: user.to_string(),
+                    pass: pass.to_string(),
+                    method: plan.method.clone(),
+                    landing_url: root.clone(),
+                };
+                let response = session.get(root)?;
+                let status = response.status();
+                let landing_url = response.url().clone();
+                let body = response.text().unwrap_or_default();
+                if is_lockout(status.as_u16(), &body) {
+                    return Err(LoginFailure::locked_out());
+                }
+                // Un 404/400 o la pagina de login de nuevo no son un panel abierto.
+                if !login_succeeded(&plan.profile, status, &landing_url, &body) {
+                    return Err(LoginFailure::rejected());
+                }
+                Ok(session)
+            }
+            HttpLoginMethod::Form { login_url, form } => {
+                let page = send(client.get(login_url.clone()))?;
+                let page_url = page.url().clone();
+                let page_status = page.status();
+                let html = page.text().map_err(|e| e.to_string())?;
+                if is_lockout(page_status.as_u16(), &html) {
+                    return Err(LoginFailure::locked_out());
+                }
+                // Token CSRF fresco de esta sesion; si la pagina cambio, usamos el formulario detectado.
+                let form = html_login::find_login_form(&html, Some(&plan.profile.login)).unwrap_or_else(|| (**form).clone());
+
+                let response = submit_form(&client, &page_url, &form, user, pass)?;
+                let status = response.status();
+                let landing_url = response.url().clone();
+                let body = response.text().unwrap_or_default();
+
+                if is_lockout(status.as_u16(), &body) {
+                    return Err(LoginFailure::locked_out());
+                }
+                if !login_succeeded(&plan.profile, status, &landing_url, &body) {
+                    return Err(LoginFailure::rejected());
+                }
+                Ok(HttpSession {
+                    client,
+                    user: user.to_string(),
+                    pass: pass.to_string(),
+                    method: plan.method.clone(),
+                    landing_url,
+                })
+            }
+        }
+    }
+}
+
+// Pagina de clientes del perfil; sin `devices.path` la lista esta en la landing.
+fn device_list_url(ip: &str, profile: &RouterProfile, session: &HttpSession) -> Result<Url, String> {
+    match &profile.devices.path {
+        Some(path) => router_url(ip, path),
+        None => Ok(session.landing_url.clone()),
+    }
+}
+
+fn submit_form(client: &Client, page_url: &Url, form: &LoginForm, user: &str, pass: &str) -> Result<Response, String> {
+    let action = page_url.join(&form.action).map_err(|e| e.to_string())?;
+    // Nunca enviar credenciales fuera del router auditado.
+    if action.host_str() != page_url.host_str() || action.port_or_known_default() != page_url.port_or_known_default() {
+        return Err(format!("action del formulario fuera del router: {}", action));
+    }
+
+    let mut fields = form.extra_fields.clone();
+    if let Some(user_field) = &form.user_field {
+        fields.push((user_field.clone(), user.to_string()));
+    }
+    fields.push((form.password_field.clone(), pass.to_string()));
+
+    let mut request = if form.method == "POST" {
+        client.post(action).form(&fields)
+    } else {
+        client.get(action).query(&fields)
+    };
+    request = request.header(header::REFERER, page_url.as_str());
+    if let Some(token) = &form.csrf_header_token {
+        request = request.header("X-CSRF-Token", token);
+    }
+    send(request)
+}
+
+// Mismas reglas que en Chrome, evaluadas sobre el HTML de la respuesta (solo 2xx/3xx cuentan como panel).
+fn login_succeeded(profile: &RouterProfile, status: StatusCode, landing: &Url, body: &str) -> bool {
+    if !(status.is_success() || status.is_redirection()) {
+        return false;
+    }
+    let rules = &profile.login.success;
+    let text = body.to_lowercase();
+    if rules.failure_text.iter().any(|t| text.contains(&t.to_lowercase())) {
+        return false;
+    }
+    if html_login::selector_present(body, &rules.selector_present) {
+        return true;
+    }
+    if html_login::has_password_input(body) {
+        return false;
+    }
+    let url = landing.as_str().to_lowercase();
+    !rules.url_not_contains.iter().any(|m| url.contains(&m.to_lowercase()))
+}
+
+fn digest_get(client: &Client, url: Url, user: &str, pass: &str) -> Result<Response, String> {
+    let first = send(client.get(url.clone()))?;
+    if first.status() != StatusCode::UNAUTHORIZED {
+        return Ok(first);
+    }
+    let challenge = www_authenticate(&first).ok_or("401 sin WWW-Authenticate")?;
+    let Some(HttpAuthScheme::Digest(challenge)) = http_auth::parse_www_authenticate(&challenge) else {
+        return Err("el router ya no ofrece Digest".to_string());
+    };
+
+    let uri = match url.query() {
+        Some(q) => format!("{}?{}", url.path(), q),
+        None => url.path().to_string(),
+    };
+    let authorization = http_auth::digest_authorization(&challenge, "GET", &uri, user, pass, 1);
+    send(client.get(url).header(header::AUTHORIZATION, authorization))
+}
+
+fn www_authenticate(response: &Response) -> Option<String> {
+    // Si hay varios esquemas preferimos Digest: Basic enviaria la clave en claro.
+    let values: Vec<String> = response
+        .headers()
+        .get_all(header::WWW_AUTHENTICATE)
+        .iter()
+        .filter_map(|v| v.to_str().ok().map(str::to_string))
+        .collect();
+    values
+        .iter()
+        .find(|v| v.trim_start().to_ascii_lowercase().starts_with("digest"))
+        .or(values.first())
+        .cloned()
+}
+
+fn new_client() -> Result<Client, String> {
+    Client::builder()
+        .cookie_store(true)
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(redirect::Policy::limited(5))
+        .user_agent(USER_AGENT)
+        // Paneles HTTPS de routers: casi siempre certificado autofirmado.
+        .danger_accept_invalid_certs(true)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+fn send(request: RequestBuilder) -> Result<Response, String> {
+    request.send().map_err(|e| e.to_string())
+}
+
+fn base_url(ip: &str) -> Result<Url, String> {
+    Url::parse(&format!("http://{}/", ip)).map_err(|e| e.to_string())
+}
+
+// Ruta de un perfil dentro del router auditado: la sesion (cookies, Basic/Digest) nunca sale a otro host.
+fn router_url(ip: &str, path: &str) -> Result<Url, String> {
+    let base = base_url(ip)?;
+    let url = base.join(path).map_err(|e| e.to_string())?;
+    if url.host_str() != base.host_str() || url.port_or_known_default() != base.port_or_known_default() {
+        return Err(format!("ruta fuera del router: {}", path));
+    }
+    Ok(url)
+}
+
+#[async_trait]
+impl RouterAuditorPort for HttpAuditor {
+    async fn audit_gateway(&self, ip: &str, observer: Arc<dyn RouterAuditObserver>) -> RouterAuditResult {
+        // reqwest::blocking no puede usarse dentro del runtime async.
+        let ip_for_task = ip.to_string();
+        let ip_for_err = ip_for_task.clone();
+        let auditor = self.clone();
+
+        tauri::async_runtime::spawn_blocking(move || auditor.audit_gateway_blocking(&ip_for_task, observer.as_ref()))
+        .await
+        .unwrap_or_else(|_| RouterAuditResult {
+            audit_id: None,
+            target_ip: ip_for_err,
+            vulnerable: false,
+            credentials_found: None,
+            message: "JoinError".to_string(),
+            fingerprint: None,
+            findings: Vec::new(),
+        })
+    }
+
+    async fn fetch_connected_devices(&self, ip: &str, creds: &RouterCredentials) -> Vec<Device> {
+        let ip = ip.to_string();
+        let user = creds.user.clone();
+        let pass = creds.pass.clone();
+        let auditor = self.clone();
+
+        tauri::async_runtime::spawn_blocking(move || auditor.fetch_connected_devices_blocking(&ip, &user, &pass))
+        .await
+        .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infrastructure::service_probes::test_support::{response, spawn_stand_in_http, StandInRequest};
+    use super::*;
+    use crate::infrastructure::router_audit::attempts::test_support::RecordingObserver;
+
+    // Cada auditor con su propio rastro: los tests corren en paralelo y no comparten fichero.
+    fn silent() -> HttpAuditor {
+        static NEXT_TRAIL: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let n = NEXT_TRAIL.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let name = format!("netsentinel_http_auditor_trail_{}_{}.json", std::process::id(), n);
+        let trail = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_file(&trail);
+        let policy = AttemptPolicy {
+            delay: Duration::ZERO,
+            ..AttemptPolicy::default()
+        };
+        HttpAuditor::with_policy(Arc::new(|_| {}), policy, LoginAttemptTrail::with_path(trail))
+    }
+
+    fn creds(user: &str, pass: &str) -> RouterCredentials {
+        RouterCredentials {
+            user: user.to_string(),
+            pass: pass.to_string(),
+        }
+    }
+
+    const LOGIN_PAGE: &str = r#"<html><head><title>Home Gateway</title></head><body>
+        <form action="/cgi-bin/login" method="post">
+          <input type="hidden" name="csrf_token" value="tok42">
+          <input type="text" name="username"><input type="password" name="password">
+          <button type="submit">Login</button>
+        </form></body></html>"#;
+
+    // Login por formulario: exige la cookie de la pagina de login y el token CSRF.
+    fn form_router(req: &StandInRequest) -> String {
+        let logged_in = req.has_cookie("sid=ok");
+        match (req.method.as_str(), req.path.as_str()) {
+            ("GET", "/") if logged_in => response("302 Found", &[("Location", "/status.htm")], ""),
+            ("GET", "/") => response("200 OK", &[("Set-Cookie", "pre=1; Path=/")], LOGIN_PAGE),
+            ("POST", "/cgi-bin/login") => {
+                let valid = req.has_cookie("pre=1")
+                    && req.body.contains("csrf_token=tok42")
+                    && req.body.contains("username=admin")
+                    && req.body.contains("password=1234");
+                if valid {
+                    response("302 Found", &[("Location", "/status.htm"), ("Set-Cookie", "sid=ok; Path=/")], "")
+                } else {
+                    response("200 OK", &[], &LOGIN_PAGE.replace("<form", "<p>Invalid password</p><form"))
+                }
+            }
+            ("GET", "/status.htm") if logged_in => response(
+                "200 OK",
+                &[],
+                "<html><body><div><a id='logout'>Salir</a></div><div>Portatil</div><div>IP: 192.168.1.20</div>\
+                 <div>Signal strength: -60 dBm</div></body></html>",
+            ),
+            _ => response("302 Found", &[("Location", "/")], ""),
+        }
+    }
+
+    #[test]
+    fn form_login_with_cookies_and_csrf_finds_default_credentials() {
+        let addr = spawn_stand_in_http(form_router).to_string();
+        let auditor = silent();
+
+        let plan = auditor.detect_login(&addr).unwrap();
+        assert!(matches!(plan.method, HttpLoginMethod::Form { ref form, .. } if form.csrf_field() == Some("csrf_token")));
+
+        let result = auditor.audit_gateway_blocking(&addr, &RecordingObserver::default());
+        assert!(result.vulnerable);
+        assert_eq!(result.credentials_found, Some(creds("admin", "1234")));
+
+        let devices = auditor.fetch_parsed_devices(&addr, "admin", "1234").unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].ip, "192.168.1.20");
+        assert_eq!(devices[0].name.as_deref(), Some("Portatil"));
+        assert!(auditor.fetch_parsed_devices(&addr, "admin", "admin").is_err());
+    }
+
+    #[test]
+    fn successful_audit_stores_sanitized_html_evidence() {
+        let addr = spawn_stand_in_http(form_router).to_string();
+        let observer = RecordingObserver::default();
+
+        assert!(silent().audit_gateway_blocking(&addr, &observer).vulnerable);
+        let evidence = observer.evidence.lock().unwrap();
+        let stages: Vec<RouterEvidenceStage> = evidence.iter().map(|e| e.stage).collect();
+        assert_eq!(
+            stages,
+            vec![RouterEvidenceStage::LoginPage, RouterEvidenceStage::Landing, RouterEvidenceStage::DeviceList]
+        );
+        assert!(evidence.iter().all(|e| e.kind == RouterEvidenceKind::Html));
+        // El token CSRF de la pagina de login no llega al informe.
+        let login = String::from_utf8_lossy(&evidence[0].content);
+        assert!(login.contains("[REDACTED]") && !login.contains("tok42"));
+        assert!(String::from_utf8_lossy(&evidence[1].content).contains("Portatil"));
+    }
+
+    #[test]
+    fn basic_auth_router_is_audited_without_browser() {
+        // "admin:1234" en base64.
+        let addr = spawn_stand_in_http(|req| match req.header("authorization") {
+            Some("Basic YWRtaW46MTIzNA==") => response("200 OK", &[], "<html><body>Estado</body></html>"),
+            _ => response("401 Unauthorized", &[("WWW-Authenticate", "Basic realm=\"Home Router\"")], ""),
+        }).to_string();
+        let auditor = silent();
+
+        assert!(matches!(auditor.detect_login(&addr).unwrap().method, HttpLoginMethod::Basic));
+        let result = auditor.audit_gateway_blocking(&addr, &RecordingObserver::default());
+        assert_eq!(result.credentials_found, Some(creds("admin", "1234")));
+    }
+
+    #[test]
+    fn basic_auth_without_a_panel_is_not_a_finding() {
+        // Acepta cualquier cabecera Authorization pero no hay nada detras (404), o vuelve al login.
+        let addr = spawn_stand_in_http(|req| match req.header("authorization") {
+            Some("Basic YWRtaW46MTIzNA==") => response(
+                "200 OK",
+                &[],
+                "<html><body><form><input type='password' name='pwd'></form></body></html>",
+            ),
+            Some(_) => response("404 Not Found", &[], "<html><body>Not Found</body></html>"),
+            None => response("401 Unauthorized", &[("WWW-Authenticate", "Basic realm=\"Home Router\"")], ""),
+        }).to_string();
+
+        let result = silent().audit_gateway_blocking(&addr, &RecordingObserver::default());
+        assert!(!result.vulnerable);
+        assert!(result.credentials_found.is_none());
+    }
+
+    #[test]
+    fn digest_auth_router_validates_response_hash() {
+        const NONCE: &str = "5f1a9c";
+        let addr = spawn_stand_in_http(|req| {
+            let challenge = format!("Digest realm=\"router\", qop=\"auth\", nonce=\"{}\"", NONCE);
+            let denied = response("401 Unauthorized", &[("WWW-Authenticate", &challenge)], "");
+            let Some(auth) = req.header("authorization") else {
+                return denied;
+            };
+            let params = http_auth::test_support::digest_params(auth);
+            let param = |key: &str| params.get(key).cloned().unwrap_or_default();
+            let ha1 = http_auth::md5_hex(b"admin:router:1234");
+            let ha2 = http_auth::md5_hex(format!("GET:{}", req.path).as_bytes());
+            let expected = http_auth::md5_hex(
+                format!("{}:{}:{}:{}:auth:{}", ha1, NONCE, param("nc"), param("cnonce"), ha2).as_bytes(),
+            );
+            if param("response") == expected {
+                response("200 OK", &[], "<html><body>Estado</body></html>")
+            } else {
+                denied
+            }
+        }).to_string();
+
+        let result = silent().audit_gateway_blocking(&addr, &RecordingObserver::default());
+        assert_eq!(result.credentials_found, Some(creds("admin", "1234")));
+    }
+
+    #[test]
+    fn lockout_response_stops_the_audit() {
+        let attempts = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let addr = spawn_stand_in_http(move |req| {
+            if req.header("authorization").is_none() {
+                return response("401 Unauthorized", &[("WWW-Authenticate", "Basic realm=\"Router\"")], "");
+            }
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            response("429 Too Many Requests", &[("Retry-After", "300")], "Too many attempts")
+        }).to_string();
+
+        let result = silent().audit_gateway_blocking(&addr, &RecordingObserver::default());
+        assert!(!result.vulnerable);
+        assert!(result.message.starts_with("Stopped: lockout"));
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn profile_paths_never_leave_the_router() {
+        assert_eq!(router_url("192.168.1.1", "/status.htm").unwrap().as_str(), "http://192.168.1.1/status.htm");
+        assert!(router_url("192.168.1.1", "//evil.example/x").is_err());
+        assert!(router_url("192.168.1.1", "/\\evil.example/x").is_err());
+    }
+
+    #[test]
+    fn js_only_login_is_left_to_chrome() {
+        let addr = spawn_stand_in_http(|_| {
+            response("200 OK", &[], "<html><body><div id='app'></div><script src='/app.js'></script></body></html>")
+        }).to_string();
+        assert!(silent().detect_login(&addr).is_none());
+    }
+}
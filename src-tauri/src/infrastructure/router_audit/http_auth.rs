@@ -0,0 +1,242 @@
+// src-tauri/src/infrastructure/router_audit/http_auth.rs
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Autenticacion HTTP nativa de routers (RFC 7617 Basic / RFC 2617 Digest).
+// Solo implementamos MD5 y MD5-sess con qop=auth: es lo que exponen los firmwares domesticos.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HttpAuthScheme {
+    Basic,
+    Digest(DigestChallenge),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DigestChallenge {
+    pub realm: String,
+    pub nonce: String,
+    pub opaque: Option<String>,
+    pub qop_auth: bool,
+    pub session: bool,
+}
+
+// Interpreta la cabecera `WWW-Authenticate`. `None` si el esquema/algoritmo no esta soportado.
+pub fn parse_www_authenticate(header: &str) -> Option<HttpAuthScheme> {
+    let header = header.trim();
+    let (scheme, rest) = header.split_once(' ').unwrap_or((header, ""));
+
+    if scheme.eq_ignore_ascii_case("basic") {
+        return Some(HttpAuthScheme::Basic);
+    }
+    if !scheme.eq_ignore_ascii_case("digest") {
+        return None;
+    }
+
+    let params = parse_params(rest);
+    let session = match params.get("algorithm").map(|a| a.to_ascii_uppercase()) {
+        None => false,
+        Some(a) if a == "MD5" => false,
+        Some(a) if a == "MD5-SESS" => true,
+        Some(_) => return None,
+    };
+    let qop = params.get("qop").map(|q| q.to_ascii_lowercase());
+    if qop.as_deref().is_some_and(|q| !q.split(',').any(|v| v.trim() == "auth")) {
+        // Solo auth-int: requiere hash del cuerpo, no lo soportamos.
+        return None;
+    }
+
+    Some(HttpAuthScheme::Digest(DigestChallenge {
+        realm: params.get("realm").cloned().unwrap_or_default(),
+        nonce: params.get("nonce").cloned()?,
+        opaque: params.get("opaque").cloned(),
+        qop_auth: qop.is_some(),
+        session,
+    }))
+}
+
+// `key=value` o `key="value, con comas"` separados por comas.
+fn parse_params(input: &str) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    let mut rest = input.trim();
+    while !rest.is_empty() {
+        let Some((key, tail)) = rest.split_once('=') else {
+            break;
+        };
+        let key = key.trim().trim_start_matches(',').trim().to_ascii_lowercase();
+        let tail = tail.trim_start();
+        let (value, remaining) = if let Some(quoted) = tail.strip_prefix('"') {
+            match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            }
+        } else {
+            match tail.find(',') {
+                Some(end) => (&tail[..end], &tail[end..]),
+                None => (tail, ""),
+            }
+        };
+        out.insert(key, value.trim().to_string());
+        rest = remaining.trim_start().trim_start_matches(',').trim_start();
+    }
+    out
+}
+
+pub fn digest_authorization(
+    challenge: &DigestChallenge,
+    method: &str,
+    uri: &str,
+    user: &str,
+    pass: &str,
+    nonce_count: u32,
+) -> String {
+    digest_authorization_with_cnonce(challenge, method, uri, user, pass, nonce_count, &new_cnonce())
+}
+
+fn digest_authorization_with_cnonce(
+    challenge: &DigestChallenge,
+    method: &str,
+    uri: &str,
+    user: &str,
+    pass: &str,
+    nonce_count: u32,
+    cnonce: &str,
+) -> String {
+    let nc = format!("{:08x}", nonce_count);
+    let mut ha1 = md5_hex(format!("{}:{}:{}", user, challenge.realm, pass).as_bytes());
+    if challenge.session {
+        ha1 = md5_hex(format!("{}:{}:{}", ha1, challenge.nonce, cnonce).as_bytes());
+    }
+    let ha2 = md5_hex(format!("{}:{}", method, uri).as_bytes());
+    let response = if challenge.qop_auth {
+        md5_hex(format!("{}:{}:{}:{}:auth:{}", ha1, challenge.nonce, nc, cnonce, ha2).as_bytes())
+    } else {
+        md5_hex(format!("{}:{}:{}", ha1, challenge.nonce, ha2).as_bytes())
+    };
+
+    let mut header = format!(
+        "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", response=\"{}\"",
+        user, challenge.realm, challenge.nonce, uri, response
+    );
+    if challenge.session {
+        header.push_str(", algorithm=MD5-sess");
+    }
+    if challenge.qop_auth {
+        header.push_str(&format!(", qop=auth, nc={}, cnonce=\"{}\"", nc, cnonce));
+    }
+    if let Some(opaque) = &challenge.opaque {
+        header.push_str(&format!(", opaque=\"{}\"", opaque));
+    }
+    header
+}
+
+fn new_cnonce() -> String {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+    md5_hex(nanos.to_string().as_bytes())[..16].to_string()
+}
+
+// --- MD5 (RFC 1321) minimo: solo para Digest, no usar como primitiva de seguridad ---
+
+const MD5_SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 4,
+    11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+pub fn md5_hex(data: &[u8]) -> String {
+    md5(data).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn md5(data: &[u8]) -> [u8; 16] {
+    let k: Vec<u32> = (0..64).map(|i| ((i as f64 + 1.0).sin().abs() * 4294967296.0) as u32).collect();
+    let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_le_bytes());
+
+    for chunk in message.chunks(64) {
+        let words: Vec<u32> = chunk
+            .chunks(4)
+            .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+            .collect();
+        let [mut a, mut b, mut c, mut d] = state;
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let rotated = a
+                .wrapping_add(f)
+                .wrapping_add(k[i])
+                .wrapping_add(words[g])
+                .rotate_left(MD5_SHIFTS[i]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(rotated);
+        }
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+
+    let mut out = [0u8; 16];
+    for (i, word) in state.iter().enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+pub mod test_support {
+    use super::*;
+
+    // Parametros de una cabecera `Authorization: Digest ...` (lado servidor de los routers de pega).
+    pub fn digest_params(authorization: &str) -> HashMap<String, String> {
+        let rest = authorization.trim().split_once(' ').map(|(_, rest)| rest).unwrap_or("");
+        parse_params(rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn md5_matches_rfc_1321_vectors() {
+        assert_eq!(md5_hex(b""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(md5_hex(b"abc"), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(
+            md5_hex(b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"),
+            "57edf4a22be3c955ac49da2e2107b67a"
+        );
+    }
+
+    #[test]
+    fn digest_matches_rfc_2617_example() {
+        let header = r#"Digest realm="testrealm@host.com", qop="auth,auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", opaque="5ccc069c403ebaf9f0171e9517f40e41""#;
+        let Some(HttpAuthScheme::Digest(challenge)) = parse_www_authenticate(header) else {
+            panic!("challenge Digest no reconocido");
+        };
+        assert_eq!(challenge.realm, "testrealm@host.com");
+        assert!(challenge.qop_auth);
+
+        let auth = digest_authorization_with_cnonce(&challenge, "GET", "/dir/index.html", "Mufasa", "Circle Of Life", 1, "0a4f113b");
+        assert!(auth.contains(r#"response="6629fae49393a05397450978507c4ef1""#));
+        assert!(auth.contains("nc=00000001"));
+        assert!(auth.contains(r#"opaque="5ccc069c403ebaf9f0171e9517f40e41""#));
+    }
+
+    #[test]
+    fn parse_www_authenticate_rejects_unsupported_schemes() {
+        assert_eq!(parse_www_authenticate(r#"Basic realm="TP-LINK""#), Some(HttpAuthScheme::Basic));
+        assert!(parse_www_authenticate(r#"Digest realm="x", nonce="n", algorithm=SHA-256"#).is_none());
+        assert!(parse_www_authenticate(r#"Bearer realm="api""#).is_none());
+    }
+}
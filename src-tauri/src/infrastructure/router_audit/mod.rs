@@ -1,8 +1,19 @@
 // src-tauri/src/infrastructure/router_audit/mod.rs
 
+pub mod attempts;
+pub mod auto_auditor;
 pub mod browser_driver;
 pub mod chrome_auditor;
 pub mod credentials;
 pub mod dom_parser;
 pub mod enrichment;
+pub mod evidence;
+pub mod fingerprint;
+pub mod firmware_db;
+pub mod html_extractor;
+pub mod html_login;
+pub mod http_auditor;
+pub mod http_auth;
+pub mod profiles;
 pub mod scripts;
+pub mod tr064;
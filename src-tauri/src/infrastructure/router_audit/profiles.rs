@@ -0,0 +1,291 @@
+// src-tauri/src/infrastructure/router_audit/profiles.rs
+
+use directories::ProjectDirs;
+use serde::Deserialize;
+use std::fs;
+
+use super::dom_parser::{TableRules, TextRules};
+
+// Perfiles de fabricante embebidos en el binario (`router_audit/profiles/*.json`).
+// Para soportar un router nuevo basta con dejar otro JSON con el mismo esquema en
+// AppData `router_profiles/`; un `id` repetido sustituye al perfil embebido.
+const BUILTIN_PROFILES: &[&str] = &[
+    include_str!("profiles/tp_link.json"),
+    include_str!("profiles/huawei.json"),
+    include_str!("profiles/zte.json"),
+    include_str!("profiles/netgear.json"),
+    include_str!("profiles/avm.json"),
+    include_str!("profiles/sagemcom.json"),
+    include_str!("profiles/generic.json"),
+];
+
+pub const GENERIC_PROFILE_ID: &str = "generic";
+
+// Limite defensivo: cada selector acaba dentro de un script inyectado en la pagina.
+const MAX_SELECTORS: usize = 16;
+const MAX_USER_PROFILES: usize = 64;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouterProfile {
+    pub id: String,
+    pub vendor: String,
+    #[serde(default)]
+    pub fingerprint: FingerprintRules,
+    pub login: LoginRules,
+    pub devices: DeviceListRules,
+    // Credenciales de fabrica del modelo (`[["usuario","clave"], ...]`), antes de la lista generica.
+    #[serde(default)]
+    pub default_credentials: Vec<(String, String)>,
+    // Paginas de estado (relativas) con modelo/hardware/firmware, leidas tras un login valido.
+    #[serde(default)]
+    pub status_paths: Vec<String>,
+}
+
+// Palabras clave (sin distinguir mayusculas) buscadas en la pagina inicial del router.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FingerprintRules {
+    pub title_contains: Vec<String>,
+    pub html_contains: Vec<String>,
+    pub url_contains: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginRules {
+    // Ruta relativa a `http://<ip>`; por defecto la raiz.
+    #[serde(default = "default_login_path")]
+    pub path: String,
+    // Vacio = formulario solo con clave (p.ej. FRITZ!Box).
+    #[serde(default)]
+    pub user_selectors: Vec<String>,
+    pub password_selectors: Vec<String>,
+    #[serde(default)]
+    pub submit_selectors: Vec<String>,
+    #[serde(default)]
+    pub success: SuccessRules,
+}
+
+// Exito = aparece un selector del panel, o la URL deja de contener todos los marcadores de login.
+// Un texto de `failure_text` en la pagina corta el intento antes de esperar.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SuccessRules {
+    pub selector_present: Vec<String>,
+    pub url_not_contains: Vec<String>,
+    pub failure_text: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceListRules {
+    // Pagina con la lista de clientes (relativa); `None` = la pagina tras el login.
+    #[serde(default)]
+    pub path: Option<String>,
+    // Clicks en orden para SPAs donde la lista no tiene URL propia.
+    #[serde(default)]
+    pub click_selectors: Vec<String>,
+    #[serde(default = "default_wait_ms")]
+    pub wait_ms: u64,
+    pub extraction: ExtractionRules,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum ExtractionRules {
+    Text(TextRules),
+    Table(TableRules),
+}
+
+// Lo que el auditor lee de la pagina inicial para elegir perfil.
+#[derive(Clone, Debug, Default)]
+pub struct PageFingerprint {
+    pub title: String,
+    pub url: String,
+    pub html: String,
+}
+
+fn default_login_path() -> String {
+    "/".to_string()
+}
+
+fn default_wait_ms() -> u64 {
+    6000
+}
+
+pub fn load_profiles() -> Vec<RouterProfile> {
+    let mut profiles = builtin_profiles();
+    for custom in appdata_profiles() {
+        match profiles.iter().position(|p| p.id == custom.id) {
+            Some(idx) => profiles[idx] = custom,
+            None => profiles.push(custom),
+        }
+    }
+    profiles
+}
+
+fn builtin_profiles() -> Vec<RouterProfile> {
+    BUILTIN_PROFILES.iter().filter_map(|raw| parse_profile(raw).ok()).collect()
+}
+
+fn appdata_profiles() -> Vec<RouterProfile> {
+    let Some(proj_dirs) = ProjectDirs::from("com", "netsentinel", "app") else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(proj_dirs.data_dir().join("router_profiles")) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().and_then(|x| x.to_str()) == Some("json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .take(MAX_USER_PROFILES)
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            match parse_profile(&content) {
+                Ok(profile) => Some(profile),
+                Err(e) => {
+                    println!("⚠️ [ROUTER] Perfil ignorado {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+pub fn parse_profile(content: &str) -> Result<RouterProfile, String> {
+    let profile: RouterProfile = serde_json::from_str(content).map_err(|e| e.to_string())?;
+
+    if profile.id.trim().is_empty() {
+        return Err("id vacio".to_string());
+    }
+    if profile.login.password_selectors.is_empty() {
+        return Err("passwordSelectors vacio".to_string());
+    }
+    if !is_router_path(&profile.login.path)
+        || profile.devices.path.as_deref().is_some_and(|p| !is_router_path(p))
+        || profile.status_paths.iter().any(|p| !is_router_path(p))
+    {
+        return Err("las rutas deben ser relativas al router (empezar por '/', sin '//' ni '\\')".to_string());
+    }
+    let selector_lists = [
+        &profile.login.user_selectors,
+        &profile.login.password_selectors,
+        &profile.login.submit_selectors,
+        &profile.login.success.selector_present,
+        &profile.devices.click_selectors,
+        &profile.status_paths,
+    ];
+    if selector_lists.iter().any(|list| list.len() > MAX_SELECTORS) {
+        return Err(format!("mas de {MAX_SELECTORS} selectores en una lista"));
+    }
+    if let ExtractionRules::Table(rules) = &profile.devices.extraction {
+        if rules.row_selector.trim().is_empty() {
+            return Err("rowSelector vacio".to_string());
+        }
+    }
+    Ok(profile)
+}
+
+// "//host/x" (y "/\host", que el navegador lee igual) es una URL de otro host, no una ruta del router.
+fn is_router_path(path: &str) -> bool {
+    path.starts_with('/') && !path.starts_with("//") && !path.contains('\\')
+}
+
+// Puntuacion por coincidencias: titulo 3, HTML 2, URL 1. Sin coincidencias => perfil generico.
+pub fn select_profile<'a>(profiles: &'a [RouterProfile], page: &PageFingerprint) -> Option<&'a RouterProfile> {
+    let title = page.title.to_lowercase();
+    let html = page.html.to_lowercase();
+    let url = page.url.to_lowercase();
+    let hits = |needles: &[String], haystack: &str| {
+        needles
+            .iter()
+            .filter(|n| !n.is_empty() && haystack.contains(&n.to_lowercase()))
+            .count()
+    };
+
+    let mut best: Option<(&RouterProfile, usize)> = None;
+    for profile in profiles {
+        let fp = &profile.fingerprint;
+        let score = 3 * hits(&fp.title_contains, &title) + 2 * hits(&fp.html_contains, &html) + hits(&fp.url_contains, &url);
+        if score > best.map(|(_, s)| s).unwrap_or(0) {
+            best = Some((profile, score));
+        }
+    }
+
+    best.map(|(p, _)| p)
+        .or_else(|| profiles.iter().find(|p| p.id == GENERIC_PROFILE_ID))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_profiles_parse_with_unique_ids_and_generic_fallback() {
+        let profiles = builtin_profiles();
+        assert_eq!(profiles.len(), BUILTIN_PROFILES.len(), "algun perfil embebido no es valido");
+
+        let mut ids: Vec<&str> = profiles.iter().map(|p| p.id.as_str()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), profiles.len());
+        assert!(ids.contains(&GENERIC_PROFILE_ID));
+    }
+
+    #[test]
+    fn select_profile_uses_fingerprint_and_falls_back_to_generic() {
+        let profiles = builtin_profiles();
+
+        let fritz = PageFingerprint {
+            title: "FRITZ!Box".to_string(),
+            url: "http://192.168.178.1/".to_string(),
+            html: "<html><body>FRITZ!Box 7590</body></html>".to_string(),
+        };
+        assert_eq!(select_profile(&profiles, &fritz).unwrap().id, "avm");
+
+        let huawei = PageFingerprint {
+            title: "".to_string(),
+            url: "http://192.168.100.1/".to_string(),
+            html: "<input id=\"txt_Username\" type=\"text\">".to_string(),
+        };
+        assert_eq!(select_profile(&profiles, &huawei).unwrap().id, "huawei");
+
+        let unknown = PageFingerprint {
+            title: "Router".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(select_profile(&profiles, &unknown).unwrap().id, GENERIC_PROFILE_ID);
+    }
+
+    #[test]
+    fn parse_profile_rejects_absolute_urls_and_missing_password_field() {
+        let absolute = r##"{"id":"x","vendor":"X","login":{"path":"http://evil/","passwordSelectors":["#p"]},
+            "devices":{"extraction":{"mode":"text"}}}"##;
+        assert!(parse_profile(absolute).is_err());
+        for path in ["//evil.example/x", "/\\\\evil.example/x"] {
+            let json = format!(
+                r##"{{"id":"x","vendor":"X","login":{{"passwordSelectors":["#p"]}},"statusPaths":["{}"],
+                "devices":{{"extraction":{{"mode":"text"}}}}}}"##,
+                path
+            );
+            assert!(parse_profile(&json).is_err(), "{path}");
+        }
+
+        let no_pass = r##"{"id":"x","vendor":"X","login":{"passwordSelectors":[]},"devices":{"extraction":{"mode":"text"}}}"##;
+        assert!(parse_profile(no_pass).is_err());
+
+        let table = r##"{"id":"x","vendor":"X","login":{"passwordSelectors":["#p"]},
+            "devices":{"path":"/clients","extraction":{"mode":"table","rowSelector":"tr","ipColumn":0}}}"##;
+        let profile = parse_profile(table).unwrap();
+        assert_eq!(profile.login.path, "/");
+        assert_eq!(profile.devices.wait_ms, 6000);
+        assert!(matches!(profile.devices.extraction, ExtractionRules::Table(ref t) if t.ip_column == 0));
+    }
+}
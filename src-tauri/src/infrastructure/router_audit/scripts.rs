@@ -2,41 +2,183 @@
 
 // Scripts de inyeccion JavaScript para automatizar login y extraccion.
 // Regla: mantener el JS aislado para que el auditor Rust sea legible y testeable.
+// Los selectores vienen de perfiles (ficheros de usuario): siempre se inyectan como JSON, nunca concatenados.
+
+use serde_json::json;
+
+use super::attempts::LOCKOUT_MARKERS;
+use super::dom_parser::TableRules;
+use super::profiles::{LoginRules, SuccessRules};
 
 pub struct ScriptArsenal;
 
 impl ScriptArsenal {
-    pub fn injection_login(user: &str, pass: &str) -> String {
+    // Devuelve {title, url, html} de la pagina actual (html recortado) para elegir perfil.
+    pub fn injection_fingerprint() -> &'static str {
+        r#"
+        JSON.stringify({
+            title: document.title || "",
+            url: location.href,
+            html: (document.documentElement ? document.documentElement.outerHTML : "").slice(0, 20000)
+        })
+        "#
+    }
+
+    pub fn injection_login(rules: &LoginRules, user: &str, pass: &str) -> String {
+        let args = json!({
+            "userSelectors": rules.user_selectors,
+            "passwordSelectors": rules.password_selectors,
+            "user": user,
+            "pass": pass,
+        });
         format!(
             r#"
-            (function() {{
-                var u = document.getElementById('name')
-                  || document.querySelector("input[ng-model='username']")
-                  || document.querySelector("input[id='user']");
-                if(u) {{ u.value = '{}'; u.dispatchEvent(new Event('input', {{bubbles:true}})); u.dispatchEvent(new Event('change', {{bubbles:true}})); }}
-                else {{ return "NO_USER"; }}
+            (function(cfg) {{
+                function first(list) {{
+                    for (var i = 0; i < list.length; i++) {{
+                        try {{ var el = document.querySelector(list[i]); if (el) return el; }} catch (e) {{}}
+                    }}
+                    return null;
+                }}
+                function fill(el, value) {{
+                    el.value = value;
+                    el.dispatchEvent(new Event('input', {{bubbles:true}}));
+                    el.dispatchEvent(new Event('change', {{bubbles:true}}));
+                }}
+
+                if (cfg.userSelectors.length) {{
+                    var u = first(cfg.userSelectors);
+                    if (u) {{ fill(u, cfg.user); }} else {{ return "NO_USER"; }}
+                }}
 
-                var p = document.getElementById('password') || document.querySelector("input[type='password']");
-                if(p) {{ p.value = '{}'; p.dispatchEvent(new Event('input', {{bubbles:true}})); p.dispatchEvent(new Event('change', {{bubbles:true}})); }}
-                else {{ return "NO_PASS"; }}
+                var p = first(cfg.passwordSelectors);
+                if (p) {{ fill(p, cfg.pass); }} else {{ return "NO_PASS"; }}
 
                 return "OK";
-            }})()
+            }})({})
         "#,
-            user, pass
+            args
         )
     }
 
-    pub fn injection_click_submit() -> &'static str {
-        r#"
-        var btn = document.querySelector(".submit button") || document.querySelector("button[type='submit']");
-        if(btn) btn.click();
-        else { var e = new KeyboardEvent('keydown', {bubbles:true, keyCode:13}); document.querySelector("input[type='password']").dispatchEvent(e); }
-        "#
+    pub fn injection_click_submit(rules: &LoginRules) -> String {
+        let args = json!({
+            "submitSelectors": rules.submit_selectors,
+            "passwordSelectors": rules.password_selectors,
+        });
+        format!(
+            r#"
+            (function(cfg) {{
+                function first(list) {{
+                    for (var i = 0; i < list.length; i++) {{
+                        try {{ var el = document.querySelector(list[i]); if (el) return el; }} catch (e) {{}}
+                    }}
+                    return null;
+                }}
+                var btn = first(cfg.submitSelectors);
+                if (btn) {{ btn.click(); return "CLICK"; }}
+                var p = first(cfg.passwordSelectors);
+                if (p) {{ p.dispatchEvent(new KeyboardEvent('keydown', {{bubbles:true, keyCode:13}})); return "ENTER"; }}
+                return "NONE";
+            }})({})
+        "#,
+            args
+        )
+    }
+
+    // "OK" | "FAIL" | "LOCKED" | "WAIT" segun las reglas de exito del perfil y los textos de bloqueo.
+    pub fn injection_login_status(rules: &SuccessRules, login_url: &str) -> String {
+        let args = json!({
+            "selectorPresent": rules.selector_present,
+            "urlNotContains": rules.url_not_contains,
+            "failureText": rules.failure_text,
+            "lockoutText": LOCKOUT_MARKERS,
+            "loginUrl": login_url,
+        });
+        format!(
+            r#"
+            (function(cfg) {{
+                var href = location.href;
+                var body = ((document.body && document.body.innerText) || "").toLowerCase();
+                for (var k = 0; k < cfg.lockoutText.length; k++) {{
+                    if (body.indexOf(cfg.lockoutText[k]) >= 0) return "LOCKED";
+                }}
+                for (var i = 0; i < cfg.failureText.length; i++) {{
+                    if (body.indexOf(cfg.failureText[i].toLowerCase()) >= 0) return "FAIL";
+                }}
+                for (var j = 0; j < cfg.selectorPresent.length; j++) {{
+                    try {{ if (document.querySelector(cfg.selectorPresent[j])) return "OK"; }} catch (e) {{}}
+                }}
+                if (cfg.urlNotContains.length && href !== cfg.loginUrl) {{
+                    var lower = href.toLowerCase();
+                    var clean = cfg.urlNotContains.every(function(t) {{ return lower.indexOf(t.toLowerCase()) < 0; }});
+                    if (clean) return "OK";
+                }}
+                return "WAIT";
+            }})({})
+        "#,
+            args
+        )
+    }
+
+    pub fn injection_click(selector: &str) -> String {
+        format!(
+            r#"
+            (function(sel) {{
+                try {{ var el = document.querySelector(sel); if (el) {{ el.click(); return true; }} }} catch (e) {{}}
+                return false;
+            }})({})
+        "#,
+            json!(selector)
+        )
     }
 
     pub fn injection_extract_text() -> &'static str {
         "document.body.innerText"
     }
+
+    // DOM ya renderizado (incluye tablas generadas por JS).
+    pub fn injection_extract_html() -> &'static str {
+        "document.documentElement.outerHTML"
+    }
+
+    // Filas como JSON `[[celda, ...], ...]` (texto visible de cada td/th).
+    pub fn injection_extract_table_rows(rules: &TableRules) -> String {
+        format!(
+            r#"
+            (function(sel) {{
+                var rows = [];
+                try {{ rows = Array.prototype.slice.call(document.querySelectorAll(sel)); }} catch (e) {{}}
+                return JSON.stringify(rows.map(function(r) {{
+                    return Array.prototype.map.call(r.querySelectorAll('td, th, [role=cell]'), function(c) {{
+                        return (c.innerText || c.textContent || "").trim();
+                    }});
+                }}));
+            }})({})
+        "#,
+            json!(rules.row_selector)
+        )
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn login_values_are_injected_as_json_literals() {
+        let rules = LoginRules {
+            path: "/".to_string(),
+            user_selectors: vec!["#user".to_string()],
+            password_selectors: vec!["input[type=password]".to_string()],
+            submit_selectors: vec![],
+            success: SuccessRules::default(),
+        };
+        let hostile = "x\"); alert('pwn'); //:\\\n";
+        let js = ScriptArsenal::injection_login(&rules, "admin", hostile);
+
+        // La clave aparece solo como string JSON escapado dentro del objeto de argumentos.
+        assert!(js.contains(&serde_json::to_string(hostile).unwrap()));
+        assert!(!js.contains(hostile));
+    }
+}
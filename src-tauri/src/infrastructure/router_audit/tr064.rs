@@ -0,0 +1,353 @@
+// src-tauri/src/infrastructure/router_audit/tr064.rs
+
+use reqwest::blocking::{Client, Response};
+use reqwest::{header, redirect, StatusCode, Url};
+use std::cell::{Cell, RefCell};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::entities::{Device, RouterCredentials};
+use crate::infrastructure::network::upnp_client::{xml, IgdService, SoapError};
+
+use super::dom_parser::ParsedRouterDevice;
+use super::enrichment::enrich_router_devices;
+use super::http_auth::{self, DigestChallenge, HttpAuthScheme};
+
+const TR064_PORT: u16 = 49000;
+const DESCRIPTION_PATH: &str = "/tr64desc.xml";
+const HOSTS_SERVICE: &str = "urn:dslforum-org:service:Hosts:1";
+
+// Tope de la tabla de hosts: un firmware roto podria anunciar miles de entradas.
+const MAX_HOSTS: u32 = 512;
+// Un router sin TR-064 suele filtrar el puerto: no bloqueamos la sync esperando.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(6);
+
+// Cliente SOAP del servicio Hosts (bloqueante). Autentica con Digest y reutiliza el
+// ultimo desafio (nc creciente) hasta que el router pida uno nuevo.
+struct Tr064Client {
+    http: Client,
+    service: IgdService,
+    user: String,
+    pass: String,
+    challenge: RefCell<Option<DigestChallenge>>,
+    nonce_count: Cell<u32>,
+}
+
+impl Tr064Client {
+    // Descarga `tr64desc.xml` y localiza el servicio Hosts. La descripcion no requiere login.
+    fn connect(ip: &str, creds: &RouterCredentials) -> Result<Self, String> {
+        let http = Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .redirect(redirect::Policy::none())
+            .build()
+            .map_err(|e| format!("No se pudo crear el cliente HTTP: {e}"))?;
+        let location = description_url(ip)?;
+        let response = http
+            .get(location.clone())
+            .send()
+            .map_err(|e| format!("TR-064 no accesible: {e}"))?;
+        if !response.status().is_success() {
+            return Err(format!("TR-064 no accesible: HTTP {}", response.status()));
+        }
+        let description = response.text().map_err(|e| e.to_string())?;
+        let service = xml::find_service(&description, &location, &[HOSTS_SERVICE])
+            .ok_or("El router no expone el servicio TR-064 Hosts")?;
+
+        Ok(Self {
+            http,
+            service,
+            user: creds.user.clone(),
+            pass: creds.pass.clone(),
+            challenge: RefCell::new(None),
+            nonce_count: Cell::new(0),
+        })
+    }
+
+    fn host_count(&self) -> Result<u32, SoapError> {
+        let body = self.call("GetHostNumberOfEntries", &[])?;
+        Ok(xml::tag_text(&body, "NewHostNumberOfEntries")
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(0))
+    }
+
+    fn host_entry(&self, index: u32) -> Result<Option<ParsedRouterDevice>, SoapError> {
+        let body = self.call("GetGenericHostEntry", &[("NewIndex", index.to_string())])?;
+        let text = |tag: &str| xml::tag_text(&body, tag).filter(|v| !v.is_empty());
+
+        // Hosts recordados sin lease actual: el router no les asigna IP.
+        let Some(ip) = text("NewIPAddress") else {
+            return Ok(None);
+        };
+        Ok(Some(ParsedRouterDevice {
+            ip,
+            name: text("NewHostName"),
+            mac: text("NewMACAddress").map(|m| m.replace('-', ":").to_uppercase()),
+            signal_strength: None,
+            signal_rate: None,
+            wifi_band: None,
+            interface_type: text("NewInterfaceType"),
+            active: text("NewActive").map(|a| matches!(a.as_str(), "1" | "true" | "True")),
+        }))
+    }
+
+    fn call(&self, action: &str, args: &[(&str, String)]) -> Result<String, SoapError> {
+        let envelope = xml::soap_envelope(&self.service.service_type, action, args);
+
+        let mut response = self.post(action, &envelope)?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            // Primer acceso o nonce caducado: nuevo desafio y un unico reintento.
+            let challenge = digest_challenge(&response)
+                .ok_or_else(|| SoapError::Transport("TR-064 no ofrece autenticacion Digest".to_string()))?;
+            self.challenge.replace(Some(challenge));
+            self.nonce_count.set(0);
+            response = self.post(action, &envelope)?;
+            if response.status() == StatusCode::UNAUTHORIZED {
+                return Err(SoapError::Transport("Credenciales TR-064 rechazadas".to_string()));
+            }
+        }
+
+        let status = response.status();
+        let body = response.text().map_err(|e| SoapError::Transport(e.to_string()))?;
+        if let Some(code) = xml::tag_text(&body, "errorCode").and_then(|c| c.parse().ok()) {
+            return Err(SoapError::Fault(code));
+        }
+        if !status.is_success() {
+            return Err(SoapError::Transport(format!("HTTP {}", status)));
+        }
+        Ok(body)
+    }
+
+    fn post(&self, action: &str, envelope: &str) -> Result<Response, SoapError> {
+        let url = &self.service.control_url;
+        let mut request = self
+            .http
+            .post(url.clone())
+            .header(header::CONTENT_TYPE, "text/xml; charset=\"utf-8\"")
+            .header("SOAPAction", format!("\"{}#{}\"", self.service.service_type, action))
+            .body(envelope.to_string());
+
+        if let Some(challenge) = self.challenge.borrow().as_ref() {
+            let nc = self.nonce_count.get() + 1;
+            self.nonce_count.set(nc);
+            let authorization = http_auth::digest_authorization(challenge, "POST", url.path(), &self.user, &self.pass, nc);
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        request.send().map_err(|e| SoapError::Transport(e.to_string()))
+    }
+}
+
+fn digest_challenge(response: &Response) -> Option<DigestChallenge> {
+    response
+        .headers()
+        .get_all(header::WWW_AUTHENTICATE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .find_map(|v| match http_auth::parse_www_authenticate(v) {
+            Some(HttpAuthScheme::Digest(challenge)) => Some(challenge),
+            _ => None,
+        })
+}
+
+// IP sola -> puerto TR-064 estandar; `host:puerto` se respeta tal cual.
+fn description_url(ip: &str) -> Result<Url, String> {
+    let ip = ip.trim();
+    let authority = match ip.parse::<IpAddr>() {
+        Ok(addr) => SocketAddr::new(addr, TR064_PORT).to_string(),
+        Err(_) => ip.to_string(),
+    };
+    Url::parse(&format!("http://{}{}", authority, DESCRIPTION_PATH)).map_err(|e| e.to_string())
+}
+
+// Lista de clientes via TR-064 (AVM FritzBox y muchos CPE de operador):
+// - `tr64desc.xml` en el puerto 49000 anuncia el servicio Hosts
+// - `GetHostNumberOfEntries` + `GetGenericHostEntry(NewIndex)` con Digest
+// Datos estructurados (MAC, IP, nombre, interfaz, activo): no depende del layout de la web.
+#[derive(Clone)]
+pub struct Tr064Auditor {
+    log_callback: Arc<dyn Fn(String) + Send + Sync>,
+}
+
+impl Tr064Auditor {
+    pub fn new(logger: Arc<dyn Fn(String) + Send + Sync>) -> Self {
+        Self { log_callback: logger }
+    }
+
+    fn log(&self, msg: &str) {
+        (self.log_callback)(msg.to_string());
+    }
+
+    // Solo comprueba que el servicio Hosts esta anunciado (sin credenciales).
+    pub fn is_available(&self, ip: &str) -> bool {
+        let anonymous = RouterCredentials {
+            user: String::new(),
+            pass: String::new(),
+        };
+        Tr064Client::connect(ip, &anonymous).is_ok()
+    }
+
+    pub fn fetch_parsed_devices(&self, ip: &str, creds: &RouterCredentials) -> Result<Vec<ParsedRouterDevice>, String> {
+        let client = Tr064Client::connect(ip, creds)?;
+        self.log("   🔑 Autenticandose (TR-064 Digest)...");
+        let count = client.host_count().map_err(soap_message)?;
+        if count > MAX_HOSTS {
+            self.log(&format!("   ⚠️ El router anuncia {} hosts; se leen los primeros {}", count, MAX_HOSTS));
+        }
+
+        let mut devices = Vec::new();
+        for index in 0..count.min(MAX_HOSTS) {
+            match client.host_entry(index) {
+                Ok(Some(device)) => devices.push(device),
+                Ok(None) => {}
+                Err(e) => {
+                    self.log(&format!("   ⚠️ Tabla de hosts interrumpida en {}: {}", index, soap_message(e)));
+                    break;
+                }
+            }
+        }
+        Ok(devices)
+    }
+
+    // El error se devuelve al dispatcher: el usuario TR-064 suele ser distinto del de la web y
+    // entonces hay que caer al backend HTTP/Chrome en vez de sincronizar una lista vacia.
+    pub fn fetch_connected_devices_blocking(
+        &self,
+        ip: &str,
+        creds: &RouterCredentials,
+    ) -> Result<Vec<Device>, String> {
+        self.log(&format!("📡 SYNC (TR-064): Conectando a {}...", ip));
+        let parsed = self.fetch_parsed_devices(ip, creds).inspect_err(|e| {
+            self.log(&format!("❌ SYNC (TR-064): {}", e));
+        })?;
+        let devices = enrich_router_devices(parsed);
+        for d in devices.iter() {
+            self.log(&format!(
+                "   ✨ DETECTADO: {} ({}) MAC={} VENDOR={} IF={}",
+                d.name.as_deref().unwrap_or("Unknown"),
+                d.ip,
+                d.mac,
+                d.vendor,
+                d.interface_type.as_deref().unwrap_or("-")
+            ));
+        }
+        Ok(devices)
+    }
+}
+
+fn soap_message(error: SoapError) -> String {
+    match error {
+        SoapError::Fault(code) => format!("fault UPnP {}", code),
+        SoapError::Transport(e) => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::service_probes::test_support::{response, spawn_stand_in_http, StandInRequest};
+
+    const DESCRIPTION: &str = r#"<?xml version="1.0"?><root xmlns="urn:dslforum-org:device-1-0"><device>
+        <deviceType>urn:dslforum-org:device:InternetGatewayDevice:1</deviceType>
+        <serviceList>
+          <service><serviceType>urn:dslforum-org:service:DeviceInfo:1</serviceType><controlURL>/upnp/control/deviceinfo</controlURL></service>
+          <service><serviceType>urn:dslforum-org:service:Hosts:1</serviceType><controlURL>/upnp/control/hosts</controlURL></service>
+        </serviceList></device></root>"#;
+    const NONCE: &str = "A1B2C3D4";
+
+    // (ip, mac, nombre, interfaz, activo)
+    const HOSTS: &[(&str, &str, &str, &str, &str)] = &[
+        ("192.168.178.20", "AA:BB:CC:00:11:22", "laptop", "802.11", "1"),
+        ("", "AA:BB:CC:00:11:33", "old-phone", "802.11", "0"),
+        ("192.168.178.30", "aa-bb-cc-00-11-44", "nas", "Ethernet", "0"),
+    ];
+
+    fn soap(action: &str, inner: &str) -> String {
+        let body = format!(
+            "<?xml version=\"1.0\"?><s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\"><s:Body>\
+             <u:{action}Response xmlns:u=\"{HOSTS_SERVICE}\">{inner}</u:{action}Response></s:Body></s:Envelope>"
+        );
+        response("200 OK", &[("Content-Type", "text/xml")], &body)
+    }
+
+    // Valida el Digest igual que un FritzBox (usuario "admin", clave "fritz", qop=auth).
+    fn authorized(req: &StandInRequest) -> bool {
+        let Some(auth) = req.header("authorization") else {
+            return false;
+        };
+        let params = http_auth::test_support::digest_params(auth);
+        let param = |key: &str| params.get(key).cloned().unwrap_or_default();
+        let ha1 = http_auth::md5_hex(b"admin:F!Box SOAP-Auth:fritz");
+        let ha2 = http_auth::md5_hex(format!("POST:{}", req.path).as_bytes());
+        let expected = http_auth::md5_hex(
+            format!("{}:{}:{}:{}:auth:{}", ha1, NONCE, param("nc"), param("cnonce"), ha2).as_bytes(),
+        );
+        param("response") == expected
+    }
+
+    fn fritz_handler(req: &StandInRequest) -> String {
+        match (req.method.as_str(), req.path.as_str()) {
+            ("GET", "/tr64desc.xml") => response("200 OK", &[("Content-Type", "text/xml")], DESCRIPTION),
+            ("POST", "/upnp/control/hosts") if !authorized(req) => {
+                let challenge = format!("Digest realm=\"F!Box SOAP-Auth\", nonce=\"{}\", algorithm=MD5, qop=\"auth\"", NONCE);
+                response("401 Unauthorized", &[("WWW-Authenticate", &challenge)], "")
+            }
+            ("POST", "/upnp/control/hosts") => {
+                let action = req.header("soapaction").unwrap_or("");
+                if action.contains("#GetHostNumberOfEntries") {
+                    return soap("GetHostNumberOfEntries", &format!("<NewHostNumberOfEntries>{}</NewHostNumberOfEntries>", HOSTS.len()));
+                }
+                let index: usize = xml::tag_text(&req.body, "NewIndex").and_then(|i| i.parse().ok()).unwrap_or(usize::MAX);
+                let Some((ip, mac, name, iface, active)) = HOSTS.get(index) else {
+                    return response("500 Internal Server Error", &[], "<s:Fault><errorCode>713</errorCode></s:Fault>");
+                };
+                soap(
+                    "GetGenericHostEntry",
+                    &format!(
+                        "<NewIPAddress>{ip}</NewIPAddress><NewAddressSource>DHCP</NewAddressSource>\
+                         <NewLeaseTimeRemaining>0</NewLeaseTimeRemaining><NewMACAddress>{mac}</NewMACAddress>\
+                         <NewInterfaceType>{iface}</NewInterfaceType><NewActive>{active}</NewActive>\
+                         <NewHostName>{name}</NewHostName>"
+                    ),
+                )
+            }
+            _ => response("404 Not Found", &[], ""),
+        }
+    }
+
+    fn creds(user: &str, pass: &str) -> RouterCredentials {
+        RouterCredentials {
+            user: user.to_string(),
+            pass: pass.to_string(),
+        }
+    }
+
+    #[test]
+    fn hosts_table_is_read_with_digest_and_skips_entries_without_ip() {
+        let addr = spawn_stand_in_http(fritz_handler).to_string();
+        let auditor = Tr064Auditor::new(Arc::new(|_| {}));
+
+        assert!(auditor.is_available(&addr));
+        let devices = auditor.fetch_parsed_devices(&addr, &creds("admin", "fritz")).unwrap();
+
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].ip, "192.168.178.20");
+        assert_eq!(devices[0].name.as_deref(), Some("laptop"));
+        assert_eq!(devices[0].interface_type.as_deref(), Some("802.11"));
+        assert_eq!(devices[0].active, Some(true));
+        assert_eq!(devices[1].mac.as_deref(), Some("AA:BB:CC:00:11:44"));
+        assert_eq!(devices[1].active, Some(false));
+
+        let rejected = auditor.fetch_parsed_devices(&addr, &creds("admin", "wrong"));
+        assert_eq!(rejected.unwrap_err(), "Credenciales TR-064 rechazadas");
+    }
+
+    #[test]
+    fn router_without_tr064_description_is_not_available() {
+        let addr = spawn_stand_in_http(|_| response("404 Not Found", &[], "")).to_string();
+        let auditor = Tr064Auditor::new(Arc::new(|_| {}));
+        assert!(!auditor.is_available(&addr));
+        assert_eq!(description_url("192.168.178.1").unwrap().as_str(), "http://192.168.178.1:49000/tr64desc.xml");
+    }
+}
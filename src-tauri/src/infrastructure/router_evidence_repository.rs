@@ -0,0 +1,166 @@
+// src-tauri/src/infrastructure/router_evidence_repository.rs
+
+use directories::ProjectDirs;
+use std::fs;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use crate::domain::entities::{RouterEvidence, RouterEvidenceItem, RouterEvidenceKind, RouterEvidenceStage};
+use crate::domain::ports::RouterEvidenceRepositoryPort;
+
+// Un pantallazo de pagina completa ronda 1-3 MB; por encima no se guarda.
+const MAX_EVIDENCE_BYTES: usize = 8 * 1024 * 1024;
+const MAX_AUDIT_ID_LEN: usize = 64;
+
+// Evidencias en AppData: `router_evidence/<audit_id>/<stage>.<ext>`.
+// Los nombres salen de una lista cerrada (etapa x tipo), nunca de la ruta que llega por IPC.
+pub struct FileRouterEvidenceRepository {
+    root: PathBuf,
+}
+
+impl FileRouterEvidenceRepository {
+    pub fn new() -> Self {
+        Self::with_root(Self::default_root())
+    }
+
+    pub fn with_root(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn default_root() -> PathBuf {
+        if let Some(proj_dirs) = ProjectDirs::from("com", "netsentinel", "app") {
+            return proj_dirs.data_dir().join("router_evidence");
+        }
+        PathBuf::from("netsentinel_router_evidence")
+    }
+
+    fn audit_dir(&self, audit_id: &str) -> Result<PathBuf, String> {
+        validate_audit_id(audit_id)?;
+        Ok(self.root.join(audit_id))
+    }
+}
+
+impl RouterEvidenceRepositoryPort for FileRouterEvidenceRepository {
+    fn save_evidence(&self, audit_id: &str, evidence: &RouterEvidence) -> Result<RouterEvidenceItem, String> {
+        if evidence.content.is_empty() || evidence.content.len() > MAX_EVIDENCE_BYTES {
+            return Err(format!("evidencia vacia o demasiado grande ({} bytes)", evidence.content.len()));
+        }
+        let dir = self.audit_dir(audit_id)?;
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+        let name = file_name(evidence.stage, evidence.kind);
+        let path = dir.join(&name);
+        fs::write(&path, &evidence.content).map_err(|e| e.to_string())?;
+        item_for(audit_id, &name, evidence.stage, evidence.kind, &path)
+    }
+
+    fn list_evidence(&self, audit_id: &str) -> Result<Vec<RouterEvidenceItem>, String> {
+        let dir = self.audit_dir(audit_id)?;
+        let mut items = Vec::new();
+        for stage in RouterEvidenceStage::ALL {
+            for kind in RouterEvidenceKind::ALL {
+                let name = file_name(stage, kind);
+                let path = dir.join(&name);
+                if path.is_file() {
+                    items.push(item_for(audit_id, &name, stage, kind, &path)?);
+                }
+            }
+        }
+        Ok(items)
+    }
+
+    fn read_evidence(&self, audit_id: &str, name: &str) -> Result<(RouterEvidenceItem, Vec<u8>), String> {
+        let (stage, kind) = parse_file_name(name).ok_or_else(|| format!("evidencia desconocida: '{}'", name))?;
+        let path = self.audit_dir(audit_id)?.join(name);
+        let content = fs::read(&path).map_err(|_| format!("evidencia no encontrada: {}/{}", audit_id, name))?;
+        Ok((item_for(audit_id, name, stage, kind, &path)?, content))
+    }
+}
+
+// Formato de `new_router_audit_id` ("router_audit_<ts>_<n>"); basta con excluir separadores y "..".
+fn validate_audit_id(audit_id: &str) -> Result<(), String> {
+    let valid = !audit_id.is_empty()
+        && audit_id.len() <= MAX_AUDIT_ID_LEN
+        && audit_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err("audit_id invalido".to_string())
+    }
+}
+
+fn file_name(stage: RouterEvidenceStage, kind: RouterEvidenceKind) -> String {
+    format!("{}.{}", stage.as_str(), kind.extension())
+}
+
+fn parse_file_name(name: &str) -> Option<(RouterEvidenceStage, RouterEvidenceKind)> {
+    RouterEvidenceStage::ALL
+        .into_iter()
+        .flat_map(|stage| RouterEvidenceKind::ALL.into_iter().map(move |kind| (stage, kind)))
+        .find(|(stage, kind)| file_name(*stage, *kind) == name)
+}
+
+fn item_for(
+    audit_id: &str,
+    name: &str,
+    stage: RouterEvidenceStage,
+    kind: RouterEvidenceKind,
+    path: &PathBuf,
+) -> Result<RouterEvidenceItem, String> {
+    let meta = fs::metadata(path).map_err(|e| e.to_string())?;
+    let captured_at = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    Ok(RouterEvidenceItem {
+        audit_id: audit_id.to_string(),
+        name: name.to_string(),
+        stage,
+        kind,
+        size_bytes: meta.len(),
+        captured_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_list_and_read_stay_inside_the_audit_directory() {
+        let root = std::env::temp_dir().join(format!("netsentinel_router_evidence_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let repo = FileRouterEvidenceRepository::with_root(root.clone());
+
+        let html = RouterEvidence {
+            stage: RouterEvidenceStage::Landing,
+            kind: RouterEvidenceKind::Html,
+            content: b"<html>ok</html>".to_vec(),
+        };
+        let png = RouterEvidence {
+            stage: RouterEvidenceStage::LoginPage,
+            kind: RouterEvidenceKind::Screenshot,
+            content: vec![0x89, b'P', b'N', b'G'],
+        };
+        let saved = repo.save_evidence("router_audit_1_0", &html).unwrap();
+        assert_eq!(saved.name, "landing.html");
+        repo.save_evidence("router_audit_1_0", &png).unwrap();
+
+        let items = repo.list_evidence("router_audit_1_0").unwrap();
+        let names: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["login_page.png", "landing.html"]);
+
+        let (item, content) = repo.read_evidence("router_audit_1_0", "landing.html").unwrap();
+        assert_eq!(item.size_bytes, 15);
+        assert_eq!(content, b"<html>ok</html>");
+
+        assert!(repo.list_evidence("router_audit_2_0").unwrap().is_empty());
+        assert!(repo.list_evidence("../etc").is_err());
+        assert!(repo.read_evidence("router_audit_1_0", "../../secret.png").is_err());
+        assert!(repo.read_evidence("router_audit_1_0", "device_list.png").is_err());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
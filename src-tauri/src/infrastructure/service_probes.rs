@@ -0,0 +1,67 @@
+// src-tauri/src/infrastructure/service_probes.rs
+
+use async_trait::async_trait;
+use std::net::{IpAddr, SocketAddr};
+
+use crate::domain::entities::ServiceProbeResult;
+use crate::domain::ports::ServiceProbePort;
+
+// Submodulos para separar responsabilidades (SOLID) sin cambiar la API publica del adaptador.
+#[path = "service_probes/io.rs"]
+mod io;
+#[path = "service_probes/ftp.rs"]
+mod ftp;
+#[path = "service_probes/telnet.rs"]
+mod telnet;
+#[path = "service_probes/smtp.rs"]
+mod smtp;
+#[path = "service_probes/redis.rs"]
+mod redis;
+#[path = "service_probes/mongodb.rs"]
+mod mongodb;
+#[path = "service_probes/elasticsearch.rs"]
+mod elasticsearch;
+#[path = "service_probes/couchdb.rs"]
+mod couchdb;
+#[path = "service_probes/mqtt.rs"]
+mod mqtt;
+#[path = "service_probes/docker.rs"]
+mod docker;
+
+// Servidor HTTP de pega compartido con los tests del router, TR-064 y UPnP.
+#[cfg(test)]
+pub use io::test_support;
+
+// Sondas activas sobre TCP:
+// - servicios en texto plano (FTP/Telnet/SMTP)
+// - bases de datos y APIs de contenedores sin autenticacion (Redis, MongoDB, Elasticsearch, CouchDB, MQTT, Docker)
+// Cada sonda es bloqueante (std::net) y se ejecuta en `spawn_blocking`.
+pub struct TcpServiceProbe;
+
+impl TcpServiceProbe {
+    fn probe_blocking(addr: SocketAddr) -> Option<ServiceProbeResult> {
+        match addr.port() {
+            21 => ftp::probe(addr),
+            23 | 2323 => telnet::probe(addr),
+            25 | 587 => smtp::probe(addr),
+            1883 => mqtt::probe(addr),
+            2375 => docker::probe(addr),
+            5984 => couchdb::probe(addr),
+            6379 => redis::probe(addr),
+            9200 => elasticsearch::probe(addr),
+            27017 => mongodb::probe(addr),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl ServiceProbePort for TcpServiceProbe {
+    async fn probe_service(&self, ip: &str, port: u16) -> Option<ServiceProbeResult> {
+        let addr = SocketAddr::new(ip.trim().parse::<IpAddr>().ok()?, port);
+        tauri::async_runtime::spawn_blocking(move || Self::probe_blocking(addr))
+            .await
+            .ok()
+            .flatten()
+    }
+}
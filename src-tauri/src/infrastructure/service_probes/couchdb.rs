@@ -0,0 +1,82 @@
+// src-tauri/src/infrastructure/service_probes/couchdb.rs
+
+use std::net::SocketAddr;
+
+use crate::domain::entities::{ServiceProbeResult, Vulnerability};
+
+use super::io;
+
+const MAX_DATABASES: usize = 15;
+
+// CouchDB en "admin party" (sin admins) o con /_all_dbs publico.
+// GET / siempre responde (bienvenida + version); la exposicion se confirma con /_all_dbs.
+pub fn probe(addr: SocketAddr) -> Option<ServiceProbeResult> {
+    let root = io::http_get(addr, "/").ok()?;
+    if root.status != 200 {
+        return None;
+    }
+    let json: serde_json::Value = serde_json::from_str(&root.body).ok()?;
+    json.get("couchdb")?;
+    let number = json.get("version").and_then(|v| v.as_str()).unwrap_or("?");
+
+    let mut result = ServiceProbeResult {
+        port: addr.port(),
+        service: "COUCHDB".to_string(),
+        version: Some(format!("CouchDB {number}")),
+        banner: Some(io::sanitize_evidence(&root.body)),
+        vulnerabilities: Vec::new(),
+    };
+
+    let Ok(all_dbs) = io::http_get(addr, "/_all_dbs") else {
+        return Some(result);
+    };
+    if all_dbs.status != 200 {
+        return Some(result);
+    }
+    let names: Vec<String> = serde_json::from_str::<Vec<String>>(&all_dbs.body).unwrap_or_default();
+
+    result.vulnerabilities.push(Vulnerability {
+        id: "COUCHDB-NO-AUTH".to_string(),
+        description: "CouchDB permite listar (y probablemente leer/escribir) bases de datos sin autenticacion.".to_string(),
+        severity: "CRITICAL".to_string(),
+        recommendation: "Crear un administrador (salir de 'admin party'), exigir require_valid_user y restringir el bind a localhost.".to_string(),
+        evidence: Some(io::sanitize_evidence(&format!(
+            "GET /_all_dbs sin credenciales -> 200\nBases de datos: {}",
+            names.iter().take(MAX_DATABASES).cloned().collect::<Vec<_>>().join(", ")
+        ))),
+    });
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::service_probes::io::test_support::{response, spawn_stand_in_http};
+
+    #[test]
+    fn probe_flags_public_all_dbs() {
+        let addr = spawn_stand_in_http(|req| match req.path.as_str() {
+            "/" => response("200 OK", &[], r#"{"couchdb":"Welcome","version":"2.3.1"}"#),
+            "/_all_dbs" => response("200 OK", &[], r#"["_users","invoices"]"#),
+            _ => response("404 Not Found", &[], "{}"),
+        });
+        let result = probe(addr).unwrap();
+
+        assert_eq!(result.version.as_deref(), Some("CouchDB 2.3.1"));
+        assert_eq!(result.vulnerabilities[0].id, "COUCHDB-NO-AUTH");
+        assert!(result.vulnerabilities[0].evidence.as_deref().unwrap().contains("invoices"));
+    }
+
+    #[test]
+    fn probe_with_admin_only_reports_version() {
+        let addr = spawn_stand_in_http(|req| match req.path.as_str() {
+            "/" => response("200 OK", &[], r#"{"couchdb":"Welcome","version":"3.3.2"}"#),
+            "/_all_dbs" => response("401 Unauthorized", &[], r#"{"error":"unauthorized"}"#),
+            _ => response("404 Not Found", &[], "{}"),
+        });
+        let result = probe(addr).unwrap();
+
+        assert!(result.vulnerabilities.is_empty());
+    }
+}
@@ -0,0 +1,74 @@
+// src-tauri/src/infrastructure/service_probes/docker.rs
+
+use std::net::SocketAddr;
+
+use crate::domain::entities::{ServiceProbeResult, Vulnerability};
+
+use super::io;
+
+// API de Docker Engine en TCP sin TLS (2375): equivale a root en el host.
+// Solo consultamos /version y /containers/json (lectura).
+pub fn probe(addr: SocketAddr) -> Option<ServiceProbeResult> {
+    let version = io::http_get(addr, "/version").ok()?;
+    if version.status != 200 {
+        return None;
+    }
+    let json: serde_json::Value = serde_json::from_str(&version.body).ok()?;
+    let engine = json.get("Version").and_then(|v| v.as_str())?;
+    let api = json.get("ApiVersion").and_then(|v| v.as_str()).unwrap_or("?");
+    let os = json.get("Os").and_then(|v| v.as_str()).unwrap_or("?");
+    let arch = json.get("Arch").and_then(|v| v.as_str()).unwrap_or("?");
+
+    let containers: Vec<String> = io::http_get(addr, "/containers/json?all=1")
+        .ok()
+        .filter(|r| r.status == 200)
+        .and_then(|r| serde_json::from_str::<serde_json::Value>(&r.body).ok())
+        .and_then(|v| v.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|c| c.pointer("/Names/0").and_then(|n| n.as_str()))
+        .map(|n| n.trim_start_matches('/').to_string())
+        .collect();
+
+    Some(ServiceProbeResult {
+        port: addr.port(),
+        service: "DOCKER-API".to_string(),
+        version: Some(format!("Docker Engine {engine} (API {api})")),
+        banner: Some(format!("{os}/{arch}")),
+        vulnerabilities: vec![Vulnerability {
+            id: "DOCKER-API-NO-AUTH".to_string(),
+            description: "La API de Docker esta expuesta sin TLS ni autenticacion: permite lanzar contenedores privilegiados y tomar control del host.".to_string(),
+            severity: "CRITICAL".to_string(),
+            recommendation: "Cerrar 2375; si se necesita acceso remoto usar 2376 con TLS mutuo o SSH (DOCKER_HOST=ssh://).".to_string(),
+            evidence: Some(io::sanitize_evidence(&format!(
+                "GET /version sin credenciales -> 200 (Engine {engine}, {os}/{arch})\nContenedores: {}",
+                if containers.is_empty() { "(ninguno)".to_string() } else { containers.join(", ") }
+            ))),
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::service_probes::io::test_support::{response, spawn_stand_in_http};
+
+    #[test]
+    fn probe_flags_exposed_docker_api() {
+        let addr = spawn_stand_in_http(|req| match req.path.as_str() {
+            "/version" => {
+                response("200 OK", &[], r#"{"Version":"24.0.7","ApiVersion":"1.43","Os":"linux","Arch":"amd64"}"#)
+            }
+            "/containers/json?all=1" => {
+                response("200 OK", &[], r#"[{"Id":"abc","Names":["/pihole"]},{"Id":"def","Names":["/nextcloud"]}]"#)
+            }
+            _ => response("404 Not Found", &[], "{}"),
+        });
+        let result = probe(addr).unwrap();
+
+        assert_eq!(result.version.as_deref(), Some("Docker Engine 24.0.7 (API 1.43)"));
+        let vuln = &result.vulnerabilities[0];
+        assert_eq!(vuln.severity, "CRITICAL");
+        assert!(vuln.evidence.as_deref().unwrap().contains("pihole, nextcloud"));
+    }
+}
@@ -0,0 +1,91 @@
+// src-tauri/src/infrastructure/service_probes/elasticsearch.rs
+
+use std::net::SocketAddr;
+
+use crate::domain::entities::{ServiceProbeResult, Vulnerability};
+
+use super::io;
+
+const MAX_INDICES: usize = 15;
+
+// Elasticsearch/OpenSearch sin seguridad: GET / devuelve el cluster sin 401.
+// Con X-Pack security activo responde 401 + WWW-Authenticate.
+pub fn probe(addr: SocketAddr) -> Option<ServiceProbeResult> {
+    let root = io::http_get(addr, "/").ok()?;
+    let mut result = ServiceProbeResult {
+        port: addr.port(),
+        service: "ELASTICSEARCH".to_string(),
+        version: None,
+        banner: None,
+        vulnerabilities: Vec::new(),
+    };
+
+    if root.status == 401 || root.status == 403 {
+        result.banner = root.header("www-authenticate").map(io::sanitize_evidence);
+        return Some(result);
+    }
+    if root.status != 200 {
+        return None;
+    }
+
+    let json: serde_json::Value = serde_json::from_str(&root.body).ok()?;
+    let number = json.pointer("/version/number").and_then(|v| v.as_str())?;
+    let distribution = json
+        .pointer("/version/distribution")
+        .and_then(|v| v.as_str())
+        .unwrap_or("Elasticsearch");
+    let cluster = json.get("cluster_name").and_then(|v| v.as_str()).unwrap_or("?");
+    result.version = Some(format!("{distribution} {number}"));
+    result.banner = Some(format!("cluster_name={cluster}"));
+
+    let indices: Vec<String> = io::http_get(addr, "/_cat/indices?h=index")
+        .ok()
+        .filter(|r| r.status == 200)
+        .map(|r| r.body.lines().take(MAX_INDICES).map(|l| l.trim().to_string()).collect())
+        .unwrap_or_default();
+
+    result.vulnerabilities.push(Vulnerability {
+        id: "ELASTICSEARCH-NO-AUTH".to_string(),
+        description: "La API REST de Elasticsearch responde sin autenticacion: los indices se pueden leer, modificar o borrar.".to_string(),
+        severity: "CRITICAL".to_string(),
+        recommendation: "Activar xpack.security (usuarios + TLS) y no exponer 9200 fuera de la red de servicio.".to_string(),
+        evidence: Some(io::sanitize_evidence(&format!(
+            "GET / -> 200 (cluster_name={cluster}, version={number})\nGET /_cat/indices -> {}",
+            if indices.is_empty() { "(sin indices visibles)".to_string() } else { indices.join(", ") }
+        ))),
+    });
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::service_probes::io::test_support::{response, spawn_stand_in_http};
+
+    #[test]
+    fn probe_flags_open_cluster_and_lists_indices() {
+        let addr = spawn_stand_in_http(|req| match req.path.as_str() {
+            "/" => response(
+                "200 OK",
+                &[],
+                r#"{"name":"node-1","cluster_name":"homelab","version":{"number":"7.17.9"},"tagline":"You Know, for Search"}"#,
+            ),
+            "/_cat/indices?h=index" => response("200 OK", &[], "customers\nlogs-2024\n"),
+            _ => response("404 Not Found", &[], "{}"),
+        });
+        let result = probe(addr).unwrap();
+
+        assert_eq!(result.version.as_deref(), Some("Elasticsearch 7.17.9"));
+        assert_eq!(result.vulnerabilities[0].id, "ELASTICSEARCH-NO-AUTH");
+        assert!(result.vulnerabilities[0].evidence.as_deref().unwrap().contains("customers, logs-2024"));
+    }
+
+    #[test]
+    fn probe_respects_401() {
+        let addr = spawn_stand_in_http(|_| response("401 Unauthorized", &[], "{}"));
+        let result = probe(addr).unwrap();
+
+        assert!(result.vulnerabilities.is_empty());
+    }
+}
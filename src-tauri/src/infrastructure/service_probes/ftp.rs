@@ -0,0 +1,191 @@
+// src-tauri/src/infrastructure/service_probes/ftp.rs
+
+use regex::Regex;
+use std::io::Read;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+
+use crate::domain::entities::{ServiceProbeResult, Vulnerability};
+
+use super::io::{self, LineSession};
+
+const MAX_LISTING_BYTES: usize = 4096;
+const MAX_LISTING_LINES: usize = 20;
+const ANONYMOUS_PASS: &str = "netsentinel@example.com";
+
+// Comprobacion de FTP anonimo:
+// - captura el banner (version)
+// - intenta USER anonymous / PASS
+// - si entra, lista la raiz en modo pasivo (solo lectura, sin descargar ni subir nada)
+pub fn probe(addr: SocketAddr) -> Option<ServiceProbeResult> {
+    let mut session = LineSession::open(addr).ok()?;
+    let (code, banner) = session.read_reply().ok()?;
+    if code != 220 {
+        return None;
+    }
+
+    let mut result = ServiceProbeResult {
+        port: addr.port(),
+        service: "FTP".to_string(),
+        version: Some(io::reply_text(&banner)).filter(|v| !v.is_empty()),
+        banner: Some(io::sanitize_evidence(&banner)),
+        vulnerabilities: Vec::new(),
+    };
+
+    if let Some(login) = anonymous_login(&mut session) {
+        let listing = list_root(&mut session, addr.ip());
+        let mut evidence = format!("{}\n{}", banner.trim(), login.join("\n"));
+        if let Some(listing) = &listing {
+            evidence.push_str("\nLIST /:\n");
+            evidence.push_str(listing);
+        }
+
+        result.vulnerabilities.push(Vulnerability {
+            id: "FTP-ANONYMOUS-LOGIN".to_string(),
+            description: "El servidor FTP acepta login anonimo: cualquiera en la red puede leer (y quiza escribir) ficheros.".to_string(),
+            severity: "HIGH".to_string(),
+            recommendation: "Desactivar el acceso anonimo o sustituir FTP por SFTP con cuentas nominales.".to_string(),
+            evidence: Some(io::sanitize_evidence(&evidence)),
+        });
+    }
+
+    let _ = session.send("QUIT");
+    Some(result)
+}
+
+// Dialogo real del login anonimo ("USER anonymous -> 331 ...", "PASS ... -> 230 ..."); `None` si no entra.
+fn anonymous_login(session: &mut LineSession) -> Option<Vec<String>> {
+    let mut transcript = Vec::new();
+    let mut step = |session: &mut LineSession, command: &str| {
+        let (code, reply) = session.command(command).ok()?;
+        transcript.push(format!("{} -> {}", command, reply.lines().last().unwrap_or("").trim()));
+        Some(code)
+    };
+    let logged_in = match step(session, "USER anonymous")? {
+        230 => true,
+        331 => step(session, &format!("PASS {ANONYMOUS_PASS}"))? == 230,
+        _ => false,
+    };
+    logged_in.then_some(transcript)
+}
+
+fn list_root(session: &mut LineSession, control_ip: IpAddr) -> Option<String> {
+    let (code, reply) = session.command("PASV").ok()?;
+    if code != 227 {
+        return None;
+    }
+    // Usamos siempre la IP del canal de control: ignoramos la IP anunciada (evita FTP bounce / NAT roto).
+    let data_port = parse_pasv_port(&reply)?;
+    let mut data = io::connect(SocketAddr::new(control_ip, data_port)).ok()?;
+
+    let (code, _) = session.command("LIST").ok()?;
+    if code != 150 && code != 125 {
+        return None;
+    }
+
+    let mut raw = Vec::new();
+    let _ = data.by_ref().take(MAX_LISTING_BYTES as u64).read_to_end(&mut raw);
+    drop(data);
+    let _ = session.read_reply(); // 226 Transfer complete (best-effort)
+
+    let text = String::from_utf8_lossy(&raw);
+    let lines: Vec<&str> = text.lines().take(MAX_LISTING_LINES).collect();
+    Some(lines.join("\n"))
+}
+
+fn parse_pasv_port(reply: &str) -> Option<u16> {
+    static PASV_RE: OnceLock<Regex> = OnceLock::new();
+    let re = PASV_RE.get_or_init(|| Regex::new(r"(\d+),(\d+),(\d+),(\d+),(\d+),(\d+)").unwrap());
+    let caps = re.captures(reply)?;
+    let p1: u16 = caps[5].parse().ok()?;
+    let p2: u16 = caps[6].parse().ok()?;
+    if p1 > 255 || p2 > 255 {
+        return None;
+    }
+    Some(p1 * 256 + p2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // Servidor FTP minimo (vsFTPd ficticio) con acceso anonimo y un LIST en modo pasivo.
+    fn spawn_stand_in_ftp(allow_anonymous: bool) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let Ok((stream, _)) = listener.accept() else {
+                return;
+            };
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let data_listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let data_port = data_listener.local_addr().unwrap().port();
+
+            let _ = writer.write_all(b"220-Welcome\r\n220 (vsFTPd 3.0.3)\r\n");
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or(0) > 0 {
+                let cmd = line.trim().to_string();
+                line.clear();
+                let reply = if cmd.starts_with("USER") {
+                    "331 Please specify the password.".to_string()
+                } else if cmd.starts_with("PASS") && allow_anonymous {
+                    "230 Login successful.".to_string()
+                } else if cmd.starts_with("PASS") {
+                    "530 Login incorrect.".to_string()
+                } else if cmd == "PASV" {
+                    // IP anunciada falsa: el cliente debe ignorarla y usar la del canal de control.
+                    format!("227 Entering Passive Mode (10,9,9,9,{},{}).", data_port / 256, data_port % 256)
+                } else if cmd == "LIST" {
+                    let _ = writer.write_all(b"150 Here comes the directory listing.\r\n");
+                    if let Ok((mut data, _)) = data_listener.accept() {
+                        let _ = data.write_all(b"drwxr-xr-x 2 0 0 4096 Jan 01 00:00 pub\r\n-rw-r--r-- 1 0 0 12 Jan 01 00:00 backup.cfg\r\n");
+                    }
+                    "226 Directory send OK.".to_string()
+                } else if cmd == "QUIT" {
+                    let _ = writer.write_all(b"221 Goodbye.\r\n");
+                    return;
+                } else {
+                    "500 Unknown command.".to_string()
+                };
+                let _ = writer.write_all(format!("{reply}\r\n").as_bytes());
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn probe_detects_anonymous_login_and_captures_listing() {
+        let addr = spawn_stand_in_ftp(true);
+        let result = probe(addr).unwrap();
+
+        assert_eq!(result.version.as_deref(), Some("(vsFTPd 3.0.3)"));
+        assert_eq!(result.vulnerabilities.len(), 1);
+        let vuln = &result.vulnerabilities[0];
+        assert_eq!(vuln.id, "FTP-ANONYMOUS-LOGIN");
+        let evidence = vuln.evidence.as_deref().unwrap();
+        assert!(evidence.contains("vsFTPd 3.0.3"));
+        // La evidencia es el dialogo que hubo, no un 230 directo supuesto.
+        assert!(evidence.contains("USER anonymous -> 331 Please specify the password."), "evidence={evidence}");
+        assert!(evidence.contains("PASS netsentinel@example.com -> 230 Login successful."), "evidence={evidence}");
+        assert!(evidence.contains("backup.cfg"), "evidence={evidence}");
+    }
+
+    #[test]
+    fn probe_without_anonymous_only_reports_banner() {
+        let addr = spawn_stand_in_ftp(false);
+        let result = probe(addr).unwrap();
+
+        assert!(result.vulnerabilities.is_empty());
+        assert!(result.banner.unwrap().contains("vsFTPd"));
+    }
+
+    #[test]
+    fn parse_pasv_port_reads_last_two_octets() {
+        assert_eq!(parse_pasv_port("227 Entering Passive Mode (192,168,1,10,195,80)."), Some(50000));
+        assert_eq!(parse_pasv_port("227 nope"), None);
+    }
+}
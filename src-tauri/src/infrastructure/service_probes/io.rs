@@ -0,0 +1,277 @@
+// src-tauri/src/infrastructure/service_probes/io.rs
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+pub const CONNECT_TIMEOUT: Duration = Duration::from_millis(1500);
+pub const IO_TIMEOUT: Duration = Duration::from_millis(2500);
+
+// Limite de evidencia por hallazgo: suficiente para el informe sin volcar respuestas enormes.
+const MAX_EVIDENCE_CHARS: usize = 600;
+const MAX_REPLY_LINES: usize = 50;
+const MAX_HTTP_BYTES: u64 = 64 * 1024;
+
+pub fn connect(addr: SocketAddr) -> Result<TcpStream, String> {
+    let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).map_err(|e| format!("connect {addr}: {e}"))?;
+    stream.set_read_timeout(Some(IO_TIMEOUT)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(IO_TIMEOUT)).map_err(|e| e.to_string())?;
+    Ok(stream)
+}
+
+// Sesion de texto linea a linea (FTP/SMTP comparten el formato de respuesta "NNN texto").
+pub struct LineSession {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl LineSession {
+    pub fn open(addr: SocketAddr) -> Result<Self, String> {
+        let stream = connect(addr)?;
+        let writer = stream.try_clone().map_err(|e| e.to_string())?;
+        Ok(Self {
+            reader: BufReader::new(stream),
+            writer,
+        })
+    }
+
+    pub fn send(&mut self, line: &str) -> Result<(), String> {
+        self.writer
+            .write_all(format!("{line}\r\n").as_bytes())
+            .map_err(|e| e.to_string())
+    }
+
+    // Lee una respuesta completa, incluidas las multilinea ("220-..." hasta "220 ...").
+    pub fn read_reply(&mut self) -> Result<(u16, String), String> {
+        let mut lines = Vec::new();
+        let mut code: Option<u16> = None;
+
+        while lines.len() < MAX_REPLY_LINES {
+            let mut line = String::new();
+            let n = self.reader.read_line(&mut line).map_err(|e| e.to_string())?;
+            if n == 0 {
+                break;
+            }
+            let line = line.trim_end().to_string();
+            let parsed = line.get(0..3).and_then(|c| c.parse::<u16>().ok());
+            let is_last = match (code, parsed) {
+                (None, Some(c)) => {
+                    code = Some(c);
+                    line.as_bytes().get(3) != Some(&b'-')
+                }
+                (Some(c), Some(p)) => p == c && line.as_bytes().get(3) != Some(&b'-'),
+                _ => false,
+            };
+            lines.push(line);
+            if is_last {
+                break;
+            }
+        }
+
+        let code = code.ok_or("respuesta sin codigo")?;
+        Ok((code, lines.join("\n")))
+    }
+
+    pub fn command(&mut self, line: &str) -> Result<(u16, String), String> {
+        self.send(line)?;
+        self.read_reply()
+    }
+}
+
+// Respuesta HTTP minima para APIs REST sin autenticacion (Elasticsearch, CouchDB, Docker).
+pub struct HttpReply {
+    pub status: u16,
+    pub headers: String,
+    pub body: String,
+}
+
+impl HttpReply {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.trim().eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    }
+}
+
+// GET en HTTP/1.0 (sin chunked ni keep-alive): basta para APIs JSON pequenas y evita depender de un runtime.
+pub fn http_get(addr: SocketAddr, path: &str) -> Result<HttpReply, String> {
+    let mut stream = connect(addr)?;
+    let request = format!(
+        "GET {path} HTTP/1.0\r\nHost: {}\r\nUser-Agent: NetSentinel\r\nAccept: application/json\r\nConnection: close\r\n\r\n",
+        addr.ip()
+    );
+    stream.write_all(request.as_bytes()).map_err(|e| e.to_string())?;
+
+    let mut raw = Vec::new();
+    let _ = stream.take(MAX_HTTP_BYTES).read_to_end(&mut raw);
+    let text = String::from_utf8_lossy(&raw);
+    let (head, body) = text.split_once("\r\n\r\n").unwrap_or((&text, ""));
+    let (status_line, headers) = head.split_once("\r\n").unwrap_or((head, ""));
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse::<u16>().ok())
+        .ok_or_else(|| format!("respuesta HTTP invalida de {addr}"))?;
+
+    Ok(HttpReply {
+        status,
+        headers: headers.to_string(),
+        body: body.to_string(),
+    })
+}
+
+// Evidencia apta para informes: sin caracteres de control y con longitud acotada.
+pub fn sanitize_evidence(text: &str) -> String {
+    let clean: String = text
+        .chars()
+        .map(|c| if c == '\n' || c == '\t' { c } else if c.is_control() { ' ' } else { c })
+        .collect();
+    let clean = clean.trim();
+    if clean.chars().count() <= MAX_EVIDENCE_CHARS {
+        return clean.to_string();
+    }
+    let truncated: String = clean.chars().take(MAX_EVIDENCE_CHARS).collect();
+    format!("{truncated}...")
+}
+
+// Texto tras el codigo de la ultima linea (ej: "220 (vsFTPd 3.0.3)" -> "(vsFTPd 3.0.3)").
+// En respuestas multilinea el producto suele ir en la linea final.
+pub fn reply_text(reply: &str) -> String {
+    reply
+        .lines()
+        .last()
+        .unwrap_or("")
+        .get(4..)
+        .unwrap_or("")
+        .trim()
+        .to_string()
+}
+
+// Servidor HTTP de pruebas compartido (sondas REST, router, TR-064, UPnP): respuesta por peticion.
+#[cfg(test)]
+pub mod test_support {
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpListener};
+    use std::thread;
+
+    pub struct StandInRequest {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl StandInRequest {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+
+        pub fn has_cookie(&self, cookie: &str) -> bool {
+            self.header("cookie").is_some_and(|c| c.split(';').any(|p| p.trim() == cookie))
+        }
+    }
+
+    // Servidor HTTP de pega en loopback: `handler` devuelve la respuesta cruda (status + cabeceras + cuerpo).
+    pub fn spawn_stand_in_http<F>(handler: F) -> SocketAddr
+    where
+        F: Fn(&StandInRequest) -> String + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    return;
+                };
+                let Some(request) = read_request(&mut stream) else {
+                    continue;
+                };
+                let _ = stream.write_all(handler(&request).as_bytes());
+            }
+        });
+        addr
+    }
+
+    pub fn response(status: &str, headers: &[(&str, &str)], body: &str) -> String {
+        let mut out = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n", status, body.len());
+        for (k, v) in headers {
+            out.push_str(&format!("{}: {}\r\n", k, v));
+        }
+        out.push_str("\r\n");
+        out.push_str(body);
+        out
+    }
+
+    fn read_request(stream: &mut std::net::TcpStream) -> Option<StandInRequest> {
+        let mut raw = Vec::new();
+        let mut buf = [0u8; 4096];
+        let header_end = loop {
+            let n = stream.read(&mut buf).ok()?;
+            if n == 0 {
+                return None;
+            }
+            raw.extend_from_slice(&buf[..n]);
+            if let Some(pos) = raw.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&raw[..header_end]).to_string();
+        let mut lines = head.lines();
+        let mut start = lines.next()?.split_whitespace();
+        let method = start.next()?.to_string();
+        let path = start.next()?.to_string();
+        let headers: Vec<(String, String)> = lines
+            .filter_map(|l| l.split_once(':'))
+            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+            .collect();
+
+        let len = headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, v)| v.parse::<usize>().ok())
+            .unwrap_or(0);
+        while raw.len() < header_end + len {
+            let n = stream.read(&mut buf).ok()?;
+            if n == 0 {
+                break;
+            }
+            raw.extend_from_slice(&buf[..n]);
+        }
+        let body = String::from_utf8_lossy(&raw[header_end..]).to_string();
+        Some(StandInRequest {
+            method,
+            path,
+            headers,
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_evidence_strips_control_chars_and_truncates() {
+        assert_eq!(sanitize_evidence("220 ok\u{0007}\r\n"), "220 ok");
+        let long = "a".repeat(700);
+        assert!(sanitize_evidence(&long).ends_with("..."));
+        assert_eq!(reply_text("220 (vsFTPd 3.0.3)"), "(vsFTPd 3.0.3)");
+    }
+
+    #[test]
+    fn http_get_parses_status_headers_and_body() {
+        let addr = test_support::spawn_stand_in_http(|_| {
+            test_support::response("200 OK", &[("Content-Type", "application/json")], "{\"ok\":true}")
+        });
+        let reply = http_get(addr, "/").unwrap();
+        assert_eq!(reply.status, 200);
+        assert_eq!(reply.header("content-type"), Some("application/json"));
+        assert_eq!(reply.body, "{\"ok\":true}");
+    }
+}
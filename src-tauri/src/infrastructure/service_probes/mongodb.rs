@@ -0,0 +1,330 @@
+// src-tauri/src/infrastructure/service_probes/mongodb.rs
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+
+use crate::domain::entities::{ServiceProbeResult, Vulnerability};
+
+use super::io;
+
+const OP_MSG: i32 = 2013;
+const MAX_REPLY_BYTES: usize = 1024 * 1024;
+// buildInfo/listDatabases no pasan de 2-3 niveles; mas anidamiento es una respuesta hostil.
+const MAX_BSON_DEPTH: usize = 16;
+
+// MongoDB sin --auth: `listDatabases` responde ok:1 sin credenciales.
+// `buildInfo` no requiere auth en ninguna version y sirve para capturar la version.
+pub fn probe(addr: SocketAddr) -> Option<ServiceProbeResult> {
+    let mut stream = io::connect(addr).ok()?;
+
+    let build_info = run_command(&mut stream, 1, "buildInfo")?;
+    let version = lookup(&build_info, "version").and_then(Bson::as_str).map(|v| format!("MongoDB {v}"));
+
+    let mut result = ServiceProbeResult {
+        port: addr.port(),
+        service: "MONGODB".to_string(),
+        version: version.clone(),
+        banner: version,
+        vulnerabilities: Vec::new(),
+    };
+
+    let Some(listing) = run_command(&mut stream, 2, "listDatabases") else {
+        return Some(result);
+    };
+    if !is_ok(&listing) {
+        // Unauthorized (code 13): el servidor exige autenticacion.
+        return Some(result);
+    }
+
+    let names: Vec<String> = match lookup(&listing, "databases") {
+        Some(Bson::Array(items)) => items
+            .iter()
+            .filter_map(|db| match db {
+                Bson::Document(fields) => lookup(fields, "name").and_then(Bson::as_str).map(str::to_string),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    result.vulnerabilities.push(Vulnerability {
+        id: "MONGODB-NO-AUTH".to_string(),
+        description: "MongoDB acepta comandos sin autenticacion: cualquiera en la red puede leer, modificar o borrar las bases de datos.".to_string(),
+        severity: "CRITICAL".to_string(),
+        recommendation: "Activar security.authorization, crear usuarios con roles minimos y enlazar (bindIp) solo a interfaces necesarias.".to_string(),
+        evidence: Some(io::sanitize_evidence(&format!(
+            "listDatabases sin credenciales -> ok:1\nBases de datos: {}",
+            names.join(", ")
+        ))),
+    });
+
+    Some(result)
+}
+
+fn run_command(stream: &mut TcpStream, request_id: i32, command: &str) -> Option<Vec<(String, Bson)>> {
+    let body = encode_document(&[(command, Bson::Int32(1)), ("$db", Bson::String("admin".to_string()))]);
+    stream.write_all(&encode_op_msg(request_id, &body)).ok()?;
+
+    let mut header = [0u8; 16];
+    stream.read_exact(&mut header).ok()?;
+    let len = usize::try_from(i32::from_le_bytes(header[0..4].try_into().ok()?)).ok()?;
+    let op_code = i32::from_le_bytes(header[12..16].try_into().ok()?);
+    if op_code != OP_MSG || !(21..=MAX_REPLY_BYTES).contains(&len) {
+        return None;
+    }
+    let mut payload = vec![0u8; len - 16];
+    stream.read_exact(&mut payload).ok()?;
+
+    // flagBits (4) + seccion tipo 0 (1) + documento BSON.
+    if payload.get(4) != Some(&0) {
+        return None;
+    }
+    decode_document(&payload[5..], 0).map(|(doc, _)| doc)
+}
+
+fn is_ok(doc: &[(String, Bson)]) -> bool {
+    match lookup(doc, "ok") {
+        Some(Bson::Double(v)) => *v == 1.0,
+        Some(Bson::Int32(v)) => *v == 1,
+        Some(Bson::Int64(v)) => *v == 1,
+        Some(Bson::Boolean(v)) => *v,
+        _ => false,
+    }
+}
+
+fn encode_op_msg(request_id: i32, document: &[u8]) -> Vec<u8> {
+    let len = (16 + 4 + 1 + document.len()) as i32;
+    let mut out = Vec::with_capacity(len as usize);
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(&request_id.to_le_bytes());
+    out.extend_from_slice(&0i32.to_le_bytes()); // responseTo
+    out.extend_from_slice(&OP_MSG.to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes()); // flagBits
+    out.push(0); // seccion "body"
+    out.extend_from_slice(document);
+    out
+}
+
+// --- BSON minimo (solo los tipos que devuelven buildInfo/listDatabases) ---
+
+#[derive(Debug, Clone, PartialEq)]
+enum Bson {
+    Double(f64),
+    String(String),
+    Document(Vec<(String, Bson)>),
+    Array(Vec<Bson>),
+    Boolean(bool),
+    Int32(i32),
+    Int64(i64),
+    Other,
+}
+
+impl Bson {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Bson::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+fn lookup<'a>(doc: &'a [(String, Bson)], key: &str) -> Option<&'a Bson> {
+    doc.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+fn encode_document(fields: &[(&str, Bson)]) -> Vec<u8> {
+    let mut body = Vec::new();
+    for (key, value) in fields {
+        let (tag, bytes) = match value {
+            Bson::Int32(v) => (0x10u8, v.to_le_bytes().to_vec()),
+            Bson::String(s) => {
+                let mut b = ((s.len() + 1) as i32).to_le_bytes().to_vec();
+                b.extend_from_slice(s.as_bytes());
+                b.push(0);
+                (0x02u8, b)
+            }
+            _ => continue,
+        };
+        body.push(tag);
+        body.extend_from_slice(key.as_bytes());
+        body.push(0);
+        body.extend_from_slice(&bytes);
+    }
+    let mut out = ((body.len() + 5) as i32).to_le_bytes().to_vec();
+    out.extend_from_slice(&body);
+    out.push(0);
+    out
+}
+
+// Devuelve (campos, bytes consumidos). Las longitudes vienen del servidor: negativas o que se
+// salen del documento invalidan la respuesta entera.
+fn decode_document(data: &[u8], depth: usize) -> Option<(Vec<(String, Bson)>, usize)> {
+    let len = usize::try_from(i32::from_le_bytes(data.get(0..4)?.try_into().ok()?)).ok()?;
+    if len < 5 || len > data.len() || depth > MAX_BSON_DEPTH {
+        return None;
+    }
+    let mut fields = Vec::new();
+    let mut pos = 4;
+    while pos < len - 1 {
+        let tag = data[pos];
+        pos += 1;
+        let key_end = pos + data[pos..len].iter().position(|b| *b == 0)?;
+        let key = String::from_utf8_lossy(&data[pos..key_end]).to_string();
+        pos = key_end + 1;
+
+        let read_i32 = |at: usize| -> Option<i32> { Some(i32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?)) };
+        let read_len = |at: usize| -> Option<usize> { usize::try_from(read_i32(at)?).ok() };
+        let (value, size) = match tag {
+            0x01 => (Bson::Double(f64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?)), 8),
+            0x02 => {
+                let n = read_len(pos)?;
+                let raw = data.get(pos + 4..(pos + 4).checked_add(n)?)?;
+                (Bson::String(String::from_utf8_lossy(&raw[..n.saturating_sub(1)]).to_string()), 4 + n)
+            }
+            0x03 | 0x04 => {
+                let (inner, used) = decode_document(&data[pos..], depth + 1)?;
+                let value = if tag == 0x03 {
+                    Bson::Document(inner)
+                } else {
+                    Bson::Array(inner.into_iter().map(|(_, v)| v).collect())
+                };
+                (value, used)
+            }
+            0x05 => (Bson::Other, read_len(pos)?.checked_add(4 + 1)?),
+            0x07 => (Bson::Other, 12),
+            0x08 => (Bson::Boolean(*data.get(pos)? != 0), 1),
+            0x09 | 0x11 | 0x12 => {
+                let v = i64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?);
+                (if tag == 0x12 { Bson::Int64(v) } else { Bson::Other }, 8)
+            }
+            0x0A => (Bson::Other, 0),
+            0x10 => (Bson::Int32(read_i32(pos)?), 4),
+            // Tipos no usados por estas respuestas: abortamos para no desalinear el parser.
+            _ => return None,
+        };
+        fields.push((key, value));
+        pos = pos.checked_add(size).filter(|end| *end < len)?;
+    }
+    Some((fields, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn reply(request: &[u8], doc: &[u8]) -> Vec<u8> {
+        let request_id = i32::from_le_bytes(request[4..8].try_into().unwrap());
+        let mut out = encode_op_msg(99, doc);
+        out[8..12].copy_from_slice(&request_id.to_le_bytes());
+        out
+    }
+
+    fn doc_with_databases() -> Vec<u8> {
+        // { databases: [ { name: "admin" }, { name: "crm" } ], ok: 1.0 }
+        let db = |name: &str| encode_document(&[("name", Bson::String(name.to_string()))]);
+        let mut array = Vec::new();
+        for (i, name) in ["admin", "crm"].iter().enumerate() {
+            array.push(0x03);
+            array.extend_from_slice(i.to_string().as_bytes());
+            array.push(0);
+            array.extend_from_slice(&db(name));
+        }
+        let mut array_doc = ((array.len() + 5) as i32).to_le_bytes().to_vec();
+        array_doc.extend_from_slice(&array);
+        array_doc.push(0);
+
+        let mut body = vec![0x04];
+        body.extend_from_slice(b"databases\0");
+        body.extend_from_slice(&array_doc);
+        body.push(0x01);
+        body.extend_from_slice(b"ok\0");
+        body.extend_from_slice(&1.0f64.to_le_bytes());
+        let mut out = ((body.len() + 5) as i32).to_le_bytes().to_vec();
+        out.extend_from_slice(&body);
+        out.push(0);
+        out
+    }
+
+    fn spawn_stand_in_mongo(require_auth: bool) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            for _ in 0..2 {
+                let mut header = [0u8; 16];
+                if stream.read_exact(&mut header).is_err() {
+                    return;
+                }
+                let len = i32::from_le_bytes(header[0..4].try_into().unwrap()) as usize;
+                let mut payload = vec![0u8; len - 16];
+                stream.read_exact(&mut payload).unwrap();
+                let (cmd, _) = decode_document(&payload[5..], 0).unwrap();
+
+                let doc = match cmd[0].0.as_str() {
+                    "buildInfo" => encode_document(&[("version", Bson::String("6.0.14".to_string())), ("ok", Bson::Int32(1))]),
+                    _ if require_auth => encode_document(&[
+                        ("ok", Bson::Int32(0)),
+                        ("errmsg", Bson::String("command listDatabases requires authentication".to_string())),
+                    ]),
+                    _ => doc_with_databases(),
+                };
+                stream.write_all(&reply(&header, &doc)).unwrap();
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn probe_flags_mongodb_without_auth() {
+        let result = probe(spawn_stand_in_mongo(false)).unwrap();
+
+        assert_eq!(result.version.as_deref(), Some("MongoDB 6.0.14"));
+        assert_eq!(result.vulnerabilities[0].id, "MONGODB-NO-AUTH");
+        assert!(result.vulnerabilities[0].evidence.as_deref().unwrap().contains("admin, crm"));
+    }
+
+    #[test]
+    fn malformed_bson_lengths_are_rejected_without_panicking() {
+        let valid = encode_document(&[("version", Bson::String("6.0.14".to_string())), ("ok", Bson::Int32(1))]);
+        assert_eq!(decode_document(&valid, 0).unwrap().0.len(), 2);
+
+        // Longitud de string negativa (-1 -> usize::MAX si se hiciera `as usize`).
+        let mut negative_string = valid.clone();
+        negative_string[13..17].copy_from_slice(&(-1i32).to_le_bytes());
+        assert!(decode_document(&negative_string, 0).is_none());
+
+        // Binario (0x05) con longitud negativa y documento con longitud total negativa.
+        let mut binary = vec![0x05];
+        binary.extend_from_slice(b"b\0");
+        binary.extend_from_slice(&i32::MIN.to_le_bytes());
+        binary.extend_from_slice(&[0; 8]);
+        let mut doc = ((binary.len() + 5) as i32).to_le_bytes().to_vec();
+        doc.extend_from_slice(&binary);
+        doc.push(0);
+        assert!(decode_document(&doc, 0).is_none());
+        assert!(decode_document(&(-20i32).to_le_bytes(), 0).is_none());
+
+        // Anidamiento hostil: documentos dentro de documentos hasta agotar la pila.
+        let mut nested = encode_document(&[]);
+        for _ in 0..64 {
+            let mut body = vec![0x03, b'd', 0];
+            body.extend_from_slice(&nested);
+            nested = ((body.len() + 5) as i32).to_le_bytes().to_vec();
+            nested.extend_from_slice(&body);
+            nested.push(0);
+        }
+        assert!(decode_document(&nested, 0).is_none());
+    }
+
+    #[test]
+    fn probe_with_auth_only_reports_version() {
+        let result = probe(spawn_stand_in_mongo(true)).unwrap();
+
+        assert_eq!(result.version.as_deref(), Some("MongoDB 6.0.14"));
+        assert!(result.vulnerabilities.is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+// src-tauri/src/infrastructure/service_probes/mqtt.rs
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+use crate::domain::entities::{ServiceProbeResult, Vulnerability};
+
+use super::io;
+
+const CLIENT_ID: &str = "netsentinel-probe";
+const VERSION_TOPIC: &str = "$SYS/broker/version";
+const VERSION_WAIT: Duration = Duration::from_millis(1500);
+
+const CONNECT: u8 = 0x10;
+const CONNACK: u8 = 0x20;
+const PUBLISH: u8 = 0x30;
+const SUBSCRIBE: u8 = 0x82;
+const DISCONNECT: u8 = 0xE0;
+
+// Broker MQTT 3.1.1 que acepta CONNECT sin usuario/clave (CONNACK rc=0).
+// Para la version nos suscribimos a $SYS/broker/version (Mosquitto lo publica retenido). No publicamos nada.
+pub fn probe(addr: SocketAddr) -> Option<ServiceProbeResult> {
+    let mut stream = io::connect(addr).ok()?;
+    stream.write_all(&connect_packet()).ok()?;
+
+    let (kind, body) = read_packet(&mut stream)?;
+    if kind & 0xF0 != CONNACK || body.len() < 2 {
+        return None;
+    }
+    let return_code = body[1];
+
+    let mut result = ServiceProbeResult {
+        port: addr.port(),
+        service: "MQTT".to_string(),
+        version: None,
+        banner: None,
+        vulnerabilities: Vec::new(),
+    };
+
+    // 4 = usuario/clave incorrectos, 5 = no autorizado: el broker exige credenciales.
+    if return_code != 0 {
+        result.banner = Some(format!("CONNACK rc={return_code} (requiere autenticacion)"));
+        return Some(result);
+    }
+
+    result.version = read_broker_version(&mut stream);
+    result.banner = result.version.clone();
+    let _ = stream.write_all(&[DISCONNECT, 0]);
+
+    result.vulnerabilities.push(Vulnerability {
+        id: "MQTT-NO-AUTH".to_string(),
+        description: "El broker MQTT acepta clientes anonimos: cualquiera puede leer telemetria y publicar ordenes a dispositivos IoT.".to_string(),
+        severity: "CRITICAL".to_string(),
+        recommendation: "Desactivar allow_anonymous, exigir usuario/clave (o certificados) y definir ACLs por topic.".to_string(),
+        evidence: Some(io::sanitize_evidence(&format!(
+            "CONNECT sin credenciales (client_id={CLIENT_ID}) -> CONNACK rc=0{}",
+            result.version.as_deref().map(|v| format!("\n{VERSION_TOPIC} = {v}")).unwrap_or_default()
+        ))),
+    });
+
+    Some(result)
+}
+
+fn read_broker_version(stream: &mut TcpStream) -> Option<String> {
+    stream.write_all(&subscribe_packet(1, VERSION_TOPIC)).ok()?;
+    let deadline = Instant::now() + VERSION_WAIT;
+    while Instant::now() < deadline {
+        let (kind, body) = read_packet(stream)?;
+        if kind & 0xF0 != PUBLISH {
+            continue; // SUBACK u otros
+        }
+        let topic_len = u16::from_be_bytes([*body.first()?, *body.get(1)?]) as usize;
+        let topic = String::from_utf8_lossy(body.get(2..2 + topic_len)?);
+        // QoS 0: no hay packet identifier tras el topic.
+        let payload = body.get(2 + topic_len..)?;
+        if topic == VERSION_TOPIC {
+            return Some(String::from_utf8_lossy(payload).trim().to_string());
+        }
+    }
+    None
+}
+
+fn connect_packet() -> Vec<u8> {
+    let mut body = Vec::new();
+    push_str(&mut body, "MQTT");
+    body.push(4); // protocol level 3.1.1
+    body.push(0x02); // clean session, sin usuario/clave
+    body.extend_from_slice(&10u16.to_be_bytes()); // keep alive
+    push_str(&mut body, CLIENT_ID);
+    frame(CONNECT, &body)
+}
+
+fn subscribe_packet(packet_id: u16, topic: &str) -> Vec<u8> {
+    let mut body = packet_id.to_be_bytes().to_vec();
+    push_str(&mut body, topic);
+    body.push(0); // QoS 0
+    frame(SUBSCRIBE, &body)
+}
+
+fn push_str(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u16).to_be_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn frame(kind: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = vec![kind];
+    let mut len = body.len();
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+    out.extend_from_slice(body);
+    out
+}
+
+fn read_packet(stream: &mut TcpStream) -> Option<(u8, Vec<u8>)> {
+    let mut byte = [0u8; 1];
+    stream.read_exact(&mut byte).ok()?;
+    let kind = byte[0];
+
+    // Remaining length: hasta 4 bytes de 7 bits.
+    let mut len = 0usize;
+    for shift in 0..4 {
+        stream.read_exact(&mut byte).ok()?;
+        len |= ((byte[0] & 0x7F) as usize) << (7 * shift);
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+    }
+    if len > 64 * 1024 {
+        return None;
+    }
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body).ok()?;
+    Some((kind, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn spawn_stand_in_broker(allow_anonymous: bool) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            let (kind, _) = read_packet(&mut stream).unwrap();
+            assert_eq!(kind, CONNECT);
+            let rc = if allow_anonymous { 0 } else { 5 };
+            stream.write_all(&[CONNACK, 2, 0, rc]).unwrap();
+            if !allow_anonymous {
+                return;
+            }
+            let (kind, _) = read_packet(&mut stream).unwrap();
+            assert_eq!(kind, SUBSCRIBE);
+            stream.write_all(&[0x90, 3, 0, 1, 0]).unwrap(); // SUBACK
+            let mut publish = Vec::new();
+            push_str(&mut publish, VERSION_TOPIC);
+            publish.extend_from_slice(b"mosquitto version 2.0.18");
+            stream.write_all(&frame(PUBLISH | 0x01, &publish)).unwrap(); // retained
+            let _ = read_packet(&mut stream);
+        });
+        addr
+    }
+
+    #[test]
+    fn probe_flags_anonymous_broker_with_version() {
+        let result = probe(spawn_stand_in_broker(true)).unwrap();
+
+        assert_eq!(result.version.as_deref(), Some("mosquitto version 2.0.18"));
+        assert_eq!(result.vulnerabilities[0].id, "MQTT-NO-AUTH");
+        assert!(result.vulnerabilities[0].evidence.as_deref().unwrap().contains("rc=0"));
+    }
+
+    #[test]
+    fn probe_respects_not_authorized() {
+        let result = probe(spawn_stand_in_broker(false)).unwrap();
+
+        assert!(result.vulnerabilities.is_empty());
+        assert!(result.banner.unwrap().contains("rc=5"));
+    }
+
+    #[test]
+    fn frame_encodes_multi_byte_remaining_length() {
+        let packet = frame(PUBLISH, &[0u8; 200]);
+        assert_eq!(&packet[..3], &[PUBLISH, 0xC8, 0x01]);
+    }
+}
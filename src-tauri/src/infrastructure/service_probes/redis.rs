@@ -0,0 +1,133 @@
+// src-tauri/src/infrastructure/service_probes/redis.rs
+
+use std::io::{Read, Write};
+use std::net::SocketAddr;
+
+use crate::domain::entities::{ServiceProbeResult, Vulnerability};
+
+use super::io;
+
+const MAX_INFO_BYTES: usize = 16 * 1024;
+
+// Redis sin `requirepass`: INFO responde sin AUTH. Con auth activa devuelve "-NOAUTH".
+// Solo lectura: nunca enviamos CONFIG/SET/FLUSH.
+pub fn probe(addr: SocketAddr) -> Option<ServiceProbeResult> {
+    let mut stream = io::connect(addr).ok()?;
+    stream.write_all(b"INFO server\r\n").ok()?;
+
+    let mut raw = Vec::new();
+    let mut buf = [0u8; 4096];
+    while raw.len() < MAX_INFO_BYTES {
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                raw.extend_from_slice(&buf[..n]);
+                if bulk_complete(&raw) || (raw.starts_with(b"-") && raw.ends_with(b"\r\n")) {
+                    break;
+                }
+            }
+        }
+    }
+    let _ = stream.write_all(b"QUIT\r\n");
+
+    let reply = String::from_utf8_lossy(&raw).to_string();
+    let mut result = ServiceProbeResult {
+        port: addr.port(),
+        service: "REDIS".to_string(),
+        version: None,
+        banner: None,
+        vulnerabilities: Vec::new(),
+    };
+
+    if reply.starts_with('-') {
+        // -NOAUTH / -DENIED (protected-mode): el servicio existe pero esta protegido.
+        result.banner = Some(io::sanitize_evidence(&reply));
+        return Some(result);
+    }
+    if !reply.starts_with('$') {
+        return None;
+    }
+
+    result.version = info_field(&reply, "redis_version").map(|v| format!("Redis {v}"));
+    let evidence_lines: Vec<&str> = reply
+        .lines()
+        .filter(|l| ["redis_version:", "redis_mode:", "os:", "tcp_port:"].iter().any(|k| l.starts_with(k)))
+        .collect();
+    result.banner = result.version.clone();
+    result.vulnerabilities.push(Vulnerability {
+        id: "REDIS-NO-AUTH".to_string(),
+        description: "Redis acepta comandos sin autenticacion: permite leer/borrar datos y, via CONFIG SET, escribir ficheros en el host.".to_string(),
+        severity: "CRITICAL".to_string(),
+        recommendation: "Activar requirepass/ACL, enlazar a 127.0.0.1 (bind) y mantener protected-mode.".to_string(),
+        evidence: Some(io::sanitize_evidence(&format!(
+            "INFO server sin AUTH -> respuesta aceptada\n{}",
+            evidence_lines.join("\n")
+        ))),
+    });
+
+    Some(result)
+}
+
+// Respuesta RESP bulk: "$<len>\r\n<payload>\r\n".
+fn bulk_complete(raw: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(raw);
+    let Some((head, rest)) = text.split_once("\r\n") else {
+        return false;
+    };
+    match head.strip_prefix('$').and_then(|n| n.parse::<usize>().ok()) {
+        Some(len) => rest.len() >= len,
+        None => false,
+    }
+}
+
+fn info_field(info: &str, key: &str) -> Option<String> {
+    info.lines()
+        .find_map(|l| l.strip_prefix(key)?.strip_prefix(':'))
+        .map(|v| v.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn spawn_stand_in_redis(require_auth: bool) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let Ok((stream, _)) = listener.accept() else {
+                return;
+            };
+            let mut writer = stream.try_clone().unwrap();
+            let mut line = String::new();
+            let _ = BufReader::new(stream).read_line(&mut line);
+            if require_auth {
+                let _ = writer.write_all(b"-NOAUTH Authentication required.\r\n");
+                return;
+            }
+            let info = "# Server\r\nredis_version:7.0.15\r\nredis_mode:standalone\r\nos:Linux 6.1.0 x86_64\r\ntcp_port:6379\r\n";
+            let _ = writer.write_all(format!("${}\r\n{info}\r\n", info.len()).as_bytes());
+        });
+        addr
+    }
+
+    #[test]
+    fn probe_flags_redis_without_auth() {
+        let result = probe(spawn_stand_in_redis(false)).unwrap();
+
+        assert_eq!(result.version.as_deref(), Some("Redis 7.0.15"));
+        assert_eq!(result.vulnerabilities[0].id, "REDIS-NO-AUTH");
+        assert_eq!(result.vulnerabilities[0].severity, "CRITICAL");
+        assert!(result.vulnerabilities[0].evidence.as_deref().unwrap().contains("os:Linux"));
+    }
+
+    #[test]
+    fn probe_respects_noauth() {
+        let result = probe(spawn_stand_in_redis(true)).unwrap();
+
+        assert!(result.vulnerabilities.is_empty());
+        assert!(result.banner.unwrap().contains("NOAUTH"));
+    }
+}
@@ -0,0 +1,153 @@
+// src-tauri/src/infrastructure/service_probes/smtp.rs
+
+use std::net::SocketAddr;
+
+use crate::domain::entities::{ServiceProbeResult, Vulnerability};
+
+use super::io::{self, LineSession};
+
+// Remitente/destino de la prueba: dominios reservados (RFC 2606), nunca se envia DATA.
+const PROBE_MAIL_FROM: &str = "MAIL FROM:<probe@netsentinel.invalid>";
+const PROBE_RCPT_TO: &str = "RCPT TO:<relay-test@example.com>";
+
+// Comprobaciones SMTP sin enviar correo:
+// - open relay: acepta RCPT hacia un dominio externo sin autenticacion
+// - VRFY/EXPN: permiten enumerar usuarios/listas
+pub fn probe(addr: SocketAddr) -> Option<ServiceProbeResult> {
+    let mut session = LineSession::open(addr).ok()?;
+    let (code, banner) = session.read_reply().ok()?;
+    if code != 220 {
+        return None;
+    }
+
+    let mut result = ServiceProbeResult {
+        port: addr.port(),
+        service: "SMTP".to_string(),
+        version: Some(io::reply_text(&banner)).filter(|v| !v.is_empty()),
+        banner: Some(io::sanitize_evidence(&banner)),
+        vulnerabilities: Vec::new(),
+    };
+
+    // Fallback a HELO para servidores antiguos sin ESMTP.
+    let greeted = match session.command("EHLO netsentinel.local") {
+        Ok((250, _)) => true,
+        _ => matches!(session.command("HELO netsentinel.local"), Ok((250, _))),
+    };
+    if !greeted {
+        let _ = session.send("QUIT");
+        return Some(result);
+    }
+
+    if let Ok((250, _)) = session.command(PROBE_MAIL_FROM) {
+        if let Ok((rcpt_code @ (250 | 251), rcpt_reply)) = session.command(PROBE_RCPT_TO) {
+            result.vulnerabilities.push(Vulnerability {
+                id: "SMTP-OPEN-RELAY".to_string(),
+                description: "El servidor SMTP acepta reenviar correo a dominios externos sin autenticacion (open relay).".to_string(),
+                severity: "CRITICAL".to_string(),
+                recommendation: "Restringir el relay a redes/usuarios autenticados (SMTP AUTH) y revisar si ya se ha usado para spam.".to_string(),
+                evidence: Some(io::sanitize_evidence(&format!(
+                    "{}\n{PROBE_MAIL_FROM} -> 250\n{PROBE_RCPT_TO} -> {rcpt_code}: {}",
+                    banner.trim(),
+                    io::reply_text(&rcpt_reply)
+                ))),
+            });
+        }
+    }
+    let _ = session.command("RSET");
+
+    if let Ok((code @ (250 | 251), reply)) = session.command("VRFY root") {
+        result.vulnerabilities.push(Vulnerability {
+            id: "SMTP-VRFY-ENABLED".to_string(),
+            description: "El comando VRFY confirma la existencia de cuentas: facilita enumerar usuarios.".to_string(),
+            severity: "MEDIUM".to_string(),
+            recommendation: "Desactivar VRFY (ej: Postfix 'disable_vrfy_command = yes').".to_string(),
+            evidence: Some(io::sanitize_evidence(&format!("VRFY root -> {code}: {}", io::reply_text(&reply)))),
+        });
+    }
+
+    if let Ok((250, reply)) = session.command("EXPN root") {
+        result.vulnerabilities.push(Vulnerability {
+            id: "SMTP-EXPN-ENABLED".to_string(),
+            description: "El comando EXPN expande alias/listas de correo y revela destinatarios internos.".to_string(),
+            severity: "MEDIUM".to_string(),
+            recommendation: "Desactivar EXPN en la configuracion del MTA.".to_string(),
+            evidence: Some(io::sanitize_evidence(&format!("EXPN root -> 250: {}", io::reply_text(&reply)))),
+        });
+    }
+
+    let _ = session.send("QUIT");
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // MTA minimo: `relay` decide si acepta RCPT externos; VRFY siempre responde, EXPN no.
+    fn spawn_stand_in_smtp(relay: bool) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let Ok((stream, _)) = listener.accept() else {
+                return;
+            };
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let _ = writer.write_all(b"220 mail.lan ESMTP Postfix (Debian)\r\n");
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or(0) > 0 {
+                let cmd = line.trim().to_uppercase();
+                line.clear();
+                let reply = if cmd.starts_with("EHLO") {
+                    "250-mail.lan\r\n250-VRFY\r\n250 8BITMIME"
+                } else if cmd.starts_with("MAIL FROM") {
+                    "250 2.1.0 Ok"
+                } else if cmd.starts_with("RCPT TO") && relay {
+                    "250 2.1.5 Ok"
+                } else if cmd.starts_with("RCPT TO") {
+                    "554 5.7.1 Relay access denied"
+                } else if cmd == "RSET" {
+                    "250 2.0.0 Ok"
+                } else if cmd.starts_with("VRFY") {
+                    "252 2.0.0 root"
+                } else if cmd.starts_with("EXPN") {
+                    "502 5.5.2 Error: command not recognized"
+                } else if cmd.starts_with("DATA") {
+                    panic!("la sonda nunca debe enviar DATA");
+                } else if cmd == "QUIT" {
+                    let _ = writer.write_all(b"221 2.0.0 Bye\r\n");
+                    return;
+                } else {
+                    "500 5.5.2 Error"
+                };
+                let _ = writer.write_all(format!("{reply}\r\n").as_bytes());
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn probe_detects_open_relay_with_evidence() {
+        let addr = spawn_stand_in_smtp(true);
+        let result = probe(addr).unwrap();
+
+        assert_eq!(result.version.as_deref(), Some("mail.lan ESMTP Postfix (Debian)"));
+        assert_eq!(result.vulnerabilities.len(), 1);
+        let vuln = &result.vulnerabilities[0];
+        assert_eq!(vuln.id, "SMTP-OPEN-RELAY");
+        assert_eq!(vuln.severity, "CRITICAL");
+        assert!(vuln.evidence.as_deref().unwrap().contains("relay-test@example.com"));
+    }
+
+    #[test]
+    fn probe_without_relay_reports_nothing_for_ambiguous_vrfy() {
+        // 252 = "no puedo verificar, pero lo intentare": no confirma cuentas.
+        let addr = spawn_stand_in_smtp(false);
+        let result = probe(addr).unwrap();
+
+        assert!(result.vulnerabilities.is_empty(), "{:?}", result.vulnerabilities);
+    }
+}
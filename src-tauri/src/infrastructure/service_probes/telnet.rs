@@ -0,0 +1,253 @@
+// src-tauri/src/infrastructure/service_probes/telnet.rs
+
+use regex::Regex;
+use std::io::{Read, Write};
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::domain::entities::{ServiceProbeResult, Vulnerability};
+
+use super::io;
+
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+
+const MAX_BANNER_BYTES: usize = 4096;
+const READ_WINDOW: Duration = Duration::from_millis(3000);
+
+// Ultima linea con forma de prompt de shell: "#", "~ #", "root@host:~$", "[admin@MikroTik] >",
+// "Router#", "sh-4.2$", "<HUAWEI>". Un banner que acaba en ">" ("Press any key >") no cuenta.
+static SHELL_PROMPT_RE: OnceLock<Regex> = OnceLock::new();
+
+// Pistas de fabricante habituales en banners Telnet de routers/camaras/IoT.
+const VENDOR_HINTS: &[(&str, &str)] = &[
+    ("mikrotik", "MikroTik"),
+    ("routeros", "MikroTik"),
+    ("huawei", "Huawei"),
+    ("zte", "ZTE"),
+    ("tp-link", "TP-Link"),
+    ("tplink", "TP-Link"),
+    ("netgear", "Netgear"),
+    ("fritz", "AVM"),
+    ("cisco", "Cisco"),
+    ("user access verification", "Cisco"),
+    ("hikvision", "Hikvision"),
+    ("dahua", "Dahua"),
+    ("ubnt", "Ubiquiti"),
+    ("ubiquiti", "Ubiquiti"),
+    ("dd-wrt", "DD-WRT"),
+    ("openwrt", "OpenWrt"),
+    ("busybox", "BusyBox (Linux embebido)"),
+];
+
+// Captura el banner Telnet y el prompt de login sin enviar credenciales.
+// Las negociaciones IAC se rechazan (WONT/DONT) para obtener texto plano lo antes posible.
+pub fn probe(addr: SocketAddr) -> Option<ServiceProbeResult> {
+    let mut stream = io::connect(addr).ok()?;
+    let _ = stream.set_read_timeout(Some(Duration::from_millis(400)));
+
+    let started = Instant::now();
+    let mut text = Vec::new();
+    let mut buf = [0u8; 1024];
+
+    while started.elapsed() < READ_WINDOW && text.len() < MAX_BANNER_BYTES {
+        match stream.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                let (plain, replies) = strip_negotiation(&buf[..n]);
+                text.extend_from_slice(&plain);
+                if !replies.is_empty() {
+                    let _ = stream.write_all(&replies);
+                }
+                if detect_prompt(&String::from_utf8_lossy(&text)).is_some() {
+                    break;
+                }
+            }
+            // Timeout de lectura: algunos equipos solo muestran el prompt tras un Enter.
+            Err(_) if text.is_empty() => {
+                let _ = stream.write_all(b"\r\n");
+            }
+            Err(_) => {}
+        }
+    }
+
+    let banner = String::from_utf8_lossy(&text).to_string();
+    let vendor = identify_vendor(&banner);
+    let mut result = ServiceProbeResult {
+        port: addr.port(),
+        service: "TELNET".to_string(),
+        version: vendor.map(|v| v.to_string()),
+        banner: Some(io::sanitize_evidence(&banner)).filter(|b| !b.is_empty()),
+        vulnerabilities: Vec::new(),
+    };
+
+    let evidence = Some(io::sanitize_evidence(&banner));
+    match detect_prompt(&banner) {
+        Some(TelnetPrompt::Shell) => result.vulnerabilities.push(Vulnerability {
+            id: "TELNET-UNAUTHENTICATED-SHELL".to_string(),
+            description: "Telnet entrega una shell sin pedir credenciales.".to_string(),
+            severity: "CRITICAL".to_string(),
+            recommendation: "Desactivar Telnet inmediatamente y revisar el equipo por posible compromiso.".to_string(),
+            evidence,
+        }),
+        Some(TelnetPrompt::Login) => result.vulnerabilities.push(Vulnerability {
+            id: "TELNET-LOGIN-EXPOSED".to_string(),
+            description: format!(
+                "Telnet expone un prompt de login en texto plano{}: las credenciales viajan sin cifrar y es objetivo de botnets (Mirai).",
+                vendor.map(|v| format!(" ({v})")).unwrap_or_default()
+            ),
+            severity: "CRITICAL".to_string(),
+            recommendation: "Desactivar Telnet y usar SSH; si no es posible, restringirlo por ACL a una VLAN de gestion.".to_string(),
+            evidence,
+        }),
+        None => {}
+    }
+
+    Some(result)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum TelnetPrompt {
+    Login,
+    Shell,
+}
+
+fn detect_prompt(banner: &str) -> Option<TelnetPrompt> {
+    let lower = banner.to_lowercase();
+    let tail = lower.trim_end();
+    if ["login:", "username:", "user name:", "user:", "password:"]
+        .iter()
+        .any(|p| tail.ends_with(p) || lower.contains(&format!("\n{p}")))
+    {
+        return Some(TelnetPrompt::Login);
+    }
+    let re_shell = SHELL_PROMPT_RE.get_or_init(|| {
+        Regex::new(concat!(
+            r"^(?:[~/][\w./~-]*\s?)?[#$]$",
+            r"|^[\w.-]+@[\w.-]+(?::\S*)?\s?[#$]$",
+            r"|^\[[\w.-]+@[^\]]+\]\s?[#$>]$",
+            r"|^[\w.-]+[#$>]$",
+            r"|^<[\w.-]+>$",
+        ))
+        .unwrap()
+    });
+    let last_line = banner.lines().map(str::trim).rfind(|l| !l.is_empty())?;
+    re_shell.is_match(last_line).then_some(TelnetPrompt::Shell)
+}
+
+fn identify_vendor(banner: &str) -> Option<&'static str> {
+    let lower = banner.to_lowercase();
+    VENDOR_HINTS
+        .iter()
+        .find(|(needle, _)| lower.contains(needle))
+        .map(|(_, vendor)| *vendor)
+}
+
+// Separa texto de comandos IAC y prepara las respuestas de rechazo.
+fn strip_negotiation(data: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let mut plain = Vec::with_capacity(data.len());
+    let mut replies = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        if data[i] != IAC {
+            plain.push(data[i]);
+            i += 1;
+            continue;
+        }
+        match data.get(i + 1).copied() {
+            Some(DO) | Some(DONT) => {
+                if let Some(opt) = data.get(i + 2) {
+                    replies.extend_from_slice(&[IAC, WONT, *opt]);
+                }
+                i += 3;
+            }
+            Some(WILL) | Some(WONT) => {
+                if let Some(opt) = data.get(i + 2) {
+                    replies.extend_from_slice(&[IAC, DONT, *opt]);
+                }
+                i += 3;
+            }
+            Some(SB) => {
+                // Saltamos la subnegociacion hasta IAC SE.
+                let mut j = i + 2;
+                while j + 1 < data.len() && !(data[j] == IAC && data[j + 1] == SE) {
+                    j += 1;
+                }
+                i = j + 2;
+            }
+            Some(IAC) => {
+                plain.push(IAC);
+                i += 2;
+            }
+            _ => i += 2,
+        }
+    }
+    (plain, replies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn spawn_stand_in_telnet(payload: &'static [u8]) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let _ = stream.write_all(payload);
+                let mut sink = [0u8; 64];
+                let _ = stream.set_read_timeout(Some(Duration::from_millis(500)));
+                let _ = stream.read(&mut sink);
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn probe_captures_login_prompt_and_vendor() {
+        // IAC DO ECHO + IAC WILL SGA antes del banner, como hacen muchos routers.
+        let addr = spawn_stand_in_telnet(b"\xff\xfd\x01\xff\xfb\x03\r\nMikroTik v6.49.7 (stable)\r\nLogin: ");
+        let result = probe(addr).unwrap();
+
+        assert_eq!(result.version.as_deref(), Some("MikroTik"));
+        assert_eq!(result.vulnerabilities.len(), 1);
+        assert_eq!(result.vulnerabilities[0].id, "TELNET-LOGIN-EXPOSED");
+        let evidence = result.vulnerabilities[0].evidence.as_deref().unwrap();
+        assert!(evidence.contains("MikroTik v6.49.7"));
+        assert!(!evidence.contains('\u{FFFD}'), "IAC no debe filtrarse a la evidencia");
+    }
+
+    #[test]
+    fn probe_flags_unauthenticated_shell() {
+        let addr = spawn_stand_in_telnet(b"\r\nBusyBox v1.19.4 built-in shell (ash)\r\n# ");
+        let result = probe(addr).unwrap();
+
+        assert_eq!(result.vulnerabilities[0].id, "TELNET-UNAUTHENTICATED-SHELL");
+        assert_eq!(result.version.as_deref(), Some("BusyBox (Linux embebido)"));
+    }
+
+    #[test]
+    fn detect_prompt_requires_a_shell_shape() {
+        for prompt in ["# ", "~ # ", "root@OpenWrt:~# ", "[admin@MikroTik] > ", "Router>", "sh-4.2$ ", "<HUAWEI>"] {
+            assert_eq!(detect_prompt(&format!("Welcome\r\n{prompt}")), Some(TelnetPrompt::Shell), "{prompt}");
+        }
+        for banner in ["Press any key to continue >", "<html><body>", "Menu: 1) Status 2) Exit ->", "Bienvenido"] {
+            assert_eq!(detect_prompt(banner), None, "{banner}");
+        }
+    }
+
+    #[test]
+    fn strip_negotiation_refuses_options() {
+        let (plain, replies) = strip_negotiation(b"\xff\xfd\x18\xff\xfa\x18\x01\xff\xf0hi");
+        assert_eq!(plain, b"hi");
+        assert_eq!(replies, vec![IAC, WONT, 0x18]);
+    }
+}
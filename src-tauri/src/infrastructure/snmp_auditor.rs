@@ -0,0 +1,318 @@
+// src-tauri/src/infrastructure/snmp_auditor.rs
+
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use crate::domain::entities::{SnmpAuditResult, SnmpCommunityAccess, SnmpInterface, SnmpSystemInfo};
+use crate::domain::ports::SnmpAuditorPort;
+use crate::infrastructure::network::snmp_client::ber::{self, BerValue};
+use crate::infrastructure::network::snmp_client::{SnmpClient, SnmpVersion, SNMP_PORT};
+
+// Submodulos para separar responsabilidades (SOLID) sin cambiar la API publica del adaptador.
+#[path = "snmp_auditor/communities.rs"]
+mod communities;
+#[path = "snmp_auditor/fingerprint.rs"]
+mod fingerprint;
+
+// MIB-II (RFC 1213): grupo system + columnas de ifTable.
+const OID_SYS_DESCR: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 1, 0];
+const OID_SYS_CONTACT: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 4, 0];
+const OID_SYS_NAME: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 5, 0];
+const OID_SYS_LOCATION: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 6, 0];
+const OID_IF_DESCR: &[u32] = &[1, 3, 6, 1, 2, 1, 2, 2, 1, 2];
+const OID_IF_PHYS_ADDRESS: &[u32] = &[1, 3, 6, 1, 2, 1, 2, 2, 1, 6];
+const OID_IF_OPER_STATUS: &[u32] = &[1, 3, 6, 1, 2, 1, 2, 2, 1, 8];
+
+const MAX_INTERFACES: usize = 64;
+
+// Auditor SNMP v1/v2c:
+// - prueba la lista de communities configurada contra UDP 161
+// - con la primera community valida extrae el grupo system y la tabla de interfaces
+// - comprueba escritura reescribiendo sysContact con los bytes leidos (no destructivo); si no se
+//   pudo leer, no se escribe nada y la escritura queda sin determinar
+pub struct UdpSnmpAuditor {
+    port: u16,
+    timeout: Duration,
+}
+
+impl UdpSnmpAuditor {
+    pub fn new() -> Self {
+        Self {
+            port: SNMP_PORT,
+            timeout: Duration::from_millis(700),
+        }
+    }
+
+    fn probe_blocking(ip: &str, port: u16, timeout: Duration, communities: &[String]) -> SnmpAuditResult {
+        let mut result = SnmpAuditResult {
+            target_ip: ip.to_string(),
+            communities: Vec::new(),
+            system: None,
+            interfaces: Vec::new(),
+            model: None,
+            firmware: None,
+            vulnerabilities: Vec::new(),
+            risk_level: "UNKNOWN".to_string(),
+        };
+
+        let Ok(addr) = ip.trim().parse::<IpAddr>() else {
+            return result;
+        };
+        let target = SocketAddr::new(addr, port);
+
+        for community in communities {
+            let Some((client, version, system, raw_contact)) = Self::try_community(target, community, timeout) else {
+                continue;
+            };
+            println!("🔓 [SNMP] {} acepta community '{}' ({})", ip, community, version.label());
+
+            let writable = Self::check_writable(&client, raw_contact);
+
+            if result.system.is_none() {
+                let (model, firmware) = system
+                    .descr
+                    .as_deref()
+                    .map(fingerprint::extract_model_firmware)
+                    .unwrap_or((None, None));
+                result.model = model;
+                result.firmware = firmware;
+                result.interfaces = Self::read_interfaces(&client);
+                result.system = Some(system);
+            }
+
+            result.communities.push(SnmpCommunityAccess {
+                community: community.clone(),
+                version: version.label().to_string(),
+                writable,
+            });
+        }
+
+        result
+    }
+
+    fn try_community(
+        target: SocketAddr,
+        community: &str,
+        timeout: Duration,
+    ) -> Option<(SnmpClient, SnmpVersion, SnmpSystemInfo, Option<Vec<u8>>)> {
+        let oids: Vec<Vec<u32>> = [OID_SYS_DESCR, OID_SYS_CONTACT, OID_SYS_NAME, OID_SYS_LOCATION]
+            .iter()
+            .map(|o| o.to_vec())
+            .collect();
+
+        // v2c primero (lo habitual hoy); v1 como fallback para equipos antiguos.
+        for version in [SnmpVersion::V2c, SnmpVersion::V1] {
+            let client = SnmpClient::new(target, community, version, timeout);
+            // Cualquier respuesta (incluso con error-status) confirma que la community es valida.
+            let Ok(resp) = client.get(&oids) else {
+                continue;
+            };
+            let value_of = |oid: &[u32]| {
+                resp.varbinds
+                    .iter()
+                    .find(|(o, v)| o.as_slice() == oid && !v.is_exception())
+                    .and_then(|(_, v)| v.as_display())
+                    .filter(|s| !s.is_empty())
+            };
+            let system = SnmpSystemInfo {
+                descr: value_of(OID_SYS_DESCR),
+                contact: value_of(OID_SYS_CONTACT),
+                name: value_of(OID_SYS_NAME),
+                location: value_of(OID_SYS_LOCATION),
+            };
+            // Bytes exactos de sysContact (vacio incluido) para reescribirlos tal cual.
+            let raw_contact = resp.varbinds.iter().find(|(o, _)| o.as_slice() == OID_SYS_CONTACT).and_then(|(_, v)| {
+                match v {
+                    BerValue::OctetString(bytes) => Some(bytes.clone()),
+                    _ => None,
+                }
+            });
+            return Some((client, version, system, raw_contact));
+        }
+        None
+    }
+
+    fn check_writable(client: &SnmpClient, current_contact: Option<Vec<u8>>) -> Option<bool> {
+        // Sin el valor original no hay SET posible sin pisar el campo del equipo: escritura desconocida.
+        let value = current_contact?;
+        // Reescribimos exactamente el valor leido: si el SET se acepta, la community es de escritura.
+        match client.set(OID_SYS_CONTACT, BerValue::OctetString(value)) {
+            Ok(resp) => Some(resp.error_status == 0),
+            Err(_) => Some(false),
+        }
+    }
+
+    fn read_interfaces(client: &SnmpClient) -> Vec<SnmpInterface> {
+        let mut by_index: BTreeMap<u32, SnmpInterface> = BTreeMap::new();
+
+        for (oid, value) in client.walk(OID_IF_DESCR, MAX_INTERFACES) {
+            let Some(index) = oid.last().copied() else {
+                continue;
+            };
+            by_index.insert(
+                index,
+                SnmpInterface {
+                    index,
+                    descr: value.as_display().unwrap_or_default(),
+                    mac: None,
+                    oper_status: None,
+                },
+            );
+        }
+
+        for (oid, value) in client.walk(OID_IF_PHYS_ADDRESS, MAX_INTERFACES) {
+            let iface = oid.last().and_then(|i| by_index.get_mut(i));
+            if let (Some(iface), BerValue::OctetString(bytes)) = (iface, &value) {
+                if bytes.len() == 6 {
+                    iface.mac = Some(ber::format_hex(bytes));
+                }
+            }
+        }
+
+        for (oid, value) in client.walk(OID_IF_OPER_STATUS, MAX_INTERFACES) {
+            let iface = oid.last().and_then(|i| by_index.get_mut(i));
+            if let (Some(iface), BerValue::Integer(status)) = (iface, &value) {
+                iface.oper_status = Some(oper_status_label(*status).to_string());
+            }
+        }
+
+        by_index.into_values().collect()
+    }
+}
+
+fn oper_status_label(status: i64) -> &'static str {
+    match status {
+        1 => "up",
+        2 => "down",
+        3 => "testing",
+        5 => "dormant",
+        6 => "notPresent",
+        7 => "lowerLayerDown",
+        _ => "unknown",
+    }
+}
+
+#[async_trait]
+impl SnmpAuditorPort for UdpSnmpAuditor {
+    async fn probe_snmp(&self, ip: &str) -> SnmpAuditResult {
+        // UDP bloqueante + timeouts por community: lo movemos a un hilo dedicado.
+        let ip_for_task = ip.to_string();
+        let ip_for_err = ip_for_task.clone();
+        let port = self.port;
+        let timeout = self.timeout;
+
+        tauri::async_runtime::spawn_blocking(move || {
+            let communities = communities::load_communities();
+            UdpSnmpAuditor::probe_blocking(&ip_for_task, port, timeout, &communities)
+        })
+        .await
+        .unwrap_or_else(|_| SnmpAuditResult {
+            target_ip: ip_for_err,
+            communities: Vec::new(),
+            system: None,
+            interfaces: Vec::new(),
+            model: None,
+            firmware: None,
+            vulnerabilities: Vec::new(),
+            risk_level: "UNKNOWN".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::network::snmp_client::ber::SnmpMessage;
+    use std::net::UdpSocket;
+    use std::thread;
+
+    // Impresora ficticia: "public" solo lectura, "private" lectura/escritura. "legacy" acepta SET
+    // pero no devuelve sysContact: la auditoria no debe escribir nada con esa community.
+    fn spawn_stand_in_printer() -> u16 {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = socket.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let mut buf = [0u8; 2048];
+            while let Ok((len, from)) = socket.recv_from(&mut buf) {
+                let Ok(req) = ber::decode_message(&buf[..len]) else {
+                    continue;
+                };
+                if !["public", "private", "legacy"].contains(&req.community.as_str()) {
+                    continue;
+                }
+                let mut resp = SnmpMessage {
+                    pdu_type: ber::PDU_GET_RESPONSE,
+                    ..req.clone()
+                };
+                match req.pdu_type {
+                    ber::PDU_SET_REQUEST if req.community == "public" => resp.error_status = 4, // readOnly
+                    ber::PDU_SET_REQUEST => {}
+                    ber::PDU_GET_NEXT_REQUEST => {
+                        let (oid, _) = &req.varbinds[0];
+                        let (next, value) = if oid.as_slice() == OID_IF_DESCR {
+                            ([OID_IF_DESCR, &[1]].concat(), BerValue::OctetString(b"eth0".to_vec()))
+                        } else if oid.as_slice() == OID_IF_PHYS_ADDRESS {
+                            (
+                                [OID_IF_PHYS_ADDRESS, &[1]].concat(),
+                                BerValue::OctetString(vec![0x00, 0x1B, 0xA9, 0x01, 0x02, 0x03]),
+                            )
+                        } else if oid.as_slice() == OID_IF_OPER_STATUS {
+                            ([OID_IF_OPER_STATUS, &[1]].concat(), BerValue::Integer(1))
+                        } else {
+                            (oid.clone(), BerValue::EndOfMibView)
+                        };
+                        resp.varbinds = vec![(next, value)];
+                    }
+                    _ => {
+                        resp.varbinds = req
+                            .varbinds
+                            .iter()
+                            .map(|(oid, _)| {
+                                if req.community == "legacy" && oid.as_slice() == OID_SYS_CONTACT {
+                                    return (oid.clone(), BerValue::NoSuchObject);
+                                }
+                                let value = match oid.as_slice() {
+                                    OID_SYS_DESCR => "Brother NC-8300h, Firmware Ver.1.13  (17.03.28)",
+                                    OID_SYS_NAME => "BRN-LAB",
+                                    _ => "",
+                                };
+                                (oid.clone(), BerValue::OctetString(value.as_bytes().to_vec()))
+                            })
+                            .collect();
+                    }
+                }
+                let _ = socket.send_to(&ber::encode_message(&resp).unwrap(), from);
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn probe_blocking_reports_communities_system_and_interfaces() {
+        let port = spawn_stand_in_printer();
+        let communities: Vec<String> = ["nope", "public", "private", "legacy"].iter().map(|c| c.to_string()).collect();
+
+        let result = UdpSnmpAuditor::probe_blocking("127.0.0.1", port, Duration::from_millis(300), &communities);
+
+        assert_eq!(result.communities.len(), 3);
+        assert_eq!(result.communities[0].community, "public");
+        assert_eq!(result.communities[0].version, "v2c");
+        assert_eq!(result.communities[0].writable, Some(false));
+        assert_eq!(result.communities[1].writable, Some(true));
+        // "legacy" aceptaria el SET: `None` demuestra que no se llego a enviar.
+        assert_eq!(result.communities[2].writable, None);
+
+        let system = result.system.unwrap();
+        assert_eq!(system.name.as_deref(), Some("BRN-LAB"));
+        assert_eq!(system.contact, None);
+        assert_eq!(result.model.as_deref(), Some("Brother NC-8300h"));
+        assert_eq!(result.firmware.as_deref(), Some("1.13"));
+
+        assert_eq!(result.interfaces.len(), 1);
+        assert_eq!(result.interfaces[0].descr, "eth0");
+        assert_eq!(result.interfaces[0].mac.as_deref(), Some("00:1B:A9:01:02:03"));
+        assert_eq!(result.interfaces[0].oper_status.as_deref(), Some("up"));
+    }
+}
@@ -0,0 +1,57 @@
+// src-tauri/src/infrastructure/snmp_auditor/communities.rs
+
+use directories::ProjectDirs;
+use std::fs;
+
+// Lista por defecto (laboratorio): communities de fabrica mas habituales en impresoras/routers.
+// Ampliable via `snmp_communities.json` en AppData (array JSON de strings).
+pub const DEFAULT_SNMP_COMMUNITIES: &[&str] = &["public", "private", "community", "snmp", "admin", "cisco", "manager"];
+
+// Limite defensivo: evitamos convertir la auditoria en un diccionario masivo contra UDP 161.
+const MAX_COMMUNITIES: usize = 32;
+
+pub fn load_communities() -> Vec<String> {
+    appdata_communities()
+        .filter(|list| !list.is_empty())
+        .unwrap_or_else(default_communities)
+}
+
+fn default_communities() -> Vec<String> {
+    DEFAULT_SNMP_COMMUNITIES.iter().map(|c| c.to_string()).collect()
+}
+
+fn appdata_communities() -> Option<Vec<String>> {
+    let proj_dirs = ProjectDirs::from("com", "netsentinel", "app")?;
+    let path = proj_dirs.data_dir().join("snmp_communities.json");
+    let content = fs::read_to_string(path).ok()?;
+    parse_communities(&content)
+}
+
+pub fn parse_communities(content: &str) -> Option<Vec<String>> {
+    let raw: Vec<String> = serde_json::from_str(content).ok()?;
+    let mut out: Vec<String> = Vec::new();
+    for c in raw {
+        let c = c.trim().to_string();
+        // Community = OCTET STRING; limitamos a texto corto sin caracteres de control.
+        if c.is_empty() || c.len() > 64 || c.chars().any(|ch| ch.is_control()) || out.contains(&c) {
+            continue;
+        }
+        out.push(c);
+        if out.len() >= MAX_COMMUNITIES {
+            break;
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_communities_dedups_and_drops_invalid_entries() {
+        let list = parse_communities(r#"["public", " public ", "", "priv\u0007", "lab-ro"]"#).unwrap();
+        assert_eq!(list, vec!["public".to_string(), "lab-ro".to_string()]);
+        assert!(parse_communities("not json").is_none());
+    }
+}
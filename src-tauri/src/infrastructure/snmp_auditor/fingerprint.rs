@@ -0,0 +1,73 @@
+// src-tauri/src/infrastructure/snmp_auditor/fingerprint.rs
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+static KEYWORD_RE: OnceLock<Regex> = OnceLock::new();
+static LINUX_RE: OnceLock<Regex> = OnceLock::new();
+
+// Heuristica pura sobre sysDescr para obtener modelo/firmware.
+// Los fabricantes no siguen un formato comun; preferimos devolver None antes que inventar.
+pub fn extract_model_firmware(sys_descr: &str) -> (Option<String>, Option<String>) {
+    let descr = sys_descr.trim();
+    if descr.is_empty() {
+        return (None, None);
+    }
+    (extract_model(descr), extract_firmware(descr))
+}
+
+fn extract_model(descr: &str) -> Option<String> {
+    let first = descr.split([',', ';', '\n']).next().unwrap_or("").trim();
+
+    // Un sysDescr de SO generico (ej: "Linux gw 4.4.60 #1 SMP") no identifica el modelo del equipo.
+    let lower = first.to_lowercase();
+    if first.is_empty() || lower.starts_with("linux ") || lower.starts_with("windows") {
+        return None;
+    }
+    Some(first.chars().take(64).collect())
+}
+
+fn extract_firmware(descr: &str) -> Option<String> {
+    let re_keyword = KEYWORD_RE.get_or_init(|| {
+        Regex::new(r"(?i)\b(?:firmware|fw|version|ver\.?|release|rev\.?)[\s:]*v?(\d+(?:[._\-]\w+)*(?:\(\w+\)\w*)?)")
+            .unwrap()
+    });
+    if let Some(c) = re_keyword.captures(descr) {
+        return Some(c[1].to_string());
+    }
+
+    let re_linux = LINUX_RE.get_or_init(|| Regex::new(r"(?i)^linux\s+\S+\s+(\d+\.\d+[\w.\-]*)").unwrap());
+    if let Some(c) = re_linux.captures(descr) {
+        return Some(format!("Linux {}", &c[1]));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_model_firmware_handles_printer_descr() {
+        let (model, fw) =
+            extract_model_firmware("Brother NC-8300h, Firmware Ver.1.13  (17.03.28),MID 8C5-F2B,FID 2");
+        assert_eq!(model.as_deref(), Some("Brother NC-8300h"));
+        assert_eq!(fw.as_deref(), Some("1.13"));
+    }
+
+    #[test]
+    fn extract_model_firmware_handles_cisco_version_string() {
+        let (_, fw) = extract_model_firmware(
+            "Cisco IOS Software, C2960 Software (C2960-LANBASEK9-M), Version 12.2(55)SE7, RELEASE SOFTWARE (fc2)",
+        );
+        assert_eq!(fw.as_deref(), Some("12.2(55)SE7"));
+    }
+
+    #[test]
+    fn extract_model_firmware_does_not_treat_linux_as_model() {
+        let (model, fw) = extract_model_firmware("Linux gw 4.4.60 #1 SMP Tue Mar 2 2021 mips");
+        assert_eq!(model, None);
+        assert_eq!(fw.as_deref(), Some("Linux 4.4.60"));
+        assert_eq!(extract_model_firmware("   "), (None, None));
+    }
+}
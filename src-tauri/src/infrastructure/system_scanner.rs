@@ -1,6 +1,6 @@
 // src-tauri/src/infrastructure/system_scanner.rs
 
-use crate::domain::entities::{Device, OpenPort};
+use crate::domain::entities::{Device, PortScanMode, PortScanOutcome};
 use crate::domain::ports::NetworkScannerPort;
 use async_trait::async_trait;
 
@@ -33,8 +33,8 @@ impl NetworkScannerPort for SystemScanner {
         devices
     }
 
-    async fn scan_ports(&self, ip: &str) -> Vec<OpenPort> {
-        ports::scan_common_ports(ip)
+    async fn scan_ports(&self, ip: &str, mode: PortScanMode) -> PortScanOutcome {
+        ports::scan_common_ports(ip, mode)
     }
 }
 
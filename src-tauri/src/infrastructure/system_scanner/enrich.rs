@@ -30,6 +30,10 @@ pub fn enrich_ips(active_ips: &[String]) -> Vec<Device> {
             signal_rate: None,
             wifi_band: None,
             open_ports: None,
+            model: None,
+            firmware: None,
+            interface_type: None,
+            active: None,
         });
     }
 
@@ -1,16 +1,62 @@
 // src-tauri/src/infrastructure/system_scanner/ports.rs
 
-use crate::domain::entities::OpenPort;
+use crate::domain::entities::{OpenPort, PortScanMode, PortScanOutcome};
 use crate::infrastructure::network::port_scanner::PortScanner;
+use crate::infrastructure::network::syn_scanner::{SynPortState, SynScanner};
+use std::net::Ipv4Addr;
 use std::thread;
+use std::time::Duration;
+
+// Incluye puertos de BBDD/APIs que suelen quedar abiertos sin auth en home labs.
+const COMMON_PORTS: [u16; 20] = [
+    21, 22, 23, 25, 53, 80, 110, 139, 443, 445, 587, 1883, 2323, 2375, 3389, 5984, 6379, 8080, 9200, 27017,
+];
+
+const SYN_TIMEOUT: Duration = Duration::from_millis(800);
+
+pub fn scan_common_ports(ip: &str, mode: PortScanMode) -> PortScanOutcome {
+    if mode == PortScanMode::Syn {
+        match syn_scan(ip) {
+            Ok(outcome) => return outcome,
+            // Sin raw sockets (usuario sin privilegios, Windows sin driver...): fallback transparente.
+            Err(e) => println!("⚠️ [INFRA] SYN scan no disponible ({}), usando connect()", e),
+        }
+    }
+
+    PortScanOutcome {
+        open_ports: connect_scan(ip),
+        filtered_ports: Vec::new(),
+        mode_used: PortScanMode::Connect,
+    }
+}
+
+fn syn_scan(ip: &str) -> Result<PortScanOutcome, String> {
+    let target: Ipv4Addr = ip.trim().parse().map_err(|_| format!("IP invalida: {ip}"))?;
+    let results = SynScanner::scan(target, &COMMON_PORTS, SYN_TIMEOUT)?;
 
-pub fn scan_common_ports(ip: &str) -> Vec<OpenPort> {
-    let common_ports = [21, 22, 23, 25, 53, 80, 110, 139, 443, 445, 3389, 8080];
+    let mut open_ports = Vec::new();
+    let mut filtered_ports = Vec::new();
+    for (port, state) in results {
+        match state {
+            SynPortState::Open => open_ports.push(open_port(port)),
+            SynPortState::Filtered => filtered_ports.push(port),
+            SynPortState::Closed => {}
+        }
+    }
+
+    Ok(PortScanOutcome {
+        open_ports,
+        filtered_ports,
+        mode_used: PortScanMode::Syn,
+    })
+}
+
+fn connect_scan(ip: &str) -> Vec<OpenPort> {
     let mut open_ports = Vec::new();
     let mut handles = vec![];
     let ip_target = ip.to_string();
 
-    for &port in common_ports.iter() {
+    for &port in COMMON_PORTS.iter() {
         let target = ip_target.clone();
         handles.push(thread::spawn(move || {
             if PortScanner::check_port(&target, port) {
@@ -23,17 +69,21 @@ pub fn scan_common_ports(ip: &str) -> Vec<OpenPort> {
 
     for h in handles {
         if let Ok(Some(port)) = h.join() {
-            open_ports.push(OpenPort {
-                port,
-                status: "Open".to_string(),
-                service: "Unknown".to_string(),
-                risk_level: "Unknown".to_string(),
-                description: None,
-                vulnerability: None,
-            });
+            open_ports.push(open_port(port));
         }
     }
 
     open_ports
 }
 
+fn open_port(port: u16) -> OpenPort {
+    OpenPort {
+        port,
+        status: "Open".to_string(),
+        service: "Unknown".to_string(),
+        risk_level: "Unknown".to_string(),
+        description: None,
+        vulnerability: None,
+        version: None,
+    }
+}
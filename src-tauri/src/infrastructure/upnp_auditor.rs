@@ -0,0 +1,232 @@
+// src-tauri/src/infrastructure/upnp_auditor.rs
+
+use async_trait::async_trait;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use crate::domain::entities::{UpnpAuditResult, UpnpPortMapping};
+use crate::domain::ports::UpnpAuditorPort;
+use crate::infrastructure::network::upnp_client::{self, IgdClient, PortMappingEntry, SSDP_PORT};
+
+// Tope de la tabla de mapeos: routers con bugs devuelven la misma entrada para cualquier indice.
+const MAX_PORT_MAPPINGS: u32 = 128;
+
+// Auditor UPnP IGD:
+// - SSDP M-SEARCH al gateway para obtener la descripcion del dispositivo
+// - localiza WANIPConnection/WANPPPConnection y lee la IP externa
+// - enumera `GetGenericPortMappingEntry` hasta el fault 713 (solo lectura: no crea ni borra mapeos)
+pub struct SsdpUpnpAuditor {
+    ssdp_port: u16,
+    timeout: Duration,
+}
+
+impl SsdpUpnpAuditor {
+    pub fn new() -> Self {
+        Self {
+            ssdp_port: SSDP_PORT,
+            timeout: Duration::from_secs(3),
+        }
+    }
+
+    fn probe_blocking(gateway_ip: &str, ssdp_port: u16, timeout: Duration) -> UpnpAuditResult {
+        let mut result = empty_result(gateway_ip);
+
+        let Ok(addr) = gateway_ip.trim().parse::<IpAddr>() else {
+            return result;
+        };
+        let Some(location) = upnp_client::discover_location(SocketAddr::new(addr, ssdp_port), timeout) else {
+            println!("🔒 [UPnP] {} no responde a SSDP (IGD desactivado o filtrado)", gateway_ip);
+            return result;
+        };
+        println!("📡 [UPnP] IGD anunciado en {}", location);
+
+        let client = match IgdClient::connect(&location, timeout) {
+            Ok(client) => client,
+            Err(e) => {
+                println!("⚠️ [UPnP] {}", e);
+                return result;
+            }
+        };
+        result.igd_found = true;
+        result.service_type = Some(client.service().service_type.clone());
+        result.control_url = Some(client.service().control_url.to_string());
+        result.external_ip = client.external_ip().ok().flatten();
+
+        for index in 0..MAX_PORT_MAPPINGS {
+            match client.port_mapping(index) {
+                Ok(Some(entry)) => {
+                    let mapping = to_mapping(entry);
+                    // Algunos firmwares repiten la ultima entrada en vez de devolver 713.
+                    if result.port_mappings.last().is_some_and(|last| same_mapping(last, &mapping)) {
+                        break;
+                    }
+                    result.port_mappings.push(mapping);
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    println!("⚠️ [UPnP] Enumeracion de mapeos interrumpida en {}: {:?}", index, e);
+                    break;
+                }
+            }
+        }
+        result
+    }
+}
+
+fn empty_result(gateway_ip: &str) -> UpnpAuditResult {
+    UpnpAuditResult {
+        gateway_ip: gateway_ip.to_string(),
+        igd_found: false,
+        service_type: None,
+        control_url: None,
+        external_ip: None,
+        port_mappings: Vec::new(),
+        vulnerabilities: Vec::new(),
+        risk_level: "UNKNOWN".to_string(),
+    }
+}
+
+fn to_mapping(entry: PortMappingEntry) -> UpnpPortMapping {
+    UpnpPortMapping {
+        remote_host: entry.remote_host,
+        external_port: entry.external_port,
+        protocol: entry.protocol,
+        internal_port: entry.internal_port,
+        internal_client: entry.internal_client,
+        enabled: entry.enabled,
+        description: entry.description,
+        lease_duration: entry.lease_duration,
+    }
+}
+
+fn same_mapping(a: &UpnpPortMapping, b: &UpnpPortMapping) -> bool {
+    a.external_port == b.external_port && a.protocol == b.protocol && a.internal_client == b.internal_client
+}
+
+#[async_trait]
+impl UpnpAuditorPort for SsdpUpnpAuditor {
+    async fn probe_igd(&self, gateway_ip: &str) -> UpnpAuditResult {
+        // UDP + reqwest bloqueantes: lo movemos a un hilo dedicado.
+        let ip_for_task = gateway_ip.to_string();
+        let ip_for_err = ip_for_task.clone();
+        let ssdp_port = self.ssdp_port;
+        let timeout = self.timeout;
+
+        tauri::async_runtime::spawn_blocking(move || SsdpUpnpAuditor::probe_blocking(&ip_for_task, ssdp_port, timeout))
+            .await
+            .unwrap_or_else(|_| empty_result(&ip_for_err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::service_probes::test_support::{response, spawn_stand_in_http, StandInRequest};
+    use std::net::UdpSocket;
+    use std::thread;
+
+    const DESCRIPTION: &str = r#"<?xml version="1.0"?><root><device>
+        <deviceType>urn:schemas-upnp-org:device:InternetGatewayDevice:1</deviceType>
+        <deviceList><device><deviceList><device><serviceList><service>
+          <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>
+          <controlURL>/ctl/IPConn</controlURL>
+        </service></serviceList></device></deviceList></device></deviceList>
+        </device></root>"#;
+
+    // (externo, protocolo, interno, cliente, descripcion)
+    const MAPPINGS: &[(u16, &str, u16, &str, &str)] = &[
+        (3389, "TCP", 3389, "192.168.1.20", "Remote Desktop"),
+        (51413, "UDP", 51413, "192.168.1.30", "Transmission"),
+    ];
+
+    fn soap(action: &str, inner: &str) -> String {
+        let body = format!(
+            "<?xml version=\"1.0\"?><s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\"><s:Body>\
+             <u:{action}Response xmlns:u=\"urn:schemas-upnp-org:service:WANIPConnection:1\">{inner}</u:{action}Response>\
+             </s:Body></s:Envelope>"
+        );
+        response("200 OK", &[("Content-Type", "text/xml")], &body)
+    }
+
+    fn igd_handler(req: &StandInRequest) -> String {
+        match (req.method.as_str(), req.path.as_str()) {
+            ("GET", "/rootDesc.xml") => response("200 OK", &[("Content-Type", "text/xml")], DESCRIPTION),
+            ("POST", "/ctl/IPConn") => {
+                let action = req.header("soapaction").unwrap_or("");
+                if action.contains("#GetExternalIPAddress") {
+                    return soap("GetExternalIPAddress", "<NewExternalIPAddress>203.0.113.7</NewExternalIPAddress>");
+                }
+                let index: usize = upnp_client::xml::tag_text(&req.body, "NewPortMappingIndex")
+                    .and_then(|i| i.parse().ok())
+                    .unwrap_or(usize::MAX);
+                match MAPPINGS.get(index) {
+                    Some((ext, proto, int, client, descr)) => soap(
+                        "GetGenericPortMappingEntry",
+                        &format!(
+                            "<NewRemoteHost></NewRemoteHost><NewExternalPort>{ext}</NewExternalPort>\
+                             <NewProtocol>{proto}</NewProtocol><NewInternalPort>{int}</NewInternalPort>\
+                             <NewInternalClient>{client}</NewInternalClient><NewEnabled>1</NewEnabled>\
+                             <NewPortMappingDescription>{descr}</NewPortMappingDescription>\
+                             <NewLeaseDuration>0</NewLeaseDuration>"
+                        ),
+                    ),
+                    None => response(
+                        "500 Internal Server Error",
+                        &[("Content-Type", "text/xml")],
+                        "<s:Envelope><s:Body><s:Fault><detail><UPnPError>\
+                         <errorCode>713</errorCode><errorDescription>SpecifiedArrayIndexInvalid</errorDescription>\
+                         </UPnPError></detail></s:Fault></s:Body></s:Envelope>",
+                    ),
+                }
+            }
+            _ => response("404 Not Found", &[], ""),
+        }
+    }
+
+    // Responde a M-SEARCH con el LOCATION del servidor HTTP de pega.
+    fn spawn_stand_in_ssdp(http_addr: String) -> u16 {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = socket.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let mut buf = [0u8; 2048];
+            while let Ok((len, from)) = socket.recv_from(&mut buf) {
+                if !String::from_utf8_lossy(&buf[..len]).starts_with("M-SEARCH") {
+                    continue;
+                }
+                let reply = format!(
+                    "HTTP/1.1 200 OK\r\nCACHE-CONTROL: max-age=120\r\nST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\
+                     LOCATION: http://{}/rootDesc.xml\r\nSERVER: Stand-in/1.0 UPnP/1.1 MiniUPnPd/2.2\r\n\r\n",
+                    http_addr
+                );
+                let _ = socket.send_to(reply.as_bytes(), from);
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn probe_reads_external_ip_and_enumerates_mappings_until_fault_713() {
+        let http_addr = spawn_stand_in_http(igd_handler).to_string();
+        let ssdp_port = spawn_stand_in_ssdp(http_addr.clone());
+
+        let result = SsdpUpnpAuditor::probe_blocking("127.0.0.1", ssdp_port, Duration::from_secs(2));
+
+        assert!(result.igd_found);
+        assert_eq!(result.external_ip.as_deref(), Some("203.0.113.7"));
+        assert_eq!(result.control_url, Some(format!("http://{}/ctl/IPConn", http_addr)));
+        assert_eq!(result.port_mappings.len(), 2);
+        assert_eq!(result.port_mappings[0].external_port, 3389);
+        assert_eq!(result.port_mappings[0].internal_client, "192.168.1.20");
+        assert_eq!(result.port_mappings[1].protocol, "UDP");
+        assert!(result.port_mappings.iter().all(|m| m.enabled));
+    }
+
+    #[test]
+    fn probe_without_ssdp_answer_reports_no_igd() {
+        let silent = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = silent.local_addr().unwrap().port();
+        let result = SsdpUpnpAuditor::probe_blocking("127.0.0.1", port, Duration::from_millis(200));
+        assert!(!result.igd_found);
+        assert!(result.port_mappings.is_empty());
+    }
+}
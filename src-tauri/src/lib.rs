@@ -15,17 +15,25 @@ use crate::application::latest_snapshot_service::LatestSnapshotService;
 use crate::application::credential_service::CredentialService;
 use crate::application::jammer_service::JammerService;
 use crate::application::traffic_service::TrafficService;
+use crate::application::snmp_service::SnmpService;
+use crate::application::upnp_service::UpnpService;
+use crate::application::port_history_service::PortHistoryService;
 
 // 2. Imports propios (Infraestructura)
 use crate::infrastructure::{
-    router_audit::chrome_auditor::ChromeAuditor, fs_repository::FileHistoryRepository,
+    router_audit::auto_auditor::AutoRouterAuditor, fs_repository::FileHistoryRepository,
     system_scanner::SystemScanner,
 };
 use crate::infrastructure::latest_snapshot_repository::FileLatestSnapshotRepository;
+use crate::infrastructure::port_audit_repository::FilePortAuditRepository;
+use crate::infrastructure::router_evidence_repository::FileRouterEvidenceRepository;
 use crate::infrastructure::credential_store::KeyringCredentialStore;
 use crate::infrastructure::wifi::wifi_scanner::SystemWifiScanner;
 use crate::infrastructure::network::vendor_resolver::VendorResolver;
 use crate::infrastructure::network::vendor_lookup::SystemVendorLookup;
+use crate::infrastructure::snmp_auditor::UdpSnmpAuditor;
+use crate::infrastructure::upnp_auditor::SsdpUpnpAuditor;
+use crate::infrastructure::service_probes::TcpServiceProbe;
 
 // 3. Imports propios (Aplicacion)
 use crate::application::{
@@ -43,6 +51,9 @@ pub fn run() {
             // 1. CAPA DE INFRAESTRUCTURA (los "musculos")
             // =====================================================
             let scanner_infra = Arc::new(SystemScanner);
+            let service_probe_infra = Arc::new(TcpServiceProbe);
+            let snmp_infra = Arc::new(UdpSnmpAuditor::new());
+            let upnp_infra = Arc::new(SsdpUpnpAuditor::new());
             let wifi_scanner_infra = Arc::new(SystemWifiScanner::new());
             // Seed opcional del OUI para mejorar resolucion de vendors en el primer arranque.
             VendorResolver::ensure_oui_seeded();
@@ -52,18 +63,23 @@ pub fn run() {
             let logger_callback = Arc::new(move |msg: String| {
                 let _ = handle.emit("audit-log", msg);
             });
-            let auditor_infra = Arc::new(ChromeAuditor::new(logger_callback));
+            let auditor_infra = Arc::new(AutoRouterAuditor::new(logger_callback));
 
             let history_infra = Arc::new(FileHistoryRepository);
             let latest_snapshot_infra = Arc::new(FileLatestSnapshotRepository);
+            let port_audit_infra = Arc::new(FilePortAuditRepository::new());
             let credential_store_infra = Arc::new(KeyringCredentialStore::new("netsentinel"));
+            let router_evidence_infra = Arc::new(FileRouterEvidenceRepository::new());
             let jammer_service = JammerService::new();
             // =====================================================
             // 2. CAPA DE APLICACION (el "cerebro")
             // =====================================================
-            let scanner_service = ScannerService::new(scanner_infra);
-            let audit_service = AuditService::new(auditor_infra);
+            let scanner_service = ScannerService::new(scanner_infra, service_probe_infra);
+            let snmp_service = SnmpService::new(snmp_infra);
+            let upnp_service = UpnpService::new(upnp_infra);
+            let audit_service = AuditService::new(auditor_infra, credential_store_infra.clone(), router_evidence_infra);
             let history_service = HistoryService::new(history_infra);
+            let port_history_service = PortHistoryService::new(port_audit_infra);
             let latest_snapshot_service = LatestSnapshotService::new(latest_snapshot_infra);
             let credential_service = CredentialService::new(credential_store_infra);
             let vendor_lookup_infra = Arc::new(SystemVendorLookup);
@@ -77,8 +93,11 @@ pub fn run() {
             // 3. GESTION DE ESTADO (registrar en Tauri)
             // =====================================================
             app.manage(scanner_service);
+            app.manage(snmp_service);
+            app.manage(upnp_service);
             app.manage(audit_service);
             app.manage(history_service);
+            app.manage(port_history_service);
             app.manage(latest_snapshot_service);
             app.manage(credential_service);
             app.manage(wifi_service);
@@ -93,8 +112,15 @@ pub fn run() {
             // API (comandos) - facade en `src-tauri/src/api/commands.rs`
             api::commands::scan_network,
             api::commands::audit_target,
+            api::commands::get_port_audit_history,
+            api::commands::audit_snmp,
+            api::commands::audit_upnp,
             api::commands::audit_router,
+            api::commands::start_router_audit,
+            api::commands::cancel_router_audit,
             api::commands::fetch_router_devices,
+            api::commands::list_router_evidence,
+            api::commands::get_router_evidence,
             api::commands::save_scan,
             api::commands::get_history,
             api::commands::save_latest_snapshot,
@@ -108,7 +134,18 @@ pub fn run() {
             // System / runtime
             api::commands::get_identity,
             api::commands::start_traffic_sniffing,
+            api::commands::set_traffic_filter,
             api::commands::stop_traffic_sniffing,
+            api::commands::get_flows,
+            api::commands::get_dns_log,
+            api::commands::get_tls_log,
+            api::commands::get_user_agents,
+            api::commands::get_bandwidth_stats,
+            api::commands::get_arp_watch,
+            api::commands::start_capture_file,
+            api::commands::stop_capture_file,
+            api::commands::rotate_capture_file,
+            api::commands::analyze_capture_file,
             api::commands::start_jamming,
             api::commands::stop_jamming
         ])
@@ -318,7 +318,7 @@ function App() {
                 auditResults={auditResults}
                 consoleLogs={consoleLogs}
                 auditing={auditing}
-                onAudit={() => startAudit(selectedDevice.ip)}
+                onAudit={() => startAudit(selectedDevice.ip, selectedDevice.mac)}
                 isJammed={jammedDevices.includes(selectedDevice.ip)}
                 onToggleJam={() => toggleJammer(selectedDevice.ip)}
                 onRouterAudit={checkRouterSecurity}
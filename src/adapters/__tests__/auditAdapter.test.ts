@@ -29,6 +29,18 @@ describe('auditAdapter', () => {
     expect(ports[0].port).toBe(22);
   });
 
+  it('debe enviar scanMode y mac solo cuando se piden', async () => {
+    invokeCommandMock.mockResolvedValue({ targetIp: '192.168.1.50', openPorts: [], riskLevel: 'LOW', vulnerabilities: [] });
+
+    await auditAdapter.auditTargetPorts('192.168.1.50', { scanMode: 'syn', mac: 'AA:BB:CC:DD:EE:FF' });
+
+    expect(invokeCommandMock).toHaveBeenCalledWith('audit_target', {
+      ip: '192.168.1.50',
+      scanMode: 'syn',
+      mac: 'AA:BB:CC:DD:EE:FF',
+    });
+  });
+
   it('debe devolver array vacio si openPorts no existe', async () => {
     invokeCommandMock.mockResolvedValue({
       targetIp: '192.168.1.50',
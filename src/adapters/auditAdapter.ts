@@ -1,22 +1,75 @@
-import { invokeCommand } from "../shared/tauri/bridge";
-import { DeviceDTO, OpenPortDTO, RouterAuditResult, SecurityReportDTO } from "../shared/dtos/NetworkDTOs";
+import { invokeCommand, listenEvent, UnlistenFn } from "../shared/tauri/bridge";
+import { DeviceDTO, OpenPortDTO, PortAuditRecordDTO, PortChangeEventDTO, PortScanMode, RouterAuditFinishedEvent, RouterAuditProgressEvent, RouterAuditResult, RouterEvidenceContentDTO, RouterEvidenceItemDTO, SecurityReportDTO, SnmpAuditResultDTO, UpnpAuditResultDTO } from "../shared/dtos/NetworkDTOs";
 
 export const auditAdapter = {
-  auditTargetPorts: async (ip: string): Promise<OpenPortDTO[]> => {
+  auditTargetPorts: async (ip: string, options: { scanMode?: PortScanMode; mac?: string } = {}): Promise<OpenPortDTO[]> => {
     // El backend retorna un SecurityReportDTO, nosaltres volem els ports
-    const report = await invokeCommand<SecurityReportDTO>('audit_target', { ip });
+    const args: Record<string, unknown> = { ip };
+    if (options.scanMode) args.scanMode = options.scanMode;
+    if (options.mac) args.mac = options.mac; // clau de l'historial de ports (sobreviu a canvis d'IP)
+    const report = await invokeCommand<SecurityReportDTO>('audit_target', args);
     return report.openPorts || [];
   },
 
+  getPortAuditHistory: async (ip: string, mac?: string): Promise<PortAuditRecordDTO[]> => {
+    return await invokeCommand<PortAuditRecordDTO[]>('get_port_audit_history', { ip, mac: mac ?? null });
+  },
+
+  onPortChanges: async (callback: (event: PortChangeEventDTO) => void): Promise<UnlistenFn> => {
+    return await listenEvent<PortChangeEventDTO>('port-audit-changed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  auditSnmp: async (ip: string, device?: DeviceDTO): Promise<SnmpAuditResultDTO> => {
+    return await invokeCommand<SnmpAuditResultDTO>('audit_snmp', { ip, device: device ?? null });
+  },
+
+  // knownIps: IPs de l'inventari actual; els mapejos cap a IPs fora d'aquesta llista es marquen.
+  auditUpnp: async (gatewayIp: string, knownIps: string[] = []): Promise<UpnpAuditResultDTO> => {
+    return await invokeCommand<UpnpAuditResultDTO>('audit_upnp', { gatewayIp, knownIps });
+  },
+
   auditRouter: async (gatewayIp: string): Promise<RouterAuditResult> => {
     return await invokeCommand<RouterAuditResult>('audit_router', { gatewayIp });
   },
 
-  fetchRouterDevices: async (gatewayIp: string, user: string, pass: string): Promise<DeviceDTO[]> => {
+  // Auditoria en segon pla: retorna l'auditId; el resultat arriba per 'router-audit-finished'.
+  startRouterAudit: async (gatewayIp: string): Promise<string> => {
+    return await invokeCommand<string>('start_router_audit', { gatewayIp });
+  },
+
+  cancelRouterAudit: async (auditId: string): Promise<void> => {
+    await invokeCommand('cancel_router_audit', { auditId });
+  },
+
+  // Evidències per a l'informe (només el backend Chrome en genera).
+  listRouterEvidence: async (auditId: string): Promise<RouterEvidenceItemDTO[]> => {
+    return await invokeCommand<RouterEvidenceItemDTO[]>('list_router_evidence', { auditId });
+  },
+
+  getRouterEvidence: async (auditId: string, name: string): Promise<RouterEvidenceContentDTO> => {
+    return await invokeCommand<RouterEvidenceContentDTO>('get_router_evidence', { auditId, name });
+  },
+
+  onRouterAuditProgress: async (callback: (event: RouterAuditProgressEvent) => void): Promise<UnlistenFn> => {
+    return await listenEvent<RouterAuditProgressEvent>('router-audit-progress', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  onRouterAuditFinished: async (callback: (event: RouterAuditFinishedEvent) => void): Promise<UnlistenFn> => {
+    return await listenEvent<RouterAuditFinishedEvent>('router-audit-finished', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Sense user/pass el backend fa servir les credencials guardades per a aquest gateway.
+  fetchRouterDevices: async (gatewayIp: string, user?: string, pass?: string): Promise<DeviceDTO[]> => {
     return await invokeCommand<DeviceDTO[]>('fetch_router_devices', { 
       gatewayIp, 
-      user, 
-      pass 
+      user: user ?? null, 
+      pass: pass ?? null 
     });
   }
 };
@@ -1,9 +1,91 @@
-import { listenEvent, UnlistenFn } from '../shared/tauri/bridge';
+import { invokeCommand, listenEvent, UnlistenFn } from '../shared/tauri/bridge';
+import { ArpAlertDTO, ArpWatchReportDTO, BandwidthStatsDTO, BandwidthWindow, CaptureAnalysisDTO, CaptureFileStatusDTO, DeviceDomainLogDTO, DeviceTlsLogDTO, DeviceUserAgentsDTO, TrafficFlowDTO } from '../shared/dtos/NetworkDTOs';
 
 export const systemAdapter = {
   onAuditLog: async (callback: (log: string) => void): Promise<UnlistenFn> => {
     return await listenEvent<string>('audit-log', (event) => {
       callback(event.payload);
     });
-  }
+  },
+
+  // Converses actives (les més recents primer) i després les ja tancades.
+  getFlows: async (): Promise<TrafficFlowDTO[]> => {
+    return await invokeCommand<TrafficFlowDTO[]>('get_flows');
+  },
+
+  // Cada ~2 s amb el monitor actiu: fluxos nous, actualitzats o tancats des de l'emissió anterior.
+  onTrafficFlows: async (callback: (flows: TrafficFlowDTO[]) => void): Promise<UnlistenFn> => {
+    return await listenEvent<TrafficFlowDTO[]>('traffic-flows', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Qui consumeix l'amplada de banda: top-N d'equips locals i extrems remots, amb sèries d'1 s / 1 min / 1 h.
+  getBandwidthStats: async (window: BandwidthWindow = 'minute', topN = 10): Promise<BandwidthStatsDTO> => {
+    return await invokeCommand<BandwidthStatsDTO>('get_bandwidth_stats', { window, topN });
+  },
+
+  // Cada segon amb el monitor actiu: consum de l'últim segon complet (top-N sense sèries).
+  onTrafficBandwidth: async (callback: (summary: BandwidthStatsDTO) => void): Promise<UnlistenFn> => {
+    return await listenEvent<BandwidthStatsDTO>('traffic-bandwidth', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Taula IP→MAC vigilada i alertes d'ARP spoofing; la sembra l'últim escaneig de xarxa.
+  getArpWatch: async (): Promise<ArpWatchReportDTO> => {
+    return await invokeCommand<ArpWatchReportDTO>('get_arp_watch');
+  },
+
+  // Amb el monitor actiu: cada alerta nova (canvi de MAC, suplantació del gateway, allau de respostes...).
+  onArpAlert: async (callback: (alert: ArpAlertDTO) => void): Promise<UnlistenFn> => {
+    return await listenEvent<ArpAlertDTO>('traffic-arp-alert', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Dominis consultats per equip durant aquesta execució; deviceIp en limita el resultat a un equip.
+  getDnsLog: async (deviceIp?: string): Promise<DeviceDomainLogDTO[]> => {
+    return await invokeCommand<DeviceDomainLogDTO[]>('get_dns_log', { deviceIp: deviceIp ?? null });
+  },
+
+  // SNI, ALPN i empremta JA3 dels clients TLS/QUIC de cada equip; deviceIp en limita el resultat.
+  getTlsLog: async (deviceIp?: string): Promise<DeviceTlsLogDTO[]> => {
+    return await invokeCommand<DeviceTlsLogDTO[]>('get_tls_log', { deviceIp: deviceIp ?? null });
+  },
+
+  // User-Agents HTTP en clar de cada equip i la pista de SO/tipus per al classificador.
+  getUserAgents: async (deviceIp?: string): Promise<DeviceUserAgentsDTO[]> => {
+    return await invokeCommand<DeviceUserAgentsDTO[]>('get_user_agents', { deviceIp: deviceIp ?? null });
+  },
+
+  // Canvia el filtre del monitor en calent (p. ex. "host 192.168.1.20 and udp port 53"); buit o null torna al filtre per defecte.
+  setTrafficFilter: async (filter: string | null): Promise<void> => {
+    await invokeCommand<void>('set_traffic_filter', { filter });
+  },
+
+  // Requereix el monitor de trànsit actiu; en superar un límit es rota a un fitxer nou.
+  startCaptureFile: async (limits: { maxBytes?: number; maxSeconds?: number } = {}): Promise<CaptureFileStatusDTO> => {
+    return await invokeCommand<CaptureFileStatusDTO>('start_capture_file', {
+      maxBytes: limits.maxBytes ?? null,
+      maxSeconds: limits.maxSeconds ?? null,
+    });
+  },
+
+  stopCaptureFile: async (): Promise<CaptureFileStatusDTO> => {
+    return await invokeCommand<CaptureFileStatusDTO>('stop_capture_file');
+  },
+
+  rotateCaptureFile: async (): Promise<CaptureFileStatusDTO> => {
+    return await invokeCommand<CaptureFileStatusDTO>('rotate_capture_file');
+  },
+
+  // No cal el monitor actiu; focusIp limita el resultat al trànsit d'aquest equip.
+  analyzeCaptureFile: async (path: string, focusIp?: string, filter?: string): Promise<CaptureAnalysisDTO> => {
+    return await invokeCommand<CaptureAnalysisDTO>('analyze_capture_file', {
+      path,
+      focusIp: focusIp ?? null,
+      filter: filter ?? null,
+    });
+  },
 };
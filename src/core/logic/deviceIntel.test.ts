@@ -23,5 +23,20 @@ describe("deviceIntel", () => {
     const intel = classifyDeviceIntel({ ip: "192.168.1.130", mac: "AA:BB", vendor: "Xiaomi", name: "Redmi-15" } as any);
     expect(intel.deviceType).toBe("PHONE");
   });
+
+  it("debe usar el User-Agent HTTP cuando la MAC aleatoria oculta el fabricante", () => {
+    const device = { ip: "192.168.1.140", mac: "DA:BB", vendor: "Private Device (Random MAC)" } as any;
+    const intel = classifyDeviceIntel(device, {
+      userAgents: { ip: "192.168.1.140", osHint: "iOS", deviceHint: "PHONE", agents: [] },
+    });
+    expect(intel.deviceType).toBe("PHONE");
+    expect(intel.reasons.join(" ")).toContain("iOS");
+
+    // Pista de otro equipo: se ignora.
+    const other = classifyDeviceIntel(device, {
+      userAgents: { ip: "192.168.1.141", osHint: "iOS", deviceHint: "PHONE", agents: [] },
+    });
+    expect(other.deviceType).toBe("UNKNOWN");
+  });
 });
 
@@ -1,4 +1,4 @@
-import type { DeviceDTO, DeviceType } from "../../shared/dtos/NetworkDTOs";
+import type { DeviceDTO, DeviceType, DeviceUserAgentsDTO } from "../../shared/dtos/NetworkDTOs";
 
 export type DeviceIntel = {
   deviceType: DeviceType;
@@ -12,7 +12,10 @@ const norm = (s?: string) => (s || "").trim().toLowerCase();
 
 const hasAny = (hay: string, needles: string[]) => needles.some((n) => hay.includes(n));
 
-export const classifyDeviceIntel = (device: DeviceDTO, ctx?: { gatewayIp?: string; hostIp?: string | null }): DeviceIntel => {
+export const classifyDeviceIntel = (
+  device: DeviceDTO,
+  ctx?: { gatewayIp?: string; hostIp?: string | null; userAgents?: DeviceUserAgentsDTO | null }
+): DeviceIntel => {
   const reasons: string[] = [];
 
   // Host local
@@ -64,6 +67,15 @@ export const classifyDeviceIntel = (device: DeviceDTO, ctx?: { gatewayIp?: strin
     return { deviceType: "IOT", confidence: 74, reasons };
   }
 
+  // User-Agent HTTP observado por el monitor: util sobre todo cuando la MAC aleatoria oculta el fabricante
+  const ua = ctx?.userAgents?.ip === device.ip ? ctx?.userAgents : null;
+  const uaHint = ua?.deviceHint;
+  if (uaHint && uaHint !== "UNKNOWN") {
+    const os = ua?.osHint;
+    reasons.push(os ? `User-Agent HTTP sugiere ${os}` : "User-Agent HTTP de cliente embebido");
+    return { deviceType: uaHint, confidence: 68, reasons };
+  }
+
   // MAC aleatoria: en redes modernas puede ocultar fabricante real
   if (vendor.includes("private device") || vendor.includes("random mac")) {
     reasons.push("MAC aleatoria (privacy): fabricante no fiable");
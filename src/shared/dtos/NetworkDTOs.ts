@@ -6,6 +6,7 @@ export interface VulnerabilityDTO {
   description: string;
   severity: 'LOW' | 'MEDIUM' | 'HIGH' | 'CRITICAL';
   recommendation: string;
+  evidence?: string; // Prova capturada per la sonda activa (banner, resposta del servidor)
 }
 
 // 2. Definició d'un Port Obert
@@ -22,11 +23,38 @@ export interface OpenPortDTO {
 }
 
 // 3. Report Final d'Auditoria
+// 'syn' requereix privilegis (raw sockets); si no n'hi ha, el backend torna 'connect' a scanMode.
+export type PortScanMode = 'connect' | 'syn';
+
 export interface SecurityReportDTO {
   targetIp: string;
   openPorts: OpenPortDTO[];
   riskLevel: 'LOW' | 'MEDIUM' | 'HIGH' | 'CRITICAL';
-  vulnerabilities: string[]; // Resum de text per llistats ràpids
+  vulnerabilities: VulnerabilityDTO[]; // Troballes de les sondes actives (FTP/Telnet/SMTP) amb evidencia
+  scanMode?: PortScanMode; // Mode realment usat
+  filteredPorts?: number[]; // Ports sense resposta (nomes en mode SYN)
+}
+
+// 3b. Historial d'auditories de ports per dispositiu (clau = MAC o "ip:<ip>")
+export interface PortAuditRecordDTO {
+  deviceKey: string;
+  ip: string;
+  mac?: string;
+  timestamp: number;
+  scanMode: PortScanMode;
+  riskLevel: string;
+  openPorts: OpenPortDTO[];
+}
+
+// Event 'port-audit-changed': ports oberts/tancats respecte l'auditoria anterior
+export interface PortChangeEventDTO {
+  deviceKey: string;
+  ip: string;
+  mac?: string;
+  timestamp: number;
+  previousTimestamp: number;
+  opened: OpenPortDTO[];
+  closed: OpenPortDTO[];
 }
 
 // 4. Progrés en temps real (Console Logs)
@@ -49,6 +77,10 @@ export interface DeviceDTO {
   signal_strength?: number; 
   signal_rate?: number; 
   wifi_band?: string; 
+  model?: string; // Aprendido por sondas activas (SNMP sysDescr)
+  firmware?: string;
+  interfaceType?: string; // Taula de hosts TR-064: 'Ethernet' | '802.11'...
+  active?: boolean;
 
   // Intel local (frontend): calculado en UI a partir de señales (vendor/hostname/servicios).
   // No depende de backend y no rompe contratos, porque es opcional.
@@ -58,10 +90,129 @@ export interface DeviceDTO {
 
 export type DeviceType = 'PHONE' | 'PC' | 'TV' | 'SPEAKER' | 'ROUTER' | 'IOT' | 'UNKNOWN';
 
+// 5b. Auditoria SNMP (audit_snmp)
+export interface SnmpCommunityAccessDTO {
+  community: string;
+  version: 'v1' | 'v2c';
+  writable: boolean | null; // null: sysContact il·legible, no s'ha provat l'escriptura
+}
+
+export interface SnmpSystemInfoDTO {
+  descr?: string;
+  name?: string;
+  contact?: string;
+  location?: string;
+}
+
+export interface SnmpInterfaceDTO {
+  index: number;
+  descr: string;
+  mac?: string;
+  operStatus?: string;
+}
+
+export interface SnmpAuditResultDTO {
+  targetIp: string;
+  communities: SnmpCommunityAccessDTO[];
+  system?: SnmpSystemInfoDTO;
+  interfaces: SnmpInterfaceDTO[];
+  model?: string;
+  firmware?: string;
+  vulnerabilities: VulnerabilityDTO[];
+  riskLevel: 'SAFE' | 'HIGH' | 'CRITICAL' | (string & {});
+  device?: DeviceDTO;
+}
+
+// Auditoria UPnP IGD del gateway (audit_upnp)
+export interface UpnpPortMappingDTO {
+  remoteHost: string;
+  externalPort: number;
+  protocol: 'TCP' | 'UDP' | (string & {});
+  internalPort: number;
+  internalClient: string;
+  enabled: boolean;
+  description: string;
+  leaseDuration: number;
+}
+
+export interface UpnpAuditResultDTO {
+  gatewayIp: string;
+  igdFound: boolean;
+  serviceType?: string;
+  controlUrl?: string;
+  externalIp?: string;
+  portMappings: UpnpPortMappingDTO[];
+  vulnerabilities: VulnerabilityDTO[]; // Troballes del gateway (IGD actiu, RDP/SMB/Telnet exposats, clients desconeguts)
+  riskLevel: 'SAFE' | 'MEDIUM' | 'HIGH' | 'CRITICAL' | (string & {});
+}
+
+// Credencials trobades, tipades: la clau pot contenir ':' (mai "user:pass")
+export interface RouterCredentialsDTO {
+  user: string;
+  password: string;
+}
+
 export interface RouterAuditResult {
+  audit_id?: string | null; // Clau de les evidències (list_router_evidence / get_router_evidence)
+  vulnerable: boolean;
+  credentials_found?: RouterCredentialsDTO | null;
+  message: string;
+  fingerprint?: RouterFingerprintDTO | null;
+  findings: VulnerabilityDTO[]; // ROUTER-EOL / ROUTER-FIRMWARE-OUTDATED (llista local de firmwares)
+}
+
+// Empremta del router: capçaleres, favicon i pàgines d'estat després del login
+export interface RouterFingerprintDTO {
+  vendor?: string | null;
+  model?: string | null;
+  hardwareVersion?: string | null;
+  firmwareVersion?: string | null;
+  server?: string | null;
+  faviconMd5?: string | null;
+  sources: string[];
+}
+
+// Evidències d'una auditoria de router (captures + HTML sanejat), per auditId
+export type RouterEvidenceStage = 'login_page' | 'landing' | 'device_list';
+export type RouterEvidenceKind = 'screenshot' | 'html';
+
+export interface RouterEvidenceItemDTO {
+  auditId: string;
+  name: string; // "<stage>.<ext>", ex: "landing.png"
+  stage: RouterEvidenceStage;
+  kind: RouterEvidenceKind;
+  sizeBytes: number;
+  capturedAt: number;
+}
+
+export interface RouterEvidenceContentDTO extends RouterEvidenceItemDTO {
+  mimeType: string;
+  dataBase64: string;
+}
+
+// Jobs d'auditoria de router (start_router_audit / cancel_router_audit)
+export type RouterAuditPhase = 'detecting' | 'attempting' | 'attempted';
+export type RouterLoginOutcome = 'success' | 'rejected' | 'locked_out' | 'error';
+
+export interface RouterAuditProgressEvent {
+  auditId: string;
+  phase: RouterAuditPhase;
+  backend: 'http' | 'chrome' | (string & {});
+  profileId?: string;
+  attemptIndex?: number;
+  attemptTotal?: number;
+  user?: string;
+  outcome?: RouterLoginOutcome;
+}
+
+export interface RouterAuditFinishedEvent {
+  auditId: string;
+  cancelled: boolean;
   vulnerable: boolean;
-  credentials_found?: string;
+  credentialsFound?: RouterCredentialsDTO | null;
   message: string;
+  fingerprint?: RouterFingerprintDTO | null;
+  findings: VulnerabilityDTO[];
 }
 
 
@@ -96,14 +247,193 @@ export interface GatewayCredentialsDTO {
 export interface TrafficPacket {
   id: number;
   timestamp: number;
-  sourceIp: string;
+  sourceIp: string; // IPv4 o IPv6 (forma canònica, p. ex. 'fe80::1')
   destinationIp: string;
-  protocol: string;
+  protocol: string; // 'TCP' | 'UDP' | 'DNS' | 'NDP' | 'ICMPv6' | 'MCAST' | ...
   length: number;
   info: string;
   isIntercepted: boolean;
 }
 
+// Conversa agregada per 5-tupla (get_flows / esdeveniment 'traffic-flows'); camps alineats amb el conn.log de Zeek
+export interface TrafficFlowDTO {
+  uid: string;
+  protocol: string; // 'tcp' | 'udp' | 'icmp' | número IP
+  service: string;
+  origIp: string;
+  origPort?: number | null;
+  respIp: string;
+  respPort?: number | null;
+  origPackets: number;
+  origBytes: number;
+  respPackets: number;
+  respBytes: number;
+  firstSeen: number;
+  lastSeen: number;
+  tcpState?: 'syn_sent' | 'syn_received' | 'established' | 'closing' | 'closed' | 'reset' | 'midstream' | null;
+  connState: string; // S0, S1, SF, REJ, RSTO, RSTR, SH, S2, S3, OTH
+  active: boolean;
+}
+
+// Dominis consultats per cada equip (get_dns_log), a partir del DNS descodificat pel monitor
+export interface DnsDomainStatDTO {
+  domain: string;
+  queries: number;
+  firstSeen: number;
+  lastSeen: number;
+  queryTypes: string[]; // A, AAAA, HTTPS...
+  answers: string[]; // últimes respostes diferents (IPs o noms CNAME)
+  lastRcode?: string | null; // NOERROR, NXDOMAIN...
+}
+
+export interface DeviceDomainLogDTO {
+  ip: string;
+  totalQueries: number;
+  domains: DnsDomainStatDTO[]; // més consultats primer
+}
+
+// Empremtes TLS/QUIC per equip (get_tls_log): SNI, ALPN i JA3 dels ClientHello observats
+export interface TlsClientStatDTO {
+  serverName?: string | null; // SNI
+  alpn: string[];
+  tlsVersion: string; // 'TLS 1.3', 'TLS 1.2'...
+  ja3: string;
+  ja3Hash: string;
+  transport: 'TLS' | 'QUIC';
+  connections: number;
+  firstSeen: number;
+  lastSeen: number;
+  lastServerIp: string;
+}
+
+export interface DeviceTlsLogDTO {
+  ip: string;
+  fingerprints: string[]; // hashes JA3 diferents de l'equip
+  clients: TlsClientStatDTO[]; // més recents primer
+}
+
+// User-Agents HTTP en clar per equip (get_user_agents), amb la pista de SO/tipus que se'n dedueix
+export interface UserAgentStatDTO {
+  userAgent: string;
+  os?: string | null; // 'iOS', 'Android', 'Windows', 'Smart TV'...
+  client?: string | null; // 'Safari', 'Chrome', 'curl'...
+  deviceType?: DeviceType | null;
+  requests: number;
+  firstSeen: number;
+  lastSeen: number;
+  hosts: string[]; // últims Host diferents
+}
+
+export interface DeviceUserAgentsDTO {
+  ip: string;
+  osHint?: string | null;
+  deviceHint?: DeviceType | null;
+  agents: UserAgentStatDTO[]; // més peticions primer
+}
+
+// Consum d'amplada de banda (get_bandwidth_stats i esdeveniment 'traffic-bandwidth' cada segon)
+export type BandwidthWindow = 'second' | 'minute' | 'hour';
+
+export interface BandwidthPointDTO {
+  timestamp: number; // inici de la galleda
+  bytesIn: number;
+  bytesOut: number;
+}
+
+export interface HostBandwidthDTO {
+  ip: string;
+  bytesIn: number; // rebut per aquesta IP dins la finestra
+  bytesOut: number; // enviat per aquesta IP dins la finestra
+  totalBytesIn: number;
+  totalBytesOut: number;
+  packets: number;
+  lastSeen: number;
+  // Sèries per segon (60), minut (60) i hora (24); buides a l'esdeveniment periòdic.
+  perSecond: BandwidthPointDTO[];
+  perMinute: BandwidthPointDTO[];
+  perHour: BandwidthPointDTO[];
+}
+
+export interface BandwidthStatsDTO {
+  timestamp: number;
+  window: BandwidthWindow;
+  totalBytes: number; // tot el trànsit observat a la finestra
+  bytesPerSecond: number;
+  devices: HostBandwidthDTO[]; // equips locals, més bytes primer
+  endpoints: HostBandwidthDTO[]; // extrems remots, més bytes primer
+}
+
+// Vigilància ARP (get_arp_watch i esdeveniment 'traffic-arp-alert'): taula IP→MAC sembrada per l'escaneig
+export interface ArpBindingDTO {
+  ip: string;
+  mac: string;
+  source: 'scan' | 'arp'; // últim escaneig o après del trànsit
+  isGateway: boolean;
+  firstSeen: number;
+  lastSeen: number;
+  macChanges: number;
+}
+
+export type ArpAlertKind = 'mac_change' | 'gratuitous_arp_change' | 'gateway_impersonation' | 'mac_multiple_ips' | 'unsolicited_reply_flood';
+
+export interface ArpAlertDTO {
+  id: number;
+  timestamp: number;
+  kind: ArpAlertKind | (string & {});
+  severity: 'LOW' | 'MEDIUM' | 'HIGH' | 'CRITICAL';
+  ip?: string | null; // null si l'alerta és d'una MAC (diverses IPs)
+  mac: string;
+  message: string;
+  evidence: string[];
+}
+
+export interface ArpWatchReportDTO {
+  gatewayIp?: string | null;
+  bindings: ArpBindingDTO[]; // ordenats per IP
+  alerts: ArpAlertDTO[]; // les més recents primer
+}
+
+// Anàlisi offline d'un pcap/pcapng (analyze_capture_file): mateix pipeline que el monitor en viu
+export interface CaptureProtocolStatDTO {
+  protocol: string;
+  info: string;
+  packets: number;
+  bytes: number;
+}
+
+export interface CaptureTalkerStatDTO {
+  ip: string;
+  packets: number;
+  bytes: number;
+}
+
+export interface CaptureAnalysisDTO {
+  fileName: string;
+  format: 'pcap' | 'pcapng';
+  totalFrames: number;
+  decodedPackets: number;
+  skippedFrames: number;
+  firstTimestamp?: number | null;
+  lastTimestamp?: number | null;
+  protocols: CaptureProtocolStatDTO[];
+  topTalkers: CaptureTalkerStatDTO[];
+  packets: TrafficPacket[]; // primers paquets (acotat); timestamps originals de la captura
+  packetsTruncated: boolean;
+  flows: TrafficFlowDTO[];
+  readError?: string | null; // fitxer tallat: l'anàlisi cobreix el que s'ha pogut llegir
+}
+
+// Gravació PCAPNG en paral·lel al monitor (start/stop/rotate_capture_file)
+export interface CaptureFileStatusDTO {
+  active: boolean;
+  currentFile?: string | null;
+  files: string[]; // rutes a AppData/captures, la més recent al final
+  packetsWritten: number;
+  bytesWritten: number;
+  maxBytes?: number | null;
+  maxSeconds?: number | null;
+}
+
 // 6. WiFi Radar View (scan_airwaves)
 export interface WifiNetworkDTO {
   bssid: string;
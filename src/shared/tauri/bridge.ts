@@ -1,6 +1,6 @@
 import { invoke as tauriInvoke } from '@tauri-apps/api/core';
 import { listen as tauriListen } from '@tauri-apps/api/event';
-import type { DeviceDTO, ExternalAuditExitEvent, ExternalAuditLogEvent, GatewayCredentialsDTO, LatestSnapshotDTO, TrafficPacket, WifiNetworkDTO } from '../dtos/NetworkDTOs';
+import type { ArpWatchReportDTO, BandwidthStatsDTO, BandwidthWindow, CaptureAnalysisDTO, CaptureFileStatusDTO, DeviceDomainLogDTO, DeviceDTO, DeviceTlsLogDTO, DeviceUserAgentsDTO, ExternalAuditExitEvent, ExternalAuditLogEvent, GatewayCredentialsDTO, LatestSnapshotDTO, RouterAuditFinishedEvent, RouterAuditProgressEvent, RouterEvidenceContentDTO, RouterEvidenceItemDTO, TrafficFlowDTO, TrafficPacket, WifiNetworkDTO } from '../dtos/NetworkDTOs';
 
 type EventEnvelope<T> = { payload: T };
 type EventCallback<T> = (event: EventEnvelope<T>) => void;
@@ -24,6 +24,9 @@ let packetId = 0;
 let extAuditTimer: ReturnType<typeof setInterval> | null = null;
 let extAuditSeq = 0;
 let activeExtAuditId: string | null = null;
+let routerAuditTimer: ReturnType<typeof setInterval> | null = null;
+let routerAuditSeq = 0;
+let activeRouterAuditId: string | null = null;
 let mockLatestSnapshot: LatestSnapshotDTO | null = null;
 const mockGatewayCreds = new Map<string, GatewayCredentialsDTO>();
 
@@ -114,10 +117,82 @@ const startMockTraffic = () => {
       info: packetId % 2 === 0 ? 'HTTPS Traffic' : 'DNS Query',
       isIntercepted: packetId % 5 === 0,
     };
-    emit('traffic-event', packet);
+    // Cada 10 paquets, un NA de NDP per veure també trànsit IPv6. Lots d'un paquet, com un tick del backend.
+    emit('traffic-packets', [packetId % 10 === 0 ? mockNdpPacket(packet) : packet]);
+    if (packetId % 8 === 0) emit('traffic-flows', mockFlows());
+    if (packetId % 4 === 0) emit('traffic-bandwidth', mockBandwidth('second', 10, false));
   }, 250);
 };
 
+const mockNdpPacket = (base: TrafficPacket): TrafficPacket => ({
+  ...base,
+  sourceIp: 'fe80::1',
+  destinationIp: 'fe80::1c2a:3bff:fe4d:5e6f',
+  protocol: 'NDP',
+  length: 72,
+  info: 'NDP Neighbor Advertisement: fe80::1 esta en aa:bb:cc:00:00:01 (router, solicitado)',
+  isIntercepted: false,
+});
+
+// Dues converses fixes amb comptadors derivats dels paquets simulats.
+const mockFlows = (): TrafficFlowDTO[] => {
+  const half = Math.floor(packetId / 2);
+  const now = Date.now();
+  return [
+    {
+      uid: 'Cmock000001', protocol: 'tcp', service: 'HTTPS (Web segura)',
+      origIp: '192.168.1.10', origPort: 51000, respIp: '8.8.8.8', respPort: 443,
+      origPackets: half, origBytes: half * 120, respPackets: 0, respBytes: 0,
+      firstSeen: now - packetId * 250, lastSeen: now, tcpState: 'established', connState: 'S1', active: trafficTimer !== null,
+    },
+    {
+      uid: 'Cmock000002', protocol: 'udp', service: 'DNS',
+      origIp: '192.168.1.21', origPort: 53000, respIp: '192.168.1.1', respPort: 53,
+      origPackets: packetId - half, origBytes: (packetId - half) * 60, respPackets: 0, respBytes: 0,
+      firstSeen: now - packetId * 250, lastSeen: now, tcpState: null, connState: 'S0', active: trafficTimer !== null,
+    },
+  ];
+};
+
+// Consum derivat del ritme dels paquets simulats: 4 per segon, 2 de 120 B i 2 de 60 B.
+const mockBandwidth = (window: BandwidthWindow, topN: number, withSeries: boolean): BandwidthStatsDTO => {
+  const now = Date.now();
+  const seconds = window === 'second' ? 1 : window === 'minute' ? 60 : 3600;
+  const elapsed = Math.min(seconds, Math.floor(packetId / 4));
+  const host = (ip: string, bytesIn: number, bytesOut: number) => ({
+    ip,
+    bytesIn: bytesIn * elapsed,
+    bytesOut: bytesOut * elapsed,
+    totalBytesIn: bytesIn * Math.floor(packetId / 4),
+    totalBytesOut: bytesOut * Math.floor(packetId / 4),
+    packets: Math.floor(packetId / 2),
+    lastSeen: now,
+    perSecond: withSeries ? [{ timestamp: Math.floor(now / 1000) * 1000, bytesIn, bytesOut }] : [],
+    perMinute: [],
+    perHour: [],
+  });
+  return {
+    timestamp: now,
+    window,
+    totalBytes: 360 * elapsed,
+    bytesPerSecond: elapsed > 0 ? 360 : 0,
+    devices: packetId > 0 ? [host('192.168.1.10', 0, 240), host('192.168.1.21', 0, 120), host('192.168.1.1', 120, 0)].slice(0, topN) : [],
+    endpoints: packetId > 0 ? [host('8.8.8.8', 240, 0)].slice(0, topN) : [],
+  };
+};
+
+let mockCaptureFiles: string[] = [];
+
+const mockCaptureStatus = (active: boolean): CaptureFileStatusDTO => ({
+  active,
+  currentFile: active ? mockCaptureFiles[mockCaptureFiles.length - 1] : null,
+  files: [...mockCaptureFiles],
+  packetsWritten: packetId,
+  bytesWritten: packetId * 120,
+  maxBytes: null,
+  maxSeconds: null,
+});
+
 const stopMockTraffic = () => {
   if (!trafficTimer) return;
   clearInterval(trafficTimer);
@@ -188,15 +263,153 @@ const invokeMock = async <T>(command: string, args?: Record<string, unknown>): P
         ],
         riskLevel: 'LOW',
         vulnerabilities: [],
+        scanMode: 'connect',
+        filteredPorts: [],
+      } as T;
+    }
+    case 'get_port_audit_history':
+      return [] as T;
+    case 'audit_upnp': {
+      const gatewayIp = (args?.gatewayIp as string) || '192.168.1.1';
+      return {
+        gatewayIp,
+        igdFound: true,
+        serviceType: 'urn:schemas-upnp-org:service:WANIPConnection:1',
+        controlUrl: `http://${gatewayIp}:5000/ctl/IPConn`,
+        externalIp: '203.0.113.7',
+        portMappings: [
+          {
+            remoteHost: '',
+            externalPort: 3389,
+            protocol: 'TCP',
+            internalPort: 3389,
+            internalClient: '192.168.1.10',
+            enabled: true,
+            description: 'Remote Desktop',
+            leaseDuration: 0,
+          },
+        ],
+        vulnerabilities: [
+          {
+            id: 'UPNP-DANGEROUS-FORWARD',
+            description: 'Mapeo UPnP publica RDP de 192.168.1.10 a Internet (puerto externo 3389/TCP).',
+            severity: 'CRITICAL',
+            recommendation: 'Eliminar el mapeo y acceder a RDP solo via VPN.',
+          },
+        ],
+        riskLevel: 'CRITICAL',
       } as T;
     }
     case 'audit_router':
       if (scenario.failAuditRouter) throw new Error('E2E mock: fallo forzado en audit_router');
       return {
+        audit_id: 'mock_router_audit_sync',
         vulnerable: true,
-        credentials_found: 'admin:1234',
+        credentials_found: { user: 'admin', password: '1234' },
         message: 'Default credentials detected',
+        fingerprint: {
+          vendor: 'Netgear',
+          model: 'DGN2200',
+          hardwareVersion: 'DGN2200v1',
+          firmwareVersion: 'V1.0.0.50_7.0.50',
+          server: null,
+          faviconMd5: null,
+          sources: ['realm', 'page:/RST_status.htm'],
+        },
+        findings: [
+          {
+            id: 'ROUTER-EOL',
+            description: 'El router Netgear DGN2200 esta fuera de soporte del fabricante.',
+            severity: 'HIGH',
+            recommendation: 'Sustituir el equipo por un modelo con soporte.',
+            evidence: 'NETGEAR-DGN2200-EOL (CVE-2017-6334)',
+          },
+        ],
       } as T;
+    case 'start_router_audit': {
+      routerAuditSeq += 1;
+      const auditId = `mock_router_audit_${routerAuditSeq}`;
+      activeRouterAuditId = auditId;
+      const mockCreds = [['admin', 'admin'], ['admin', '1234']];
+
+      // Un intent per tick; el segon parell "funciona".
+      if (routerAuditTimer) clearInterval(routerAuditTimer);
+      let attempt = 0;
+      routerAuditTimer = setInterval(() => {
+        const [user] = mockCreds[attempt];
+        attempt += 1;
+        const outcome = attempt === mockCreds.length ? 'success' : 'rejected';
+        const evt: RouterAuditProgressEvent = {
+          auditId,
+          phase: 'attempted',
+          backend: 'http',
+          profileId: 'generic',
+          attemptIndex: attempt,
+          attemptTotal: mockCreds.length,
+          user,
+          outcome,
+        };
+        emit('router-audit-progress', evt);
+
+        if (outcome === 'success') {
+          if (routerAuditTimer) clearInterval(routerAuditTimer);
+          routerAuditTimer = null;
+          const finishedEvt: RouterAuditFinishedEvent = {
+            auditId,
+            cancelled: false,
+            vulnerable: true,
+            credentialsFound: { user: 'admin', password: '1234' },
+            message: "Success: credenciales por defecto validas para 'admin'",
+            fingerprint: null,
+            findings: [],
+          };
+          emit('router-audit-finished', finishedEvt);
+        }
+      }, 120);
+
+      return auditId as T;
+    }
+    case 'cancel_router_audit': {
+      const auditId = (args?.auditId as string) || activeRouterAuditId || 'unknown';
+      if (routerAuditTimer) {
+        clearInterval(routerAuditTimer);
+        routerAuditTimer = null;
+      }
+      const finishedEvt: RouterAuditFinishedEvent = {
+        auditId,
+        cancelled: true,
+        vulnerable: false,
+        message: 'Cancelled after 0 attempts',
+        fingerprint: null,
+        findings: [],
+      };
+      emit('router-audit-finished', finishedEvt);
+      return undefined as T;
+    }
+    case 'list_router_evidence': {
+      const auditId = (args?.auditId as string) || 'unknown';
+      const items: RouterEvidenceItemDTO[] = [
+        { auditId, name: 'login_page.html', stage: 'login_page', kind: 'html', sizeBytes: 64, capturedAt: Date.now() },
+        { auditId, name: 'landing.html', stage: 'landing', kind: 'html', sizeBytes: 64, capturedAt: Date.now() },
+      ];
+      return items as T;
+    }
+    case 'get_router_evidence': {
+      const auditId = (args?.auditId as string) || 'unknown';
+      const name = (args?.name as string) || 'landing.html';
+      const html = `<html><body><h1>Mock ${name}</h1></body></html>`;
+      const content: RouterEvidenceContentDTO = {
+        auditId,
+        name,
+        stage: name.startsWith('login_page') ? 'login_page' : 'landing',
+        kind: 'html',
+        sizeBytes: html.length,
+        capturedAt: Date.now(),
+        mimeType: 'text/html; charset=utf-8',
+        dataBase64: btoa(html),
+      };
+      return content as T;
+    }
     case 'fetch_router_devices':
       return [
         {
@@ -213,9 +426,149 @@ const invokeMock = async <T>(command: string, args?: Record<string, unknown>): P
       if (scenario.failTrafficStart) throw new Error('E2E mock: fallo forzado en start_traffic_sniffing');
       startMockTraffic();
       return undefined as T;
+    case 'get_flows':
+      return (packetId > 0 ? mockFlows() : []) as T;
+    case 'get_bandwidth_stats':
+      return mockBandwidth((args?.window as BandwidthWindow | null) ?? 'minute', (args?.topN as number | null) ?? 10, true) as T;
+    case 'get_arp_watch': {
+      const now = Date.now();
+      const report: ArpWatchReportDTO = {
+        gatewayIp: '192.168.1.1',
+        bindings: [
+          { ip: '192.168.1.1', mac: 'aa:bb:cc:00:00:01', source: 'scan', isGateway: true, firstSeen: now - 60_000, lastSeen: now, macChanges: 0 },
+          { ip: '192.168.1.10', mac: 'aa:bb:cc:00:00:10', source: 'scan', isGateway: false, firstSeen: now - 60_000, lastSeen: now, macChanges: 0 },
+          { ip: '192.168.1.21', mac: 'aa:bb:cc:00:00:21', source: 'arp', isGateway: false, firstSeen: now - packetId * 250, lastSeen: now, macChanges: 0 },
+        ],
+        alerts: [],
+      };
+      return report as T;
+    }
+    case 'get_dns_log': {
+      if (packetId === 0) return [] as T;
+      const now = Date.now();
+      const logs: DeviceDomainLogDTO[] = [
+        {
+          ip: '192.168.1.21',
+          totalQueries: Math.ceil(packetId / 2),
+          domains: [
+            {
+              domain: 'time.vendor-cloud.example',
+              queries: Math.ceil(packetId / 2),
+              firstSeen: now - packetId * 250,
+              lastSeen: now,
+              queryTypes: ['A'],
+              answers: ['203.0.113.10'],
+              lastRcode: 'NOERROR',
+            },
+          ],
+        },
+      ];
+      const deviceIp = args?.deviceIp as string | null | undefined;
+      return (deviceIp ? logs.filter((log) => log.ip === deviceIp) : logs) as T;
+    }
+    case 'get_tls_log': {
+      if (packetId === 0) return [] as T;
+      const now = Date.now();
+      const ja3Hash = 'cd08e31494f9531f560d64c695473da9';
+      const logs: DeviceTlsLogDTO[] = [
+        {
+          ip: '192.168.1.10',
+          fingerprints: [ja3Hash],
+          clients: [
+            {
+              serverName: 'www.example.com',
+              alpn: ['h2', 'http/1.1'],
+              tlsVersion: 'TLS 1.3',
+              ja3: '771,4865-4866-4867,0-23-65281-10-11-35-16-5-13-18-51-45-43-27-21,29-23-24,0',
+              ja3Hash,
+              transport: 'TLS',
+              connections: Math.ceil(packetId / 10),
+              firstSeen: now - packetId * 250,
+              lastSeen: now,
+              lastServerIp: '8.8.8.8',
+            },
+          ],
+        },
+      ];
+      const deviceIp = args?.deviceIp as string | null | undefined;
+      return (deviceIp ? logs.filter((log) => log.ip === deviceIp) : logs) as T;
+    }
+    case 'get_user_agents': {
+      if (packetId === 0) return [] as T;
+      const now = Date.now();
+      const logs: DeviceUserAgentsDTO[] = [
+        {
+          ip: '192.168.1.10',
+          osHint: 'Windows',
+          deviceHint: 'PC',
+          agents: [
+            {
+              userAgent: 'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0 Safari/537.36',
+              os: 'Windows',
+              client: 'Chrome',
+              deviceType: 'PC',
+              requests: Math.ceil(packetId / 20),
+              firstSeen: now - packetId * 250,
+              lastSeen: now,
+              hosts: ['example.com'],
+            },
+          ],
+        },
+      ];
+      const deviceIp = args?.deviceIp as string | null | undefined;
+      return (deviceIp ? logs.filter((log) => log.ip === deviceIp) : logs) as T;
+    }
+    case 'set_traffic_filter':
+      return undefined as T;
     case 'stop_traffic_sniffing':
       stopMockTraffic();
       return undefined as T;
+    case 'start_capture_file':
+      if (!trafficTimer) throw new Error('Inicia el monitor de trafico antes de grabar');
+      mockCaptureFiles = [`C:/mock/captures/capture_${Date.now()}_001.pcapng`];
+      return mockCaptureStatus(true) as T;
+    case 'rotate_capture_file':
+      if (mockCaptureFiles.length === 0) throw new Error('No hay ninguna grabacion en curso');
+      mockCaptureFiles.push(`C:/mock/captures/capture_${Date.now()}_${String(mockCaptureFiles.length + 1).padStart(3, '0')}.pcapng`);
+      return mockCaptureStatus(true) as T;
+    case 'stop_capture_file': {
+      const status = mockCaptureStatus(false);
+      mockCaptureFiles = [];
+      return status as T;
+    }
+    case 'analyze_capture_file': {
+      const path = (args?.path as string) || 'capture.pcapng';
+      const base = 1_700_000_000_000;
+      const analysis: CaptureAnalysisDTO = {
+        fileName: path.split(/[\\/]/).pop() || path,
+        format: path.toLowerCase().endsWith('.pcapng') ? 'pcapng' : 'pcap',
+        totalFrames: 3,
+        decodedPackets: 2,
+        skippedFrames: 1,
+        firstTimestamp: base,
+        lastTimestamp: base + 250,
+        protocols: [{ protocol: 'UDP', info: 'DNS', packets: 2, bytes: 128 }],
+        topTalkers: [
+          { ip: '192.168.1.1', packets: 2, bytes: 128 },
+          { ip: '192.168.1.20', packets: 2, bytes: 128 },
+        ],
+        packets: [
+          { id: 0, timestamp: base, sourceIp: '192.168.1.20', destinationIp: '192.168.1.1', protocol: 'UDP', length: 64, info: 'DNS', isIntercepted: false },
+          { id: 1, timestamp: base + 250, sourceIp: '192.168.1.1', destinationIp: '192.168.1.20', protocol: 'UDP', length: 64, info: 'DNS', isIntercepted: false },
+        ],
+        packetsTruncated: false,
+        flows: [
+          {
+            uid: 'Cmock000010', protocol: 'udp', service: 'DNS',
+            origIp: '192.168.1.20', origPort: 53000, respIp: '192.168.1.1', respPort: 53,
+            origPackets: 1, origBytes: 64, respPackets: 1, respBytes: 64,
+            firstSeen: base, lastSeen: base + 250, tcpState: null, connState: 'SF', active: false,
+          },
+        ],
+        readError: null,
+      };
+      return analysis as T;
+    }
     case 'start_jamming':
       emit('audit-log', `E2E MOCK: Jammer activo en ${(args?.ip as string) || 'unknown'}`);
       return undefined as T;
@@ -63,7 +63,7 @@ describe('💀 Integration: useRouterHacker Hook', () => {
     // 1. El router diu que sí
     (auditAdapter.auditRouter as any).mockResolvedValue({
       vulnerable: true,
-      credentials_found: 'admin:1234',
+      credentials_found: { user: 'admin', password: 'pa:ss' },
       message: 'Success'
     });
 
@@ -89,6 +89,8 @@ describe('💀 Integration: useRouterHacker Hook', () => {
 
     // ASSERT
     expect(mockAddLog).toHaveBeenCalledWith('192.168.1.1', expect.stringContaining('PASSWORD FOUND'));
+    // La clau amb ':' arriba intacta a la sincronitzacio.
+    expect(auditAdapter.fetchRouterDevices).toHaveBeenCalledWith('192.168.1.1', 'admin', 'pa:ss');
     
     // 🔥 EL TEST DE FOC: Comprovar la lògica de FUSIÓ
     // mockSetDevices es crida amb una funció callback (prev => ...).
@@ -1,7 +1,7 @@
 import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
 import { act, renderHook } from '@testing-library/react';
 import { useTrafficMonitor } from '../useTrafficMonitor';
-import type { TrafficPacket } from '../../../../shared/dtos/NetworkDTOs';
+import type { BandwidthStatsDTO, TrafficPacket } from '../../../../shared/dtos/NetworkDTOs';
 
 const { invokeCommandMock, listenEventMock } = vi.hoisted(() => ({
   invokeCommandMock: vi.fn(),
@@ -14,15 +14,18 @@ vi.mock('../../../../shared/tauri/bridge', () => ({
 }));
 
 describe('useTrafficMonitor', () => {
-  let trafficHandler: ((event: { payload: TrafficPacket }) => void) | null = null;
+  let trafficHandler: ((event: { payload: TrafficPacket[] }) => void) | null = null;
+  let bandwidthHandler: ((event: { payload: BandwidthStatsDTO }) => void) | null = null;
 
   beforeEach(() => {
     vi.useFakeTimers();
     vi.clearAllMocks();
     trafficHandler = null;
+    bandwidthHandler = null;
 
-    listenEventMock.mockImplementation(async (_eventName: string, handler: (event: { payload: TrafficPacket }) => void) => {
-      trafficHandler = handler;
+    listenEventMock.mockImplementation(async (eventName: string, handler: (event: { payload: any }) => void) => {
+      if (eventName === 'traffic-packets') trafficHandler = handler;
+      if (eventName === 'traffic-bandwidth') bandwidthHandler = handler;
       return () => {};
     });
   });
@@ -92,17 +95,30 @@ describe('useTrafficMonitor', () => {
     };
 
     act(() => {
-      trafficHandler?.({ payload: packetA });
-      trafficHandler?.({ payload: packetB });
+      trafficHandler?.({ payload: [packetA, packetB] });
+      // La velocidad ya no se suma paquete a paquete: llega en el resumen periodico del backend.
+      bandwidthHandler?.({
+        payload: { timestamp: Date.now(), window: 'second', totalBytes: 750, bytesPerSecond: 750, devices: [], endpoints: [] },
+      });
     });
 
     act(() => {
       vi.advanceTimersByTime(200);
     });
 
+    expect(bandwidthHandler).not.toBeNull();
     expect(result.current.packets).toHaveLength(2);
     expect(result.current.jammedPackets).toHaveLength(1);
     expect(result.current.speed).toBe(750);
+    // Lo mas reciente arriba tambien dentro de un mismo lote
+    expect(result.current.packets.map((p) => p.id)).toEqual([2, 1]);
+
+    // Sin lotes nuevos no se vuelve a publicar el estado
+    const published = result.current.packets;
+    act(() => {
+      vi.advanceTimersByTime(1000);
+    });
+    expect(result.current.packets).toBe(published);
   });
 
   it('debe limpiar buffers y velocidad con clearPackets', async () => {
@@ -130,7 +146,7 @@ describe('useTrafficMonitor', () => {
     };
 
     act(() => {
-      trafficHandler?.({ payload: packet });
+      trafficHandler?.({ payload: [packet] });
       vi.advanceTimersByTime(200);
     });
 
@@ -1,4 +1,4 @@
-import { useState } from 'react';
+import { useEffect, useRef, useState } from 'react';
 import { OpenPortDTO } from '../../../shared/dtos/NetworkDTOs';
 import { auditAdapter } from '../../../adapters/auditAdapter';
 
@@ -6,7 +6,24 @@ export const usePortAuditor = (addLog: (ip: string, msg: string) => void) => {
   const [auditing, setAuditing] = useState(false);
   const [auditResults, setAuditResults] = useState<OpenPortDTO[]>([]);
 
-  const startAudit = async (ip: string) => {
+  // addLog no es estable entre renders: el listener el llegeix via ref per subscriure's un sol cop
+  const addLogRef = useRef(addLog);
+  addLogRef.current = addLog;
+
+  // Alertes de canvis entre auditories (ex: una camera que de sobte exposa Telnet)
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    auditAdapter
+      .onPortChanges((evt) => {
+        evt.opened.forEach((p) => addLogRef.current(evt.ip, `> ALERT: NEW OPEN PORT ${p.port}/${p.service}`));
+        evt.closed.forEach((p) => addLogRef.current(evt.ip, `> INFO: PORT CLOSED ${p.port}/${p.service}`));
+      })
+      .then((fn) => { unlisten = fn; })
+      .catch(() => {});
+    return () => { if (unlisten) unlisten(); };
+  }, []);
+
+  const startAudit = async (ip: string, mac?: string) => {
     if (auditing) return;
     setAuditing(true);
     setAuditResults([]);
@@ -14,7 +31,7 @@ export const usePortAuditor = (addLog: (ip: string, msg: string) => void) => {
     addLog(ip, `> EXEC: FULL_TCP_CONNECT_SCAN...`);
 
     try {
-      const ports = await auditAdapter.auditTargetPorts(ip);
+      const ports = await auditAdapter.auditTargetPorts(ip, { mac });
       addLog(ip, `> ANALYSIS COMPLETE. PORTS FOUND: ${ports.length}`);
       setAuditResults(ports);
     } catch (e) {
@@ -38,11 +38,21 @@ export const useRouterHacker = (
       const result = await auditAdapter.auditRouter(gatewayIp);
       setRouterRisk(result);
 
+      // Empremta i troballes de firmware (poden existir encara que el login resisteixi).
+      const fp = result.fingerprint;
+      if (fp && (fp.vendor || fp.model)) {
+        const fw = fp.firmwareVersion ? ` FW ${fp.firmwareVersion}` : '';
+        addLog(gatewayIp, `> FINGERPRINT: ${[fp.vendor, fp.model].filter(Boolean).join(' ')}${fw}`);
+      }
+      (result.findings ?? []).forEach((f) => addLog(gatewayIp, `⚠️ ${f.severity} ${f.id}: ${f.description}`));
+
       if (result.vulnerable) {
-        addLog(gatewayIp, `💀 CRITICAL: PASSWORD FOUND: ${result.credentials_found}`);
+        const found = result.credentials_found;
+        addLog(gatewayIp, `💀 CRITICAL: PASSWORD FOUND${found ? ` for user '${found.user}'` : ''}`);
         
-        if (result.credentials_found) {
-          const [user, pass] = result.credentials_found.split(':');
+        if (found) {
+          // Usuari i clau arriben separats: res a partir (la clau pot contenir ':').
+          const { user, password: pass } = found;
 
           // Persistencia local (equipo del auditor): evita repetir audit_router en cada arranque.
           try {
@@ -74,7 +84,9 @@ export const useRouterHacker = (
                   name: (rd.name ?? existing.name),
                   signal_strength: rd.signal_strength,
                   signal_rate: rd.signal_rate,
-                  wifi_band: rd.wifi_band
+                  wifi_band: rd.wifi_band,
+                  interfaceType: rd.interfaceType ?? existing.interfaceType,
+                  active: rd.active ?? existing.active
                 });
               } else {
                 // Si el router no aporta MAC valida, evitamos placeholders y dejamos Unknown.
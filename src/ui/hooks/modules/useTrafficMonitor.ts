@@ -1,5 +1,5 @@
 import { useState, useEffect, useRef, useCallback } from 'react';
-import { TrafficPacket } from '../../../shared/dtos/NetworkDTOs';
+import { BandwidthStatsDTO, TrafficPacket } from '../../../shared/dtos/NetworkDTOs';
 import { invokeCommand, listenEvent } from '../../../shared/tauri/bridge';
 
 export interface UITrafficPacket extends TrafficPacket {
@@ -22,14 +22,16 @@ export const useTrafficMonitor = () => {
   // 2) Buffer seguro de paquetes interceptados
   const jammedBufferRef = useRef<UITrafficPacket[]>([]);
   
-  const byteCountRef = useRef(0);
   const seqRef = useRef(0);
+  // Hay lotes nuevos desde el ultimo refresco de la UI
+  const dirtyRef = useRef(false);
 
   const toggleMonitoring = async () => {
     try {
       if (isActive) {
         await invokeCommand('stop_traffic_sniffing');
         setIsActive(false);
+        setSpeed(0);
       } else {
         // Reset completo al arrancar monitor
         bufferRef.current = [];
@@ -47,56 +49,60 @@ export const useTrafficMonitor = () => {
 
   useEffect(() => {
     let unlisten: (() => void) | null = null;
+    let unlistenBandwidth: (() => void) | null = null;
 
     const setup = async () => {
-      unlisten = await listenEvent<TrafficPacket>('traffic-event', (event) => {
-        seqRef.current++;
-        
-        const newPacket: UITrafficPacket = {
-            ...event.payload,
-            _uiId: `pkt-${seqRef.current}-${Math.random().toString(36).substr(2, 5)}`,
-            _seq: seqRef.current
-        };
+      // El backend agrupa los paquetes en lotes (cada ~250 ms): se procesa el lote entero de una vez,
+      // sin trabajo ni copias por paquete
+      unlisten = await listenEvent<TrafficPacket[]>('traffic-packets', (event) => {
+        if (event.payload.length === 0) return;
 
-        // A) Se inserta al inicio para mostrar lo mas reciente arriba
-        bufferRef.current.unshift(newPacket);
-        
-        // B) Si el paquete fue interceptado, se conserva en lista jammed
-        if (newPacket.isIntercepted) {
-            jammedBufferRef.current.unshift(newPacket);
-            // Limite defensivo para evitar crecimiento no acotado
-            if (jammedBufferRef.current.length > 1000) {
-                jammedBufferRef.current = jammedBufferRef.current.slice(0, 1000);
-            }
-        }
+        // A) Lo mas reciente arriba: el lote llega en orden de llegada, se invierte
+        const batch: UITrafficPacket[] = event.payload
+          .map((packet) => {
+            seqRef.current++;
+            return {
+              ...packet,
+              _uiId: `pkt-${seqRef.current}-${Math.random().toString(36).substr(2, 5)}`,
+              _seq: seqRef.current,
+            };
+          })
+          .reverse();
 
-        byteCountRef.current += event.payload.length;
+        // B) Limite principal de historial en vivo
+        bufferRef.current = batch.concat(bufferRef.current).slice(0, 5000);
 
-        // C) Limite principal de historial en vivo
-        if (bufferRef.current.length > 5000) {
-          bufferRef.current = bufferRef.current.slice(0, 5000);
+        // C) Los interceptados se conservan en lista jammed (limite defensivo)
+        const jammed = batch.filter((packet) => packet.isIntercepted);
+        if (jammed.length > 0) {
+          jammedBufferRef.current = jammed.concat(jammedBufferRef.current).slice(0, 1000);
         }
+        dirtyRef.current = true;
+      });
+
+      // Velocidad: resumen periodico del backend (todo lo que ve la interfaz, no solo los paquetes filtrados)
+      unlistenBandwidth = await listenEvent<BandwidthStatsDTO>('traffic-bandwidth', (event) => {
+        setSpeed(event.payload.bytesPerSecond);
       });
     };
 
     setup();
 
-    // Refresco de UI cada 200ms para reducir renders
+    // Refresco de UI cada 200ms, y solo si llego algun lote: sin trafico no hay renders
     const interval = setInterval(() => {
-        if (isActive || bufferRef.current.length > 0) {
-            setSpeed(byteCountRef.current * 5);
-            byteCountRef.current = 0;
-            
-            // Se publica snapshot de ambas listas
+        if (dirtyRef.current) {
+            dirtyRef.current = false;
+            // Los buffers se reemplazan (no se mutan) en cada lote: se publican tal cual
             setData({
-                all: [...bufferRef.current],
-                jammed: [...jammedBufferRef.current]
+                all: bufferRef.current,
+                jammed: jammedBufferRef.current
             });
         }
     }, 200);
 
     return () => {
       if (unlisten) unlisten();
+      if (unlistenBandwidth) unlistenBandwidth();
       clearInterval(interval);
     };
   }, [isActive]);
@@ -104,6 +110,7 @@ export const useTrafficMonitor = () => {
   const clearPackets = useCallback(() => {
     bufferRef.current = [];
     jammedBufferRef.current = [];
+    dirtyRef.current = false;
     setData({ all: [], jammed: [] });
     setSpeed(0);
     seqRef.current = 0;
@@ -127,7 +127,9 @@ export const useNetworkManager = () => {
               hostname: rd.hostname ?? existing.hostname,
               signal_strength: rd.signal_strength ?? existing.signal_strength,
               signal_rate: rd.signal_rate ?? existing.signal_rate,
-              wifi_band: rd.wifi_band ?? existing.wifi_band
+              wifi_band: rd.wifi_band ?? existing.wifi_band,
+              interfaceType: rd.interfaceType ?? existing.interfaceType,
+              active: rd.active ?? existing.active
             });
           });
           const merged = Array.from(map.values());